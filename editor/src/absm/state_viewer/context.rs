@@ -117,6 +117,7 @@ impl CanvasContextMenu {
                         parent_state: current_state,
                     },
                     animation: Default::default(),
+                    animation_resource: None,
                     output_pose: Default::default(),
                 }))
             } else if message.destination() == self.create_blend_animations {
@@ -15,6 +15,9 @@ macro_rules! define_universal_commands {
                 fyrox::gui::inspector::PropertyAction::RemoveItem { index } => Some(<$command_wrapper>::new(
                     RemoveCollectionItemCommand::new($handle_ident, property_changed.path(), index, $($field_name),*)
                 )),
+                fyrox::gui::inspector::PropertyAction::MoveItem { from, to } => Some(<$command_wrapper>::new(
+                    MoveCollectionItemCommand::new($handle_ident, property_changed.path(), from, to, $($field_name),*)
+                )),
                 // Must be handled outside, there is not enough context and it near to impossible to create universal reversion
                 // for InheritableVariable<T>.
                 fyrox::gui::inspector::PropertyAction::Revert => None
@@ -221,5 +224,69 @@ macro_rules! define_universal_commands {
                 })
             }
         }
+
+        #[derive(Debug)]
+        pub struct MoveCollectionItemCommand {
+            #[allow(dead_code)]
+            $handle_ident: $handle,
+            path: String,
+            from: usize,
+            to: usize,
+            $($field_name: $field_type),*
+        }
+
+        impl MoveCollectionItemCommand {
+            pub fn new($handle_ident: $handle, path: String, from: usize, to: usize, $($field_name: $field_type),*) -> Self {
+                Self {
+                    $handle_ident,
+                    path,
+                    from,
+                    to,
+                    $($field_name),*
+                }
+            }
+        }
+
+        fn move_collection_item(field: &mut dyn fyrox::core::reflect::Reflect, path: &str, from: usize, to: usize) {
+            field.as_list_mut(&mut |result| {
+                if let Some(list) = result {
+                    if let Some(item) = list.reflect_remove(from) {
+                        if let Err(item) = list.reflect_insert(to, item) {
+                            // Restore the collection, insertion of the same item cannot fail at the old position.
+                            let _ = list.reflect_insert(from, item);
+                            fyrox::utils::log::Log::err(format!(
+                                "Failed to move item in {} collection. Invalid index {}!",
+                                path, to
+                            ))
+                        }
+                    } else {
+                        fyrox::utils::log::Log::err(format!(
+                            "Failed to move item in {} collection. Invalid index {}!",
+                            path, from
+                        ))
+                    }
+                } else {
+                    fyrox::utils::log::Log::err(format!("Property {} is not a collection!", path))
+                }
+            })
+        }
+
+        impl $command for MoveCollectionItemCommand {
+            fn name(&mut $self, _: &$ctx) -> String {
+                format!("Move collection {} item {} to {}", $self.path, $self.from, $self.to)
+            }
+
+            fn execute(&mut $self, $ctx_ident: &mut $ctx) {
+                try_modify_property($entity_getter, &$self.path, |field| {
+                    move_collection_item(field, &$self.path, $self.from, $self.to)
+                })
+            }
+
+            fn revert(&mut $self, $ctx_ident: &mut $ctx) {
+                try_modify_property($entity_getter, &$self.path, |field| {
+                    move_collection_item(field, &$self.path, $self.to, $self.from)
+                })
+            }
+        }
     };
 }
@@ -20,7 +20,10 @@ use fyrox::{
     animation::{
         machine::{
             node::BasePoseNode,
-            transition::{AndNode, LogicNode, NotNode, OrNode, XorNode},
+            transition::{
+                AndNode, CompareNode, ComparisonOperator, ExitTimeNode, InRangeNode, LogicNode,
+                NotNode, OrNode, XorNode,
+            },
             BlendAnimations, BlendAnimationsByIndex, BlendPose, IndexedBlendInput, Machine,
             PlayAnimation, PoseNode, PoseWeight, State,
         },
@@ -28,6 +31,7 @@ use fyrox::{
     },
     core::{
         futures::executor::block_on,
+        math::spline::SplinePoint,
         parking_lot::Mutex,
         pool::{ErasedHandle, Handle},
     },
@@ -41,6 +45,7 @@ use fyrox::{
         SharedMaterial,
     },
     resource::{
+        animation::{AnimationResource, AnimationResourceError, AnimationResourceState},
         curve::{CurveResource, CurveResourceError, CurveResourceState},
         model::{MaterialSearchOptions, Model, ModelData, ModelLoadError},
         texture::{
@@ -51,6 +56,7 @@ use fyrox::{
     scene::{
         base::{
             Base, LevelOfDetail, LodControlledObject, LodGroup, Mobility, Property, PropertyValue,
+            ScriptRecord,
         },
         camera::{
             ColorGradingLut, Exposure, OrthographicProjection, PerspectiveProjection, Projection,
@@ -91,6 +97,7 @@ use fyrox::{
             Attenuate, AudioBus, Biquad, DistanceModel, Effect, EffectWrapper, SoundBufferResource,
             SoundBufferResourceLoadError, SoundBufferState, Status,
         },
+        spline::SplineKind,
         terrain::Layer,
         transform::Transform,
     },
@@ -152,6 +159,8 @@ pub fn make_property_editors_container(
     container.register_inheritable_vec_collection::<Property>();
     container.register_inheritable_vec_collection::<LodControlledObject>();
     container.register_inheritable_vec_collection::<GeometrySource>();
+    container.register_inheritable_inspectable::<SplinePoint>();
+    container.register_inheritable_vec_collection::<SplinePoint>();
 
     container.insert(make_status_enum_editor_definition());
 
@@ -201,6 +210,17 @@ pub fn make_property_editors_container(
     })));
     container.insert(InheritablePropertyEditorDefinition::<Option<CurveResource>>::new());
 
+    container.insert(ResourceFieldPropertyEditorDefinition::<
+        AnimationResource,
+        AnimationResourceState,
+        AnimationResourceError,
+    >::new(Rc::new(|resource_manager, path| {
+        block_on(resource_manager.request_animation(path))
+    })));
+    container.insert(InheritablePropertyEditorDefinition::<
+        Option<AnimationResource>,
+    >::new());
+
     container.insert(ResourceFieldPropertyEditorDefinition::<
         Shader,
         ShaderState,
@@ -279,8 +299,11 @@ pub fn make_property_editors_container(
     container.register_inheritable_enum::<DistanceModel, _>();
     container.register_inheritable_enum::<sound::Renderer, _>();
     container.register_inheritable_enum::<RenderPath, _>();
+    container.register_inheritable_enum::<SplineKind, _>();
 
     container.insert(ScriptPropertyEditorDefinition {});
+    container.insert(InspectablePropertyEditorDefinition::<ScriptRecord>::new());
+    container.insert(VecCollectionPropertyEditorDefinition::<ScriptRecord>::new());
     container.insert(BitFieldPropertyEditorDefinition::<BitMask>::new());
 
     container.register_inheritable_inspectable::<BallShape>();
@@ -335,6 +358,10 @@ pub fn make_property_editors_container(
     container.insert(InspectablePropertyEditorDefinition::<OrNode>::new());
     container.insert(InspectablePropertyEditorDefinition::<XorNode>::new());
     container.insert(InspectablePropertyEditorDefinition::<NotNode>::new());
+    container.insert(InspectablePropertyEditorDefinition::<CompareNode>::new());
+    container.insert(InspectablePropertyEditorDefinition::<InRangeNode>::new());
+    container.insert(InspectablePropertyEditorDefinition::<ExitTimeNode>::new());
+    container.insert(EnumPropertyEditorDefinition::<ComparisonOperator>::new());
 
     container.insert(InspectablePropertyEditorDefinition::<ParticleSystemRng>::new());
 
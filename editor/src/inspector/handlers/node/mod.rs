@@ -3,13 +3,19 @@ use crate::{
     scene::commands::{
         make_set_node_property_command,
         terrain::{AddTerrainLayerCommand, DeleteTerrainLayerCommand},
+        SetPropertyCommand,
     },
     SceneCommand,
 };
 use fyrox::{
     core::pool::Handle,
     gui::inspector::{CollectionChanged, FieldKind, PropertyChanged},
-    scene::{node::Node, terrain::Terrain},
+    scene::{
+        base::{Base, ScriptRecord},
+        node::Node,
+        terrain::Terrain,
+    },
+    script::Script,
 };
 use std::any::TypeId;
 
@@ -20,7 +26,7 @@ impl SceneNodePropertyChangedHandler {
         &self,
         args: &PropertyChanged,
         handle: Handle<Node>,
-        _node: &mut Node,
+        node: &mut Node,
     ) -> Option<SceneCommand> {
         // Terrain is special and have its own commands for specific properties.
         if args.path() == Terrain::LAYERS && args.owner_type_id == TypeId::of::<Terrain>() {
@@ -32,14 +38,89 @@ impl SceneNodePropertyChangedHandler {
                     CollectionChanged::Remove(index) => Some(SceneCommand::new(
                         DeleteTerrainLayerCommand::new(handle, index),
                     )),
-                    CollectionChanged::ItemChanged { .. } => None,
+                    CollectionChanged::ItemChanged { .. } | CollectionChanged::Move { .. } => None,
                 },
                 _ => None,
             }
         } else {
-            None
+            try_get_scripts_command(args, handle, node)
+        }
+    }
+}
+
+// Scripts of a node must be replaced as a whole list, otherwise they won't be correctly initialized
+// and destroyed.
+fn try_get_scripts_command(
+    args: &PropertyChanged,
+    handle: Handle<Node>,
+    node: &Node,
+) -> Option<SceneCommand> {
+    let mut path = args.name.clone();
+    let mut property = args;
+    while let FieldKind::Inspectable(ref inner) = property.value {
+        path += ".";
+        path += &inner.name;
+        property = inner;
+    }
+
+    if property.name != Base::SCRIPTS || property.owner_type_id != TypeId::of::<Base>() {
+        return None;
+    }
+
+    let collection_changed = match property.value {
+        FieldKind::Collection(ref collection_changed) => collection_changed,
+        _ => return None,
+    };
+
+    let mut scripts = (0..node.script_count())
+        .map(|i| {
+            node.script_at(i)
+                .map(|script| ScriptRecord::new(script.clone()))
+                .unwrap_or_default()
+        })
+        .collect::<Vec<_>>();
+
+    match **collection_changed {
+        CollectionChanged::Add(ref value) => {
+            value.cast_clone(&mut |record: Option<ScriptRecord>| {
+                scripts.push(record.unwrap_or_default())
+            });
+        }
+        CollectionChanged::Remove(index) => {
+            if index < scripts.len() {
+                scripts.remove(index);
+            }
         }
+        CollectionChanged::Move { from, to } => {
+            if from < scripts.len() && to < scripts.len() {
+                let record = scripts.remove(from);
+                scripts.insert(to, record);
+            }
+        }
+        CollectionChanged::ItemChanged {
+            index,
+            property: ref item_property,
+        } => match item_property.value {
+            // Replacement of a script, changes of script properties are handled as usual.
+            FieldKind::Object(ref value) if item_property.name == ScriptRecord::SCRIPT => {
+                let mut script = None;
+                value.cast_clone(&mut |value: Option<Option<Script>>| script = value);
+                match (scripts.get_mut(index), script) {
+                    (Some(record), Some(script)) => {
+                        *record = script.map(ScriptRecord::new).unwrap_or_default()
+                    }
+                    _ => return None,
+                }
+            }
+            _ => return None,
+        },
     }
+
+    Some(SceneCommand::new(SetPropertyCommand::new(
+        handle,
+        path,
+        Box::new(scripts),
+    )))
 }
 
 impl SceneNodePropertyChangedHandler {
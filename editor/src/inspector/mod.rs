@@ -10,7 +10,10 @@ use crate::{
     inspector::{
         editors::make_property_editors_container, handlers::node::SceneNodePropertyChangedHandler,
     },
-    scene::{commands::effect::make_set_audio_bus_property_command, EditorScene, Selection},
+    scene::{
+        commands::{effect::make_set_audio_bus_property_command, graph::InvokeMethodCommand},
+        EditorScene, Selection,
+    },
     send_sync_message,
     utils::window_content,
     Brush, CommandGroup, GameEngine, Message, Mode, WidgetMessage, WrapMode, MSG_SYNC_FLAG,
@@ -20,6 +23,7 @@ use fyrox::{
     core::{color::Color, pool::Handle, reflect::prelude::*},
     engine::{resource_manager::ResourceManager, SerializationContext},
     gui::{
+        button::{ButtonBuilder, ButtonMessage},
         grid::{Column, GridBuilder, Row},
         inspector::{
             editors::PropertyEditorDefinitionContainer, InspectorBuilder, InspectorContext,
@@ -28,18 +32,22 @@ use fyrox::{
         message::{MessageDirection, UiMessage},
         scroll_viewer::ScrollViewerBuilder,
         text::{TextBuilder, TextMessage},
+        utils::make_simple_tooltip,
         widget::WidgetBuilder,
         window::{WindowBuilder, WindowTitle},
-        BuildContext, Thickness, UiNode, UserInterface,
+        wrap_panel::WrapPanelBuilder,
+        BuildContext, Orientation, Thickness, UiNode, UserInterface,
     },
     scene::{
         animation::{absm::AnimationBlendingStateMachine, AnimationPlayer},
         graph::Graph,
+        node::Node,
     },
     utils::log::{Log, MessageKind},
 };
 use std::{
     any::Any,
+    collections::HashMap,
     rc::Rc,
     sync::{mpsc::Sender, Arc},
 };
@@ -88,6 +96,15 @@ pub struct Inspector {
     node_property_changed_handler: SceneNodePropertyChangedHandler,
     warning_text: Handle<UiNode>,
     type_name_text: Handle<UiNode>,
+    actions_panel: Handle<UiNode>,
+    actions: HashMap<Handle<UiNode>, InspectorAction>,
+}
+
+// A button that invokes a method without arguments of the selected scene node or one of its
+// scripts.
+struct InspectorAction {
+    script_index: Option<usize>,
+    method: &'static str,
 }
 
 #[macro_export]
@@ -139,6 +156,7 @@ impl Inspector {
 
         let warning_text;
         let type_name_text;
+        let actions_panel;
         let inspector;
         let window = WindowBuilder::new(WidgetBuilder::new())
             .with_title(WindowTitle::text("Inspector"))
@@ -168,8 +186,18 @@ impl Inspector {
                             .build(ctx);
                             type_name_text
                         })
+                        .with_child({
+                            actions_panel = WrapPanelBuilder::new(
+                                WidgetBuilder::new()
+                                    .with_margin(Thickness::uniform(2.0))
+                                    .on_row(2),
+                            )
+                            .with_orientation(Orientation::Horizontal)
+                            .build(ctx);
+                            actions_panel
+                        })
                         .with_child(
-                            ScrollViewerBuilder::new(WidgetBuilder::new().on_row(2))
+                            ScrollViewerBuilder::new(WidgetBuilder::new().on_row(3))
                                 .with_content({
                                     inspector =
                                         InspectorBuilder::new(WidgetBuilder::new()).build(ctx);
@@ -180,6 +208,7 @@ impl Inspector {
                 )
                 .add_row(Row::auto())
                 .add_row(Row::auto())
+                .add_row(Row::auto())
                 .add_row(Row::stretch())
                 .add_column(Column::stretch())
                 .build(ctx),
@@ -194,6 +223,59 @@ impl Inspector {
             node_property_changed_handler: SceneNodePropertyChangedHandler,
             warning_text,
             type_name_text,
+            actions_panel,
+            actions: Default::default(),
+        }
+    }
+
+    fn update_actions(&mut self, node: Option<&Node>, ui: &mut UserInterface) {
+        self.actions.clear();
+        for &child in ui.node(self.actions_panel).children() {
+            send_sync_message(ui, WidgetMessage::remove(child, MessageDirection::ToWidget));
+        }
+
+        let node = match node {
+            Some(node) => node,
+            None => return,
+        };
+
+        let mut methods = Vec::new();
+        node.methods_info(&mut |info| methods.extend(info.into_iter().map(|m| (None, m))));
+        for index in 0..node.script_count() {
+            if let Some(script) = node.script_at(index) {
+                script.methods_info(&mut |info| {
+                    methods.extend(info.into_iter().map(|m| (Some(index), m)))
+                });
+            }
+        }
+
+        // Only methods without arguments could be invoked by a single click.
+        for (script_index, method) in methods
+            .into_iter()
+            .filter(|(_, method)| method.argument_type_names.is_empty())
+        {
+            let ctx = &mut ui.build_ctx();
+            let mut widget_builder = WidgetBuilder::new().with_margin(Thickness::uniform(1.0));
+            if !method.description.is_empty() {
+                widget_builder =
+                    widget_builder.with_tooltip(make_simple_tooltip(ctx, method.description));
+            }
+            let button = ButtonBuilder::new(widget_builder)
+                .with_text(method.display_name)
+                .build(ctx);
+
+            send_sync_message(
+                ui,
+                WidgetMessage::link(button, MessageDirection::ToWidget, self.actions_panel),
+            );
+
+            self.actions.insert(
+                button,
+                InspectorAction {
+                    script_index,
+                    method: method.name,
+                },
+            );
         }
     }
 
@@ -385,6 +467,16 @@ impl Inspector {
                     editor_scene.selection.len() > 1,
                 ));
 
+            let selected_node = if let Selection::Graph(selection) = &editor_scene.selection {
+                selection
+                    .nodes()
+                    .first()
+                    .and_then(|handle| scene.graph.try_get(*handle))
+            } else {
+                None
+            };
+            self.update_actions(selected_node, &mut engine.user_interface);
+
             if !editor_scene.selection.is_empty() {
                 match &editor_scene.selection {
                     Selection::Graph(selection) => {
@@ -492,12 +584,14 @@ impl Inspector {
                     _ => (),
                 };
             } else {
-                self.clear(&engine.user_interface);
+                self.clear(&mut engine.user_interface);
             }
         }
     }
 
-    pub fn clear(&self, ui: &UserInterface) {
+    pub fn clear(&mut self, ui: &mut UserInterface) {
+        self.update_actions(None, ui);
+
         ui.send_message(InspectorMessage::context(
             self.inspector,
             MessageDirection::ToWidget,
@@ -520,6 +614,22 @@ impl Inspector {
         engine: &mut GameEngine,
         sender: &Sender<Message>,
     ) {
+        if let Some(ButtonMessage::Click) = message.data() {
+            if let Some(action) = self.actions.get(&message.destination()) {
+                if let Selection::Graph(selection) = &editor_scene.selection {
+                    if let Some(&node) = selection.nodes().first() {
+                        sender
+                            .send(Message::do_scene_command(InvokeMethodCommand::new(
+                                node,
+                                action.script_index,
+                                action.method.to_owned(),
+                            )))
+                            .unwrap();
+                    }
+                }
+            }
+        }
+
         let scene = &mut engine.scenes[editor_scene.scene];
 
         if message.destination() == self.inspector
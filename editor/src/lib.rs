@@ -32,6 +32,7 @@ mod preview;
 mod scene;
 mod scene_viewer;
 mod settings;
+mod timeline;
 mod utils;
 mod world;
 
@@ -71,6 +72,7 @@ use crate::{
     },
     scene_viewer::SceneViewer,
     settings::{camera::SceneCameraSettings, Settings},
+    timeline::FrameTimelineWindow,
     utils::path_fixer::PathFixer,
     world::{graph::selection::GraphSelection, WorldViewer},
 };
@@ -109,7 +111,7 @@ use fyrox::{
         BuildContext, UiNode, UserInterface, VerticalAlignment,
     },
     material::{shader::Shader, Material, PropertyValue, SharedMaterial},
-    plugin::PluginConstructor,
+    plugin::{dynamic::DynamicPluginError, PluginConstructor},
     resource::texture::{CompressionOptions, Texture, TextureKind},
     scene::{
         camera::{Camera, Projection},
@@ -479,6 +481,7 @@ pub struct Editor {
     configurator: Configurator,
     log: LogPanel,
     command_stack_viewer: CommandStackViewer,
+    frame_timeline: FrameTimelineWindow,
     validation_message_box: Handle<UiNode>,
     navmesh_panel: NavmeshPanel,
     settings: Settings,
@@ -618,6 +621,7 @@ impl Editor {
         let navmesh_panel = NavmeshPanel::new(ctx, message_sender.clone());
         let world_outliner = WorldViewer::new(ctx, message_sender.clone(), &settings);
         let command_stack_viewer = CommandStackViewer::new(ctx, message_sender.clone());
+        let frame_timeline = FrameTimelineWindow::new(ctx);
         let log = LogPanel::new(ctx, log_message_receiver);
         let inspector = Inspector::new(ctx, message_sender.clone());
         let animation_editor = AnimationEditor::new(ctx);
@@ -782,6 +786,7 @@ impl Editor {
             log,
             light_panel,
             command_stack_viewer,
+            frame_timeline,
             validation_message_box,
             settings,
             path_fixer,
@@ -1123,6 +1128,7 @@ impl Editor {
                     curve_editor: &self.curve_editor,
                     absm_editor: &self.absm_editor,
                     command_stack_panel: self.command_stack_viewer.window,
+                    frame_timeline: self.frame_timeline.window,
                     scene_settings: &self.scene_settings,
                     animation_editor: &self.animation_editor,
                 },
@@ -1136,6 +1142,8 @@ impl Editor {
         self.asset_browser
             .handle_ui_message(message, engine, self.message_sender.clone());
         self.command_stack_viewer.handle_ui_message(message);
+        self.frame_timeline
+            .handle_ui_message(message, &mut engine.user_interface);
         self.curve_editor.handle_ui_message(message, engine);
         self.path_fixer.handle_ui_message(
             message,
@@ -1385,7 +1393,7 @@ impl Editor {
                 &mut engine.user_interface,
             )
         } else {
-            self.inspector.clear(&engine.user_interface);
+            self.inspector.clear(&mut engine.user_interface);
             self.world_viewer.clear(&engine.user_interface);
         }
     }
@@ -1627,6 +1635,14 @@ impl Editor {
             }
         }
 
+        // Imported resources are cached in the project directory, so the heavy import (FBX conversion,
+        // texture compression, etc.) is done only once.
+        engine
+            .resource_manager
+            .state()
+            .import_cache()
+            .set_directory(Some(working_directory.join(".cache").join("import")));
+
         engine.resource_manager.state().destroy_unused_resources();
 
         graphics_context.renderer.flush();
@@ -2010,6 +2026,13 @@ impl Editor {
         self.engine.add_plugin_constructor(plugin)
     }
 
+    pub fn add_dynamic_game_plugin<P>(&mut self, path: P) -> Result<(), DynamicPluginError>
+    where
+        P: AsRef<Path>,
+    {
+        self.engine.add_dynamic_plugin(path, true)
+    }
+
     pub fn run(mut self, event_loop: EventLoop<()>) -> ! {
         event_loop.run(move |event, _, control_flow| match event {
             Event::MainEventsCleared => {
@@ -2160,6 +2183,11 @@ fn update(editor: &mut Editor, control_flow: &mut ControlFlow) {
         }
     }
 
+    let alpha = (editor.game_loop_data.lag / FIXED_TIMESTEP).clamp(0.0, 1.0);
+    editor
+        .engine
+        .frame(elapsed, alpha, control_flow, &mut editor.game_loop_data.lag);
+
     let window = &editor.engine.graphics_context.as_initialized_ref().window;
     window.set_cursor_icon(translate_cursor_icon(editor.engine.user_interface.cursor()));
     window.request_redraw();
@@ -37,6 +37,7 @@ use fyrox::{
         },
         pivot::PivotBuilder,
         sound::{listener::ListenerBuilder, SoundBuilder},
+        spline::SplineBuilder,
         sprite::SpriteBuilder,
         terrain::{Layer, TerrainBuilder},
     },
@@ -92,6 +93,7 @@ pub struct CreateEntityMenu {
     create_spot_light: Handle<UiNode>,
     create_directional_light: Handle<UiNode>,
     create_navmesh: Handle<UiNode>,
+    create_spline: Handle<UiNode>,
     create_terrain: Handle<UiNode>,
     create_camera: Handle<UiNode>,
     create_sprite: Handle<UiNode>,
@@ -118,6 +120,7 @@ impl CreateEntityMenu {
         let create_sprite;
         let create_decal;
         let create_navmesh;
+        let create_spline;
         let create_particle_system;
         let create_terrain;
         let create_pivot;
@@ -220,6 +223,10 @@ impl CreateEntityMenu {
                 create_navmesh = create_menu_item("Navmesh", vec![], ctx);
                 create_navmesh
             },
+            {
+                create_spline = create_menu_item("Spline", vec![], ctx);
+                create_spline
+            },
         ];
 
         (
@@ -240,6 +247,7 @@ impl CreateEntityMenu {
                 create_sound_source,
                 create_listener,
                 create_navmesh,
+                create_spline,
                 create_decal,
                 physics_menu,
                 physics2d_menu,
@@ -350,6 +358,16 @@ impl CreateEntityMenu {
                                 .with_navmesh(navmesh)
                                 .build_node(),
                         )
+                    } else if message.destination() == self.create_spline {
+                        Some(
+                            SplineBuilder::new(BaseBuilder::new().with_name("Spline"))
+                                .with_positions(&[
+                                    Vector3::new(-1.0, 0.0, 0.0),
+                                    Vector3::new(0.0, 0.0, 1.0),
+                                    Vector3::new(1.0, 0.0, 0.0),
+                                ])
+                                .build_node(),
+                        )
                     } else if message.destination() == self.create_sprite {
                         Some(
                             SpriteBuilder::new(BaseBuilder::new().with_name("Sprite")).build_node(),
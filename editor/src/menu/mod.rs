@@ -48,6 +48,7 @@ pub struct Panels<'b> {
     pub navmesh_panel: Handle<UiNode>,
     pub audio_panel: Handle<UiNode>,
     pub command_stack_panel: Handle<UiNode>,
+    pub frame_timeline: Handle<UiNode>,
     pub inspector_window: Handle<UiNode>,
     pub world_outliner_window: Handle<UiNode>,
     pub asset_window: Handle<UiNode>,
@@ -3,12 +3,14 @@ use fyrox::{
     core::{
         algebra::{UnitQuaternion, Vector3},
         pool::{Handle, Ticket},
+        reflect::prelude::*,
     },
     scene::{
         base::Base,
         graph::{Graph, SubGraph},
         node::Node,
     },
+    utils::log::Log,
 };
 
 #[derive(Debug)]
@@ -403,3 +405,71 @@ impl Command for ReplaceNodeCommand {
         self.swap(context);
     }
 }
+
+/// Invokes a method (see `Reflect::invoke_method`) of a scene node or one of its scripts. A copy of
+/// the node is made before the invocation, so the changes could be reverted.
+#[derive(Debug)]
+pub struct InvokeMethodCommand {
+    handle: Handle<Node>,
+    script_index: Option<usize>,
+    method: String,
+    node: Option<Node>,
+}
+
+impl InvokeMethodCommand {
+    pub fn new(handle: Handle<Node>, script_index: Option<usize>, method: String) -> Self {
+        Self {
+            handle,
+            script_index,
+            method,
+            node: None,
+        }
+    }
+
+    fn swap(&mut self, context: &mut SceneContext) {
+        std::mem::swap(
+            &mut context.scene.graph[self.handle],
+            self.node.as_mut().unwrap(),
+        );
+    }
+}
+
+impl Command for InvokeMethodCommand {
+    fn name(&mut self, _context: &SceneContext) -> String {
+        format!("Invoke {}", self.method)
+    }
+
+    fn execute(&mut self, context: &mut SceneContext) {
+        if self.node.is_some() {
+            self.swap(context);
+            return;
+        }
+
+        let node = &mut context.scene.graph[self.handle];
+        self.node = Some(node.clone_box());
+
+        let result = match self.script_index {
+            Some(index) => node
+                .script_at_mut(index)
+                .map(|script| (script as &mut dyn Reflect).call_method(&self.method, vec![])),
+            None => {
+                let mut result = None;
+                node.as_reflect_mut(&mut |node| {
+                    result = Some(node.call_method(&self.method, vec![]))
+                });
+                result
+            }
+        };
+
+        if let Some(Err(error)) = result {
+            Log::err(format!(
+                "Failed to invoke method {}. Reason: {}",
+                self.method, error
+            ));
+        }
+    }
+
+    fn revert(&mut self, context: &mut SceneContext) {
+        self.swap(context);
+    }
+}
@@ -8,7 +8,7 @@ use fyrox::{
     core::{
         algebra::{Matrix4, Point3, UnitQuaternion, Vector3},
         color::Color,
-        math::{aabb::AxisAlignedBoundingBox, frustum::Frustum, Matrix4Ext},
+        math::{aabb::AxisAlignedBoundingBox, frustum::Frustum, spline::Spline as _, Matrix4Ext},
         pool::Handle,
         visitor::Visitor,
     },
@@ -26,6 +26,7 @@ use fyrox::{
         navmesh::NavigationalMesh,
         node::Node,
         pivot::PivotBuilder,
+        spline::{Spline, SplineKind},
         Scene,
     },
 };
@@ -282,6 +283,36 @@ impl EditorScene {
                         false,
                     );
                 }
+            } else if let Some(spline) = node.query_component_ref::<Spline>() {
+                const SAMPLES_PER_SEGMENT: usize = 16;
+
+                let curve = spline.world_curve();
+                let sample_count = curve.segment_count() * SAMPLES_PER_SEGMENT;
+                for i in 0..sample_count {
+                    ctx.add_line(Line {
+                        begin: curve.position(i as f32 / SAMPLES_PER_SEGMENT as f32),
+                        end: curve.position((i + 1) as f32 / SAMPLES_PER_SEGMENT as f32),
+                        color: Color::GREEN,
+                    });
+                }
+
+                let transform = spline.global_transform();
+                let radius = settings.debugging.pictogram_size * 0.1;
+                for point in spline.points() {
+                    let position = transform
+                        .transform_point(&Point3::from(point.position))
+                        .coords;
+                    ctx.draw_sphere(position, 8, 8, radius, Color::ORANGE);
+                    if spline.kind() == SplineKind::Bezier {
+                        for handle in [point.in_tangent, point.out_tangent] {
+                            ctx.add_line(Line {
+                                begin: position,
+                                end: position + transform.transform_vector(&handle),
+                                color: Color::ORANGE,
+                            });
+                        }
+                    }
+                }
             } else if let Some(navmesh) = node.query_component_ref::<NavigationalMesh>() {
                 if settings.navmesh.draw_all {
                     let selection =
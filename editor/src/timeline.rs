@@ -0,0 +1,304 @@
+//! Frame timeline window shows scopes of every thread recorded by the profiler during a single frame. It
+//! is available only if the editor is compiled with `enable_profiler` feature.
+
+use fyrox::{
+    core::{
+        algebra::Vector2,
+        color::Color,
+        pool::Handle,
+        profiler::{self, FrameRecord, TimelineSnapshot},
+        scope_profile,
+    },
+    gui::{
+        border::BorderBuilder,
+        brush::Brush,
+        button::{ButtonBuilder, ButtonMessage},
+        canvas::CanvasBuilder,
+        formatted_text::WrapMode,
+        grid::{Column, GridBuilder, Row},
+        message::{MessageDirection, UiMessage},
+        scroll_viewer::{ScrollViewerBuilder, ScrollViewerMessage},
+        stack_panel::StackPanelBuilder,
+        text::{TextBuilder, TextMessage},
+        utils::make_simple_tooltip,
+        widget::WidgetBuilder,
+        window::{WindowBuilder, WindowTitle},
+        BuildContext, Orientation, Thickness, UiNode, UserInterface, VerticalAlignment,
+    },
+    utils::log::Log,
+};
+
+/// Horizontal scale of the timeline in pixels per millisecond.
+const PIXELS_PER_MS: f64 = 100.0;
+const ROW_HEIGHT: f32 = 20.0;
+const THREAD_HEADER_HEIGHT: f32 = 22.0;
+const TRACE_FILE_NAME: &str = "trace.json";
+
+pub struct FrameTimelineWindow {
+    pub window: Handle<UiNode>,
+    capture: Handle<UiNode>,
+    previous: Handle<UiNode>,
+    next: Handle<UiNode>,
+    longest: Handle<UiNode>,
+    export: Handle<UiNode>,
+    summary: Handle<UiNode>,
+    scroll_viewer: Handle<UiNode>,
+    snapshot: TimelineSnapshot,
+    frame: usize,
+}
+
+fn make_button(ctx: &mut BuildContext, text: &str, tooltip: &str) -> Handle<UiNode> {
+    ButtonBuilder::new(
+        WidgetBuilder::new()
+            .with_width(90.0)
+            .with_margin(Thickness::uniform(1.0))
+            .with_tooltip(make_simple_tooltip(ctx, tooltip)),
+    )
+    .with_text(text)
+    .build(ctx)
+}
+
+// Picks a stable color for a scope name, so the same scopes have the same color in every frame.
+fn scope_color(name: &str) -> Color {
+    let hash = name
+        .bytes()
+        .fold(5381u32, |hash, byte| hash.wrapping_mul(33) ^ byte as u32);
+    Color::opaque(
+        80 + (hash % 120) as u8,
+        80 + ((hash >> 8) % 120) as u8,
+        80 + ((hash >> 16) % 120) as u8,
+    )
+}
+
+impl FrameTimelineWindow {
+    pub fn new(ctx: &mut BuildContext) -> Self {
+        let capture = make_button(
+            ctx,
+            "Capture",
+            "Capture recorded frames and show the longest one.",
+        );
+        let previous = make_button(ctx, "< Previous", "Show previous frame.");
+        let next = make_button(ctx, "Next >", "Show next frame.");
+        let longest = make_button(ctx, "Longest", "Show the longest captured frame.");
+        let export = make_button(
+            ctx,
+            "Export",
+            "Save captured frames in Chrome trace format to trace.json file in the working directory.\n\
+            It could be opened in chrome://tracing or https://ui.perfetto.dev",
+        );
+
+        let summary;
+        let scroll_viewer;
+        let window = WindowBuilder::new(WidgetBuilder::new().with_width(700.0).with_height(400.0))
+            .open(false)
+            .with_title(WindowTitle::text("Frame Timeline"))
+            .with_content(
+                GridBuilder::new(
+                    WidgetBuilder::new()
+                        .with_child(
+                            StackPanelBuilder::new(
+                                WidgetBuilder::new()
+                                    .with_child(capture)
+                                    .with_child(previous)
+                                    .with_child(next)
+                                    .with_child(longest)
+                                    .with_child(export)
+                                    .with_child({
+                                        summary = TextBuilder::new(
+                                            WidgetBuilder::new().with_margin(Thickness::left(5.0)),
+                                        )
+                                        .with_vertical_text_alignment(VerticalAlignment::Center)
+                                        .with_text(if profiler::is_timeline_enabled() {
+                                            "Press Capture to show recorded frames."
+                                        } else {
+                                            "Profiler is disabled, compile the editor with \
+                                            enable_profiler feature."
+                                        })
+                                        .build(ctx);
+                                        summary
+                                    }),
+                            )
+                            .with_orientation(Orientation::Horizontal)
+                            .build(ctx),
+                        )
+                        .with_child({
+                            scroll_viewer = ScrollViewerBuilder::new(
+                                WidgetBuilder::new()
+                                    .on_row(1)
+                                    .with_margin(Thickness::uniform(1.0)),
+                            )
+                            .with_horizontal_scroll_allowed(true)
+                            .with_vertical_scroll_allowed(true)
+                            .build(ctx);
+                            scroll_viewer
+                        }),
+                )
+                .add_column(Column::stretch())
+                .add_row(Row::strict(26.0))
+                .add_row(Row::stretch())
+                .build(ctx),
+            )
+            .build(ctx);
+
+        Self {
+            window,
+            capture,
+            previous,
+            next,
+            longest,
+            export,
+            summary,
+            scroll_viewer,
+            snapshot: Default::default(),
+            frame: 0,
+        }
+    }
+
+    pub fn handle_ui_message(&mut self, message: &UiMessage, ui: &mut UserInterface) {
+        scope_profile!();
+
+        if let Some(ButtonMessage::Click) = message.data() {
+            if message.destination() == self.capture {
+                self.snapshot = profiler::timeline();
+                self.show_longest_frame(ui);
+            } else if message.destination() == self.longest {
+                self.show_longest_frame(ui);
+            } else if message.destination() == self.previous {
+                if self.frame > 0 {
+                    self.show_frame(self.frame - 1, ui);
+                }
+            } else if message.destination() == self.next {
+                self.show_frame(self.frame + 1, ui);
+            } else if message.destination() == self.export {
+                match self.snapshot.save_chrome_trace(TRACE_FILE_NAME) {
+                    Ok(_) => Log::info(format!(
+                        "Frame timeline was saved to {TRACE_FILE_NAME} file."
+                    )),
+                    Err(e) => Log::err(format!("Unable to save frame timeline. Reason: {e:?}")),
+                }
+            }
+        }
+    }
+
+    fn show_longest_frame(&mut self, ui: &mut UserInterface) {
+        let longest = self
+            .snapshot
+            .longest_frame()
+            .and_then(|longest| self.snapshot.frames.iter().position(|f| f == longest))
+            .unwrap_or_default();
+        self.show_frame(longest, ui);
+    }
+
+    fn show_frame(&mut self, index: usize, ui: &mut UserInterface) {
+        let frame = match self.snapshot.frames.get(index) {
+            Some(frame) => frame.clone(),
+            None => return,
+        };
+        self.frame = index;
+
+        let average = self
+            .snapshot
+            .frames
+            .iter()
+            .map(|frame| frame.duration)
+            .sum::<f64>()
+            / self.snapshot.frames.len() as f64;
+
+        ui.send_message(TextMessage::text(
+            self.summary,
+            MessageDirection::ToWidget,
+            format!(
+                "Frame {} ({}/{}) took {:.3} ms, average is {:.3} ms.",
+                frame.index,
+                index + 1,
+                self.snapshot.frames.len(),
+                frame.duration * 1000.0,
+                average * 1000.0
+            ),
+        ));
+
+        let canvas = self.build_frame_canvas(&frame, &mut ui.build_ctx());
+        ui.send_message(ScrollViewerMessage::content(
+            self.scroll_viewer,
+            MessageDirection::ToWidget,
+            canvas,
+        ));
+    }
+
+    fn build_frame_canvas(&self, frame: &FrameRecord, ctx: &mut BuildContext) -> Handle<UiNode> {
+        let to_pixels = |time: f64| ((time - frame.start) * 1000.0 * PIXELS_PER_MS) as f32;
+        let width = to_pixels(frame.end());
+
+        let mut children = Vec::new();
+        let mut y = 0.0;
+        for (thread, thread_name) in self.snapshot.threads.iter().enumerate() {
+            let scopes = self
+                .snapshot
+                .scopes_in_range(frame.start, frame.end())
+                .filter(|scope| scope.thread == thread)
+                .collect::<Vec<_>>();
+
+            if scopes.is_empty() {
+                continue;
+            }
+
+            children.push(
+                TextBuilder::new(
+                    WidgetBuilder::new()
+                        .with_desired_position(Vector2::new(0.0, y))
+                        .with_height(THREAD_HEADER_HEIGHT)
+                        .with_foreground(Brush::Solid(Color::opaque(220, 220, 220))),
+                )
+                .with_vertical_text_alignment(VerticalAlignment::Center)
+                .with_text(thread_name)
+                .build(ctx),
+            );
+            y += THREAD_HEADER_HEIGHT;
+
+            let mut max_depth = 0;
+            for scope in scopes {
+                max_depth = max_depth.max(scope.depth);
+
+                let left = to_pixels(scope.start.max(frame.start));
+                let right = to_pixels(scope.end().min(frame.end()));
+                let tooltip = format!("{}\n{:.3} ms", scope.name, scope.duration * 1000.0);
+
+                children.push(
+                    BorderBuilder::new(
+                        WidgetBuilder::new()
+                            .with_desired_position(Vector2::new(
+                                left,
+                                y + scope.depth as f32 * ROW_HEIGHT,
+                            ))
+                            .with_width((right - left).max(1.0))
+                            .with_height(ROW_HEIGHT - 1.0)
+                            .with_background(Brush::Solid(scope_color(&scope.name)))
+                            .with_foreground(Brush::Solid(Color::opaque(40, 40, 40)))
+                            .with_tooltip(make_simple_tooltip(ctx, &tooltip))
+                            .with_child(
+                                TextBuilder::new(
+                                    WidgetBuilder::new().with_margin(Thickness::left(2.0)),
+                                )
+                                .with_wrap(WrapMode::NoWrap)
+                                .with_vertical_text_alignment(VerticalAlignment::Center)
+                                .with_text(scope.name.as_ref())
+                                .build(ctx),
+                            ),
+                    )
+                    .with_stroke_thickness(Thickness::uniform(1.0))
+                    .build(ctx),
+                );
+            }
+
+            y += (max_depth + 1) as f32 * ROW_HEIGHT;
+        }
+
+        CanvasBuilder::new(
+            WidgetBuilder::new()
+                .with_width(width)
+                .with_height(y)
+                .with_children(children),
+        )
+        .build(ctx)
+    }
+}
@@ -191,12 +191,15 @@ fn main() {
     // Set up our custom loaders
     {
         let mut state = resource_manager.state();
+        let import_cache = state.import_cache().clone();
         let containers = state.containers_mut();
         containers.set_model_loader(CustomModelLoader(Arc::new(ModelLoader {
             resource_manager: resource_manager.clone(),
             serialization_context: serialization_context.clone(),
         })));
-        containers.set_texture_loader(CustomTextureLoader(Arc::new(TextureLoader)));
+        containers.set_texture_loader(CustomTextureLoader(Arc::new(TextureLoader::new(
+            import_cache,
+        ))));
     }
 
     let mut engine = Engine::new(EngineInitParams {
@@ -8,6 +8,9 @@ use syn::{parse_macro_input, DeriveInput};
 /// Implements `Visit` trait
 ///
 /// User has to import `Visit`, `Visitor` and `VisitResult` to use this macro.
+///
+/// `#[visit(version = N)]` attribute on a type stores version of its data, older data is upgraded using
+/// migrations registered with `Visitor::register_migration` before it is read.
 #[proc_macro_derive(Visit, attributes(visit))]
 pub fn visit(input: TokenStream) -> TokenStream {
     let ast = parse_macro_input!(input as DeriveInput);
@@ -408,6 +408,7 @@ fn gen_impl(
 
     let as_list_impl = ty_args.as_list_impl();
     let as_array_impl = ty_args.as_array_impl();
+    let methods_impl = ty_args.methods_impl();
 
     let set_field = set_field.map(|set_field| {
         quote! {
@@ -483,6 +484,8 @@ fn gen_impl(
             #as_array_impl
 
             #as_list_impl
+
+            #methods_impl
         }
     }
 }
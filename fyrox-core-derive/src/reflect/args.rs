@@ -1,5 +1,6 @@
 //! Derive input types defined with `darling`.
 
+use convert_case::{Case, Casing};
 use darling::*;
 use proc_macro2::TokenStream as TokenStream2;
 use quote::quote;
@@ -28,6 +29,13 @@ pub struct TypeArgs {
 
     #[darling(default, rename = "ReflectList")]
     pub impl_as_list: bool,
+
+    /// `#[reflect(method(name = "<method name>"))]`
+    ///
+    /// Methods that could be invoked using `Reflect::invoke_method`, the attribute could be used
+    /// multiple times.
+    #[darling(default, multiple)]
+    pub method: Vec<MethodArgs>,
 }
 
 impl TypeArgs {
@@ -90,6 +98,52 @@ impl TypeArgs {
         }
     }
 
+    pub fn methods_impl(&self) -> TokenStream2 {
+        if self.method.is_empty() {
+            return quote!();
+        }
+
+        let names = self
+            .method
+            .iter()
+            .map(|m| m.name.to_string())
+            .collect::<Vec<_>>();
+        let idents = self.method.iter().map(|m| &m.name).collect::<Vec<_>>();
+        let display_names = self.method.iter().map(|m| {
+            m.display_name
+                .clone()
+                .unwrap_or_else(|| m.name.to_string().to_case(Case::Title))
+        });
+        let descriptions = self
+            .method
+            .iter()
+            .map(|m| m.description.clone().unwrap_or_default());
+
+        quote! {
+            fn methods_info(&self, func: &mut dyn FnMut(Vec<MethodInfo>)) {
+                func(vec![
+                    #(
+                        MethodInfo::new::<Self, _, _>(#names, #display_names, #descriptions, &Self::#idents),
+                    )*
+                ])
+            }
+
+            fn invoke_method(
+                &mut self,
+                name: &str,
+                args: Vec<Box<dyn Reflect>>,
+                func: &mut dyn FnMut(Result<Box<dyn Reflect>, InvokeError>),
+            ) {
+                func(match name {
+                    #(
+                        #names => <_ as ReflectMethod<Self, _>>::invoke(&Self::#idents, self, args),
+                    )*
+                    _ => Err(InvokeError::UnknownMethod { name: name.to_owned() }),
+                })
+            }
+        }
+    }
+
     pub fn as_array_impl(&self) -> TokenStream2 {
         if !self.impl_as_array {
             return quote!();
@@ -216,6 +270,20 @@ impl FieldArgs {
     }
 }
 
+#[derive(FromMeta, Clone)]
+pub struct MethodArgs {
+    /// Name of the method.
+    pub name: Ident,
+
+    /// A human-readable name (default: the name in Title Case).
+    #[darling(default)]
+    pub display_name: Option<String>,
+
+    /// Description of the method.
+    #[darling(default)]
+    pub description: Option<String>,
+}
+
 #[derive(FromVariant)]
 #[darling(attributes(reflect))]
 pub struct VariantArgs {
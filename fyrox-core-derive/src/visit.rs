@@ -20,6 +20,8 @@ fn impl_visit_struct(
     ty_args: &args::TypeArgs,
     field_args: &ast::Fields<args::FieldArgs>,
 ) -> TokenStream2 {
+    let version_visit = utils::create_version_visit(ty_args);
+
     let visit_fn_body = if field_args.style == ast::Style::Unit {
         quote! { Ok(()) }
     } else {
@@ -32,6 +34,7 @@ fn impl_visit_struct(
                 Ok(x) => x,
                 Err(err) => return Err(err),
             };
+            #version_visit
             #(#field_visits)*
             Ok(())
         }
@@ -148,6 +151,8 @@ fn impl_visit_enum(ty_args: &args::TypeArgs, variant_args: &[args::VariantArgs])
         }
     };
 
+    let version_visit = utils::create_version_visit(ty_args);
+
     // visit every field of each variant
     let variant_visits = variant_args.iter().map(|variant| {
         let (fields, style) = (&variant.fields, variant.fields.style);
@@ -194,6 +199,8 @@ fn impl_visit_enum(ty_args: &args::TypeArgs, variant_args: &[args::VariantArgs])
                  Err(err) => return Err(err),
              };
 
+             #version_visit
+
              let mut id = id(self);
              if let Err(err) = id.visit("Id", &mut region) {
                  return Err(err);
@@ -9,6 +9,11 @@ pub struct TypeArgs {
     pub generics: Generics,
     pub data: ast::Data<VariantArgs, FieldArgs>,
     // attrs: Vec<Attribute>
+    /// `#[visit(version = ..)]`
+    ///
+    /// Version of the data, see `Visitor::visit_version`
+    #[darling(default)]
+    pub version: Option<u32>,
 }
 
 /// Parsed from struct's or enum variant's field
@@ -14,7 +14,14 @@ pub fn create_impl(
     impl_body: TokenStream2,
 ) -> TokenStream2 {
     let ty_ident = &ty_args.ident;
-    let generics = self::create_impl_generics(&ty_args.generics, field_args);
+    let mut generics = self::create_impl_generics(&ty_args.generics, field_args);
+    if ty_args.version.is_some() {
+        // `Visitor::visit_version` uses `TypeId` of the type to find migrations
+        generics
+            .make_where_clause()
+            .predicates
+            .push(parse_quote! { Self: 'static });
+    }
     let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
 
     quote! {
@@ -48,6 +55,18 @@ fn create_impl_generics(
     generics
 }
 
+/// `region.visit_version::<Self>(version)?;` if the type has `#[visit(version = ..)]` attribute
+pub fn create_version_visit(ty_args: &args::TypeArgs) -> TokenStream2 {
+    match ty_args.version {
+        Some(version) => quote! {
+            if let Err(err) = region.visit_version::<Self>(#version) {
+                return Err(err);
+            }
+        },
+        None => quote! {},
+    }
+}
+
 /// `<prefix>field.visit("name", visitor)?;`
 pub fn create_field_visits<'a>(
     // false if enum variant
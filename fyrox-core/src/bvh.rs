@@ -0,0 +1,685 @@
+//! Dynamic bounding volume hierarchy (BVH) - a binary tree of axis-aligned bounding boxes that
+//! could be updated incrementally. See [`DynamicBvh`] docs for more info.
+
+use crate::{
+    algebra::Vector3,
+    math::{aabb::AxisAlignedBoundingBox, frustum::Frustum, ray::Ray},
+    pool::{Handle, Pool},
+};
+
+/// Default margin that is used to enlarge bounds of leaves (see [`DynamicBvh::with_margin`]).
+pub const DEFAULT_MARGIN: f32 = 0.1;
+
+/// Kind of a node of the hierarchy.
+#[derive(Clone, Debug)]
+pub enum BvhNodeKind<T> {
+    /// A leaf node that holds user data.
+    Leaf {
+        /// Exact bounds of the user data, enlarged bounds are stored in the node.
+        aabb: AxisAlignedBoundingBox,
+        /// User data.
+        data: T,
+    },
+    /// A branch node with exactly two children.
+    Branch {
+        /// Handle of the left child.
+        left: Handle<BvhNode<T>>,
+        /// Handle of the right child.
+        right: Handle<BvhNode<T>>,
+    },
+}
+
+/// A node of the hierarchy.
+#[derive(Clone, Debug)]
+pub struct BvhNode<T> {
+    bounds: AxisAlignedBoundingBox,
+    parent: Handle<BvhNode<T>>,
+    height: u32,
+    kind: BvhNodeKind<T>,
+}
+
+impl<T> BvhNode<T> {
+    /// Returns bounds of the node. For leaves it is enlarged bounds of the user data, for branches
+    /// it is bounds that contains both children.
+    #[inline]
+    pub fn bounds(&self) -> &AxisAlignedBoundingBox {
+        &self.bounds
+    }
+
+    /// Returns handle of the parent node.
+    #[inline]
+    pub fn parent(&self) -> Handle<BvhNode<T>> {
+        self.parent
+    }
+
+    /// Returns height of the node in the tree, leaves have zero height.
+    #[inline]
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// Returns kind of the node.
+    #[inline]
+    pub fn kind(&self) -> &BvhNodeKind<T> {
+        &self.kind
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn children(&self) -> Option<(Handle<BvhNode<T>>, Handle<BvhNode<T>>)> {
+        match self.kind {
+            BvhNodeKind::Leaf { .. } => None,
+            BvhNodeKind::Branch { left, right } => Some((left, right)),
+        }
+    }
+
+    fn replace_child(&mut self, old: Handle<BvhNode<T>>, new: Handle<BvhNode<T>>) {
+        if let BvhNodeKind::Branch { left, right } = &mut self.kind {
+            if *left == old {
+                *left = new;
+            } else if *right == old {
+                *right = new;
+            }
+        }
+    }
+}
+
+fn union(a: &AxisAlignedBoundingBox, b: &AxisAlignedBoundingBox) -> AxisAlignedBoundingBox {
+    let mut result = *a;
+    result.add_box(*b);
+    result
+}
+
+fn surface_area(aabb: &AxisAlignedBoundingBox) -> f32 {
+    let d = aabb.max - aabb.min;
+    2.0 * (d.x * d.y + d.y * d.z + d.z * d.x)
+}
+
+fn contains(outer: &AxisAlignedBoundingBox, inner: &AxisAlignedBoundingBox) -> bool {
+    outer.min.x <= inner.min.x
+        && outer.min.y <= inner.min.y
+        && outer.min.z <= inner.min.z
+        && outer.max.x >= inner.max.x
+        && outer.max.y >= inner.max.y
+        && outer.max.z >= inner.max.z
+}
+
+/// Dynamic bounding volume hierarchy allows you to quickly find objects whose bounds intersect
+/// a frustum, a ray, a sphere or an axis-aligned bounding box. Unlike [`crate::octree::Octree`],
+/// which is built once, the hierarchy supports insertion, removal and movement of objects at
+/// any time.
+///
+/// Each leaf stores exact bounds of an object and slightly enlarged (by a margin) bounds, which
+/// are used to build the tree. When an object moves within its enlarged bounds, the tree is not
+/// modified at all, otherwise the leaf is re-inserted and the bounds of its ancestors are refitted.
+/// The tree is kept balanced using tree rotations, so every operation has `O(log(n))` complexity.
+///
+/// # Example
+///
+/// ```
+/// use fyrox_core::{
+///     algebra::Vector3, bvh::DynamicBvh, math::aabb::AxisAlignedBoundingBox,
+/// };
+///
+/// let mut bvh = DynamicBvh::new();
+/// let a = bvh.insert(AxisAlignedBoundingBox::unit(), "a");
+/// let mut b_bounds = AxisAlignedBoundingBox::unit();
+/// b_bounds.offset(Vector3::new(10.0, 0.0, 0.0));
+/// bvh.insert(b_bounds, "b");
+///
+/// let mut buffer = Vec::new();
+/// bvh.sphere_query(Vector3::new(10.0, 0.0, 0.0), 1.0, &mut buffer);
+/// assert_eq!(buffer, ["b"]);
+///
+/// // Move the first object next to the second one.
+/// bvh.set_bounds(a, b_bounds);
+/// bvh.sphere_query(Vector3::new(10.0, 0.0, 0.0), 1.0, &mut buffer);
+/// assert_eq!(buffer.len(), 2);
+/// ```
+#[derive(Clone, Debug)]
+pub struct DynamicBvh<T> {
+    nodes: Pool<BvhNode<T>>,
+    root: Handle<BvhNode<T>>,
+    margin: f32,
+    leaf_count: usize,
+}
+
+impl<T: 'static> Default for DynamicBvh<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: 'static> DynamicBvh<T> {
+    /// Creates new empty hierarchy with [`DEFAULT_MARGIN`].
+    pub fn new() -> Self {
+        Self::with_margin(DEFAULT_MARGIN)
+    }
+
+    /// Creates new empty hierarchy with the given margin. Margin defines how much the bounds of
+    /// leaves will be enlarged, larger margin means less tree updates for moving objects, but less
+    /// precise tree traversal.
+    pub fn with_margin(margin: f32) -> Self {
+        Self {
+            nodes: Pool::new(),
+            root: Handle::NONE,
+            margin,
+            leaf_count: 0,
+        }
+    }
+
+    /// Returns handle of the root node of the tree.
+    #[inline]
+    pub fn root(&self) -> Handle<BvhNode<T>> {
+        self.root
+    }
+
+    /// Returns a reference to every node of the tree.
+    #[inline]
+    pub fn nodes(&self) -> &Pool<BvhNode<T>> {
+        &self.nodes
+    }
+
+    /// Returns amount of objects in the hierarchy.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.leaf_count
+    }
+
+    /// Returns `true` if the hierarchy has no objects.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.leaf_count == 0
+    }
+
+    /// Returns height of the tree.
+    #[inline]
+    pub fn height(&self) -> u32 {
+        self.nodes
+            .try_borrow(self.root)
+            .map_or(0, |root| root.height)
+    }
+
+    /// Removes every object from the hierarchy.
+    pub fn clear(&mut self) {
+        self.nodes.clear();
+        self.root = Handle::NONE;
+        self.leaf_count = 0;
+    }
+
+    /// Adds new object with the given bounds to the hierarchy. Returned handle could be used to
+    /// move or remove the object later on.
+    pub fn insert(&mut self, aabb: AxisAlignedBoundingBox, data: T) -> Handle<BvhNode<T>> {
+        let leaf = self.nodes.spawn(BvhNode {
+            bounds: self.fat_bounds(&aabb),
+            parent: Handle::NONE,
+            height: 0,
+            kind: BvhNodeKind::Leaf { aabb, data },
+        });
+        self.insert_leaf(leaf);
+        self.leaf_count += 1;
+        leaf
+    }
+
+    /// Removes an object from the hierarchy and returns its data. Returns `None` if the handle
+    /// does not point to a leaf.
+    pub fn remove(&mut self, leaf: Handle<BvhNode<T>>) -> Option<T> {
+        match self.nodes.try_borrow(leaf)?.kind {
+            BvhNodeKind::Leaf { .. } => (),
+            BvhNodeKind::Branch { .. } => return None,
+        }
+        self.remove_leaf(leaf);
+        self.leaf_count -= 1;
+        match self.nodes.free(leaf).kind {
+            BvhNodeKind::Leaf { data, .. } => Some(data),
+            BvhNodeKind::Branch { .. } => unreachable!(),
+        }
+    }
+
+    /// Sets new bounds of an object. The tree will be changed only if the new bounds are not
+    /// contained in the enlarged bounds of the leaf, in this case the method returns `true`.
+    pub fn set_bounds(
+        &mut self,
+        leaf: Handle<BvhNode<T>>,
+        new_aabb: AxisAlignedBoundingBox,
+    ) -> bool {
+        let fat_bounds = self.fat_bounds(&new_aabb);
+        let node = match self.nodes.try_borrow_mut(leaf) {
+            Some(node) => node,
+            None => return false,
+        };
+        match &mut node.kind {
+            BvhNodeKind::Leaf { aabb, .. } => *aabb = new_aabb,
+            BvhNodeKind::Branch { .. } => return false,
+        }
+        if contains(&node.bounds, &new_aabb) {
+            return false;
+        }
+        node.bounds = fat_bounds;
+
+        self.remove_leaf(leaf);
+        self.insert_leaf(leaf);
+        true
+    }
+
+    /// Returns exact bounds of an object.
+    pub fn bounds(&self, leaf: Handle<BvhNode<T>>) -> Option<&AxisAlignedBoundingBox> {
+        match &self.nodes.try_borrow(leaf)?.kind {
+            BvhNodeKind::Leaf { aabb, .. } => Some(aabb),
+            BvhNodeKind::Branch { .. } => None,
+        }
+    }
+
+    /// Returns a reference to the data of an object.
+    pub fn data(&self, leaf: Handle<BvhNode<T>>) -> Option<&T> {
+        match &self.nodes.try_borrow(leaf)?.kind {
+            BvhNodeKind::Leaf { data, .. } => Some(data),
+            BvhNodeKind::Branch { .. } => None,
+        }
+    }
+
+    /// Returns a mutable reference to the data of an object.
+    pub fn data_mut(&mut self, leaf: Handle<BvhNode<T>>) -> Option<&mut T> {
+        match &mut self.nodes.try_borrow_mut(leaf)?.kind {
+            BvhNodeKind::Leaf { data, .. } => Some(data),
+            BvhNodeKind::Branch { .. } => None,
+        }
+    }
+
+    /// Visits every leaf whose exact bounds pass the given test. The test is also used to skip
+    /// entire sub-trees, so it must return `true` for any bounds that contain bounds that pass
+    /// the test. It is a building block for every other query.
+    pub fn query<P, F>(&self, mut test: P, mut func: F)
+    where
+        P: FnMut(&AxisAlignedBoundingBox) -> bool,
+        F: FnMut(Handle<BvhNode<T>>, &T),
+    {
+        if self.root.is_some() {
+            self.query_recursive(self.root, &mut test, &mut func);
+        }
+    }
+
+    fn query_recursive<P, F>(&self, node: Handle<BvhNode<T>>, test: &mut P, func: &mut F)
+    where
+        P: FnMut(&AxisAlignedBoundingBox) -> bool,
+        F: FnMut(Handle<BvhNode<T>>, &T),
+    {
+        let node_ref = self.nodes.borrow(node);
+        if !test(&node_ref.bounds) {
+            return;
+        }
+        match &node_ref.kind {
+            BvhNodeKind::Leaf { aabb, data } => {
+                if test(aabb) {
+                    func(node, data)
+                }
+            }
+            BvhNodeKind::Branch { left, right } => {
+                self.query_recursive(*left, test, func);
+                self.query_recursive(*right, test, func);
+            }
+        }
+    }
+
+    /// Collects every object whose bounds intersect the given bounds.
+    pub fn aabb_query(&self, aabb: &AxisAlignedBoundingBox, buffer: &mut Vec<T>)
+    where
+        T: Clone,
+    {
+        buffer.clear();
+        self.query(
+            |bounds| bounds.intersect_aabb(aabb),
+            |_, data| buffer.push(data.clone()),
+        );
+    }
+
+    /// Collects every object whose bounds intersect the given sphere.
+    pub fn sphere_query(&self, position: Vector3<f32>, radius: f32, buffer: &mut Vec<T>)
+    where
+        T: Clone,
+    {
+        buffer.clear();
+        self.query(
+            |bounds| bounds.is_intersects_sphere(position, radius),
+            |_, data| buffer.push(data.clone()),
+        );
+    }
+
+    /// Collects every object whose bounds intersect the given frustum.
+    pub fn frustum_query(&self, frustum: &Frustum, buffer: &mut Vec<T>)
+    where
+        T: Clone,
+    {
+        buffer.clear();
+        self.query(
+            |bounds| frustum.is_intersects_aabb(bounds),
+            |_, data| buffer.push(data.clone()),
+        );
+    }
+
+    /// Collects every object whose bounds intersect the given ray. Objects are not sorted by
+    /// distance.
+    pub fn ray_query(&self, ray: &Ray, buffer: &mut Vec<T>)
+    where
+        T: Clone,
+    {
+        buffer.clear();
+        self.query(
+            |bounds| ray.box_intersection(&bounds.min, &bounds.max).is_some(),
+            |_, data| buffer.push(data.clone()),
+        );
+    }
+
+    fn fat_bounds(&self, aabb: &AxisAlignedBoundingBox) -> AxisAlignedBoundingBox {
+        let mut fat = *aabb;
+        fat.inflate(Vector3::repeat(2.0 * self.margin));
+        fat
+    }
+
+    fn insert_leaf(&mut self, leaf: Handle<BvhNode<T>>) {
+        if self.root.is_none() {
+            self.root = leaf;
+            self.nodes[leaf].parent = Handle::NONE;
+            return;
+        }
+
+        // Find the best sibling using surface area heuristic.
+        let leaf_bounds = self.nodes[leaf].bounds;
+        let mut index = self.root;
+        while let Some((left, right)) = self.nodes[index].children() {
+            let area = surface_area(&self.nodes[index].bounds);
+            let combined_area = surface_area(&union(&self.nodes[index].bounds, &leaf_bounds));
+
+            // Cost of creating a new parent for this node and the new leaf.
+            let cost = 2.0 * combined_area;
+            // Minimum cost of pushing the leaf further down the tree.
+            let inheritance_cost = 2.0 * (combined_area - area);
+
+            let child_cost = |child: Handle<BvhNode<T>>| {
+                let child = &self.nodes[child];
+                let combined = surface_area(&union(&child.bounds, &leaf_bounds));
+                match child.kind {
+                    BvhNodeKind::Leaf { .. } => combined + inheritance_cost,
+                    BvhNodeKind::Branch { .. } => {
+                        combined - surface_area(&child.bounds) + inheritance_cost
+                    }
+                }
+            };
+            let left_cost = child_cost(left);
+            let right_cost = child_cost(right);
+
+            if cost < left_cost && cost < right_cost {
+                break;
+            }
+
+            index = if left_cost < right_cost { left } else { right };
+        }
+
+        // Create a new parent for the sibling and the leaf.
+        let sibling = index;
+        let old_parent = self.nodes[sibling].parent;
+        let new_parent = self.nodes.spawn(BvhNode {
+            bounds: union(&self.nodes[sibling].bounds, &leaf_bounds),
+            parent: old_parent,
+            height: self.nodes[sibling].height + 1,
+            kind: BvhNodeKind::Branch {
+                left: sibling,
+                right: leaf,
+            },
+        });
+        if old_parent.is_some() {
+            self.nodes[old_parent].replace_child(sibling, new_parent);
+        } else {
+            self.root = new_parent;
+        }
+        self.nodes[sibling].parent = new_parent;
+        self.nodes[leaf].parent = new_parent;
+
+        self.refit(new_parent);
+    }
+
+    fn remove_leaf(&mut self, leaf: Handle<BvhNode<T>>) {
+        if leaf == self.root {
+            self.root = Handle::NONE;
+            return;
+        }
+
+        let parent = self.nodes[leaf].parent;
+        let grand_parent = self.nodes[parent].parent;
+        let sibling = match self.nodes[parent].children() {
+            Some((left, right)) if left == leaf => right,
+            Some((left, _)) => left,
+            None => unreachable!("parent of a leaf must be a branch"),
+        };
+
+        self.nodes[leaf].parent = Handle::NONE;
+        self.nodes.free(parent);
+        self.nodes[sibling].parent = grand_parent;
+        if grand_parent.is_some() {
+            self.nodes[grand_parent].replace_child(parent, sibling);
+            self.refit(grand_parent);
+        } else {
+            self.root = sibling;
+        }
+    }
+
+    // Walks up the tree from the given node, balancing the tree and fixing heights and bounds of
+    // every ancestor.
+    fn refit(&mut self, mut index: Handle<BvhNode<T>>) {
+        while index.is_some() {
+            index = self.balance(index);
+
+            let (left, right) = self.nodes[index]
+                .children()
+                .expect("ancestor must be a branch");
+            let bounds = union(&self.nodes[left].bounds, &self.nodes[right].bounds);
+            let height = 1 + self.nodes[left].height.max(self.nodes[right].height);
+            let node = &mut self.nodes[index];
+            node.bounds = bounds;
+            node.height = height;
+
+            index = node.parent;
+        }
+    }
+
+    // Performs a left or right rotation if the node is imbalanced. Returns new root of the sub-tree.
+    fn balance(&mut self, a: Handle<BvhNode<T>>) -> Handle<BvhNode<T>> {
+        if self.nodes[a].height < 2 {
+            return a;
+        }
+
+        let (b, c) = match self.nodes[a].children() {
+            Some(children) => children,
+            None => return a,
+        };
+        let balance = self.nodes[c].height as i64 - self.nodes[b].height as i64;
+
+        if balance > 1 {
+            self.rotate(a, c, b, false)
+        } else if balance < -1 {
+            self.rotate(a, b, c, true)
+        } else {
+            a
+        }
+    }
+
+    // Promotes the `child` of `a`, `other` is the second child of `a`. `child_is_left` defines the
+    // side of the child in `a`.
+    fn rotate(
+        &mut self,
+        a: Handle<BvhNode<T>>,
+        child: Handle<BvhNode<T>>,
+        other: Handle<BvhNode<T>>,
+        child_is_left: bool,
+    ) -> Handle<BvhNode<T>> {
+        let (f, g) = self.nodes[child]
+            .children()
+            .expect("child with height more than 1 must be a branch");
+
+        // Swap `a` and `child`.
+        let a_parent = self.nodes[a].parent;
+        self.nodes[child].parent = a_parent;
+        self.nodes[a].parent = child;
+        if a_parent.is_some() {
+            self.nodes[a_parent].replace_child(a, child);
+        } else {
+            self.root = child;
+        }
+
+        // Keep the higher grand child in the promoted node, the other one goes to `a`.
+        let (keep, give) = if self.nodes[f].height > self.nodes[g].height {
+            (f, g)
+        } else {
+            (g, f)
+        };
+
+        self.nodes[child].kind = if child_is_left {
+            BvhNodeKind::Branch {
+                left: a,
+                right: keep,
+            }
+        } else {
+            BvhNodeKind::Branch {
+                left: keep,
+                right: a,
+            }
+        };
+        self.nodes[a].kind = if child_is_left {
+            BvhNodeKind::Branch {
+                left: give,
+                right: other,
+            }
+        } else {
+            BvhNodeKind::Branch {
+                left: other,
+                right: give,
+            }
+        };
+        self.nodes[give].parent = a;
+
+        let a_bounds = union(&self.nodes[other].bounds, &self.nodes[give].bounds);
+        let a_height = 1 + self.nodes[other].height.max(self.nodes[give].height);
+        self.nodes[a].bounds = a_bounds;
+        self.nodes[a].height = a_height;
+
+        let child_bounds = union(&a_bounds, &self.nodes[keep].bounds);
+        let child_height = 1 + a_height.max(self.nodes[keep].height);
+        self.nodes[child].bounds = child_bounds;
+        self.nodes[child].height = child_height;
+
+        child
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        algebra::Vector3,
+        bvh::{BvhNodeKind, DynamicBvh},
+        math::{aabb::AxisAlignedBoundingBox, ray::Ray},
+        pool::Handle,
+    };
+
+    fn cube(x: f32, y: f32, z: f32) -> AxisAlignedBoundingBox {
+        let mut aabb = AxisAlignedBoundingBox::unit();
+        aabb.offset(Vector3::new(x, y, z));
+        aabb
+    }
+
+    // Checks that every branch contains bounds of its children and has correct height and parent
+    // links.
+    fn validate<T: 'static>(bvh: &DynamicBvh<T>, node: Handle<super::BvhNode<T>>) -> u32 {
+        let node_ref = &bvh.nodes()[node];
+        match node_ref.kind() {
+            BvhNodeKind::Leaf { aabb, .. } => {
+                assert!(super::contains(node_ref.bounds(), aabb));
+                assert_eq!(node_ref.height(), 0);
+                0
+            }
+            BvhNodeKind::Branch { left, right } => {
+                for child in [*left, *right] {
+                    assert_eq!(bvh.nodes()[child].parent(), node);
+                    assert!(super::contains(
+                        node_ref.bounds(),
+                        bvh.nodes()[child].bounds()
+                    ));
+                }
+                let left_height = validate(bvh, *left);
+                let right_height = validate(bvh, *right);
+                let height = 1 + left_height.max(right_height);
+                assert_eq!(node_ref.height(), height);
+                height
+            }
+        }
+    }
+
+    #[test]
+    fn test_dynamic_bvh() {
+        let mut bvh = DynamicBvh::new();
+        let mut leaves = Vec::new();
+        for i in 0..100 {
+            let x = (i % 10) as f32 * 3.0;
+            let z = (i / 10) as f32 * 3.0;
+            leaves.push(bvh.insert(cube(x, 0.0, z), i));
+        }
+        assert_eq!(bvh.len(), 100);
+        validate(&bvh, bvh.root());
+        // Balanced tree of 100 leaves.
+        assert!(bvh.height() <= 10);
+
+        let mut buffer = Vec::new();
+        let between_first_two = AxisAlignedBoundingBox::from_min_max(
+            Vector3::new(0.0, 0.0, 0.0),
+            Vector3::new(3.0, 0.0, 0.0),
+        );
+
+        bvh.sphere_query(Vector3::new(3.0, 0.0, 3.0), 0.1, &mut buffer);
+        assert_eq!(buffer, [11]);
+
+        bvh.aabb_query(&between_first_two, &mut buffer);
+        buffer.sort_unstable();
+        assert_eq!(buffer, [0, 1]);
+
+        let ray = Ray::from_two_points(Vector3::new(-5.0, 0.0, 0.0), Vector3::new(100.0, 0.0, 0.0));
+        bvh.ray_query(&ray, &mut buffer);
+        buffer.sort_unstable();
+        assert_eq!(buffer, [0, 1, 2, 3, 4, 5, 6, 7, 8, 9]);
+
+        // Small movement does not change the tree.
+        assert!(!bvh.set_bounds(leaves[0], cube(0.01, 0.0, 0.0)));
+        // Move far away.
+        assert!(bvh.set_bounds(leaves[0], cube(100.0, 0.0, 100.0)));
+        validate(&bvh, bvh.root());
+        bvh.sphere_query(Vector3::new(100.0, 0.0, 100.0), 0.1, &mut buffer);
+        assert_eq!(buffer, [0]);
+        bvh.sphere_query(Vector3::default(), 0.1, &mut buffer);
+        assert!(buffer.is_empty());
+
+        // Shuffle everything and move back.
+        for (i, leaf) in leaves.iter().enumerate() {
+            bvh.set_bounds(*leaf, cube((i * 7 % 13) as f32, i as f32, 0.0));
+        }
+        validate(&bvh, bvh.root());
+        for (i, leaf) in leaves.iter().enumerate() {
+            let x = (i % 10) as f32 * 3.0;
+            let z = (i / 10) as f32 * 3.0;
+            bvh.set_bounds(*leaf, cube(x, 0.0, z));
+        }
+        validate(&bvh, bvh.root());
+        assert!(bvh.height() <= 12);
+
+        for (i, leaf) in leaves.iter().enumerate().filter(|(i, _)| i % 2 == 0) {
+            assert_eq!(bvh.remove(*leaf), Some(i));
+        }
+        assert_eq!(bvh.len(), 50);
+        validate(&bvh, bvh.root());
+        bvh.aabb_query(&between_first_two, &mut buffer);
+        assert_eq!(buffer, [1]);
+
+        for leaf in leaves.iter().skip(1).step_by(2) {
+            assert!(bvh.remove(*leaf).is_some());
+        }
+        assert!(bvh.is_empty());
+        assert!(bvh.root().is_none());
+        assert_eq!(bvh.nodes().alive_count(), 0);
+    }
+}
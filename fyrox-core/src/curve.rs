@@ -1,9 +1,11 @@
 use crate::{
-    math::{cubicf, lerpf},
+    algebra::{UnitQuaternion, Vector2, Vector3, Vector4},
+    color::Color,
+    math::{cubicf, cubicf_derivative, lerpf, quat_from_euler, RotationOrder},
     reflect::prelude::*,
     visitor::prelude::*,
 };
-use std::cmp::Ordering;
+use std::{cmp::Ordering, fmt::Debug};
 use uuid::Uuid;
 
 fn stepf(p0: f32, p1: f32, t: f32) -> f32 {
@@ -69,6 +71,41 @@ impl CurveKey {
         self.location
     }
 
+    /// Calculates derivative of the span between this key and the `other` key at the given
+    /// normalized position `t` of the span. The result is measured in value units per span
+    /// length, divide it by the length of the span to get the slope.
+    #[inline]
+    pub fn derivative(&self, other: &Self, t: f32) -> f32 {
+        match (&self.kind, &other.kind) {
+            (CurveKeyKind::Constant, _) => 0.0,
+            (CurveKeyKind::Linear, _) => other.value - self.value,
+            (
+                CurveKeyKind::Cubic {
+                    right_tangent: left_tangent,
+                    ..
+                },
+                CurveKeyKind::Constant,
+            )
+            | (
+                CurveKeyKind::Cubic {
+                    right_tangent: left_tangent,
+                    ..
+                },
+                CurveKeyKind::Linear,
+            ) => cubicf_derivative(self.value, other.value, t, *left_tangent, 0.0),
+            (
+                CurveKeyKind::Cubic {
+                    right_tangent: left_tangent,
+                    ..
+                },
+                CurveKeyKind::Cubic {
+                    left_tangent: right_tangent,
+                    ..
+                },
+            ) => cubicf_derivative(self.value, other.value, t, *left_tangent, *right_tangent),
+        }
+    }
+
     #[inline]
     pub fn interpolate(&self, other: &Self, t: f32) -> f32 {
         match (&self.kind, &other.kind) {
@@ -117,6 +154,50 @@ impl CurveKey {
     }
 }
 
+/// Defines how a curve is evaluated outside of the range of its keys.
+#[derive(Visit, Reflect, Copy, Clone, Debug, PartialEq, Eq)]
+pub enum CurveWrapMode {
+    /// Value of the nearest key is used.
+    Clamp,
+    /// The curve repeats itself.
+    Loop,
+    /// The curve repeats itself, but every odd repetition is mirrored.
+    PingPong,
+    /// The curve continues with the slope of its nearest span.
+    Extrapolate,
+}
+
+impl Default for CurveWrapMode {
+    #[inline]
+    fn default() -> Self {
+        Self::Clamp
+    }
+}
+
+impl CurveWrapMode {
+    /// Maps the given location into `[min; max]` range. [`CurveWrapMode::Extrapolate`] works the
+    /// same as [`CurveWrapMode::Clamp`] here, because extrapolation depends on the curve itself.
+    #[inline]
+    pub fn wrap(self, location: f32, min: f32, max: f32) -> f32 {
+        let length = max - min;
+        if length <= 0.0 {
+            return min;
+        }
+        match self {
+            CurveWrapMode::Clamp | CurveWrapMode::Extrapolate => location.clamp(min, max),
+            CurveWrapMode::Loop => min + (location - min).rem_euclid(length),
+            CurveWrapMode::PingPong => {
+                let offset = (location - min).rem_euclid(2.0 * length);
+                if offset > length {
+                    min + 2.0 * length - offset
+                } else {
+                    min + offset
+                }
+            }
+        }
+    }
+}
+
 #[derive(Visit, Reflect, Clone, Debug, PartialEq)]
 #[reflect(hide_all)]
 pub struct Curve {
@@ -127,6 +208,12 @@ pub struct Curve {
     name: String,
 
     keys: Vec<CurveKey>,
+
+    #[visit(optional)] // Backward compatibility
+    pre_infinity: CurveWrapMode,
+
+    #[visit(optional)] // Backward compatibility
+    post_infinity: CurveWrapMode,
 }
 
 impl Default for Curve {
@@ -135,6 +222,8 @@ impl Default for Curve {
             id: Uuid::new_v4(),
             name: Default::default(),
             keys: Default::default(),
+            pre_infinity: Default::default(),
+            post_infinity: Default::default(),
         }
     }
 }
@@ -155,9 +244,8 @@ impl From<Vec<CurveKey>> for Curve {
     fn from(mut keys: Vec<CurveKey>) -> Self {
         sort_keys(&mut keys);
         Self {
-            id: Uuid::new_v4(),
-            name: Default::default(),
             keys,
+            ..Default::default()
         }
     }
 }
@@ -212,14 +300,78 @@ impl Curve {
         }
     }
 
+    /// Sets how the curve is evaluated before its first key.
+    #[inline]
+    pub fn set_pre_infinity(&mut self, mode: CurveWrapMode) {
+        self.pre_infinity = mode;
+    }
+
+    /// Returns how the curve is evaluated before its first key.
+    #[inline]
+    pub fn pre_infinity(&self) -> CurveWrapMode {
+        self.pre_infinity
+    }
+
+    /// Sets how the curve is evaluated after its last key.
+    #[inline]
+    pub fn set_post_infinity(&mut self, mode: CurveWrapMode) {
+        self.post_infinity = mode;
+    }
+
+    /// Returns how the curve is evaluated after its last key.
+    #[inline]
+    pub fn post_infinity(&self) -> CurveWrapMode {
+        self.post_infinity
+    }
+
+    #[inline]
+    pub fn min_location(&self) -> f32 {
+        self.keys.first().map(|k| k.location).unwrap_or_default()
+    }
+
     #[inline]
     pub fn max_location(&self) -> f32 {
         self.keys.last().map(|k| k.location).unwrap_or_default()
     }
 
+    /// Returns slope of the first (`end == false`) or the last (`end == true`) span of the curve.
+    fn edge_slope(&self, end: bool) -> f32 {
+        let count = self.keys.len();
+        if count < 2 {
+            return 0.0;
+        }
+        let (left, right, t) = if end {
+            (&self.keys[count - 2], &self.keys[count - 1], 1.0)
+        } else {
+            (&self.keys[0], &self.keys[1], 0.0)
+        };
+        let span = right.location - left.location;
+        if span > f32::EPSILON {
+            left.derivative(right, t) / span
+        } else {
+            0.0
+        }
+    }
+
     #[inline]
-    pub fn value_at(&self, location: f32) -> f32 {
+    pub fn value_at(&self, mut location: f32) -> f32 {
         if let (Some(first), Some(last)) = (self.keys.first(), self.keys.last()) {
+            if location < first.location {
+                if self.pre_infinity == CurveWrapMode::Extrapolate {
+                    return first.value + self.edge_slope(false) * (location - first.location);
+                }
+                location = self
+                    .pre_infinity
+                    .wrap(location, first.location, last.location);
+            } else if location > last.location {
+                if self.post_infinity == CurveWrapMode::Extrapolate {
+                    return last.value + self.edge_slope(true) * (location - last.location);
+                }
+                location = self
+                    .post_infinity
+                    .wrap(location, first.location, last.location);
+            }
+
             if location <= first.location {
                 first.value
             } else if location >= last.location {
@@ -238,11 +390,356 @@ impl Curve {
             0.0
         }
     }
+
+    /// Samples the curve into a lookup table with the given amount of samples, that could be used
+    /// in hot paths. See [`BakedCurve`] docs for more info.
+    #[inline]
+    pub fn bake(&self, sample_count: usize) -> BakedCurve<f32> {
+        BakedCurve::new(
+            self.min_location(),
+            self.max_location(),
+            sample_count,
+            self.pre_infinity,
+            self.post_infinity,
+            |location| self.value_at(location),
+        )
+    }
+}
+
+/// A value that could be stored in a [`BakedCurve`].
+pub trait CurveValue: Copy + Debug {
+    /// Interpolates between `self` and `other` using the given factor. The factor could be out of
+    /// `[0; 1]` range on extrapolation.
+    fn interpolate(&self, other: &Self, t: f32) -> Self;
+}
+
+impl CurveValue for f32 {
+    #[inline]
+    fn interpolate(&self, other: &Self, t: f32) -> Self {
+        lerpf(*self, *other, t)
+    }
+}
+
+impl CurveValue for Vector2<f32> {
+    #[inline]
+    fn interpolate(&self, other: &Self, t: f32) -> Self {
+        self.lerp(other, t)
+    }
 }
 
+impl CurveValue for Vector3<f32> {
+    #[inline]
+    fn interpolate(&self, other: &Self, t: f32) -> Self {
+        self.lerp(other, t)
+    }
+}
+
+impl CurveValue for Vector4<f32> {
+    #[inline]
+    fn interpolate(&self, other: &Self, t: f32) -> Self {
+        self.lerp(other, t)
+    }
+}
+
+impl CurveValue for UnitQuaternion<f32> {
+    #[inline]
+    fn interpolate(&self, other: &Self, t: f32) -> Self {
+        self.nlerp(other, t)
+    }
+}
+
+impl CurveValue for Color {
+    #[inline]
+    fn interpolate(&self, other: &Self, t: f32) -> Self {
+        Color::from(self.as_frgba().lerp(&other.as_frgba(), t))
+    }
+}
+
+/// Baked curve is a lookup table of uniformly distributed samples of a curve. Sampling of the table
+/// has `O(1)` complexity (instead of `O(log(n))` of a [`Curve`]) and does not depend on the kinds of
+/// the keys, which makes it suitable for hot paths like particle systems. The price is the precision:
+/// the values between samples are interpolated linearly, so sharp features of the source curve could
+/// be smoothed if the amount of samples is too low.
+///
+/// Baked curve keeps the wrap modes of its source curve. Extrapolation uses the slope between two
+/// outermost samples.
+///
+/// # Example
+///
+/// ```rust
+/// use fyrox_core::curve::{Curve, CurveKey, CurveKeyKind, CurveWrapMode};
+///
+/// let mut curve = Curve::from(vec![
+///     CurveKey::new(0.0, 0.0, CurveKeyKind::Linear),
+///     CurveKey::new(1.0, 2.0, CurveKeyKind::Linear),
+/// ]);
+/// curve.set_post_infinity(CurveWrapMode::Loop);
+///
+/// let baked = curve.bake(64);
+/// assert_eq!(baked.value_at(1.5), curve.value_at(1.5));
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct BakedCurve<T> {
+    min_location: f32,
+    max_location: f32,
+    step: f32,
+    samples: Vec<T>,
+    pre_infinity: CurveWrapMode,
+    post_infinity: CurveWrapMode,
+}
+
+impl<T: CurveValue> BakedCurve<T> {
+    /// Creates new baked curve by sampling the given function in `[min_location; max_location]`
+    /// range. The amount of samples is clamped to be at least 2.
+    pub fn new<F>(
+        min_location: f32,
+        max_location: f32,
+        sample_count: usize,
+        pre_infinity: CurveWrapMode,
+        post_infinity: CurveWrapMode,
+        mut func: F,
+    ) -> Self
+    where
+        F: FnMut(f32) -> T,
+    {
+        let sample_count = sample_count.max(2);
+        let max_location = max_location.max(min_location);
+        let step = (max_location - min_location) / (sample_count - 1) as f32;
+        Self {
+            min_location,
+            max_location,
+            step,
+            samples: (0..sample_count)
+                .map(|i| func(min_location + i as f32 * step))
+                .collect(),
+            pre_infinity,
+            post_infinity,
+        }
+    }
+
+    /// Returns the samples of the table.
+    #[inline]
+    pub fn samples(&self) -> &[T] {
+        &self.samples
+    }
+
+    /// Returns location of the first sample.
+    #[inline]
+    pub fn min_location(&self) -> f32 {
+        self.min_location
+    }
+
+    /// Returns location of the last sample.
+    #[inline]
+    pub fn max_location(&self) -> f32 {
+        self.max_location
+    }
+
+    /// Returns the value at the given location.
+    #[inline]
+    pub fn value_at(&self, mut location: f32) -> T {
+        let count = self.samples.len();
+        if self.step <= 0.0 {
+            return self.samples[0];
+        }
+
+        if location < self.min_location {
+            if self.pre_infinity == CurveWrapMode::Extrapolate {
+                let t = (location - self.min_location) / self.step;
+                return self.samples[0].interpolate(&self.samples[1], t);
+            }
+            location = self
+                .pre_infinity
+                .wrap(location, self.min_location, self.max_location);
+        } else if location > self.max_location {
+            if self.post_infinity == CurveWrapMode::Extrapolate {
+                let t = (location - self.max_location) / self.step;
+                return self.samples[count - 2].interpolate(&self.samples[count - 1], 1.0 + t);
+            }
+            location = self
+                .post_infinity
+                .wrap(location, self.min_location, self.max_location);
+        }
+
+        let position = (location - self.min_location) / self.step;
+        let index = (position as usize).min(count - 2);
+        self.samples[index].interpolate(&self.samples[index + 1], position - index as f32)
+    }
+}
+
+/// Baked compound curve is a set of [`BakedCurve`]s of the components of a compound curve (for
+/// example [`Vector3Curve`]). Components are baked separately, so the components with different
+/// ranges or wrap modes produce the same values as the source curve.
+#[derive(Clone, Debug)]
+pub struct BakedCompoundCurve<T, const N: usize> {
+    curves: Vec<BakedCurve<f32>>,
+    convert: fn([f32; N]) -> T,
+}
+
+impl<T, const N: usize> BakedCompoundCurve<T, N> {
+    /// Returns a reference to the baked component curves.
+    #[inline]
+    pub fn curves(&self) -> &[BakedCurve<f32>] {
+        &self.curves
+    }
+
+    /// Returns the value at the given location.
+    #[inline]
+    pub fn value_at(&self, location: f32) -> T {
+        let mut components = [0.0; N];
+        for (component, curve) in components.iter_mut().zip(self.curves.iter()) {
+            *component = curve.value_at(location);
+        }
+        (self.convert)(components)
+    }
+}
+
+macro_rules! define_compound_curve {
+    ($(#[$meta:meta])* $name:ident, $value:ty, $count:expr, $convert:expr) => {
+        $(#[$meta])*
+        #[derive(Visit, Reflect, Clone, Debug, Default, PartialEq)]
+        #[reflect(hide_all)]
+        pub struct $name {
+            curves: [Curve; $count],
+        }
+
+        impl $name {
+            /// Creates new curve from the given set of component curves.
+            #[inline]
+            pub fn new(curves: [Curve; $count]) -> Self {
+                Self { curves }
+            }
+
+            /// Returns a reference to the component curves.
+            #[inline]
+            pub fn curves(&self) -> &[Curve; $count] {
+                &self.curves
+            }
+
+            /// Returns a reference to the component curves.
+            #[inline]
+            pub fn curves_mut(&mut self) -> &mut [Curve; $count] {
+                &mut self.curves
+            }
+
+            /// Sets how every component curve is evaluated before its first key.
+            #[inline]
+            pub fn set_pre_infinity(&mut self, mode: CurveWrapMode) {
+                for curve in self.curves.iter_mut() {
+                    curve.set_pre_infinity(mode);
+                }
+            }
+
+            /// Sets how every component curve is evaluated after its last key.
+            #[inline]
+            pub fn set_post_infinity(&mut self, mode: CurveWrapMode) {
+                for curve in self.curves.iter_mut() {
+                    curve.set_post_infinity(mode);
+                }
+            }
+
+            /// Returns the left-most location of the keys among all component curves.
+            #[inline]
+            pub fn min_location(&self) -> f32 {
+                self.curves
+                    .iter()
+                    .filter(|c| !c.is_empty())
+                    .map(|c| c.min_location())
+                    .fold(None, |min: Option<f32>, l| Some(min.map_or(l, |m| m.min(l))))
+                    .unwrap_or_default()
+            }
+
+            /// Returns the right-most location of the keys among all component curves.
+            #[inline]
+            pub fn max_location(&self) -> f32 {
+                self.curves
+                    .iter()
+                    .filter(|c| !c.is_empty())
+                    .map(|c| c.max_location())
+                    .fold(None, |max: Option<f32>, l| Some(max.map_or(l, |m| m.max(l))))
+                    .unwrap_or_default()
+            }
+
+            /// Returns the value at the given location.
+            #[inline]
+            pub fn value_at(&self, location: f32) -> $value {
+                let mut components = [0.0; $count];
+                for (component, curve) in components.iter_mut().zip(self.curves.iter()) {
+                    *component = curve.value_at(location);
+                }
+                let convert: fn([f32; $count]) -> $value = $convert;
+                convert(components)
+            }
+
+            /// Samples every component curve into its own lookup table with the given amount of
+            /// samples. Every table keeps the range and the wrap modes of its component curve.
+            #[inline]
+            pub fn bake(&self, sample_count: usize) -> BakedCompoundCurve<$value, $count> {
+                BakedCompoundCurve {
+                    curves: self
+                        .curves
+                        .iter()
+                        .map(|curve| curve.bake(sample_count))
+                        .collect(),
+                    convert: $convert,
+                }
+            }
+        }
+    };
+}
+
+define_compound_curve!(
+    /// A curve of 2-dimensional vectors, every component has its own curve: `X = 0`, `Y = 1`.
+    Vector2Curve,
+    Vector2<f32>,
+    2,
+    |v| Vector2::new(v[0], v[1])
+);
+
+define_compound_curve!(
+    /// A curve of 3-dimensional vectors, every component has its own curve: `X = 0`, `Y = 1`,
+    /// `Z = 2`.
+    Vector3Curve,
+    Vector3<f32>,
+    3,
+    |v| Vector3::new(v[0], v[1], v[2])
+);
+
+define_compound_curve!(
+    /// A curve of 4-dimensional vectors, every component has its own curve: `X = 0`, `Y = 1`,
+    /// `Z = 2`, `W = 3`.
+    Vector4Curve,
+    Vector4<f32>,
+    4,
+    |v| Vector4::new(v[0], v[1], v[2], v[3])
+);
+
+define_compound_curve!(
+    /// A curve of rotations. It consists of three curves of Euler angles (in radians), where
+    /// `XAngle = 0`, `YAngle = 1`, `ZAngle = 2`. The angles are interpolated separately and then
+    /// converted to a quaternion using `XYZ` order of rotations, the same way as animation tracks do.
+    QuaternionCurve,
+    UnitQuaternion<f32>,
+    3,
+    |v| quat_from_euler(Vector3::new(v[0], v[1], v[2]), RotationOrder::XYZ)
+);
+
+define_compound_curve!(
+    /// A curve of colors. It consists of four curves of normalized color components, where
+    /// `R = 0`, `G = 1`, `B = 2`, `A = 3`. Values out of `[0; 1]` range are clamped.
+    ColorCurve,
+    Color,
+    4,
+    |v| Color::from(Vector4::new(v[0], v[1], v[2], v[3]))
+);
+
 #[cfg(test)]
 mod test {
-    use crate::curve::{Curve, CurveKey, CurveKeyKind};
+    use crate::{
+        algebra::Vector2,
+        color::Color,
+        curve::{ColorCurve, Curve, CurveKey, CurveKeyKind, CurveWrapMode, Vector2Curve},
+    };
 
     #[test]
     fn test_curve_key_insertion_order() {
@@ -302,4 +799,83 @@ mod test {
         // Check interpolation.
         assert_eq!(curve.value_at(0.5), 0.5);
     }
+
+    #[test]
+    fn test_curve_wrap_modes() {
+        let mut curve = Curve::from(vec![
+            CurveKey::new(0.0, 0.0, CurveKeyKind::Linear),
+            CurveKey::new(1.0, 1.0, CurveKeyKind::Linear),
+        ]);
+
+        curve.set_post_infinity(CurveWrapMode::Loop);
+        curve.set_pre_infinity(CurveWrapMode::Loop);
+        assert_eq!(curve.value_at(1.25), 0.25);
+        assert_eq!(curve.value_at(-0.25), 0.75);
+
+        curve.set_post_infinity(CurveWrapMode::PingPong);
+        curve.set_pre_infinity(CurveWrapMode::PingPong);
+        assert_eq!(curve.value_at(1.25), 0.75);
+        assert_eq!(curve.value_at(2.25), 0.25);
+        assert_eq!(curve.value_at(-0.25), 0.25);
+
+        curve.set_post_infinity(CurveWrapMode::Extrapolate);
+        curve.set_pre_infinity(CurveWrapMode::Extrapolate);
+        assert_eq!(curve.value_at(3.0), 3.0);
+        assert_eq!(curve.value_at(-2.0), -2.0);
+
+        curve.set_post_infinity(CurveWrapMode::Clamp);
+        assert_eq!(curve.value_at(3.0), 1.0);
+    }
+
+    #[test]
+    fn test_compound_and_baked_curves() {
+        let mut curve = Vector2Curve::new([
+            Curve::from(vec![
+                CurveKey::new(0.0, 0.0, CurveKeyKind::Linear),
+                CurveKey::new(2.0, 2.0, CurveKeyKind::Linear),
+            ]),
+            Curve::from(vec![CurveKey::new(1.0, 5.0, CurveKeyKind::Constant)]),
+        ]);
+        curve.set_post_infinity(CurveWrapMode::Loop);
+        assert_eq!(curve.min_location(), 0.0);
+        assert_eq!(curve.max_location(), 2.0);
+        assert_eq!(curve.value_at(1.0), Vector2::new(1.0, 5.0));
+        assert_eq!(curve.value_at(2.5), Vector2::new(0.5, 5.0));
+
+        let baked = curve.bake(5);
+        assert_eq!(baked.curves()[0].samples().len(), 5);
+        assert_eq!(baked.value_at(1.25), Vector2::new(1.25, 5.0));
+        assert_eq!(baked.value_at(2.5), Vector2::new(0.5, 5.0));
+
+        // Components with different ranges and wrap modes must be baked separately.
+        let mut curve = Vector2Curve::new([
+            Curve::from(vec![
+                CurveKey::new(0.0, 0.0, CurveKeyKind::Linear),
+                CurveKey::new(1.0, 1.0, CurveKeyKind::Linear),
+            ]),
+            Curve::from(vec![
+                CurveKey::new(0.0, 0.0, CurveKeyKind::Linear),
+                CurveKey::new(4.0, 4.0, CurveKeyKind::Linear),
+            ]),
+        ]);
+        curve.curves_mut()[0].set_post_infinity(CurveWrapMode::Loop);
+        curve.curves_mut()[1].set_post_infinity(CurveWrapMode::Extrapolate);
+        let baked = curve.bake(5);
+        for location in [0.5, 1.5, 3.0, 5.0] {
+            assert_eq!(baked.value_at(location), curve.value_at(location));
+        }
+
+        let color = ColorCurve::new([
+            Curve::from(vec![
+                CurveKey::new(0.0, 0.0, CurveKeyKind::Linear),
+                CurveKey::new(1.0, 1.0, CurveKeyKind::Linear),
+            ]),
+            Default::default(),
+            Default::default(),
+            Curve::from(vec![CurveKey::new(0.0, 1.0, CurveKeyKind::Constant)]),
+        ]);
+        assert_eq!(color.value_at(0.0), Color::opaque(0, 0, 0));
+        assert_eq!(color.value_at(1.0), Color::opaque(255, 0, 0));
+        assert_eq!(color.bake(3).value_at(2.0), Color::opaque(255, 0, 0));
+    }
 }
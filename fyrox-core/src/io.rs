@@ -1,3 +1,4 @@
+use crate::vfs::VFS;
 use std::{io::Error, path::Path};
 
 #[derive(Debug)]
@@ -27,7 +28,13 @@ impl From<wasm_bindgen::JsValue> for FileLoadError {
     }
 }
 
+/// Loads entire file. The file is searched in the virtual file system first (see [`crate::vfs::Vfs`]) and
+/// then in the OS file system (or fetched from the server on WebAssembly).
 pub async fn load_file<P: AsRef<Path>>(path: P) -> Result<Vec<u8>, FileLoadError> {
+    if let Some(result) = VFS.read().read_file(path.as_ref()) {
+        return result;
+    }
+
     #[cfg(all(not(target_os = "android"), not(target_arch = "wasm32")))]
     {
         use std::fs::File;
@@ -73,7 +80,13 @@ pub async fn load_file<P: AsRef<Path>>(path: P) -> Result<Vec<u8>, FileLoadError
     }
 }
 
+/// Checks if a file exists in the virtual file system (see [`crate::vfs::Vfs`]) or in the OS file system
+/// (or on the server on WebAssembly).
 pub async fn exists<P: AsRef<Path>>(path: P) -> bool {
+    if VFS.read().exists(path.as_ref()) {
+        return true;
+    }
+
     #[cfg(all(not(target_os = "android"), not(target_arch = "wasm32")))]
     {
         path.as_ref().exists()
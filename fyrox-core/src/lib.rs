@@ -16,6 +16,8 @@ pub use nalgebra as algebra;
 pub use num_traits;
 pub use parking_lot;
 pub use rand;
+#[cfg(feature = "rayon")]
+pub use rayon;
 pub use uuid;
 
 use crate::visitor::{Visit, VisitResult, Visitor};
@@ -27,6 +29,7 @@ use std::{
     path::{Path, PathBuf},
 };
 
+pub mod bvh;
 pub mod color;
 pub mod color_gradient;
 pub mod curve;
@@ -42,6 +45,7 @@ pub mod reflect;
 pub mod sparse;
 pub mod sstorage;
 pub mod variable;
+pub mod vfs;
 pub mod visitor;
 
 pub use futures;
@@ -5,6 +5,7 @@ pub mod aabb;
 pub mod frustum;
 pub mod plane;
 pub mod ray;
+pub mod spline;
 pub mod triangulator;
 
 use crate::{
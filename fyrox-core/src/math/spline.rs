@@ -0,0 +1,449 @@
+//! Spline is a smooth 3D path defined by a set of control points. This module contains Catmull-Rom
+//! and cubic Bezier splines, arc-length parameterization and various queries (closest point, tangent,
+//! moving frame).
+//!
+//! Every spline is parameterized by a single value `t` in `[0; segment_count]` range, where the integer
+//! part of the parameter is the index of a segment and the fractional part is the position in the segment.
+//! This parameterization is not uniform - equal steps of the parameter do not give equal distances along
+//! the spline, use [`ArcLengthTable`] to move along the spline with constant speed.
+
+use crate::{algebra::Vector3, reflect::prelude::*, visitor::prelude::*};
+
+/// Position and orientation of a point on a spline.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct SplineFrame {
+    /// Position of the point.
+    pub position: Vector3<f32>,
+    /// Normalized direction of the spline at the point.
+    pub tangent: Vector3<f32>,
+    /// Normalized vector which is perpendicular to the tangent and as close as possible to the `up`
+    /// vector used to calculate the frame.
+    pub normal: Vector3<f32>,
+    /// Normalized vector which is perpendicular to both the tangent and the normal.
+    pub binormal: Vector3<f32>,
+}
+
+/// Common functionality of every spline.
+pub trait Spline {
+    /// Returns amount of segments of the spline.
+    fn segment_count(&self) -> usize;
+
+    /// Returns a position on the given segment at the given local parameter in `[0; 1]` range.
+    fn segment_position(&self, segment: usize, t: f32) -> Vector3<f32>;
+
+    /// Returns first derivative of the segment at the given local parameter in `[0; 1]` range.
+    fn segment_derivative(&self, segment: usize, t: f32) -> Vector3<f32>;
+
+    /// Returns a position at the given point of the spline. Returns zero vector if the spline has no segments.
+    fn position(&self, t: f32) -> Vector3<f32> {
+        match split_parameter(self.segment_count(), t) {
+            Some((segment, local)) => self.segment_position(segment, local),
+            None => Vector3::default(),
+        }
+    }
+
+    /// Returns first derivative (non-normalized direction) at the given point of the spline.
+    fn derivative(&self, t: f32) -> Vector3<f32> {
+        match split_parameter(self.segment_count(), t) {
+            Some((segment, local)) => self.segment_derivative(segment, local),
+            None => Vector3::default(),
+        }
+    }
+
+    /// Returns normalized direction at the given point of the spline. Returns `None` if the direction is
+    /// degenerate (for example if two control points have the same position).
+    fn tangent(&self, t: f32) -> Option<Vector3<f32>> {
+        self.derivative(t).try_normalize(f32::EPSILON)
+    }
+
+    /// Calculates position and orientation at the given point of the spline. The `up` vector is used to
+    /// orient the frame around the tangent (for example a camera on a rail will have its up vector as close
+    /// as possible to the given one).
+    fn frame(&self, t: f32, up: Vector3<f32>) -> SplineFrame {
+        let tangent = self.tangent(t).unwrap_or_else(Vector3::z);
+        let binormal = tangent
+            .cross(&up)
+            .try_normalize(f32::EPSILON)
+            // The tangent is parallel to up vector, pick any perpendicular vector.
+            .or_else(|| tangent.cross(&Vector3::x()).try_normalize(f32::EPSILON))
+            .unwrap_or_else(|| tangent.cross(&Vector3::y()).normalize());
+        SplineFrame {
+            position: self.position(t),
+            tangent,
+            normal: binormal.cross(&tangent),
+            binormal,
+        }
+    }
+
+    /// Finds a parameter of the point on the spline which is closest to the given point. The spline is
+    /// sampled `samples_per_segment` times per segment and then the closest sample is refined, so the
+    /// amount of samples should be high enough to not miss sharp turns.
+    fn closest_parameter(&self, point: Vector3<f32>, samples_per_segment: usize) -> f32 {
+        let segment_count = self.segment_count();
+        if segment_count == 0 {
+            return 0.0;
+        }
+
+        let sample_count = segment_count * samples_per_segment.max(1);
+        let step = segment_count as f32 / sample_count as f32;
+        let distance = |t: f32| (self.position(t) - point).norm_squared();
+
+        let mut closest = 0.0;
+        let mut closest_distance = f32::MAX;
+        for i in 0..=sample_count {
+            let t = i as f32 * step;
+            let d = distance(t);
+            if d < closest_distance {
+                closest = t;
+                closest_distance = d;
+            }
+        }
+
+        // Refine using golden section search in the neighbourhood of the closest sample.
+        const INV_PHI: f32 = 0.618_034;
+        let mut a = (closest - step).max(0.0);
+        let mut b = (closest + step).min(segment_count as f32);
+        for _ in 0..24 {
+            let c = b - (b - a) * INV_PHI;
+            let d = a + (b - a) * INV_PHI;
+            if distance(c) < distance(d) {
+                b = d;
+            } else {
+                a = c;
+            }
+        }
+        let refined = (a + b) * 0.5;
+        if distance(refined) < closest_distance {
+            refined
+        } else {
+            closest
+        }
+    }
+
+    /// Returns a point on the spline which is closest to the given point. See [`Spline::closest_parameter`]
+    /// for more info.
+    fn closest_point(&self, point: Vector3<f32>, samples_per_segment: usize) -> Vector3<f32> {
+        self.position(self.closest_parameter(point, samples_per_segment))
+    }
+}
+
+// Splits global spline parameter into segment index and local parameter.
+fn split_parameter(segment_count: usize, t: f32) -> Option<(usize, f32)> {
+    if segment_count == 0 {
+        return None;
+    }
+    let t = t.max(0.0).min(segment_count as f32);
+    let segment = (t as usize).min(segment_count - 1);
+    Some((segment, t - segment as f32))
+}
+
+/// Catmull-Rom spline is a spline that passes through every control point. Tangents of the spline are
+/// calculated automatically using neighbour points, which makes it the simplest way of defining smooth
+/// paths - camera rails, patrol paths, etc. This implementation uses uniform parameterization.
+#[derive(Clone, Debug, Default, PartialEq, Visit, Reflect)]
+pub struct CatmullRomSpline {
+    /// Control points of the spline.
+    pub points: Vec<Vector3<f32>>,
+    /// Whether the last point connected with the first one or not.
+    pub closed: bool,
+}
+
+impl CatmullRomSpline {
+    /// Creates new spline from the given set of points.
+    pub fn new(points: Vec<Vector3<f32>>, closed: bool) -> Self {
+        Self { points, closed }
+    }
+
+    fn point(&self, index: isize) -> Vector3<f32> {
+        let count = self.points.len() as isize;
+        let index = if self.closed {
+            index.rem_euclid(count)
+        } else {
+            index.max(0).min(count - 1)
+        };
+        self.points[index as usize]
+    }
+
+    fn segment_points(&self, segment: usize) -> [Vector3<f32>; 4] {
+        let i = segment as isize;
+        [
+            self.point(i - 1),
+            self.point(i),
+            self.point(i + 1),
+            self.point(i + 2),
+        ]
+    }
+}
+
+impl Spline for CatmullRomSpline {
+    fn segment_count(&self) -> usize {
+        match self.points.len() {
+            0 | 1 => 0,
+            n if self.closed => n,
+            n => n - 1,
+        }
+    }
+
+    fn segment_position(&self, segment: usize, t: f32) -> Vector3<f32> {
+        let [p0, p1, p2, p3] = self.segment_points(segment);
+        let t2 = t * t;
+        let t3 = t2 * t;
+        (p1.scale(2.0)
+            + (p2 - p0).scale(t)
+            + (p0.scale(2.0) - p1.scale(5.0) + p2.scale(4.0) - p3).scale(t2)
+            + (p1.scale(3.0) - p0 - p2.scale(3.0) + p3).scale(t3))
+        .scale(0.5)
+    }
+
+    fn segment_derivative(&self, segment: usize, t: f32) -> Vector3<f32> {
+        let [p0, p1, p2, p3] = self.segment_points(segment);
+        ((p2 - p0)
+            + (p0.scale(2.0) - p1.scale(5.0) + p2.scale(4.0) - p3).scale(2.0 * t)
+            + (p1.scale(3.0) - p0 - p2.scale(3.0) + p3).scale(3.0 * t * t))
+        .scale(0.5)
+    }
+}
+
+/// Control point of a Bezier spline.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Visit, Reflect)]
+pub struct SplinePoint {
+    /// Position of the point.
+    pub position: Vector3<f32>,
+    /// Offset of the incoming control handle relative to the position.
+    pub in_tangent: Vector3<f32>,
+    /// Offset of the outgoing control handle relative to the position.
+    pub out_tangent: Vector3<f32>,
+}
+
+impl SplinePoint {
+    /// Creates new control point with symmetric control handles. The `tangent` is the offset of the
+    /// outgoing handle.
+    pub fn new(position: Vector3<f32>, tangent: Vector3<f32>) -> Self {
+        Self {
+            position,
+            in_tangent: -tangent,
+            out_tangent: tangent,
+        }
+    }
+}
+
+/// Cubic Bezier spline is a chain of cubic Bezier curves. Unlike [`CatmullRomSpline`], it allows you to
+/// define the shape of the spline precisely using control handles of each point (as in most of vector
+/// graphics editors).
+#[derive(Clone, Debug, Default, PartialEq, Visit, Reflect)]
+pub struct BezierSpline {
+    /// Control points of the spline.
+    pub points: Vec<SplinePoint>,
+    /// Whether the last point connected with the first one or not.
+    pub closed: bool,
+}
+
+impl BezierSpline {
+    /// Creates new spline from the given set of points.
+    pub fn new(points: Vec<SplinePoint>, closed: bool) -> Self {
+        Self { points, closed }
+    }
+
+    fn segment_points(&self, segment: usize) -> [Vector3<f32>; 4] {
+        let a = &self.points[segment];
+        let b = &self.points[(segment + 1) % self.points.len()];
+        [
+            a.position,
+            a.position + a.out_tangent,
+            b.position + b.in_tangent,
+            b.position,
+        ]
+    }
+}
+
+impl Spline for BezierSpline {
+    fn segment_count(&self) -> usize {
+        match self.points.len() {
+            0 | 1 => 0,
+            n if self.closed => n,
+            n => n - 1,
+        }
+    }
+
+    fn segment_position(&self, segment: usize, t: f32) -> Vector3<f32> {
+        let [p0, p1, p2, p3] = self.segment_points(segment);
+        let s = 1.0 - t;
+        p0.scale(s * s * s)
+            + p1.scale(3.0 * s * s * t)
+            + p2.scale(3.0 * s * t * t)
+            + p3.scale(t * t * t)
+    }
+
+    fn segment_derivative(&self, segment: usize, t: f32) -> Vector3<f32> {
+        let [p0, p1, p2, p3] = self.segment_points(segment);
+        let s = 1.0 - t;
+        (p1 - p0).scale(3.0 * s * s) + (p2 - p1).scale(6.0 * s * t) + (p3 - p2).scale(3.0 * t * t)
+    }
+}
+
+/// Arc-length table allows you to convert a distance along a spline to the spline parameter and vice versa.
+/// It is used to move along a spline with constant speed. The table is built once by sampling the spline,
+/// so it must be rebuilt when the spline changes.
+///
+/// # Example
+///
+/// ```
+/// use fyrox_core::{
+///     algebra::Vector3,
+///     math::spline::{ArcLengthTable, CatmullRomSpline, Spline},
+/// };
+///
+/// let spline = CatmullRomSpline::new(
+///     vec![
+///         Vector3::new(0.0, 0.0, 0.0),
+///         Vector3::new(1.0, 0.0, 0.0),
+///         Vector3::new(5.0, 0.0, 0.0),
+///     ],
+///     false,
+/// );
+/// let table = ArcLengthTable::new(&spline, 32);
+/// assert!((table.length() - 5.0).abs() < 0.01);
+/// // Point at the middle of the path.
+/// let position = spline.position(table.parameter(table.length() * 0.5));
+/// assert!((position.x - 2.5).abs() < 0.01);
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ArcLengthTable {
+    // Accumulated distance at uniformly distributed parameter values.
+    distances: Vec<f32>,
+    // Parameter step between two samples.
+    step: f32,
+}
+
+impl ArcLengthTable {
+    /// Builds new table by sampling the spline `samples_per_segment` times per segment.
+    pub fn new<S: Spline + ?Sized>(spline: &S, samples_per_segment: usize) -> Self {
+        let segment_count = spline.segment_count();
+        if segment_count == 0 {
+            return Self {
+                distances: vec![0.0],
+                step: 0.0,
+            };
+        }
+
+        let sample_count = segment_count * samples_per_segment.max(1);
+        let step = segment_count as f32 / sample_count as f32;
+        let mut distances = Vec::with_capacity(sample_count + 1);
+        let mut length = 0.0;
+        let mut prev = spline.position(0.0);
+        distances.push(0.0);
+        for i in 1..=sample_count {
+            let position = spline.position(i as f32 * step);
+            length += (position - prev).norm();
+            distances.push(length);
+            prev = position;
+        }
+
+        Self { distances, step }
+    }
+
+    /// Returns total length of the spline.
+    pub fn length(&self) -> f32 {
+        self.distances.last().cloned().unwrap_or_default()
+    }
+
+    /// Returns spline parameter at the given distance from the beginning of the spline. The distance is
+    /// clamped to `[0; length]` range.
+    pub fn parameter(&self, distance: f32) -> f32 {
+        let distance = distance.max(0.0).min(self.length());
+        // Index of the first sample with greater or equal distance.
+        let index = self.distances.partition_point(|d| *d < distance);
+        if index == 0 {
+            return 0.0;
+        }
+        let prev = self.distances[index - 1];
+        let next = self.distances[index];
+        let fraction = if next > prev {
+            (distance - prev) / (next - prev)
+        } else {
+            0.0
+        };
+        ((index - 1) as f32 + fraction) * self.step
+    }
+
+    /// Returns distance from the beginning of the spline at the given spline parameter.
+    pub fn distance(&self, t: f32) -> f32 {
+        if self.step == 0.0 {
+            return 0.0;
+        }
+        let position = (t / self.step).max(0.0);
+        let index = position as usize;
+        if index + 1 >= self.distances.len() {
+            return self.length();
+        }
+        let fraction = position - index as f32;
+        self.distances[index] + (self.distances[index + 1] - self.distances[index]) * fraction
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        algebra::Vector3,
+        math::spline::{ArcLengthTable, BezierSpline, CatmullRomSpline, Spline, SplinePoint},
+    };
+
+    fn assert_near(a: Vector3<f32>, b: Vector3<f32>) {
+        assert!((a - b).norm() < 0.001, "{a:?} != {b:?}");
+    }
+
+    #[test]
+    fn test_catmull_rom_spline() {
+        let points = vec![
+            Vector3::new(0.0, 0.0, 0.0),
+            Vector3::new(1.0, 0.0, 0.0),
+            Vector3::new(1.0, 0.0, 1.0),
+            Vector3::new(0.0, 0.0, 1.0),
+        ];
+
+        let open = CatmullRomSpline::new(points.clone(), false);
+        assert_eq!(open.segment_count(), 3);
+        // The spline passes through every control point.
+        for (i, point) in points.iter().enumerate() {
+            assert_near(open.position(i as f32), *point);
+        }
+        // Parameter is clamped.
+        assert_near(open.position(10.0), points[3]);
+
+        let closed = CatmullRomSpline::new(points.clone(), true);
+        assert_eq!(closed.segment_count(), 4);
+        assert_near(closed.position(4.0), points[0]);
+
+        // Closest point to a point outside of the square is on its edge.
+        let t = closed.closest_parameter(Vector3::new(2.0, 0.0, 0.5), 16);
+        assert!((t - 1.5).abs() < 0.01);
+
+        let frame = closed.frame(1.5, Vector3::y());
+        assert_near(frame.tangent, Vector3::z());
+        assert_near(frame.normal, Vector3::y());
+        assert_near(frame.binormal, Vector3::new(-1.0, 0.0, 0.0));
+    }
+
+    #[test]
+    fn test_bezier_spline() {
+        let spline = BezierSpline::new(
+            vec![
+                SplinePoint::new(Vector3::new(0.0, 0.0, 0.0), Vector3::new(1.0, 0.0, 0.0)),
+                SplinePoint::new(Vector3::new(3.0, 0.0, 0.0), Vector3::new(1.0, 0.0, 0.0)),
+            ],
+            false,
+        );
+        assert_eq!(spline.segment_count(), 1);
+        assert_near(spline.position(0.0), Vector3::new(0.0, 0.0, 0.0));
+        assert_near(spline.position(1.0), Vector3::new(3.0, 0.0, 0.0));
+        // Evenly distributed handles give linear motion.
+        assert_near(spline.position(0.5), Vector3::new(1.5, 0.0, 0.0));
+        assert_near(spline.derivative(0.5), Vector3::new(3.0, 0.0, 0.0));
+
+        let table = ArcLengthTable::new(&spline, 16);
+        assert!((table.length() - 3.0).abs() < 0.001);
+        assert!((table.parameter(1.5) - 0.5).abs() < 0.001);
+        assert!((table.distance(0.5) - 1.5).abs() < 0.001);
+        assert_eq!(table.parameter(100.0), 1.0);
+    }
+}
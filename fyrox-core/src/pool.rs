@@ -28,6 +28,9 @@ use crate::{
     visitor::{Visit, VisitResult, Visitor},
 };
 use arrayvec::ArrayVec;
+use fxhash::FxHashMap;
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
 use std::any::Any;
 use std::{
     fmt::{Debug, Display, Formatter},
@@ -102,6 +105,9 @@ where
 {
     records: Vec<PoolRecord<T, P>>,
     free_stack: Vec<u32>,
+    // Highest generation of the records that were removed by defragmentation. New records start from the
+    // next generation, so stale handles to the removed records do not become valid again.
+    generation_floor: u32,
 }
 
 impl<T: Reflect> Reflect for Pool<T> {
@@ -362,6 +368,14 @@ where
         let mut region = visitor.enter_region(name)?;
         self.records.visit("Records", &mut region)?;
         self.free_stack.visit("FreeStack", &mut region)?;
+        if region.is_reading() {
+            self.generation_floor = 0;
+            // Pools that were never defragmented do not have the floor.
+            let _ = self.generation_floor.visit("GenerationFloor", &mut region);
+        } else if self.generation_floor != 0 {
+            self.generation_floor
+                .visit("GenerationFloor", &mut region)?;
+        }
         Ok(())
     }
 }
@@ -439,6 +453,7 @@ impl<T: Clone> Clone for Pool<T> {
         Self {
             records: self.records.clone(),
             free_stack: self.free_stack.clone(),
+            generation_floor: self.generation_floor,
         }
     }
 }
@@ -452,6 +467,7 @@ where
         Pool {
             records: Vec::new(),
             free_stack: Vec::new(),
+            generation_floor: 0,
         }
     }
 
@@ -461,9 +477,14 @@ where
         Pool {
             records: Vec::with_capacity(capacity),
             free_stack: Vec::new(),
+            generation_floor: 0,
         }
     }
 
+    fn new_record_generation(&self) -> u32 {
+        self.generation_floor + 1
+    }
+
     fn records_len(&self) -> u32 {
         u32::try_from(self.records.len()).expect("Number of records overflowed u32")
     }
@@ -556,14 +577,14 @@ where
                 // Spawn missing records to fill gaps.
                 for i in self.records_len()..index {
                     self.records.push(PoolRecord {
-                        generation: 1,
+                        generation: self.new_record_generation(),
                         payload: P::new_empty(),
                     });
                     self.free_stack.push(i);
                 }
 
                 let generation = if desired_generation == INVALID_GENERATION {
-                    1
+                    self.new_record_generation()
                 } else {
                     desired_generation
                 };
@@ -609,7 +630,7 @@ where
             handle
         } else {
             // No free records, create new one
-            let generation = 1;
+            let generation = self.new_record_generation();
 
             let handle = Handle {
                 index: self.records.len() as u32,
@@ -664,7 +685,7 @@ where
             handle
         } else {
             // No free records, create new one
-            let generation = 1;
+            let generation = self.new_record_generation();
 
             let handle = Handle {
                 index: self.records.len() as u32,
@@ -1293,6 +1314,209 @@ where
         MultiBorrowContext::new(self)
     }
 
+    /// Borrows mutable references to every object in the given set of handles at once. Unlike
+    /// [`Self::begin_multi_borrow`], the amount of references is not limited by a const parameter,
+    /// which makes this method suitable for large handle sets that must be processed together (for
+    /// example in parallel).
+    ///
+    /// Returns `None` if at least one handle is invalid or if the set contains a handle to the same
+    /// object more than once, because returning multiple mutable references to the same object is
+    /// forbidden by Rust safety rules. References are returned in the order of the handles.
+    ///
+    /// # Performance
+    ///
+    /// The method has `O(n*log(n))` complexity, where `n` - amount of handles in the set.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use fyrox_core::pool::Pool;
+    /// let mut pool = Pool::<u32>::new();
+    /// let handles = (0..10).map(|i| pool.spawn(i)).collect::<Vec<_>>();
+    /// for value in pool.try_borrow_many_mut(&handles[2..6]).unwrap() {
+    ///     *value *= 10;
+    /// }
+    /// assert_eq!(pool[handles[3]], 30);
+    /// // Duplicated handles are rejected.
+    /// assert!(pool.try_borrow_many_mut(&[handles[0], handles[0]]).is_none());
+    /// ```
+    pub fn try_borrow_many_mut(&mut self, handles: &[Handle<T>]) -> Option<Vec<&mut T>> {
+        let mut indices = handles.iter().map(|h| h.index).collect::<Vec<_>>();
+        indices.sort_unstable();
+        if indices.windows(2).any(|pair| pair[0] == pair[1]) {
+            return None;
+        }
+
+        if !handles.iter().all(|handle| self.is_valid_handle(*handle)) {
+            return None;
+        }
+
+        let records = self.records.as_mut_ptr();
+        Some(
+            handles
+                .iter()
+                .map(|handle| {
+                    // SAFETY: Every handle is valid, so its index is in bounds, and the indices are
+                    // unique, so every reference points to a different record. The records are not
+                    // accessed in any other way while the references are alive, because the pool is
+                    // borrowed mutably.
+                    let record = unsafe { &mut *records.add(handle.index as usize) };
+                    record.payload.as_mut().expect("handle must be valid")
+                })
+                .collect(),
+        )
+    }
+
+    /// Splits the pool into two disjoint sets of mutable references - the first one contains objects
+    /// with the given handles (in the same order), the second one contains every other object in the
+    /// pool with its handle. It is useful when some objects must be modified using data of every other
+    /// object in the pool (for example when a set of scene nodes must be updated using the rest of the
+    /// graph).
+    ///
+    /// Returns `None` in the same cases as [`Self::try_borrow_many_mut`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use fyrox_core::pool::Pool;
+    /// let mut pool = Pool::<u32>::new();
+    /// let a = pool.spawn(1);
+    /// let b = pool.spawn(2);
+    /// let c = pool.spawn(3);
+    /// let (mut selected, rest) = pool.try_split_mut(&[b]).unwrap();
+    /// for (_, value) in rest {
+    ///     *selected[0] += *value;
+    /// }
+    /// assert_eq!(pool[b], 6);
+    /// # let _ = (a, c);
+    /// ```
+    #[allow(clippy::type_complexity)]
+    pub fn try_split_mut(
+        &mut self,
+        handles: &[Handle<T>],
+    ) -> Option<(Vec<&mut T>, Vec<(Handle<T>, &mut T)>)> {
+        let mut positions = vec![None; self.records.len()];
+        for (position, handle) in handles.iter().enumerate() {
+            if !self.is_valid_handle(*handle) {
+                return None;
+            }
+            let slot = &mut positions[handle.index as usize];
+            if slot.is_some() {
+                return None;
+            }
+            *slot = Some(position);
+        }
+
+        let mut selected = handles.iter().map(|_| None).collect::<Vec<_>>();
+        let mut rest = Vec::new();
+        for (handle, payload) in self.pair_iter_mut() {
+            match positions[handle.index as usize] {
+                Some(position) => selected[position] = Some(payload),
+                None => rest.push((handle, payload)),
+            }
+        }
+
+        Some((
+            selected
+                .into_iter()
+                .map(|payload| payload.expect("every handle must be valid"))
+                .collect(),
+            rest,
+        ))
+    }
+
+    /// Compacts the pool by moving objects from the end of the pool to vacant records at the beginning
+    /// and then shrinks the pool. Records that were reserved by [`Self::take_reserve`] are never moved,
+    /// so tickets remain valid. Returns a map of old handles of every moved object to their new handles,
+    /// the handles that are absent in the map remain valid. Generations of moved objects are changed and
+    /// records created at the indices of removed records start from higher generations, so stale handles
+    /// will not become valid after defragmentation.
+    ///
+    /// # Important notes
+    ///
+    /// Every handle to a moved object that is stored somewhere (including handles stored in the objects
+    /// of the pool) must be remapped using the returned map, otherwise it will be invalid or even point
+    /// to a different object.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use fyrox_core::pool::Pool;
+    /// let mut pool = Pool::<u32>::new();
+    /// let a = pool.spawn(1);
+    /// let b = pool.spawn(2);
+    /// let c = pool.spawn(3);
+    /// pool.free(a);
+    /// let remap = pool.defragment();
+    /// assert_eq!(pool.get_capacity(), 2);
+    /// assert_eq!(remap.map(b), b);
+    /// assert_eq!(pool[remap.map(c)], 3);
+    /// ```
+    pub fn defragment(&mut self) -> HandleRemap<T> {
+        let mut remap = HandleRemap::default();
+
+        let mut vacant = std::mem::take(&mut self.free_stack);
+        vacant.sort_unstable();
+
+        let mut vacant_iter = vacant.iter().cloned().peekable();
+        let mut occupied = self.records.len();
+        while let Some(&vacant_index) = vacant_iter.peek() {
+            // Find the last occupied record.
+            while occupied > 0 && self.records[occupied - 1].payload.as_ref().is_none() {
+                occupied -= 1;
+            }
+            if occupied == 0 || vacant_index as usize >= occupied - 1 {
+                break;
+            }
+            occupied -= 1;
+            vacant_iter.next();
+
+            let payload = self.records[occupied]
+                .payload
+                .take()
+                .expect("record must be occupied");
+            let old_generation = self.records[occupied].generation;
+            let target = &mut self.records[vacant_index as usize];
+            let new_generation = target.generation.max(old_generation) + 1;
+            target.generation = new_generation;
+            target.payload.replace(payload);
+            // The old record becomes vacant, it will be either removed or put in the free stack later on.
+            self.records[occupied].generation = new_generation;
+
+            remap.map.insert(
+                Handle::new(occupied as u32, old_generation),
+                Handle::new(vacant_index, new_generation),
+            );
+        }
+
+        // Vacant records that were not filled could only be located before reserved records or at the
+        // end of the pool.
+        let mut is_vacant = vec![false; self.records.len()];
+        for index in vacant_iter.chain(remap.map.keys().map(|handle| handle.index)) {
+            is_vacant[index as usize] = true;
+        }
+        let new_len = is_vacant
+            .iter()
+            .rposition(|vacant| !vacant)
+            .map_or(0, |last| last + 1);
+        if let Some(max_generation) = self.records[new_len..]
+            .iter()
+            .map(|record| record.generation)
+            .max()
+        {
+            self.generation_floor = self.generation_floor.max(max_generation);
+        }
+        self.records.truncate(new_len);
+        // Keep the lowest indices on the top of the stack, so new objects will be spawned at the
+        // beginning of the pool.
+        self.free_stack = (0..new_len as u32)
+            .rev()
+            .filter(|index| is_vacant[*index as usize])
+            .collect();
+
+        remap
+    }
+
     /// Removes all elements from the pool.
     pub fn drain(&mut self) -> impl Iterator<Item = T> + '_ {
         self.free_stack.clear();
@@ -1513,6 +1737,145 @@ where
     }
 }
 
+#[cfg(feature = "rayon")]
+impl<T, P> Pool<T, P>
+where
+    P: PayloadContainer<Element = T> + 'static,
+{
+    /// Creates new parallel iterator that iterates over filled records in pool using all available
+    /// threads.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use fyrox_core::{pool::Pool, rayon::prelude::*};
+    /// let pool = (0..1000u32).collect::<Pool<_>>();
+    /// assert_eq!(pool.par_iter().map(|v| *v as u64).sum::<u64>(), 499500);
+    /// ```
+    pub fn par_iter(&self) -> impl ParallelIterator<Item = &T> + '_
+    where
+        T: Sync,
+        P: Sync,
+    {
+        self.records
+            .par_iter()
+            .filter_map(|record| record.payload.as_ref())
+    }
+
+    /// Creates new parallel iterator that iterates over filled records in pool allowing to modify
+    /// record payload.
+    pub fn par_iter_mut(&mut self) -> impl ParallelIterator<Item = &mut T> + '_
+    where
+        T: Send,
+        P: Send,
+    {
+        self.records
+            .par_iter_mut()
+            .filter_map(|record| record.payload.as_mut())
+    }
+
+    /// Creates new parallel iterator that iterates over filled records using pair (handle, payload).
+    pub fn par_pair_iter(&self) -> impl ParallelIterator<Item = (Handle<T>, &T)> + '_
+    where
+        T: Sync,
+        P: Sync,
+    {
+        self.records
+            .par_iter()
+            .enumerate()
+            .filter_map(|(index, record)| {
+                record
+                    .payload
+                    .as_ref()
+                    .map(|payload| (Handle::new(index as u32, record.generation), payload))
+            })
+    }
+
+    /// Creates new parallel iterator that iterates over filled records using pair (handle, payload)
+    /// allowing to modify record payload.
+    pub fn par_pair_iter_mut(&mut self) -> impl ParallelIterator<Item = (Handle<T>, &mut T)> + '_
+    where
+        T: Send,
+        P: Send,
+    {
+        self.records
+            .par_iter_mut()
+            .enumerate()
+            .filter_map(|(index, record)| {
+                let generation = record.generation;
+                record
+                    .payload
+                    .as_mut()
+                    .map(|payload| (Handle::new(index as u32, generation), payload))
+            })
+    }
+}
+
+/// A map of old handles of objects to their new handles after [`Pool::defragment`].
+pub struct HandleRemap<T> {
+    map: FxHashMap<Handle<T>, Handle<T>>,
+}
+
+impl<T> Default for HandleRemap<T> {
+    fn default() -> Self {
+        Self {
+            map: Default::default(),
+        }
+    }
+}
+
+impl<T> Clone for HandleRemap<T> {
+    fn clone(&self) -> Self {
+        Self {
+            map: self.map.clone(),
+        }
+    }
+}
+
+impl<T> Debug for HandleRemap<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_map().entries(self.map.iter()).finish()
+    }
+}
+
+impl<T> HandleRemap<T> {
+    /// Returns a new handle of an object, or the same handle if the object was not moved.
+    #[inline]
+    pub fn map(&self, handle: Handle<T>) -> Handle<T> {
+        self.map.get(&handle).cloned().unwrap_or(handle)
+    }
+
+    /// Returns a new handle of an object only if the object was moved.
+    #[inline]
+    pub fn try_map(&self, handle: Handle<T>) -> Option<Handle<T>> {
+        self.map.get(&handle).cloned()
+    }
+
+    /// Remaps every handle in the slice.
+    pub fn map_slice(&self, handles: &mut [Handle<T>]) {
+        for handle in handles {
+            *handle = self.map(*handle);
+        }
+    }
+
+    /// Returns an iterator over `(old, new)` pairs of handles of moved objects.
+    pub fn iter(&self) -> impl Iterator<Item = (Handle<T>, Handle<T>)> + '_ {
+        self.map.iter().map(|(old, new)| (*old, *new))
+    }
+
+    /// Returns amount of moved objects.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    /// Returns `true` if no objects were moved.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+}
+
 /// Multi-borrow context allows you to get as many **unique** references to elements in
 /// a pool as you want.  
 pub struct MultiBorrowContext<'a, const N: usize, T, P = Option<T>>
@@ -1708,4 +2071,81 @@ mod test {
         // Test out-of-space - context has limited capacity.mut
         assert_eq!(ctx.try_get(c), None);
     }
+
+    #[test]
+    fn test_split_borrow() {
+        let mut pool = Pool::<u32>::new();
+        let handles = (0..5).map(|i| pool.spawn(i)).collect::<Vec<_>>();
+
+        let mut many = pool.try_borrow_many_mut(&[handles[3], handles[1]]).unwrap();
+        *many[0] = 30;
+        *many[1] = 10;
+        assert_eq!(pool[handles[3]], 30);
+        assert_eq!(pool[handles[1]], 10);
+
+        assert!(pool
+            .try_borrow_many_mut(&[handles[1], handles[1]])
+            .is_none());
+        assert!(pool.try_borrow_many_mut(&[Handle::NONE]).is_none());
+
+        let (selected, rest) = pool.try_split_mut(&[handles[4], handles[0]]).unwrap();
+        assert_eq!(*selected[0], 4);
+        assert_eq!(*selected[1], 0);
+        assert_eq!(
+            rest.iter().map(|(h, _)| *h).collect::<Vec<_>>(),
+            vec![handles[1], handles[2], handles[3]]
+        );
+        assert!(pool.try_split_mut(&[handles[2], handles[2]]).is_none());
+    }
+
+    #[test]
+    fn test_defragment() {
+        let mut pool = Pool::<u32>::new();
+        let handles = (0..6).map(|i| pool.spawn(i)).collect::<Vec<_>>();
+        pool.free(handles[0]);
+        pool.free(handles[2]);
+        let (ticket, value) = pool.take_reserve(handles[3]);
+
+        let remap = pool.defragment();
+
+        // Two last objects are moved to the beginning, reserved record stays in place.
+        assert_eq!(remap.len(), 2);
+        assert_eq!(pool.get_capacity(), 4);
+        assert_eq!(remap.map(handles[1]), handles[1]);
+        assert!(!pool.is_valid_handle(handles[4]));
+        assert!(!pool.is_valid_handle(handles[5]));
+        assert!(!pool.is_valid_handle(handles[0]));
+        assert_eq!(pool[remap.map(handles[4])], 4);
+        assert_eq!(pool[remap.map(handles[5])], 5);
+        assert_eq!(pool.alive_count(), 3);
+
+        assert_eq!(pool.put_back(ticket, value), handles[3]);
+        assert_eq!(pool.alive_count(), 4);
+
+        // Vacant records are not left since everything is compacted.
+        let new = pool.spawn(6);
+        assert_eq!(new.index(), 4);
+        assert!(pool.defragment().is_empty());
+
+        // Records at the indices of removed records have new generations, so old handles stay invalid.
+        let new = (0..2).map(|i| pool.spawn(7 + i)).collect::<Vec<_>>();
+        assert_eq!(new[0].index(), handles[5].index());
+        for handle in handles[4..].iter().chain(&handles[..1]) {
+            assert!(!pool.is_valid_handle(*handle));
+        }
+        for handle in new.iter() {
+            assert!(pool.is_valid_handle(*handle));
+        }
+
+        // Spawning at a distant index uses the new generations too.
+        let mut pool = Pool::<u32>::new();
+        let handles = (0..3).map(|i| pool.spawn(i)).collect::<Vec<_>>();
+        pool.free(handles[1]);
+        pool.free(handles[2]);
+        assert!(pool.defragment().is_empty());
+        assert_eq!(pool.get_capacity(), 1);
+        pool.spawn_at(2, 2).unwrap();
+        assert!(!pool.is_valid_handle(handles[1]));
+        assert!(!pool.is_valid_handle(handles[2]));
+    }
 }
@@ -1,14 +1,27 @@
 //! Built-in scoped profiler. You must compile with feature "enable_profiler" to
 //! force profiler gather info! It is disabled by default because it is not cheap
 //! and takes 3-5% of performance for internal needs.
+//!
+//! Profiler gathers two kinds of data: aggregated tree of scopes (see [`print`] and
+//! [`print_hot_path`]) and a timeline of the last frames, where every scope of every thread is
+//! recorded individually (see [`timeline`] and [`TimelineSnapshot`]). The timeline could be
+//! exported to Chrome trace format and viewed in `chrome://tracing` or [Perfetto](https://ui.perfetto.dev),
+//! it is the best tool to find the reason of hitches (frames that took much more time than others).
 
 #![allow(dead_code)]
 
+use crate::instant::Instant;
 use fxhash::{FxHashMap, FxHashSet, FxHasher};
+use once_cell::sync::Lazy;
 use std::{
+    borrow::Cow,
+    cell::Cell,
+    cmp::Ordering,
+    collections::VecDeque,
     fmt,
     fmt::Write,
     hash::{Hash, Hasher},
+    path::Path,
     sync::{Arc, Mutex},
 };
 
@@ -236,6 +249,7 @@ lazy_static! {
 pub struct ScopeDefinition {
     scope: ScopeMark,
     start_time: std::time::Instant,
+    timeline_scope: TimelineScope,
 }
 
 impl ScopeDefinition {
@@ -253,6 +267,12 @@ impl ScopeDefinition {
         Self {
             scope,
             start_time: std::time::Instant::now(),
+            // Function name is a path to `scope` function, defined by `scope_profile` macro.
+            timeline_scope: timeline_scope(
+                function_name
+                    .strip_suffix("::scope")
+                    .unwrap_or(function_name),
+            ),
         }
     }
 
@@ -269,6 +289,363 @@ impl Drop for ScopeDefinition {
     }
 }
 
+/// A scope recorded on the timeline.
+#[derive(Clone, Debug)]
+pub struct ScopeRecord {
+    /// Name of the scope.
+    pub name: Cow<'static, str>,
+    /// Index of the thread in [`TimelineSnapshot::threads`].
+    pub thread: usize,
+    /// Start time of the scope in seconds since the start of profiling.
+    pub start: f64,
+    /// Duration of the scope in seconds.
+    pub duration: f64,
+    /// Nesting depth of the scope in its thread, top-level scopes have zero depth.
+    pub depth: usize,
+}
+
+impl ScopeRecord {
+    /// Returns end time of the scope in seconds since the start of profiling.
+    pub fn end(&self) -> f64 {
+        self.start + self.duration
+    }
+}
+
+/// A frame recorded on the timeline.
+#[derive(Clone, Debug, PartialEq)]
+pub struct FrameRecord {
+    /// Index of the frame since the start of profiling.
+    pub index: u64,
+    /// Start time of the frame in seconds since the start of profiling.
+    pub start: f64,
+    /// Duration of the frame in seconds.
+    pub duration: f64,
+}
+
+impl FrameRecord {
+    /// Returns end time of the frame in seconds since the start of profiling.
+    pub fn end(&self) -> f64 {
+        self.start + self.duration
+    }
+}
+
+/// A copy of the recorded timeline, see [`timeline`].
+#[derive(Clone, Debug, Default)]
+pub struct TimelineSnapshot {
+    /// Names of every thread that has recorded scopes.
+    pub threads: Vec<String>,
+    /// Recorded frames, sorted by time.
+    pub frames: Vec<FrameRecord>,
+    /// Recorded scopes, sorted by start time.
+    pub scopes: Vec<ScopeRecord>,
+}
+
+impl TimelineSnapshot {
+    /// Returns every scope that overlaps the given time range.
+    pub fn scopes_in_range(&self, start: f64, end: f64) -> impl Iterator<Item = &ScopeRecord> {
+        self.scopes
+            .iter()
+            .filter(move |scope| scope.start < end && scope.end() > start)
+    }
+
+    /// Returns the frame that took most of the time.
+    pub fn longest_frame(&self) -> Option<&FrameRecord> {
+        self.frames.iter().max_by(|a, b| {
+            a.duration
+                .partial_cmp(&b.duration)
+                .unwrap_or(Ordering::Equal)
+        })
+    }
+
+    /// Converts the timeline to Chrome trace event format (JSON), that could be viewed in `chrome://tracing`
+    /// or [Perfetto](https://ui.perfetto.dev). Scopes are written as complete events and frames as
+    /// instant events.
+    pub fn to_chrome_trace(&self) -> String {
+        const MICROSECONDS: f64 = 1_000_000.0;
+
+        let mut events = Vec::new();
+
+        for (tid, name) in self.threads.iter().enumerate() {
+            events.push(format!(
+                r#"{{"name":"thread_name","ph":"M","pid":0,"tid":{},"args":{{"name":{}}}}}"#,
+                tid,
+                json_string(name)
+            ));
+        }
+
+        for frame in self.frames.iter() {
+            events.push(format!(
+                r#"{{"name":"Frame {}","ph":"i","s":"g","pid":0,"tid":0,"ts":{},"args":{{"duration_ms":{}}}}}"#,
+                frame.index,
+                frame.start * MICROSECONDS,
+                frame.duration * 1000.0
+            ));
+        }
+
+        for scope in self.scopes.iter() {
+            events.push(format!(
+                r#"{{"name":{},"ph":"X","pid":0,"tid":{},"ts":{},"dur":{}}}"#,
+                json_string(&scope.name),
+                scope.thread,
+                scope.start * MICROSECONDS,
+                scope.duration * MICROSECONDS
+            ));
+        }
+
+        format!(
+            r#"{{"traceEvents":[{}],"displayTimeUnit":"ms"}}"#,
+            events.join(",")
+        )
+    }
+
+    /// Saves the timeline in Chrome trace event format to the given file. See [`Self::to_chrome_trace`].
+    pub fn save_chrome_trace<P: AsRef<Path>>(&self, path: P) -> std::io::Result<()> {
+        std::fs::write(path, self.to_chrome_trace())
+    }
+}
+
+// Converts the string to JSON string literal.
+fn json_string(string: &str) -> String {
+    let mut result = String::with_capacity(string.len() + 2);
+    result.push('"');
+    for c in string.chars() {
+        match c {
+            '"' => result.push_str("\\\""),
+            '\\' => result.push_str("\\\\"),
+            '\n' => result.push_str("\\n"),
+            '\r' => result.push_str("\\r"),
+            '\t' => result.push_str("\\t"),
+            c if (c as u32) < 0x20 => result.push_str(&format!("\\u{:04x}", c as u32)),
+            c => result.push(c),
+        }
+    }
+    result.push('"');
+    result
+}
+
+/// Default amount of frames kept on the timeline.
+pub const DEFAULT_MAX_RECORDED_FRAMES: usize = 300;
+
+/// Default maximum amount of scopes kept on the timeline. The oldest scopes are removed when the limit is
+/// reached, it prevents unbounded growth of the timeline when [`mark_frame`] is never called.
+pub const DEFAULT_MAX_RECORDED_SCOPES: usize = 1_000_000;
+
+struct Timeline {
+    start_time: Instant,
+    enabled: bool,
+    max_frames: usize,
+    max_scopes: usize,
+    threads: Vec<String>,
+    frames: VecDeque<FrameRecord>,
+    frame_start: f64,
+    frame_index: u64,
+    scopes: VecDeque<ScopeRecord>,
+}
+
+impl Default for Timeline {
+    fn default() -> Self {
+        Self {
+            start_time: Instant::now(),
+            enabled: true,
+            max_frames: DEFAULT_MAX_RECORDED_FRAMES,
+            max_scopes: DEFAULT_MAX_RECORDED_SCOPES,
+            threads: Default::default(),
+            frames: Default::default(),
+            frame_start: 0.0,
+            frame_index: 0,
+            scopes: Default::default(),
+        }
+    }
+}
+
+impl Timeline {
+    fn time(&self, instant: Instant) -> f64 {
+        instant
+            .saturating_duration_since(self.start_time)
+            .as_secs_f64()
+    }
+
+    fn remove_outdated(&mut self) {
+        while self.frames.len() > self.max_frames {
+            self.frames.pop_front();
+        }
+
+        let first_frame_start = self.frames.front().map_or(self.frame_start, |f| f.start);
+        self.scopes.retain(|scope| scope.end() >= first_frame_start);
+
+        self.remove_excessive_scopes();
+    }
+
+    fn remove_excessive_scopes(&mut self) {
+        while self.scopes.len() > self.max_scopes {
+            self.scopes.pop_front();
+        }
+    }
+}
+
+static TIMELINE: Lazy<crate::parking_lot::Mutex<Timeline>> = Lazy::new(Default::default);
+
+thread_local! {
+    static THREAD_INDEX: Cell<Option<usize>> = Cell::new(None);
+}
+
+fn thread_index(timeline: &mut Timeline) -> usize {
+    THREAD_INDEX.with(|index| match index.get() {
+        Some(index) => index,
+        None => {
+            let thread = std::thread::current();
+            let name = match thread.name() {
+                Some(name) => name.to_owned(),
+                None => format!("Thread {}", timeline.threads.len()),
+            };
+            timeline.threads.push(name);
+            let new_index = timeline.threads.len() - 1;
+            index.set(Some(new_index));
+            new_index
+        }
+    })
+}
+
+/// A guard that records a scope on the timeline when dropped, see [`timeline_scope`].
+pub struct TimelineScope {
+    // Name and start time of the scope, `None` if the profiler is disabled.
+    scope: Option<(Cow<'static, str>, Instant)>,
+}
+
+impl Drop for TimelineScope {
+    fn drop(&mut self) {
+        if let Some((name, start_time)) = self.scope.take() {
+            let end_time = Instant::now();
+            let mut timeline = TIMELINE.lock();
+            if timeline.enabled {
+                let thread = thread_index(&mut timeline);
+                let start = timeline.time(start_time);
+                let end = timeline.time(end_time);
+                timeline.scopes.push_back(ScopeRecord {
+                    name,
+                    thread,
+                    start,
+                    duration: end - start,
+                    depth: 0,
+                });
+                timeline.remove_excessive_scopes();
+            }
+        }
+    }
+}
+
+/// Starts a scope with the given name, that will be recorded on the timeline when the returned guard
+/// is dropped. Unlike [`scope_profile`] macro, it allows to use any name for a scope (for example, a
+/// path of a resource that is being loaded). The scope is recorded on the thread where it was finished,
+/// so it could be used for asynchronous tasks (the guard should be created inside of the task). It does
+/// nothing if the profiler is disabled.
+#[inline]
+pub fn timeline_scope<N: Into<Cow<'static, str>>>(name: N) -> TimelineScope {
+    #[cfg(feature = "enable_profiler")]
+    {
+        TimelineScope {
+            scope: Some((name.into(), Instant::now())),
+        }
+    }
+
+    #[cfg(not(feature = "enable_profiler"))]
+    {
+        let _ = name;
+        TimelineScope { scope: None }
+    }
+}
+
+/// Marks the end of the current frame and the start of the next one. The engine calls it at the end of
+/// every rendered frame.
+pub fn mark_frame() {
+    if cfg!(feature = "enable_profiler") {
+        let now = Instant::now();
+        let mut timeline = TIMELINE.lock();
+        if timeline.enabled {
+            let end = timeline.time(now);
+            let frame = FrameRecord {
+                index: timeline.frame_index,
+                start: timeline.frame_start,
+                duration: end - timeline.frame_start,
+            };
+            timeline.frames.push_back(frame);
+            timeline.frame_index += 1;
+            timeline.frame_start = end;
+            timeline.remove_outdated();
+        }
+    }
+}
+
+/// Enables or disables timeline recording at runtime. It is enabled by default, if the profiler is
+/// enabled.
+pub fn set_timeline_enabled(enabled: bool) {
+    TIMELINE.lock().enabled = enabled;
+}
+
+/// Returns `true` if the timeline is being recorded.
+pub fn is_timeline_enabled() -> bool {
+    cfg!(feature = "enable_profiler") && TIMELINE.lock().enabled
+}
+
+/// Sets the amount of the last frames, which scopes will be kept on the timeline. Default value is
+/// [`DEFAULT_MAX_RECORDED_FRAMES`].
+pub fn set_max_recorded_frames(max_frames: usize) {
+    let mut timeline = TIMELINE.lock();
+    timeline.max_frames = max_frames;
+    timeline.remove_outdated();
+}
+
+/// Sets the maximum amount of scopes kept on the timeline. Default value is [`DEFAULT_MAX_RECORDED_SCOPES`].
+pub fn set_max_recorded_scopes(max_scopes: usize) {
+    let mut timeline = TIMELINE.lock();
+    timeline.max_scopes = max_scopes;
+    timeline.remove_excessive_scopes();
+}
+
+/// Removes every recorded frame and scope from the timeline.
+pub fn clear_timeline() {
+    let mut timeline = TIMELINE.lock();
+    let now = timeline.time(Instant::now());
+    timeline.frames.clear();
+    timeline.scopes.clear();
+    timeline.frame_start = now;
+}
+
+/// Returns a copy of the recorded timeline. Snapshot is empty if the profiler is disabled.
+pub fn timeline() -> TimelineSnapshot {
+    let timeline = TIMELINE.lock();
+
+    let mut scopes = timeline.scopes.iter().cloned().collect::<Vec<_>>();
+    // Parents start earlier and last longer than their children.
+    scopes.sort_by(|a, b| {
+        a.start
+            .partial_cmp(&b.start)
+            .unwrap_or(Ordering::Equal)
+            .then(
+                b.duration
+                    .partial_cmp(&a.duration)
+                    .unwrap_or(Ordering::Equal),
+            )
+    });
+
+    // Calculate nesting depth of the scopes using a stack of ends of the parent scopes of every thread.
+    let mut stacks = vec![Vec::<f64>::new(); timeline.threads.len()];
+    for scope in scopes.iter_mut() {
+        let stack = &mut stacks[scope.thread];
+        while stack.last().map_or(false, |end| *end <= scope.start) {
+            stack.pop();
+        }
+        scope.depth = stack.len();
+        stack.push(scope.end());
+    }
+
+    TimelineSnapshot {
+        threads: timeline.threads.clone(),
+        frames: timeline.frames.iter().cloned().collect(),
+        scopes,
+    }
+}
+
 #[inline]
 pub fn type_name_of<T>(_: T) -> &'static str {
     std::any::type_name::<T>()
@@ -314,6 +691,63 @@ mod test {
         some_func();
     }
 
+    #[test]
+    fn test_json_string() {
+        let string = "Thread \"1\"\\\n\u{1}";
+        let json = profiler::json_string(string);
+        assert_eq!(serde_json::from_str::<String>(&json).unwrap(), string);
+    }
+
+    #[test]
+    fn test_timeline() {
+        profiler::clear_timeline();
+        {
+            let _outer = profiler::timeline_scope("Outer");
+            let _inner = profiler::timeline_scope("Inner");
+            std::thread::spawn(|| {
+                let _task = profiler::timeline_scope(format!("Task {}", 1));
+            })
+            .join()
+            .unwrap();
+        }
+        profiler::mark_frame();
+
+        let timeline = profiler::timeline();
+        if cfg!(feature = "enable_profiler") {
+            assert_eq!(timeline.frames.len(), 1);
+            let scope = |name: &str| {
+                timeline
+                    .scopes
+                    .iter()
+                    .find(|scope| scope.name == name)
+                    .unwrap()
+            };
+            assert_eq!(scope("Outer").depth, 0);
+            assert_eq!(scope("Inner").depth, 1);
+            assert_eq!(scope("Task 1").depth, 0);
+            assert_ne!(scope("Task 1").thread, scope("Outer").thread);
+
+            let trace: serde_json::Value =
+                serde_json::from_str(&timeline.to_chrome_trace()).unwrap();
+            assert!(trace["traceEvents"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .any(|event| event["name"] == "Task 1" && event["ph"] == "X"));
+
+            // Scopes are bounded even if frames are not marked.
+            profiler::set_max_recorded_scopes(10);
+            for _ in 0..20 {
+                let _scope = profiler::timeline_scope("Unmarked");
+            }
+            assert!(profiler::timeline().scopes.len() <= 10);
+            profiler::set_max_recorded_scopes(profiler::DEFAULT_MAX_RECORDED_SCOPES);
+        } else {
+            assert!(timeline.frames.is_empty());
+            assert!(timeline.scopes.is_empty());
+        }
+    }
+
     #[test]
     fn test_scope_perf() {
         {
@@ -3,7 +3,13 @@
 mod external_impls;
 mod std_impls;
 
+pub mod diff;
+pub mod method;
+#[cfg(feature = "reflect_serde")]
+pub mod serde;
+
 pub use fyrox_core_derive::Reflect;
+pub use method::{InvokeError, MethodInfo, ReflectMethod};
 use std::{
     any::{Any, TypeId},
     fmt::{self, Debug, Display, Formatter},
@@ -12,8 +18,8 @@ use std::{
 
 pub mod prelude {
     pub use super::{
-        FieldInfo, Reflect, ReflectArray, ReflectHashMap, ReflectInheritableVariable, ReflectList,
-        ResolvePath, SetFieldByPathError,
+        FieldInfo, InvokeError, MethodInfo, Reflect, ReflectArray, ReflectHashMap,
+        ReflectInheritableVariable, ReflectList, ReflectMethod, ResolvePath, SetFieldByPathError,
     };
 }
 
@@ -141,6 +147,7 @@ impl<'a> PartialEq<Self> for FieldInfo<'a> {
 /// # Type attributes
 /// - `#[reflect(hide_all)]`: Hide all fields, just like `Any`
 /// - `#[reflect(bounds)]`: Add type boundary for `Reflect` impl
+/// - `#[reflect(method(name = "..."))]`: Register a method for [`Reflect::invoke_method`], see [`MethodInfo`]
 ///
 /// # Field attributes
 /// - `#[reflect(deref)]`: Delegate the field access with deref
@@ -250,6 +257,25 @@ pub trait Reflect: Any + Debug {
     fn as_hash_map_mut(&mut self, func: &mut dyn FnMut(Option<&mut dyn ReflectHashMap>)) {
         func(None)
     }
+
+    /// Returns information about the methods registered with `#[reflect(method(..))]` attribute. See
+    /// [`MethodInfo`] docs for more info.
+    fn methods_info(&self, func: &mut dyn FnMut(Vec<MethodInfo>)) {
+        func(vec![])
+    }
+
+    /// Invokes a method registered with `#[reflect(method(..))]` attribute with the given arguments.
+    /// Passes the return value of the method to the callback on success.
+    fn invoke_method(
+        &mut self,
+        name: &str,
+        #[allow(unused_variables)] args: Vec<Box<dyn Reflect>>,
+        func: &mut dyn FnMut(Result<Box<dyn Reflect>, InvokeError>),
+    ) {
+        func(Err(InvokeError::UnknownMethod {
+            name: name.to_owned(),
+        }))
+    }
 }
 
 /// [`Reflect`] sub trait for working with slices.
@@ -718,6 +744,32 @@ impl dyn Reflect {
         self.as_any_mut(&mut |any| func(any.downcast_mut::<T>()))
     }
 
+    /// Returns type id of the value returned by [`Reflect::as_any`]. Unlike [`Any::type_id`], it is the id
+    /// of the inner value for wrappers that forward `as_any` (`Box`, `InheritableVariable`, etc.).
+    #[inline]
+    pub fn any_type_id(&self) -> TypeId {
+        let mut type_id = None;
+        self.as_any(&mut |any| type_id = Some(any.type_id()));
+        type_id.unwrap()
+    }
+
+    /// Invokes a method by its name and returns its return value. See [`Reflect::invoke_method`] for
+    /// more info.
+    #[inline]
+    pub fn call_method(
+        &mut self,
+        name: &str,
+        args: Vec<Box<dyn Reflect>>,
+    ) -> Result<Box<dyn Reflect>, InvokeError> {
+        let mut result = None;
+        self.invoke_method(name, args, &mut |r| result = Some(r));
+        result.unwrap_or_else(|| {
+            Err(InvokeError::UnknownMethod {
+                name: name.to_owned(),
+            })
+        })
+    }
+
     /// Sets a field by its path in the given entity. This method always uses [`Reflect::set_field`] which means,
     /// that it will always call custom property setters.
     #[inline]
@@ -886,6 +938,19 @@ macro_rules! delegate_reflect {
         fn as_list_mut(&mut self, func: &mut dyn FnMut(Option<&mut dyn ReflectList>)) {
             self.deref_mut().as_list_mut(func)
         }
+
+        fn methods_info(&self, func: &mut dyn FnMut(Vec<MethodInfo>)) {
+            self.deref().methods_info(func)
+        }
+
+        fn invoke_method(
+            &mut self,
+            name: &str,
+            args: Vec<Box<dyn Reflect>>,
+            func: &mut dyn FnMut(Result<Box<dyn Reflect>, InvokeError>),
+        ) {
+            self.deref_mut().invoke_method(name, args, func)
+        }
     };
 }
 
@@ -0,0 +1,691 @@
+//! Property diff and patch for [`Reflect`] types. It allows to find the difference between two values
+//! of the same type as a set of changed properties and to apply the changes to other values. See
+//! [`DiffRegistry`] and [`Patch`] docs for more info.
+
+use crate::{
+    algebra::{Matrix2, Matrix3, Matrix4, UnitQuaternion, Vector2, Vector3, Vector4},
+    color::Color,
+    reflect::prelude::*,
+    sstorage::ImmutableString,
+    uuid::Uuid,
+};
+use fxhash::FxHashMap;
+use std::{
+    any::TypeId,
+    fmt::{Debug, Display, Formatter},
+    path::PathBuf,
+    time::Duration,
+};
+
+/// An error that may occur when calculating the difference between two values.
+#[derive(Debug)]
+pub enum DiffError {
+    /// Values at the path are different, but their type is not registered in the registry, so they
+    /// cannot be compared or copied to a patch.
+    UnregisteredType {
+        /// Path of the property.
+        path: String,
+        /// Name of the type.
+        type_name: &'static str,
+    },
+}
+
+impl Display for DiffError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::UnregisteredType { path, type_name } => {
+                write!(
+                    f,
+                    "values of property `{path}` are different, but type {type_name} is not registered"
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for DiffError {}
+
+/// An error that may occur when applying a patch.
+#[derive(Debug)]
+pub enum PatchError {
+    /// A property with the given path does not exist in the target value.
+    InvalidPath {
+        /// Path of the property.
+        path: String,
+        /// Description of the error.
+        reason: String,
+    },
+    /// A new value was rejected by the target property (type mismatch or a setter refused it).
+    InvalidValue {
+        /// Path of the property.
+        path: String,
+    },
+}
+
+impl Display for PatchError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidPath { path, reason } => {
+                write!(f, "invalid property path `{path}`: {reason}")
+            }
+            Self::InvalidValue { path } => {
+                write!(f, "value of property `{path}` was rejected")
+            }
+        }
+    }
+}
+
+impl std::error::Error for PatchError {}
+
+type CloneFn = fn(&dyn Reflect) -> Box<dyn Reflect>;
+type EqFn = fn(&dyn Reflect, &dyn Reflect) -> bool;
+
+#[derive(Copy, Clone)]
+struct ValueVTable {
+    clone: CloneFn,
+    eq: EqFn,
+}
+
+fn clone_value<T: Reflect + Clone>(value: &dyn Reflect) -> Box<dyn Reflect> {
+    let mut result = None;
+    value.as_any(&mut |any| {
+        result = any
+            .downcast_ref::<T>()
+            .map(|value| Box::new(value.clone()) as Box<dyn Reflect>)
+    });
+    result.expect("value must have the type it was registered with")
+}
+
+fn eq_values<T: Reflect + PartialEq>(a: &dyn Reflect, b: &dyn Reflect) -> bool {
+    let mut result = false;
+    a.as_any(&mut |a| {
+        b.as_any(&mut |b| {
+            result = matches!(
+                (a.downcast_ref::<T>(), b.downcast_ref::<T>()),
+                (Some(a), Some(b)) if a == b
+            )
+        })
+    });
+    result
+}
+
+fn string_key(key: &dyn Reflect) -> Option<String> {
+    let mut result = None;
+    key.as_any(&mut |any| {
+        result = any
+            .downcast_ref::<String>()
+            .cloned()
+            .or_else(|| any.downcast_ref::<ImmutableString>().map(|s| s.to_string()))
+    });
+    result
+}
+
+fn field_path(path: &str, field: &str) -> String {
+    if path.is_empty() {
+        field.to_owned()
+    } else {
+        format!("{path}.{field}")
+    }
+}
+
+/// A single changed property.
+pub struct PropertyChange {
+    /// Path of the property, that could be used with [`ResolvePath`].
+    pub path: String,
+    /// Value of the property before the change.
+    pub old_value: Box<dyn Reflect>,
+    /// Value of the property after the change.
+    pub new_value: Box<dyn Reflect>,
+    vtable: ValueVTable,
+}
+
+impl Clone for PropertyChange {
+    fn clone(&self) -> Self {
+        Self {
+            path: self.path.clone(),
+            old_value: (self.vtable.clone)(&*self.old_value),
+            new_value: (self.vtable.clone)(&*self.new_value),
+            vtable: self.vtable,
+        }
+    }
+}
+
+impl Debug for PropertyChange {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PropertyChange")
+            .field("path", &self.path)
+            .field("old_value", &self.old_value)
+            .field("new_value", &self.new_value)
+            .finish()
+    }
+}
+
+impl PropertyChange {
+    /// Returns `true` if the old and the new values are equal.
+    pub fn is_noop(&self) -> bool {
+        (self.vtable.eq)(&*self.old_value, &*self.new_value)
+    }
+
+    /// Returns a change, that reverts this change.
+    pub fn inverse(&self) -> Self {
+        Self {
+            path: self.path.clone(),
+            old_value: (self.vtable.clone)(&*self.new_value),
+            new_value: (self.vtable.clone)(&*self.old_value),
+            vtable: self.vtable,
+        }
+    }
+
+    /// Writes the new value to the property of the given value. Returns the previous value of the
+    /// property on success.
+    pub fn apply(&self, target: &mut dyn Reflect) -> Result<Box<dyn Reflect>, PatchError> {
+        let value = (self.vtable.clone)(&*self.new_value);
+
+        if self.path.is_empty() {
+            return target.set(value).map_err(|_| PatchError::InvalidValue {
+                path: self.path.clone(),
+            });
+        }
+
+        let mut result = None;
+        if self.path.ends_with(']') {
+            // Items of collections do not have setters, so they're replaced directly.
+            let mut value = Some(value);
+            target.resolve_path_mut(&self.path, &mut |property| {
+                result =
+                    Some(match property {
+                        Ok(property) => property.set(value.take().unwrap()).map_err(|_| {
+                            PatchError::InvalidValue {
+                                path: self.path.clone(),
+                            }
+                        }),
+                        Err(reason) => Err(PatchError::InvalidPath {
+                            path: self.path.clone(),
+                            reason: reason.to_string(),
+                        }),
+                    })
+            });
+        } else {
+            target.set_field_by_path(&self.path, value, &mut |property| {
+                result = Some(property.map_err(|e| match e {
+                    SetFieldByPathError::InvalidPath { reason, .. } => PatchError::InvalidPath {
+                        path: self.path.clone(),
+                        reason: reason.to_string(),
+                    },
+                    SetFieldByPathError::InvalidValue(_) => PatchError::InvalidValue {
+                        path: self.path.clone(),
+                    },
+                }))
+            });
+        }
+
+        result.unwrap_or_else(|| {
+            Err(PatchError::InvalidPath {
+                path: self.path.clone(),
+                reason: "property was not found".to_string(),
+            })
+        })
+    }
+}
+
+/// Patch is an ordered set of property changes, usually produced by [`DiffRegistry::diff`]. It could
+/// be applied to any value that has the properties with the same paths (not necessarily of the same
+/// type), inverted to revert the changes and merged with other patches.
+///
+/// Patches are useful to coalesce a series of small changes into a single undoable change in the
+/// editor, to send only the changed state of an object over the network or to list the properties of
+/// a prefab instance that differ from the prefab.
+#[derive(Clone, Debug, Default)]
+pub struct Patch {
+    changes: Vec<PropertyChange>,
+}
+
+impl Patch {
+    /// Returns a reference to the changes of the patch.
+    pub fn changes(&self) -> &[PropertyChange] {
+        &self.changes
+    }
+
+    /// Returns the changes of the patch.
+    pub fn into_changes(self) -> Vec<PropertyChange> {
+        self.changes
+    }
+
+    /// Returns amount of changes in the patch.
+    pub fn len(&self) -> usize {
+        self.changes.len()
+    }
+
+    /// Returns `true` if the patch has no changes.
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    /// Returns an iterator over the paths of the changed properties.
+    pub fn paths(&self) -> impl Iterator<Item = &str> {
+        self.changes.iter().map(|change| change.path.as_str())
+    }
+
+    /// Returns a patch, that reverts this patch.
+    pub fn inverse(&self) -> Self {
+        Self {
+            changes: self
+                .changes
+                .iter()
+                .rev()
+                .map(PropertyChange::inverse)
+                .collect(),
+        }
+    }
+
+    /// Merges the other patch, that was made after this one, into this patch. Changes of the same
+    /// properties are coalesced - the old value is taken from this patch and the new value is taken
+    /// from the other patch. Changes that ended up with the original values are removed.
+    pub fn merge(&mut self, other: Patch) {
+        for change in other.changes {
+            if let Some(existing) = self.changes.iter_mut().find(|c| c.path == change.path) {
+                existing.new_value = change.new_value;
+            } else {
+                self.changes.push(change);
+            }
+        }
+        self.changes.retain(|change| !change.is_noop());
+    }
+
+    /// Applies every change of the patch to the given value in order. The application stops on the
+    /// first error, which means that the value could be partially modified.
+    pub fn apply(&self, target: &mut dyn Reflect) -> Result<(), PatchError> {
+        for change in self.changes.iter() {
+            change.apply(target)?;
+        }
+        Ok(())
+    }
+}
+
+/// Registry defines how the values are compared and copied when calculating the difference between two
+/// values.
+///
+/// ## Algorithm
+///
+/// - Values of types registered with [`DiffRegistry::register_value`] are compared using `PartialEq` and
+///   copied using `Clone`. The registry created with [`DiffRegistry::new`] has every primitive type, `String`,
+///   `PathBuf`, `Duration`, `ImmutableString`, `Uuid`, `Color` and common `nalgebra` types registered.
+/// - `InheritableVariable` is transparent, its inner value is compared.
+/// - Arrays and lists of the same length are compared item by item. Hash maps with the same set of string
+///   keys are compared value by value.
+/// - Structs are compared field by field, enums with the same current variant are compared field by field.
+/// - Any other difference (lists of different length, enums with different variants, etc.) is recorded as
+///   a change of the whole value, so the type of the value must be registered, otherwise
+///   [`DiffError::UnregisteredType`] is returned. Unregistered types without fields are compared using
+///   their `Debug` representation.
+///
+/// ## Example
+///
+/// ```rust
+/// use fyrox_core::reflect::{diff::DiffRegistry, prelude::*};
+///
+/// #[derive(Reflect, Clone, Debug, PartialEq)]
+/// struct Light {
+///     intensity: f32,
+///     shadows: bool,
+/// }
+///
+/// let registry = DiffRegistry::new();
+///
+/// let old = Light { intensity: 1.0, shadows: false };
+/// let new = Light { intensity: 2.0, shadows: false };
+///
+/// let patch = registry.diff(&old, &new).unwrap();
+/// assert_eq!(patch.paths().collect::<Vec<_>>(), vec!["intensity"]);
+///
+/// let mut light = old.clone();
+/// patch.apply(&mut light).unwrap();
+/// assert_eq!(light, new);
+///
+/// patch.inverse().apply(&mut light).unwrap();
+/// assert_eq!(light, old);
+/// ```
+pub struct DiffRegistry {
+    values: FxHashMap<TypeId, ValueVTable>,
+}
+
+impl Default for DiffRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+macro_rules! register_values {
+    ($registry:expr, $($ty:ty),* $(,)?) => {
+        $(
+            $registry.register_value::<$ty>();
+        )*
+    };
+}
+
+impl DiffRegistry {
+    /// Creates new registry with every built-in type registered.
+    pub fn new() -> Self {
+        let mut registry = Self::empty();
+
+        register_values!(
+            registry,
+            bool,
+            u8,
+            u16,
+            u32,
+            u64,
+            usize,
+            i8,
+            i16,
+            i32,
+            i64,
+            isize,
+            f32,
+            f64,
+            String,
+            PathBuf,
+            Duration,
+            ImmutableString,
+            Uuid,
+            Color,
+            Vector2<f32>,
+            Vector3<f32>,
+            Vector4<f32>,
+            Vector2<f64>,
+            Vector3<f64>,
+            Vector4<f64>,
+            Matrix2<f32>,
+            Matrix3<f32>,
+            Matrix4<f32>,
+            UnitQuaternion<f32>,
+            UnitQuaternion<f64>,
+        );
+
+        registry
+    }
+
+    /// Creates new registry without any registered types.
+    pub fn empty() -> Self {
+        Self {
+            values: Default::default(),
+        }
+    }
+
+    /// Registers a type, which values will be compared and copied as a whole. It should be used for
+    /// "leaf" types, that don't have reflected fields, and for the types that could change their
+    /// structure (enums, lists, etc.).
+    pub fn register_value<T>(&mut self)
+    where
+        T: Reflect + Clone + PartialEq,
+    {
+        self.values.insert(
+            TypeId::of::<T>(),
+            ValueVTable {
+                clone: clone_value::<T>,
+                eq: eq_values::<T>,
+            },
+        );
+    }
+
+    /// Returns `true` if the type is registered.
+    pub fn is_registered<T: 'static>(&self) -> bool {
+        self.values.contains_key(&TypeId::of::<T>())
+    }
+
+    /// Calculates the difference between two values. The result could be applied to the `old` value
+    /// to make it equal to the `new` value.
+    pub fn diff(&self, old: &dyn Reflect, new: &dyn Reflect) -> Result<Patch, DiffError> {
+        let mut changes = Vec::new();
+        self.diff_recursive("", old, new, &mut changes)?;
+        Ok(Patch { changes })
+    }
+
+    fn replace(
+        &self,
+        path: &str,
+        old: &dyn Reflect,
+        new: &dyn Reflect,
+        changes: &mut Vec<PropertyChange>,
+    ) -> Result<(), DiffError> {
+        match self.values.get(&new.any_type_id()) {
+            Some(vtable) if old.any_type_id() == new.any_type_id() => {
+                if !(vtable.eq)(old, new) {
+                    changes.push(PropertyChange {
+                        path: path.to_owned(),
+                        old_value: (vtable.clone)(old),
+                        new_value: (vtable.clone)(new),
+                        vtable: *vtable,
+                    });
+                }
+                Ok(())
+            }
+            _ => Err(DiffError::UnregisteredType {
+                path: path.to_owned(),
+                type_name: new.type_name(),
+            }),
+        }
+    }
+
+    fn diff_recursive(
+        &self,
+        path: &str,
+        old: &dyn Reflect,
+        new: &dyn Reflect,
+        changes: &mut Vec<PropertyChange>,
+    ) -> Result<(), DiffError> {
+        let type_id = old.any_type_id();
+        if type_id != new.any_type_id() || self.values.contains_key(&type_id) {
+            return self.replace(path, old, new, changes);
+        }
+
+        let mut map_result = None;
+        old.as_hash_map(&mut |old_map| {
+            if let Some(old_map) = old_map {
+                new.as_hash_map(&mut |new_map| {
+                    map_result = Some(self.diff_maps(path, old_map, new_map.unwrap(), changes))
+                })
+            }
+        });
+        if let Some(map_result) = map_result {
+            return map_result.or_else(|_| self.replace(path, old, new, changes));
+        }
+
+        let mut result = None;
+
+        old.as_array(&mut |old_array| {
+            if let Some(old_array) = old_array {
+                new.as_array(&mut |new_array| {
+                    let new_array = new_array.unwrap();
+                    result = Some(if old_array.reflect_len() == new_array.reflect_len() {
+                        (0..old_array.reflect_len()).try_for_each(|i| {
+                            self.diff_recursive(
+                                &format!("{path}[{i}]"),
+                                old_array.reflect_index(i).unwrap(),
+                                new_array.reflect_index(i).unwrap(),
+                                changes,
+                            )
+                        })
+                    } else {
+                        self.replace(path, old, new, changes)
+                    })
+                })
+            }
+        });
+        if let Some(result) = result {
+            return result;
+        }
+
+        old.fields_info(&mut |old_fields| {
+            new.fields_info(&mut |new_fields| {
+                let same_structure = old_fields.len() == new_fields.len()
+                    && old_fields
+                        .iter()
+                        .zip(new_fields.iter())
+                        .all(|(a, b)| a.name == b.name);
+
+                result = Some(if old_fields.is_empty() && new_fields.is_empty() {
+                    if format!("{old:?}") == format!("{new:?}") {
+                        Ok(())
+                    } else {
+                        self.replace(path, old, new, changes)
+                    }
+                } else if same_structure {
+                    old_fields
+                        .iter()
+                        .zip(new_fields.iter())
+                        .try_for_each(|(a, b)| {
+                            self.diff_recursive(
+                                &field_path(path, a.name),
+                                a.reflect_value,
+                                b.reflect_value,
+                                changes,
+                            )
+                        })
+                } else {
+                    self.replace(path, old, new, changes)
+                })
+            })
+        });
+
+        result.unwrap_or(Ok(()))
+    }
+
+    fn diff_maps(
+        &self,
+        path: &str,
+        old: &dyn ReflectHashMap,
+        new: &dyn ReflectHashMap,
+        changes: &mut Vec<PropertyChange>,
+    ) -> Result<(), ()> {
+        if old.reflect_len() != new.reflect_len() {
+            return Err(());
+        }
+
+        // Diff is written to a separate buffer first, so a failure won't leave partial changes.
+        let mut map_changes = Vec::new();
+        for i in 0..old.reflect_len() {
+            let (key, old_value) = old.reflect_get_at(i).ok_or(())?;
+            let string_key = string_key(key).ok_or(())?;
+            let mut result = Err(());
+            new.reflect_get(key, &mut |new_value| {
+                if let Some(new_value) = new_value {
+                    result = self
+                        .diff_recursive(
+                            &format!("{path}[{string_key}]"),
+                            old_value,
+                            new_value,
+                            &mut map_changes,
+                        )
+                        .map_err(|_| ());
+                }
+            });
+            result?;
+        }
+
+        changes.extend(map_changes);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        algebra::Vector3,
+        reflect::{diff::DiffRegistry, prelude::*},
+        variable::InheritableVariable,
+    };
+    use std::collections::HashMap;
+
+    #[derive(Reflect, Clone, Debug, PartialEq)]
+    enum Shape {
+        Sphere { radius: f32 },
+        Box { size: Vector3<f32> },
+    }
+
+    #[derive(Reflect, Clone, Debug, PartialEq)]
+    struct Item {
+        name: String,
+        shape: Shape,
+        tags: Vec<String>,
+        weights: Vec<f32>,
+        properties: HashMap<String, f32>,
+        position: InheritableVariable<Vector3<f32>>,
+    }
+
+    fn item() -> Item {
+        Item {
+            name: "Crate".to_string(),
+            shape: Shape::Sphere { radius: 1.0 },
+            tags: vec!["a".to_string()],
+            weights: vec![1.0, 2.0],
+            properties: [("mass".to_string(), 1.0)].into_iter().collect(),
+            position: Default::default(),
+        }
+    }
+
+    #[test]
+    fn test_diff_and_patch() {
+        let registry = DiffRegistry::new();
+
+        let old = item();
+        let mut new = item();
+        new.shape = Shape::Sphere { radius: 2.0 };
+        new.weights[1] = 3.0;
+        new.properties.insert("mass".to_string(), 5.0);
+        new.position
+            .set_value_and_mark_modified(Vector3::new(1.0, 2.0, 3.0));
+
+        let patch = registry.diff(&old, &new).unwrap();
+        let paths = patch.paths().collect::<Vec<_>>();
+        assert_eq!(
+            paths,
+            vec![
+                "shape.Sphere@radius",
+                "weights[1]",
+                "properties[mass]",
+                "position"
+            ]
+        );
+
+        let mut target = old.clone();
+        patch.apply(&mut target).unwrap();
+        assert_eq!(target, new);
+        assert!(target.position.is_modified());
+
+        patch.inverse().apply(&mut target).unwrap();
+        assert_eq!(target.weights, old.weights);
+        assert_eq!(*target.position, *old.position);
+
+        // Structural changes require registration.
+        new.shape = Shape::Box {
+            size: Default::default(),
+        };
+        new.tags.push("b".to_string());
+        assert!(registry.diff(&old, &new).is_err());
+
+        let mut registry = DiffRegistry::new();
+        registry.register_value::<Shape>();
+        registry.register_value::<Vec<String>>();
+        let patch = registry.diff(&old, &new).unwrap();
+        let mut target = old.clone();
+        patch.apply(&mut target).unwrap();
+        assert_eq!(target, new);
+    }
+
+    #[test]
+    fn test_patch_merge() {
+        let registry = DiffRegistry::new();
+
+        let a = item();
+        let mut b = item();
+        b.name = "Barrel".to_string();
+        let mut c = b.clone();
+        c.name = "Crate".to_string();
+        c.weights[0] = 5.0;
+
+        let mut patch = registry.diff(&a, &b).unwrap();
+        patch.merge(registry.diff(&b, &c).unwrap());
+        assert_eq!(patch.paths().collect::<Vec<_>>(), vec!["weights[0]"]);
+    }
+}
@@ -0,0 +1,300 @@
+//! Runtime method invocation for [`Reflect`] types. Methods are registered with `#[reflect(method(..))]`
+//! attribute of the derive macro, see [`MethodInfo`] docs for more info.
+
+use crate::reflect::Reflect;
+use std::{
+    any::{type_name, TypeId},
+    fmt::{Display, Formatter},
+};
+
+/// An error that may occur during method invocation.
+#[derive(Debug, PartialEq, Eq)]
+pub enum InvokeError {
+    /// A type does not have a method with the given name.
+    UnknownMethod {
+        /// Name of the method.
+        name: String,
+    },
+    /// Amount of the arguments does not match the signature of the method.
+    ArgumentCountMismatch {
+        /// Amount of the arguments of the method.
+        expected: usize,
+        /// Amount of the given arguments.
+        actual: usize,
+    },
+    /// Type of an argument does not match the signature of the method.
+    ArgumentTypeMismatch {
+        /// Index of the argument.
+        index: usize,
+        /// Name of the expected type.
+        expected: &'static str,
+    },
+}
+
+impl Display for InvokeError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::UnknownMethod { name } => write!(f, "unknown method {name}"),
+            Self::ArgumentCountMismatch { expected, actual } => {
+                write!(f, "expected {expected} arguments, got {actual}")
+            }
+            Self::ArgumentTypeMismatch { index, expected } => {
+                write!(f, "argument {index} must be of type {expected}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for InvokeError {}
+
+/// Information about a method, that could be invoked using [`Reflect::invoke_method`].
+///
+/// # Registration
+///
+/// Methods are registered using `#[reflect(method(name = "<method name>"))]` attribute on a type that
+/// derives [`Reflect`]. The attribute could be used multiple times and also accepts optional
+/// `display_name` and `description` arguments. A method must take `&self` or `&mut self` and up to
+/// six arguments; every argument and the return value must implement [`Reflect`].
+///
+/// # Example
+///
+/// ```rust
+/// use fyrox_core::reflect::prelude::*;
+///
+/// #[derive(Reflect, Debug, Default)]
+/// #[reflect(method(name = "regenerate", description = "Regenerates the level."))]
+/// #[reflect(method(name = "room_count"))]
+/// struct Level {
+///     rooms: Vec<u32>,
+/// }
+///
+/// impl Level {
+///     fn regenerate(&mut self, count: u32) {
+///         self.rooms = (0..count).collect();
+///     }
+///
+///     fn room_count(&self) -> usize {
+///         self.rooms.len()
+///     }
+/// }
+///
+/// let mut level = Level::default();
+/// let level = &mut level as &mut dyn Reflect;
+///
+/// level.methods_info(&mut |methods| {
+///     assert_eq!(methods[0].display_name, "Regenerate");
+///     assert_eq!(methods[0].argument_type_names, vec!["u32"]);
+/// });
+///
+/// level.call_method("regenerate", vec![Box::new(3u32)]).unwrap();
+/// let count = level.call_method("room_count", vec![]).unwrap();
+/// assert_eq!(count.take::<usize>().ok(), Some(3));
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MethodInfo {
+    /// A type id of the owner of the method.
+    pub owner_type_id: TypeId,
+
+    /// A name of the method.
+    pub name: &'static str,
+
+    /// A human-readable name of the method.
+    pub display_name: &'static str,
+
+    /// Description of the method.
+    pub description: &'static str,
+
+    /// Type names of the arguments of the method (excluding `self`).
+    pub argument_type_names: Vec<&'static str>,
+
+    /// Type name of the return value of the method.
+    pub return_type_name: &'static str,
+
+    /// `true` if the method takes `&mut self`.
+    pub mutable: bool,
+}
+
+impl MethodInfo {
+    /// Creates new method info from the given method. The method itself is used only to infer its
+    /// signature.
+    pub fn new<T, M, F>(
+        name: &'static str,
+        display_name: &'static str,
+        description: &'static str,
+        _method: &F,
+    ) -> Self
+    where
+        T: 'static,
+        F: ReflectMethod<T, M>,
+    {
+        Self {
+            owner_type_id: TypeId::of::<T>(),
+            name,
+            display_name,
+            description,
+            argument_type_names: F::argument_type_names(),
+            return_type_name: F::return_type_name(),
+            mutable: F::MUTABLE,
+        }
+    }
+}
+
+/// Marker of methods, that take `&self`.
+pub struct RefSelf;
+
+/// Marker of methods, that take `&mut self`.
+pub struct MutSelf;
+
+/// A method of type `T` that could be invoked with type-erased arguments. It is implemented for every
+/// function that takes `&T` or `&mut T` and up to six arguments implementing [`Reflect`]. `M` is a
+/// marker type that is used to distinguish the implementations, it is inferred automatically.
+pub trait ReflectMethod<T, M> {
+    /// `true` if the method takes `&mut T`.
+    const MUTABLE: bool;
+
+    /// Returns type names of the arguments of the method.
+    fn argument_type_names() -> Vec<&'static str>;
+
+    /// Returns type name of the return value of the method.
+    fn return_type_name() -> &'static str;
+
+    /// Invokes the method with the given arguments.
+    fn invoke(
+        &self,
+        this: &mut T,
+        args: Vec<Box<dyn Reflect>>,
+    ) -> Result<Box<dyn Reflect>, InvokeError>;
+}
+
+fn take_argument<A: Reflect>(index: usize, arg: Box<dyn Reflect>) -> Result<A, InvokeError> {
+    arg.take::<A>()
+        .map_err(|_| InvokeError::ArgumentTypeMismatch {
+            index,
+            expected: type_name::<A>(),
+        })
+}
+
+macro_rules! impl_reflect_method {
+    ($($arg:ident),*) => {
+        impl_reflect_method!(@impl MutSelf, true, &mut T, [&mut *], $($arg),*);
+        impl_reflect_method!(@impl RefSelf, false, &T, [&*], $($arg),*);
+    };
+
+    (@impl $marker:ident, $mutable:expr, $this:ty, [$($borrow:tt)*], $($arg:ident),*) => {
+        impl<T, F, R, $($arg),*> ReflectMethod<T, ($marker, R, $($arg,)*)> for F
+        where
+            F: Fn($this, $($arg),*) -> R,
+            R: Reflect,
+            $($arg: Reflect),*
+        {
+            const MUTABLE: bool = $mutable;
+
+            fn argument_type_names() -> Vec<&'static str> {
+                vec![$(type_name::<$arg>()),*]
+            }
+
+            fn return_type_name() -> &'static str {
+                type_name::<R>()
+            }
+
+            #[allow(non_snake_case, unused_mut, unused_variables)]
+            fn invoke(
+                &self,
+                this: &mut T,
+                args: Vec<Box<dyn Reflect>>,
+            ) -> Result<Box<dyn Reflect>, InvokeError> {
+                let names: &[&str] = &[$(stringify!($arg)),*];
+                if args.len() != names.len() {
+                    return Err(InvokeError::ArgumentCountMismatch {
+                        expected: names.len(),
+                        actual: args.len(),
+                    });
+                }
+                let mut args = args.into_iter().enumerate();
+                $(
+                    let (index, arg) = args.next().unwrap();
+                    let $arg = take_argument::<$arg>(index, arg)?;
+                )*
+                Ok(Box::new(self($($borrow)* this, $($arg),*)))
+            }
+        }
+    };
+}
+
+impl_reflect_method!();
+impl_reflect_method!(A0);
+impl_reflect_method!(A0, A1);
+impl_reflect_method!(A0, A1, A2);
+impl_reflect_method!(A0, A1, A2, A3);
+impl_reflect_method!(A0, A1, A2, A3, A4);
+impl_reflect_method!(A0, A1, A2, A3, A4, A5);
+
+#[cfg(test)]
+mod test {
+    use crate::reflect::{method::InvokeError, prelude::*};
+
+    #[derive(Reflect, Debug, Default)]
+    #[reflect(method(name = "add", display_name = "Add Value"))]
+    #[reflect(method(name = "sum"))]
+    struct Accumulator {
+        values: Vec<f32>,
+    }
+
+    impl Accumulator {
+        fn add(&mut self, value: f32, times: usize) {
+            for _ in 0..times {
+                self.values.push(value);
+            }
+        }
+
+        fn sum(&self) -> f32 {
+            self.values.iter().sum()
+        }
+    }
+
+    #[test]
+    fn test_method_invocation() {
+        let mut accumulator = Accumulator::default();
+        let accumulator = &mut accumulator as &mut dyn Reflect;
+
+        accumulator.methods_info(&mut |methods| {
+            assert_eq!(methods.len(), 2);
+            assert_eq!(methods[0].name, "add");
+            assert_eq!(methods[0].display_name, "Add Value");
+            assert_eq!(methods[0].argument_type_names, vec!["f32", "usize"]);
+            assert_eq!(methods[0].return_type_name, "()");
+            assert!(methods[0].mutable);
+            assert_eq!(methods[1].display_name, "Sum");
+            assert!(!methods[1].mutable);
+        });
+
+        accumulator
+            .call_method("add", vec![Box::new(2.0f32), Box::new(3usize)])
+            .unwrap();
+        let sum = accumulator.call_method("sum", vec![]).unwrap();
+        assert_eq!(sum.take::<f32>().ok(), Some(6.0));
+
+        assert_eq!(
+            accumulator.call_method("add", vec![Box::new(2.0f32)]).err(),
+            Some(InvokeError::ArgumentCountMismatch {
+                expected: 2,
+                actual: 1
+            })
+        );
+        assert_eq!(
+            accumulator
+                .call_method("add", vec![Box::new(2.0f32), Box::new(1.0f32)])
+                .err(),
+            Some(InvokeError::ArgumentTypeMismatch {
+                index: 1,
+                expected: "usize"
+            })
+        );
+        assert_eq!(
+            accumulator.call_method("clear", vec![]).err(),
+            Some(InvokeError::UnknownMethod {
+                name: "clear".to_string()
+            })
+        );
+    }
+}
@@ -0,0 +1,1009 @@
+//! Serde bridge for [`Reflect`] types. It allows to serialize any type that implements `Reflect` to any
+//! format that is supported by serde (JSON, RON, YAML, etc.) and to deserialize the data back to existing
+//! values, without implementing `Serialize` and `Deserialize` traits for the types. See [`ReflectSerializer`],
+//! [`ReflectDeserializer`] and [`SerdeRegistry`] docs for more info. The module is available with
+//! `reflect_serde` feature.
+
+use crate::{reflect::prelude::*, sstorage::ImmutableString, uuid::Uuid};
+use ::serde::{
+    de::{DeserializeOwned, DeserializeSeed, Error as _},
+    ser::Error as _,
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+use fxhash::FxHashMap;
+use serde_json::{Map, Value};
+use std::{
+    any::{Any, TypeId},
+    fmt::{Display, Formatter},
+    path::PathBuf,
+    time::Duration,
+};
+
+/// An error that may occur during serialization or deserialization of [`Reflect`] values.
+#[derive(Debug)]
+pub enum ReflectSerdeError {
+    /// A type does not have a field with the given name. It also happens when data of one enum variant
+    /// is deserialized to a value with another variant.
+    UnknownField {
+        /// Name of the type.
+        type_name: &'static str,
+        /// Name of the field.
+        field: String,
+    },
+    /// Data has a different structure than the type expects.
+    TypeMismatch {
+        /// Name of the type.
+        type_name: &'static str,
+        /// Expected structure of the data.
+        expected: &'static str,
+    },
+    /// A new value of a type could not be created, because the type is not registered in the registry.
+    UnableToCreate {
+        /// Name of the type.
+        type_name: &'static str,
+    },
+    /// A value was rejected by a setter of a field.
+    Rejected {
+        /// Name of the type.
+        type_name: &'static str,
+    },
+    /// An error of a serde implementation of a registered type.
+    Json(serde_json::Error),
+}
+
+impl Display for ReflectSerdeError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::UnknownField { type_name, field } => {
+                write!(f, "type {type_name} does not have field {field}")
+            }
+            Self::TypeMismatch {
+                type_name,
+                expected,
+            } => write!(f, "type {type_name} expects {expected}"),
+            Self::UnableToCreate { type_name } => {
+                write!(
+                    f,
+                    "unable to create a value of type {type_name}, it is not registered"
+                )
+            }
+            Self::Rejected { type_name } => write!(f, "value of type {type_name} was rejected"),
+            Self::Json(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl std::error::Error for ReflectSerdeError {}
+
+impl From<serde_json::Error> for ReflectSerdeError {
+    fn from(e: serde_json::Error) -> Self {
+        Self::Json(e)
+    }
+}
+
+type SerializeFn = Box<dyn Fn(&dyn Any) -> Result<Value, ReflectSerdeError> + Send + Sync>;
+type DeserializeFn =
+    Box<dyn Fn(Value) -> Result<Box<dyn Reflect>, ReflectSerdeError> + Send + Sync>;
+type CreateFn = Box<dyn Fn() -> Box<dyn Reflect> + Send + Sync>;
+
+struct ValueEntry {
+    type_name: &'static str,
+    serialize: SerializeFn,
+    deserialize: DeserializeFn,
+}
+
+struct DefaultEntry {
+    type_name: &'static str,
+    create: CreateFn,
+}
+
+struct VariantEntry {
+    name: &'static str,
+    create: CreateFn,
+}
+
+/// Registry defines how [`Reflect`] values are mapped to serde data.
+///
+/// ## Mapping
+///
+/// - Types registered with [`SerdeRegistry::register_value`] use their own serde implementation. The
+///   registry created with [`SerdeRegistry::new`] has every primitive type, `String`, `PathBuf`, `Duration`,
+///   `ImmutableString`, `Uuid` and (with `serde` feature) common `nalgebra` types registered.
+/// - `InheritableVariable` is mapped to its inner value. Deserialization marks the variable as modified
+///   only if the value has changed, so unchanged values are still inherited.
+/// - Types that implement `ReflectHashMap` are mapped to maps (sorted by keys); maps with non-string keys
+///   are mapped to sequences of `[key, value]` pairs.
+/// - Types that implement `ReflectArray` (arrays, `Vec`, etc.) are mapped to sequences.
+/// - Structs are mapped to maps of their fields. Enums are mapped to maps of the fields of their current
+///   variant, the keys are `@Variant` paths - `Variant@field`.
+/// - Every other type (for example, enums with a unit variant as current one) is mapped to a string with
+///   its `Debug` representation.
+///
+/// ## Deserialization
+///
+/// Deserialization writes the data to an existing value, because there is no generic way of creating
+/// values of `Reflect` types. Current variant of an enum is changed only if the enum is registered with
+/// [`SerdeRegistry::register_value`] or its variants are registered with [`SerdeRegistry::register_variant`]
+/// (see also [`SerdeRegistry::register_option`]); the registry created with [`SerdeRegistry::new`] has
+/// variants of `Option` of every built-in type registered. Lists and hash maps are resized to match the
+/// data; new items are created from the types registered with [`SerdeRegistry::register_value`],
+/// [`SerdeRegistry::register_default`] and [`SerdeRegistry::register_variant`]. When the type of new items
+/// is unknown (the collection is empty), registered types are tried in the order of their names. Fields
+/// that are missing in the data are left unchanged, unknown fields are errors.
+pub struct SerdeRegistry {
+    values: FxHashMap<TypeId, ValueEntry>,
+    defaults: FxHashMap<TypeId, DefaultEntry>,
+    variants: FxHashMap<TypeId, Vec<VariantEntry>>,
+}
+
+impl Default for SerdeRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+macro_rules! register_values {
+    ($registry:expr, $($ty:ty),* $(,)?) => {
+        $(
+            $registry.register_value::<$ty>();
+            $registry.register_option::<$ty>();
+        )*
+    };
+}
+
+impl SerdeRegistry {
+    /// Creates new registry with every built-in type registered.
+    pub fn new() -> Self {
+        let mut registry = Self::empty();
+
+        register_values!(
+            registry, bool, u8, u16, u32, u64, usize, i8, i16, i32, i64, isize, f32, f64, String,
+            PathBuf, Duration
+        );
+
+        registry.register_value_with(
+            |string: &ImmutableString| Value::String(string.to_string()),
+            |value| value.as_str().map(ImmutableString::new),
+        );
+        registry.register_option::<ImmutableString>();
+        registry.register_value_with(
+            |uuid: &Uuid| Value::String(uuid.to_string()),
+            |value| {
+                value
+                    .as_str()
+                    .and_then(|string| Uuid::parse_str(string).ok())
+            },
+        );
+        registry.register_option::<Uuid>();
+
+        #[cfg(feature = "serde")]
+        {
+            use crate::algebra::{
+                Matrix2, Matrix3, Matrix4, UnitQuaternion, Vector2, Vector3, Vector4,
+            };
+
+            register_values!(
+                registry,
+                Vector2<f32>,
+                Vector3<f32>,
+                Vector4<f32>,
+                Vector2<f64>,
+                Vector3<f64>,
+                Vector4<f64>,
+                Matrix2<f32>,
+                Matrix3<f32>,
+                Matrix4<f32>,
+                UnitQuaternion<f32>,
+                UnitQuaternion<f64>,
+            );
+        }
+
+        registry
+    }
+
+    /// Creates new registry without any registered types.
+    pub fn empty() -> Self {
+        Self {
+            values: Default::default(),
+            defaults: Default::default(),
+            variants: Default::default(),
+        }
+    }
+
+    /// Registers a type, that will be serialized and deserialized using its own serde implementation. It
+    /// should be used for "leaf" types, that don't have reflected fields, and for enums with unit variants.
+    pub fn register_value<T>(&mut self)
+    where
+        T: Reflect + Serialize + DeserializeOwned,
+    {
+        self.values.insert(
+            TypeId::of::<T>(),
+            ValueEntry {
+                type_name: std::any::type_name::<T>(),
+                serialize: Box::new(|any| {
+                    let value = any.downcast_ref::<T>().unwrap();
+                    Ok(serde_json::to_value(value)?)
+                }),
+                deserialize: Box::new(|value| {
+                    Ok(Box::new(serde_json::from_value::<T>(value)?) as Box<dyn Reflect>)
+                }),
+            },
+        );
+    }
+
+    /// Registers a type, that will be serialized and deserialized using the given functions. The
+    /// deserialization function should return `None` if the data is invalid.
+    pub fn register_value_with<T, S, D>(&mut self, serialize: S, deserialize: D)
+    where
+        T: Reflect,
+        S: Fn(&T) -> Value + Send + Sync + 'static,
+        D: Fn(&Value) -> Option<T> + Send + Sync + 'static,
+    {
+        self.values.insert(
+            TypeId::of::<T>(),
+            ValueEntry {
+                type_name: std::any::type_name::<T>(),
+                serialize: Box::new(move |any| Ok(serialize(any.downcast_ref::<T>().unwrap()))),
+                deserialize: Box::new(move |value| match deserialize(&value) {
+                    Some(value) => Ok(Box::new(value) as Box<dyn Reflect>),
+                    None => Err(ReflectSerdeError::TypeMismatch {
+                        type_name: std::any::type_name::<T>(),
+                        expected: "a value of the type",
+                    }),
+                }),
+            },
+        );
+    }
+
+    /// Registers a type, which default value will be used to create new items of lists and hash maps when
+    /// deserializing. It is needed only for the types that are not registered with
+    /// [`Self::register_value`].
+    pub fn register_default<T>(&mut self)
+    where
+        T: Reflect + Default,
+    {
+        self.defaults.insert(
+            TypeId::of::<T>(),
+            DefaultEntry {
+                type_name: std::any::type_name::<T>(),
+                create: Box::new(|| Box::new(T::default()) as Box<dyn Reflect>),
+            },
+        );
+    }
+
+    /// Registers a variant of the enum `T`, so deserialization could switch values of the enum to the
+    /// variant. The name must match the name of the variant, the given function should create a value of
+    /// the variant, which fields will be then overwritten by the data. Registering a variant with the
+    /// same name again replaces it.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use fyrox_core::reflect::{prelude::*, serde::SerdeRegistry};
+    ///
+    /// #[derive(Reflect, Debug)]
+    /// enum Shape {
+    ///     Point,
+    ///     Circle { radius: f32 },
+    /// }
+    ///
+    /// let mut registry = SerdeRegistry::new();
+    /// registry.register_variant("Point", || Shape::Point);
+    /// registry.register_variant("Circle", || Shape::Circle { radius: 0.0 });
+    ///
+    /// let mut shape = Shape::Point;
+    /// let data = serde_json::json!({"Circle@radius": 2.0});
+    /// registry.apply_value(&mut shape, data).unwrap();
+    /// assert!(matches!(shape, Shape::Circle { radius } if radius == 2.0));
+    /// ```
+    pub fn register_variant<T, F>(&mut self, name: &'static str, create: F)
+    where
+        T: Reflect,
+        F: Fn() -> T + Send + Sync + 'static,
+    {
+        let variants = self.variants.entry(TypeId::of::<T>()).or_default();
+        variants.retain(|variant| variant.name != name);
+        variants.push(VariantEntry {
+            name,
+            create: Box::new(move || Box::new(create()) as Box<dyn Reflect>),
+        });
+    }
+
+    /// Registers both variants of `Option<T>`. `Some` variant is created with default value of `T`.
+    pub fn register_option<T>(&mut self)
+    where
+        T: Reflect + Default,
+        Option<T>: Reflect,
+    {
+        self.register_variant::<Option<T>, _>("None", || None);
+        self.register_variant::<Option<T>, _>("Some", || Some(T::default()));
+    }
+
+    /// Converts the given value to serde data.
+    pub fn to_value(&self, value: &dyn Reflect) -> Result<Value, ReflectSerdeError> {
+        let mut inheritable = None;
+        value.as_inheritable_variable(&mut |variable| {
+            inheritable = variable.map(|variable| self.to_value(variable.inner_value_ref()))
+        });
+        if let Some(result) = inheritable {
+            return result;
+        }
+
+        if let Some(entry) = self.values.get(&value.any_type_id()) {
+            let mut result = None;
+            value.as_any(&mut |any| result = Some((entry.serialize)(any)));
+            return result.unwrap();
+        }
+
+        let mut map = None;
+        value.as_hash_map(&mut |hash_map| {
+            map = hash_map.map(|hash_map| self.map_to_value(hash_map))
+        });
+        if let Some(result) = map {
+            return result;
+        }
+
+        let mut array = None;
+        value.as_array(&mut |array_ref| {
+            array = array_ref.map(|array_ref| {
+                (0..array_ref.reflect_len())
+                    .filter_map(|i| array_ref.reflect_index(i))
+                    .map(|item| self.to_value(item))
+                    .collect::<Result<Vec<_>, _>>()
+                    .map(Value::Array)
+            })
+        });
+        if let Some(result) = array {
+            return result;
+        }
+
+        let mut fields = Map::new();
+        let mut result = Ok(());
+        value.fields_info(&mut |fields_info| {
+            for field_info in fields_info {
+                match self.to_value(field_info.reflect_value) {
+                    Ok(field) => {
+                        fields.insert(field_info.name.to_owned(), field);
+                    }
+                    Err(e) => {
+                        result = Err(e);
+                        break;
+                    }
+                }
+            }
+        });
+        result?;
+
+        if fields.is_empty() {
+            Ok(Value::String(format!("{value:?}")))
+        } else {
+            Ok(Value::Object(fields))
+        }
+    }
+
+    fn map_to_value(&self, hash_map: &dyn ReflectHashMap) -> Result<Value, ReflectSerdeError> {
+        let mut entries = (0..hash_map.reflect_len())
+            .filter_map(|i| hash_map.reflect_get_at(i))
+            .map(|(key, value)| Ok((self.to_value(key)?, self.to_value(value)?)))
+            .collect::<Result<Vec<_>, ReflectSerdeError>>()?;
+        entries.sort_by_cached_key(|(key, _)| key.to_string());
+
+        if entries.iter().all(|(key, _)| key.is_string()) {
+            Ok(Value::Object(
+                entries
+                    .into_iter()
+                    .map(|(key, value)| match key {
+                        Value::String(key) => (key, value),
+                        _ => unreachable!(),
+                    })
+                    .collect(),
+            ))
+        } else {
+            Ok(Value::Array(
+                entries
+                    .into_iter()
+                    .map(|(key, value)| Value::Array(vec![key, value]))
+                    .collect(),
+            ))
+        }
+    }
+
+    /// Writes the given serde data to the given value. See [`SerdeRegistry`] docs for the rules of
+    /// deserialization. In case of an error, the value could be partially modified.
+    pub fn apply_value(
+        &self,
+        target: &mut dyn Reflect,
+        value: Value,
+    ) -> Result<(), ReflectSerdeError> {
+        let mut inheritable = None;
+        target.as_inheritable_variable_mut(&mut |variable| {
+            if let Some(variable) = variable {
+                inheritable = Some(
+                    self.to_value(variable.inner_value_ref())
+                        .and_then(|current| {
+                            if current == value {
+                                Ok(())
+                            } else {
+                                variable.mark_modified();
+                                self.apply_value(variable.inner_value_mut(), value.clone())
+                            }
+                        }),
+                );
+            }
+        });
+        if let Some(result) = inheritable {
+            return result;
+        }
+
+        let type_id = target.any_type_id();
+        if self.values.contains_key(&type_id) {
+            let new_value = self.create(type_id, target.type_name(), value)?;
+            return target
+                .set(new_value)
+                .map(|_| ())
+                .map_err(|_| ReflectSerdeError::Rejected {
+                    type_name: target.type_name(),
+                });
+        }
+
+        if let Some(variants) = self.variants.get(&type_id) {
+            self.switch_variant(target, variants, &value)?;
+        }
+
+        let mut map = None;
+        target.as_hash_map_mut(&mut |hash_map| {
+            map = hash_map.map(|hash_map| self.apply_map(hash_map, value.clone()))
+        });
+        if let Some(result) = map {
+            return result;
+        }
+
+        let mut list = None;
+        target.as_list_mut(&mut |list_ref| {
+            list = list_ref.map(|list_ref| self.apply_list(list_ref, value.clone()))
+        });
+        if let Some(result) = list {
+            return result;
+        }
+
+        let mut array = None;
+        target.as_array_mut(&mut |array_ref| {
+            array = array_ref.map(|array_ref| self.apply_array(array_ref, value.clone()))
+        });
+        if let Some(result) = array {
+            return result;
+        }
+
+        match value {
+            Value::Object(fields) => {
+                for (name, field_value) in fields {
+                    self.apply_field(target, &name, field_value)?;
+                }
+                Ok(())
+            }
+            Value::String(string) if string == format!("{target:?}") => Ok(()),
+            _ => Err(ReflectSerdeError::TypeMismatch {
+                type_name: target.type_name(),
+                expected: "a map of fields",
+            }),
+        }
+    }
+
+    // Switches the value to the variant of the data, if the variant differs from the current one.
+    fn switch_variant(
+        &self,
+        target: &mut dyn Reflect,
+        variants: &[VariantEntry],
+        value: &Value,
+    ) -> Result<(), ReflectSerdeError> {
+        // Fields of enum variants have `Variant@field` names, unit variants are written as strings.
+        let name = match value {
+            Value::Object(fields) => match fields.keys().next() {
+                Some(field) => {
+                    let mut exists = false;
+                    target.field(field, &mut |field| exists = field.is_some());
+                    if exists {
+                        return Ok(());
+                    }
+                    field.split('@').next().unwrap_or_default()
+                }
+                None => return Ok(()),
+            },
+            Value::String(string) if *string != format!("{target:?}") => string.as_str(),
+            _ => return Ok(()),
+        };
+
+        if let Some(variant) = variants.iter().find(|variant| variant.name == name) {
+            target
+                .set((variant.create)())
+                .map_err(|_| ReflectSerdeError::Rejected {
+                    type_name: target.type_name(),
+                })?;
+        }
+
+        Ok(())
+    }
+
+    // Returns registered value types sorted by their names, so the types are always tried in the same order.
+    fn sorted_values(&self) -> Vec<&ValueEntry> {
+        let mut values = self.values.values().collect::<Vec<_>>();
+        values.sort_by_key(|entry| entry.type_name);
+        values
+    }
+
+    fn apply_field(
+        &self,
+        target: &mut dyn Reflect,
+        name: &str,
+        value: Value,
+    ) -> Result<(), ReflectSerdeError> {
+        // Values are set using `set_field`, so custom setters of the fields will be called.
+        let mut field_type = None;
+        target.field(name, &mut |field| {
+            field_type = field.map(|field| {
+                let mut is_inheritable = false;
+                field.as_inheritable_variable(&mut |variable| is_inheritable = variable.is_some());
+                (field.any_type_id(), field.type_name(), is_inheritable)
+            })
+        });
+
+        let (type_id, type_name, is_inheritable) = match field_type {
+            Some(field_type) => field_type,
+            None => {
+                return Err(ReflectSerdeError::UnknownField {
+                    type_name: target.type_name(),
+                    field: name.to_owned(),
+                })
+            }
+        };
+
+        if self.values.contains_key(&type_id) && !is_inheritable {
+            let new_value = self.create(type_id, type_name, value)?;
+            let mut result = Ok(());
+            target.set_field(name, new_value, &mut |set_result| {
+                if set_result.is_err() {
+                    result = Err(ReflectSerdeError::Rejected { type_name });
+                }
+            });
+            result
+        } else {
+            let mut result = Ok(());
+            target.field_mut(name, &mut |field| {
+                if let Some(field) = field {
+                    result = self.apply_value(field, value.clone());
+                }
+            });
+            result
+        }
+    }
+
+    fn apply_array(
+        &self,
+        array: &mut dyn ReflectArray,
+        value: Value,
+    ) -> Result<(), ReflectSerdeError> {
+        match value {
+            Value::Array(items) if items.len() == array.reflect_len() => {
+                for (i, item) in items.into_iter().enumerate() {
+                    if let Some(target) = array.reflect_index_mut(i) {
+                        self.apply_value(target, item)?;
+                    }
+                }
+                Ok(())
+            }
+            _ => Err(ReflectSerdeError::TypeMismatch {
+                type_name: array.type_name(),
+                expected: "a sequence of the same length",
+            }),
+        }
+    }
+
+    fn apply_list(
+        &self,
+        list: &mut dyn ReflectList,
+        value: Value,
+    ) -> Result<(), ReflectSerdeError> {
+        let items = match value {
+            Value::Array(items) => items,
+            _ => {
+                return Err(ReflectSerdeError::TypeMismatch {
+                    type_name: list.type_name(),
+                    expected: "a sequence",
+                })
+            }
+        };
+
+        while list.reflect_len() > items.len() {
+            list.reflect_pop();
+        }
+
+        for (i, item) in items.into_iter().enumerate() {
+            if let Some(target) = list.reflect_index_mut(i) {
+                self.apply_value(target, item)?;
+            } else if let Some(existing) = list.reflect_index(0) {
+                let new_item = self.create(existing.any_type_id(), existing.type_name(), item)?;
+                list.reflect_push(new_item)
+                    .map_err(|_| ReflectSerdeError::Rejected {
+                        type_name: list.type_name(),
+                    })?;
+            } else {
+                let type_name = list.type_name();
+                if !self.create_any(&item, &mut |new_item| list.reflect_push(new_item).is_ok()) {
+                    return Err(ReflectSerdeError::UnableToCreate { type_name });
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn apply_map(
+        &self,
+        hash_map: &mut dyn ReflectHashMap,
+        value: Value,
+    ) -> Result<(), ReflectSerdeError> {
+        let entries = match value {
+            Value::Object(entries) => entries
+                .into_iter()
+                .map(|(key, value)| (Value::String(key), value))
+                .collect::<Vec<_>>(),
+            Value::Array(pairs) => pairs
+                .into_iter()
+                .map(|pair| match pair {
+                    Value::Array(mut pair) if pair.len() == 2 => {
+                        let value = pair.pop().unwrap();
+                        Ok((pair.pop().unwrap(), value))
+                    }
+                    _ => Err(()),
+                })
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(|_| ReflectSerdeError::TypeMismatch {
+                    type_name: hash_map.type_name(),
+                    expected: "a sequence of [key, value] pairs",
+                })?,
+            _ => {
+                return Err(ReflectSerdeError::TypeMismatch {
+                    type_name: hash_map.type_name(),
+                    expected: "a map",
+                })
+            }
+        };
+
+        // Remove the entries that are missing in the data.
+        let keys = (0..hash_map.reflect_len())
+            .filter_map(|i| hash_map.reflect_get_at(i))
+            .map(|(key, _)| Ok((self.to_value(key)?, key.any_type_id(), key.type_name())))
+            .collect::<Result<Vec<_>, ReflectSerdeError>>()?;
+        for (key, type_id, type_name) in keys {
+            if !entries.iter().any(|(new_key, _)| *new_key == key) {
+                let key = self.create(type_id, type_name, key)?;
+                hash_map.reflect_remove(&*key, &mut |_| {});
+            }
+        }
+
+        for (key, value) in entries {
+            let existing = (0..hash_map.reflect_len()).find(|i| {
+                hash_map.reflect_get_at(*i).map_or(false, |(existing, _)| {
+                    self.to_value(existing)
+                        .map_or(false, |existing| existing == key)
+                })
+            });
+
+            if let Some(index) = existing {
+                if let Some((_, target)) = hash_map.reflect_get_at_mut(index) {
+                    self.apply_value(target, value)?;
+                }
+            } else if let Some((existing_key, existing_value)) = hash_map.reflect_get_at(0) {
+                let types = (
+                    existing_key.any_type_id(),
+                    existing_key.type_name(),
+                    existing_value.any_type_id(),
+                    existing_value.type_name(),
+                );
+                let new_key = self.create(types.0, types.1, key)?;
+                let new_value = self.create(types.2, types.3, value)?;
+                hash_map.reflect_insert(new_key, new_value);
+            } else {
+                // The map is empty, so the types of keys and values are unknown - try every registered type.
+                let inserted = self.sorted_values().into_iter().any(|key_entry| {
+                    (key_entry.deserialize)(key.clone()).is_ok()
+                        && self.create_any(&value, &mut |new_value| {
+                            let len = hash_map.reflect_len();
+                            if let Ok(new_key) = (key_entry.deserialize)(key.clone()) {
+                                hash_map.reflect_insert(new_key, new_value);
+                            }
+                            hash_map.reflect_len() > len
+                        })
+                });
+                if !inserted {
+                    return Err(ReflectSerdeError::UnableToCreate {
+                        type_name: hash_map.type_name(),
+                    });
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    // Creates a value of the given type from the data.
+    fn create(
+        &self,
+        type_id: TypeId,
+        type_name: &'static str,
+        value: Value,
+    ) -> Result<Box<dyn Reflect>, ReflectSerdeError> {
+        if let Some(entry) = self.values.get(&type_id) {
+            (entry.deserialize)(value)
+        } else if let Some(default) = self.defaults.get(&type_id) {
+            let mut new_value = (default.create)();
+            self.apply_value(&mut *new_value, value)?;
+            Ok(new_value)
+        } else if let Some(variant) = self
+            .variants
+            .get(&type_id)
+            .and_then(|variants| variants.first())
+        {
+            // The variant will be switched to the one of the data.
+            let mut new_value = (variant.create)();
+            self.apply_value(&mut *new_value, value)?;
+            Ok(new_value)
+        } else {
+            Err(ReflectSerdeError::UnableToCreate { type_name })
+        }
+    }
+
+    // Creates values of every registered type that could be created from the data and passes them to the
+    // given function, until it accepts a value. Types are tried in the order of their names.
+    fn create_any(&self, value: &Value, accept: &mut dyn FnMut(Box<dyn Reflect>) -> bool) -> bool {
+        for entry in self.sorted_values() {
+            if let Ok(new_value) = (entry.deserialize)(value.clone()) {
+                if accept(new_value) {
+                    return true;
+                }
+            }
+        }
+
+        let mut defaults = self.defaults.values().collect::<Vec<_>>();
+        defaults.sort_by_key(|entry| entry.type_name);
+        for default in defaults {
+            let mut new_value = (default.create)();
+            if self.apply_value(&mut *new_value, value.clone()).is_ok() && accept(new_value) {
+                return true;
+            }
+        }
+
+        false
+    }
+}
+
+/// A wrapper over a [`Reflect`] value, that implements [`Serialize`], so the value could be serialized to
+/// any format supported by serde.
+///
+/// ## Example
+///
+/// ```rust
+/// use fyrox_core::reflect::{
+///     prelude::*,
+///     serde::{ReflectSerializer, SerdeRegistry},
+/// };
+///
+/// #[derive(Reflect, Debug)]
+/// struct Material {
+///     name: String,
+///     roughness: f32,
+/// }
+///
+/// let material = Material {
+///     name: "Metal".to_string(),
+///     roughness: 0.5,
+/// };
+///
+/// let registry = SerdeRegistry::new();
+/// let json = serde_json::to_string(&ReflectSerializer::new(&material, &registry)).unwrap();
+/// assert_eq!(json, r#"{"name":"Metal","roughness":0.5}"#);
+/// ```
+pub struct ReflectSerializer<'a> {
+    value: &'a dyn Reflect,
+    registry: &'a SerdeRegistry,
+}
+
+impl<'a> ReflectSerializer<'a> {
+    /// Creates new serializer for the given value.
+    pub fn new(value: &'a dyn Reflect, registry: &'a SerdeRegistry) -> Self {
+        Self { value, registry }
+    }
+}
+
+impl<'a> Serialize for ReflectSerializer<'a> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.registry
+            .to_value(self.value)
+            .map_err(S::Error::custom)?
+            .serialize(serializer)
+    }
+}
+
+/// A [`DeserializeSeed`] that deserializes data to an existing [`Reflect`] value. The format must be
+/// self-describing (JSON, RON, YAML, etc.). See [`SerdeRegistry`] docs for the rules of deserialization.
+///
+/// ## Example
+///
+/// ```rust
+/// use fyrox_core::reflect::{
+///     prelude::*,
+///     serde::{ReflectDeserializer, SerdeRegistry},
+/// };
+/// use serde::de::DeserializeSeed;
+///
+/// #[derive(Reflect, Debug, Default)]
+/// struct Material {
+///     name: String,
+///     roughness: f32,
+/// }
+///
+/// let mut material = Material::default();
+/// let registry = SerdeRegistry::new();
+/// let mut deserializer = serde_json::Deserializer::from_str(r#"{"roughness":0.25}"#);
+/// ReflectDeserializer::new(&mut material, &registry)
+///     .deserialize(&mut deserializer)
+///     .unwrap();
+/// assert_eq!(material.roughness, 0.25);
+/// ```
+pub struct ReflectDeserializer<'a> {
+    value: &'a mut dyn Reflect,
+    registry: &'a SerdeRegistry,
+}
+
+impl<'a> ReflectDeserializer<'a> {
+    /// Creates new deserializer that will write data to the given value.
+    pub fn new(value: &'a mut dyn Reflect, registry: &'a SerdeRegistry) -> Self {
+        Self { value, registry }
+    }
+}
+
+impl<'a, 'de> DeserializeSeed<'de> for ReflectDeserializer<'a> {
+    type Value = ();
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<(), D::Error> {
+        let value = Value::deserialize(deserializer)?;
+        self.registry
+            .apply_value(self.value, value)
+            .map_err(D::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        reflect::{
+            prelude::*,
+            serde::{ReflectSerdeError, SerdeRegistry},
+        },
+        variable::InheritableVariable,
+    };
+    use serde_json::json;
+    use std::collections::HashMap;
+
+    #[derive(Reflect, Debug, Default, Clone, PartialEq)]
+    struct Item {
+        id: u32,
+        tags: Vec<String>,
+    }
+
+    #[derive(Reflect, Debug, Default)]
+    struct Foo {
+        name: String,
+        items: Vec<Item>,
+        weights: HashMap<String, f32>,
+        ids: HashMap<u32, bool>,
+        inherited: InheritableVariable<f32>,
+        modified: InheritableVariable<f32>,
+        mode: Option<u32>,
+    }
+
+    #[test]
+    fn test_reflect_serde() {
+        let mut registry = SerdeRegistry::new();
+        registry.register_default::<Item>();
+
+        let foo = Foo {
+            name: "Foo".to_string(),
+            items: vec![Item {
+                id: 1,
+                tags: vec!["a".to_string()],
+            }],
+            weights: [("b".to_string(), 2.0), ("a".to_string(), 1.0)]
+                .into_iter()
+                .collect(),
+            ids: [(1, true)].into_iter().collect(),
+            inherited: InheritableVariable::new(1.0),
+            modified: InheritableVariable::new(1.0),
+            mode: Some(3),
+        };
+
+        let value = registry.to_value(&foo).unwrap();
+        assert_eq!(
+            value,
+            json!({
+                "name": "Foo",
+                "items": [{"id": 1, "tags": ["a"]}],
+                "weights": {"a": 1.0, "b": 2.0},
+                "ids": [[1, true]],
+                "inherited": 1.0,
+                "modified": 1.0,
+                "mode": {"Some@0": 3}
+            })
+        );
+        assert_eq!(
+            registry.to_value(&Option::<u32>::None).unwrap(),
+            json!("None")
+        );
+
+        let mut new_foo = Foo {
+            items: vec![Item::default(), Item::default()],
+            weights: [("c".to_string(), 3.0)].into_iter().collect(),
+            inherited: InheritableVariable::new(1.0),
+            mode: Some(0),
+            ..Default::default()
+        };
+
+        let mut changed_value = value.clone();
+        changed_value["modified"] = json!(2.0);
+        changed_value["items"] =
+            json!([{"id": 1, "tags": ["a"]}, {"id": 2, "tags": []}, {"id": 3}]);
+        registry.apply_value(&mut new_foo, changed_value).unwrap();
+        assert_eq!(new_foo.name, "Foo");
+        assert_eq!(new_foo.items.len(), 3);
+        assert_eq!(new_foo.items[0], foo.items[0]);
+        assert_eq!(new_foo.items[2].id, 3);
+        assert_eq!(new_foo.weights, foo.weights);
+        assert_eq!(new_foo.ids, foo.ids);
+        assert_eq!(new_foo.mode, Some(3));
+        assert!(!new_foo.inherited.is_modified());
+        assert!(new_foo.modified.is_modified());
+        assert_eq!(*new_foo.modified, 2.0);
+
+        assert!(matches!(
+            registry.apply_value(&mut new_foo, json!({"unknown": 1})),
+            Err(ReflectSerdeError::UnknownField { .. })
+        ));
+        assert!(matches!(
+            registry.apply_value(&mut new_foo, json!({"name": 1})),
+            Err(ReflectSerdeError::Json(_))
+        ));
+
+        // Variants of `Option` of built-in types are registered.
+        registry
+            .apply_value(&mut new_foo, json!({"mode": "None"}))
+            .unwrap();
+        assert_eq!(new_foo.mode, None);
+        registry
+            .apply_value(&mut new_foo, json!({"mode": {"Some@0": 5}}))
+            .unwrap();
+        assert_eq!(new_foo.mode, Some(5));
+
+        // Variant of an unregistered enum can't be changed.
+        let mut items = Some(Item::default());
+        assert!(registry.apply_value(&mut items, json!("None")).is_err());
+        registry.register_option::<Item>();
+        registry.apply_value(&mut items, json!("None")).unwrap();
+        assert_eq!(items, None);
+    }
+
+    #[test]
+    fn test_reflect_serde_create_any_order() {
+        let registry = SerdeRegistry::new();
+
+        // A number fits many registered types, the first one by name is always used.
+        let mut list: Vec<Box<dyn Reflect>> = Vec::new();
+        for _ in 0..10 {
+            list.clear();
+            assert!(registry.create_any(&json!(1), &mut |value| {
+                list.push(value);
+                true
+            }));
+            assert_eq!(list[0].type_name(), "f32");
+        }
+    }
+}
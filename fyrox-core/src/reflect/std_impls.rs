@@ -396,6 +396,21 @@ macro_rules! impl_reflect_inner_mutability {
             let mut guard = $acquire_lock_guard;
             guard.as_hash_map_mut(func)
         }
+
+        fn methods_info(&$self, func: &mut dyn FnMut(Vec<MethodInfo>)) {
+            let guard = $acquire_lock_guard;
+            guard.methods_info(func)
+        }
+
+        fn invoke_method(
+            &mut $self,
+            name: &str,
+            args: Vec<Box<dyn Reflect>>,
+            func: &mut dyn FnMut(Result<Box<dyn Reflect>, InvokeError>),
+        ) {
+            let mut guard = $acquire_lock_guard;
+            guard.invoke_method(name, args, func)
+        }
     };
 }
 
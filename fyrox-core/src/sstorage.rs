@@ -4,7 +4,7 @@
 #![warn(missing_docs)]
 
 use crate::{
-    parking_lot::Mutex,
+    parking_lot::RwLock,
     visitor::{Visit, VisitResult, Visitor},
 };
 use fxhash::{FxHashMap, FxHasher};
@@ -12,7 +12,10 @@ use std::{
     fmt::{Debug, Display, Formatter},
     hash::{Hash, Hasher},
     ops::Deref,
-    sync::Arc,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Weak,
+    },
 };
 
 #[derive(Clone, Debug)]
@@ -32,9 +35,23 @@ struct State {
 /// # Use cases
 ///
 /// Most common use case for immutable strings is hash map keys in performance-critical places.
+///
+/// # Memory
+///
+/// The content of the string is freed when the last instance of the string is dropped, the storage
+/// does not keep strings alive.
 #[derive(Clone)]
 pub struct ImmutableString(Arc<State>);
 
+impl Drop for ImmutableString {
+    #[inline]
+    fn drop(&mut self) {
+        if Arc::strong_count(&self.0) == 1 {
+            SSTORAGE.remove(&self.0);
+        }
+    }
+}
+
 impl Display for ImmutableString {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         f.write_str(self.0.string.as_ref())
@@ -55,7 +72,7 @@ impl Visit for ImmutableString {
 
         // Deduplicate on deserialization.
         if visitor.is_reading() {
-            *self = SSTORAGE.lock().insert(string);
+            *self = SSTORAGE.insert(string);
         }
 
         Ok(())
@@ -78,7 +95,7 @@ impl ImmutableString {
     /// memory allocator.
     #[inline]
     pub fn new<S: AsRef<str>>(string: S) -> ImmutableString {
-        SSTORAGE.lock().insert(string)
+        SSTORAGE.insert(string)
     }
 
     /// Returns unique identifier of the string. Keep in mind that uniqueness is guaranteed only
@@ -114,35 +131,174 @@ impl Hash for ImmutableString {
 impl PartialEq for ImmutableString {
     #[inline]
     fn eq(&self, other: &Self) -> bool {
-        self.id() == other.id()
+        // Strings are unique, so pointer comparison is enough. Unlike ids, it is not affected by
+        // hash collisions.
+        Arc::ptr_eq(&self.0, &other.0)
     }
 }
 
 impl Eq for ImmutableString {}
 
+/// Statistics of the immutable string storage, that could be used for memory budgeting. See
+/// [`ImmutableStringStorage::stats`].
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct ImmutableStringStats {
+    /// Amount of strings in the storage.
+    pub count: usize,
+    /// Total length of the strings in the storage, in bytes.
+    pub bytes: usize,
+    /// Amount of [`ImmutableString::new`] calls, that have found an existing string.
+    pub hits: usize,
+    /// Amount of [`ImmutableString::new`] calls, that have created a new string.
+    pub misses: usize,
+}
+
+impl ImmutableStringStats {
+    /// Returns a ratio of calls, that have found an existing string, in `[0; 1]` range.
+    pub fn hit_rate(&self) -> f32 {
+        let total = self.hits + self.misses;
+        if total == 0 {
+            0.0
+        } else {
+            self.hits as f32 / total as f32
+        }
+    }
+}
+
+const SHARD_COUNT: usize = 32;
+
+struct Entry {
+    state: Weak<State>,
+    len: usize,
+}
+
+#[derive(Default)]
+struct ShardData {
+    // Multiple entries per hash are needed to handle hash collisions.
+    buckets: FxHashMap<u64, Vec<Entry>>,
+    count: usize,
+    bytes: usize,
+}
+
+impl ShardData {
+    fn remove_dead_entries(&mut self, hash: u64) {
+        if let Some(bucket) = self.buckets.get_mut(&hash) {
+            let (count, bytes) = (&mut self.count, &mut self.bytes);
+            bucket.retain(|entry| {
+                let alive = entry.state.strong_count() > 0;
+                if !alive {
+                    *count -= 1;
+                    *bytes -= entry.len;
+                }
+                alive
+            });
+            if bucket.is_empty() {
+                self.buckets.remove(&hash);
+            }
+        }
+    }
+}
+
+#[derive(Default)]
+struct Shard {
+    data: RwLock<ShardData>,
+    hits: AtomicUsize,
+    misses: AtomicUsize,
+}
+
+fn find(data: &ShardData, hash: u64, string: &str) -> Option<Arc<State>> {
+    data.buckets.get(&hash).and_then(|bucket| {
+        bucket
+            .iter()
+            .filter_map(|entry| entry.state.upgrade())
+            .find(|state| state.string == string)
+    })
+}
+
 /// Immutable string storage is a backing storage for every immutable string in the application,
 /// storage is a singleton. In normal circumstances you should never use it directly.
-#[derive(Default)]
+///
+/// The storage is split into a number of shards by hashes of the strings, every shard has its own
+/// lock, so multiple threads could create strings at the same time with little contention. Lookup
+/// of an existing string takes only a read lock. The storage keeps weak references to the strings,
+/// an entry is removed when the last instance of its string is dropped.
 pub struct ImmutableStringStorage {
-    vec: FxHashMap<u64, Arc<State>>,
+    shards: Vec<Shard>,
+}
+
+impl Default for ImmutableStringStorage {
+    fn default() -> Self {
+        Self {
+            shards: (0..SHARD_COUNT).map(|_| Shard::default()).collect(),
+        }
+    }
 }
 
 impl ImmutableStringStorage {
     #[inline]
-    fn insert<S: AsRef<str>>(&mut self, string: S) -> ImmutableString {
+    fn shard(&self, hash: u64) -> &Shard {
+        &self.shards[(hash >> 32) as usize % SHARD_COUNT]
+    }
+
+    #[inline]
+    fn insert<S: AsRef<str>>(&self, string: S) -> ImmutableString {
+        let string = string.as_ref();
         let mut hasher = FxHasher::default();
-        string.as_ref().hash(&mut hasher);
+        string.hash(&mut hasher);
         let hash = hasher.finish();
 
-        if let Some(existing) = self.vec.get(&hash) {
-            ImmutableString(existing.clone())
-        } else {
-            let immutable = Arc::new(State {
-                string: string.as_ref().to_owned(),
-                hash,
-            });
-            self.vec.insert(hash, immutable.clone());
-            ImmutableString(immutable)
+        let shard = self.shard(hash);
+
+        if let Some(existing) = find(&shard.data.read(), hash, string) {
+            shard.hits.fetch_add(1, Ordering::Relaxed);
+            return ImmutableString(existing);
+        }
+
+        let mut data = shard.data.write();
+
+        // Another thread could insert the same string while the lock was released.
+        if let Some(existing) = find(&data, hash, string) {
+            shard.hits.fetch_add(1, Ordering::Relaxed);
+            return ImmutableString(existing);
+        }
+
+        data.remove_dead_entries(hash);
+
+        let immutable = Arc::new(State {
+            string: string.to_owned(),
+            hash,
+        });
+        data.buckets.entry(hash).or_default().push(Entry {
+            state: Arc::downgrade(&immutable),
+            len: string.len(),
+        });
+        data.count += 1;
+        data.bytes += string.len();
+        shard.misses.fetch_add(1, Ordering::Relaxed);
+
+        ImmutableString(immutable)
+    }
+
+    fn remove(&self, state: &Arc<State>) {
+        let mut data = self.shard(state.hash).data.write();
+
+        // New references could only be created under the lock, so if the reference is still the
+        // last one, nobody else could resurrect the string.
+        if Arc::strong_count(state) == 1 {
+            let data = &mut *data;
+            if let Some(bucket) = data.buckets.get_mut(&state.hash) {
+                if let Some(position) = bucket
+                    .iter()
+                    .position(|entry| entry.state.as_ptr() == Arc::as_ptr(state))
+                {
+                    bucket.swap_remove(position);
+                    data.count -= 1;
+                    data.bytes -= state.string.len();
+                }
+                if bucket.is_empty() {
+                    data.buckets.remove(&state.hash);
+                }
+            }
         }
     }
 }
@@ -150,13 +306,38 @@ impl ImmutableStringStorage {
 impl ImmutableStringStorage {
     /// Returns total amount of immutable strings in the storage.
     pub fn entry_count() -> usize {
-        SSTORAGE.lock().vec.len()
+        Self::stats().count
+    }
+
+    /// Returns current statistics of the storage.
+    pub fn stats() -> ImmutableStringStats {
+        let mut stats = ImmutableStringStats::default();
+        for shard in SSTORAGE.shards.iter() {
+            let data = shard.data.read();
+            stats.count += data.count;
+            stats.bytes += data.bytes;
+            stats.hits += shard.hits.load(Ordering::Relaxed);
+            stats.misses += shard.misses.load(Ordering::Relaxed);
+        }
+        stats
+    }
+
+    /// Removes entries of the strings, that were dropped concurrently with their last lookup. Such
+    /// entries are rare and are also removed lazily on insertion, so there is no need to call the
+    /// method regularly.
+    pub fn collect_garbage() {
+        for shard in SSTORAGE.shards.iter() {
+            let mut data = shard.data.write();
+            let hashes = data.buckets.keys().cloned().collect::<Vec<_>>();
+            for hash in hashes {
+                data.remove_dead_entries(hash);
+            }
+        }
     }
 }
 
 lazy_static! {
-    static ref SSTORAGE: Arc<Mutex<ImmutableStringStorage>> =
-        Arc::new(Mutex::new(ImmutableStringStorage::default()));
+    static ref SSTORAGE: ImmutableStringStorage = ImmutableStringStorage::default();
 }
 
 #[cfg(test)]
@@ -168,7 +349,54 @@ mod test {
         let a = ImmutableString::new("Foobar");
         let b = ImmutableString::new("Foobar");
 
-        assert_eq!(ImmutableStringStorage::entry_count(), 1);
+        // The storage is shared with other tests running in parallel, so compare the strings
+        // instead of the global amount of entries.
+        assert_eq!(a, b);
         assert_eq!(a.id(), b.id())
     }
+
+    #[test]
+    fn test_immutable_string_cleanup() {
+        let stats = ImmutableStringStorage::stats();
+
+        let a = ImmutableString::new("ImmutableStringCleanupTest");
+        let b = a.clone();
+        let c = ImmutableString::new("ImmutableStringCleanupTest");
+        assert_eq!(a, c);
+
+        let new_stats = ImmutableStringStorage::stats();
+        assert!(new_stats.hits > stats.hits);
+        assert!(new_stats.misses > stats.misses);
+
+        drop(a);
+        drop(b);
+        drop(c);
+
+        let d = ImmutableString::new("ImmutableStringCleanupTest");
+        assert_eq!(&*d, "ImmutableStringCleanupTest");
+    }
+
+    #[test]
+    fn test_immutable_string_concurrent_insertion() {
+        let threads = (0..8)
+            .map(|_| {
+                std::thread::spawn(|| {
+                    (0..1000)
+                        .map(|i| ImmutableString::new(format!("ConcurrentTest{}", i % 10)))
+                        .collect::<Vec<_>>()
+                })
+            })
+            .collect::<Vec<_>>();
+
+        let results = threads
+            .into_iter()
+            .map(|thread| thread.join().unwrap())
+            .collect::<Vec<_>>();
+
+        for strings in results.iter() {
+            for (i, string) in strings.iter().enumerate() {
+                assert_eq!(string, &results[0][i]);
+            }
+        }
+    }
 }
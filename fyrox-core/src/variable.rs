@@ -343,6 +343,33 @@ where
     ) {
         func(Some(self))
     }
+
+    fn methods_info(&self, func: &mut dyn FnMut(Vec<MethodInfo>)) {
+        self.value.methods_info(func)
+    }
+
+    fn invoke_method(
+        &mut self,
+        name: &str,
+        args: Vec<Box<dyn Reflect>>,
+        func: &mut dyn FnMut(Result<Box<dyn Reflect>, InvokeError>),
+    ) {
+        let mut mutable = false;
+        self.value.methods_info(&mut |methods| {
+            mutable = methods.iter().any(|m| m.name == name && m.mutable);
+        });
+
+        let mut succeeded = false;
+        self.value.invoke_method(name, args, &mut |result| {
+            succeeded = result.is_ok();
+            func(result)
+        });
+
+        // Only methods that take `&mut self` could modify the inner value.
+        if succeeded && mutable {
+            self.mark_modified_and_need_sync();
+        }
+    }
 }
 
 impl<T> ReflectInheritableVariable for InheritableVariable<T>
@@ -707,4 +734,40 @@ mod test {
             parent.inheritable_data.foo.value
         );
     }
+
+    #[test]
+    fn test_method_invocation_modified_flag() {
+        #[derive(Reflect, Clone, Debug, Default, PartialEq)]
+        #[reflect(method(name = "increment"))]
+        #[reflect(method(name = "count"))]
+        struct Counter {
+            count: u32,
+        }
+
+        impl Counter {
+            fn increment(&mut self) {
+                self.count += 1;
+            }
+
+            fn count(&self) -> u32 {
+                self.count
+            }
+        }
+
+        let mut variable = InheritableVariable::new(Counter::default());
+        let reflect = &mut variable as &mut dyn Reflect;
+
+        // Immutable, unknown and failed calls must not mark the variable as modified.
+        assert!(reflect.call_method("count", vec![]).is_ok());
+        assert!(reflect.call_method("reset", vec![]).is_err());
+        assert!(reflect
+            .call_method("increment", vec![Box::new(1u32)])
+            .is_err());
+        assert!(!variable.is_modified());
+
+        let reflect = &mut variable as &mut dyn Reflect;
+        assert!(reflect.call_method("increment", vec![]).is_ok());
+        assert!(variable.is_modified());
+        assert_eq!(variable.count, 1);
+    }
 }
@@ -0,0 +1,602 @@
+//! Archive is a single file, that contains many other files (for example, the entire `data` folder of a
+//! game). See [`Archive`] and [`ArchiveBuilder`] docs for more info.
+
+use crate::{
+    byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt},
+    io::FileLoadError,
+    parking_lot::Mutex,
+    vfs::{normalize_path, FileSystem},
+};
+use fxhash::FxHashMap;
+use miniz_oxide::{
+    deflate::{core::create_comp_flags_from_zip_params, core::CompressorOxide, stream::deflate},
+    MZError, MZFlush, MZStatus,
+};
+use std::{
+    fmt::{Debug, Display, Formatter},
+    fs::File,
+    io::{BufReader, BufWriter, Cursor, ErrorKind, Read, Seek, SeekFrom, Write},
+    path::{Path, PathBuf},
+};
+
+/// Magic number at the beginning of every archive file.
+pub const MAGIC: [u8; 4] = *b"FPAK";
+
+/// Current version of the archive format.
+pub const VERSION: u32 = 0;
+
+/// An error that may occur during archive reading or writing.
+#[derive(Debug)]
+pub enum ArchiveError {
+    /// An i/o error has occurred.
+    Io(std::io::Error),
+
+    /// A file is not an archive.
+    InvalidMagic,
+
+    /// An archive was made with newer version of the format.
+    UnsupportedVersion(u32),
+
+    /// An archive has invalid entry (for example, an entry that points outside of the file).
+    InvalidEntry(String),
+
+    /// There is no entry with the given path.
+    NoSuchEntry(String),
+
+    /// Compressed data of an entry is corrupted.
+    Decompression(String),
+
+    /// Unable to compress an entry.
+    Compression(String),
+}
+
+impl Display for ArchiveError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ArchiveError::Io(v) => write!(f, "An i/o error has occurred {v:?}"),
+            ArchiveError::InvalidMagic => write!(f, "A file is not an archive!"),
+            ArchiveError::UnsupportedVersion(v) => {
+                write!(f, "Unsupported archive version {v}!")
+            }
+            ArchiveError::InvalidEntry(v) => write!(f, "Invalid archive entry {v}!"),
+            ArchiveError::NoSuchEntry(v) => write!(f, "There is no entry {v} in the archive!"),
+            ArchiveError::Decompression(v) => {
+                write!(f, "Unable to decompress an entry. Reason: {v}")
+            }
+            ArchiveError::Compression(v) => {
+                write!(f, "Unable to compress an entry. Reason: {v}")
+            }
+        }
+    }
+}
+
+impl From<std::io::Error> for ArchiveError {
+    fn from(e: std::io::Error) -> Self {
+        Self::Io(e)
+    }
+}
+
+impl From<ArchiveError> for FileLoadError {
+    fn from(e: ArchiveError) -> Self {
+        match e {
+            ArchiveError::Io(e) => Self::Io(e),
+            _ => Self::Custom(e.to_string()),
+        }
+    }
+}
+
+/// Compression method of an archive entry.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Compression {
+    /// An entry is stored as is. It is the best option for files, that are already compressed (for
+    /// example, `.png`, `.jpg`, `.ogg`).
+    None,
+
+    /// An entry is compressed using Deflate algorithm.
+    Deflate,
+}
+
+impl Compression {
+    fn id(self) -> u8 {
+        match self {
+            Compression::None => 0,
+            Compression::Deflate => 1,
+        }
+    }
+
+    fn from_id(id: u8) -> Option<Self> {
+        match id {
+            0 => Some(Compression::None),
+            1 => Some(Compression::Deflate),
+            _ => None,
+        }
+    }
+
+    /// Selects compression method using extension of the given file: files of formats with built-in
+    /// compression are stored as is, everything else is compressed using Deflate.
+    pub fn for_path<P: AsRef<Path>>(path: P) -> Self {
+        const COMPRESSED_FORMATS: [&str; 8] =
+            ["png", "jpg", "jpeg", "ogg", "mp3", "dds", "gif", "zip"];
+
+        match path.as_ref().extension().and_then(|e| e.to_str()) {
+            Some(extension)
+                if COMPRESSED_FORMATS
+                    .iter()
+                    .any(|f| f.eq_ignore_ascii_case(extension)) =>
+            {
+                Compression::None
+            }
+            _ => Compression::Deflate,
+        }
+    }
+}
+
+/// A description of a file stored in an archive.
+#[derive(Clone, Debug)]
+pub struct ArchiveEntry {
+    offset: u64,
+    size: u64,
+    uncompressed_size: u64,
+    compression: Compression,
+}
+
+impl ArchiveEntry {
+    /// Returns size of the entry in the archive.
+    pub fn size(&self) -> u64 {
+        self.size
+    }
+
+    /// Returns size of the file.
+    pub fn uncompressed_size(&self) -> u64 {
+        self.uncompressed_size
+    }
+
+    /// Returns compression method of the entry.
+    pub fn compression(&self) -> Compression {
+        self.compression
+    }
+}
+
+trait ReadSeek: Read + Seek + Send {}
+
+impl<T> ReadSeek for T where T: Read + Seek + Send {}
+
+/// Archive is a single file, that contains many other files. It could be mounted to the virtual file system
+/// (see [`crate::vfs::Vfs`]), so its files could be loaded transparently by the engine. Archives are created
+/// using [`ArchiveBuilder`].
+///
+/// Only the table of contents is loaded when an archive is opened, files are read from the archive on demand.
+///
+/// ## Format
+///
+/// All numbers are stored in little endian.
+///
+/// - Magic number `FPAK`, format version (`u32`), amount of entries (`u32`).
+/// - Table of contents, for each entry: length of the path (`u32`), path in UTF-8, compression method (`u8`),
+///   offset of the data from the beginning of the file (`u64`), size of the data (`u64`), size of the
+///   uncompressed data (`u64`).
+/// - Data of the entries.
+pub struct Archive {
+    entries: FxHashMap<String, ArchiveEntry>,
+    source: Mutex<Box<dyn ReadSeek>>,
+}
+
+impl Debug for Archive {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Archive")
+            .field("entries", &self.entries)
+            .finish()
+    }
+}
+
+impl Archive {
+    /// Opens an archive file. This method is not supported on WebAssembly and Android, use
+    /// [`Self::from_memory`] with the data loaded by [`crate::io::load_file`] instead.
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self, ArchiveError> {
+        Self::from_source(Box::new(BufReader::new(File::open(path)?)))
+    }
+
+    /// Reads an archive from memory.
+    pub fn from_memory(data: Vec<u8>) -> Result<Self, ArchiveError> {
+        Self::from_source(Box::new(Cursor::new(data)))
+    }
+
+    fn from_source(mut source: Box<dyn ReadSeek>) -> Result<Self, ArchiveError> {
+        let mut magic = [0; 4];
+        source.read_exact(&mut magic)?;
+        if magic != MAGIC {
+            return Err(ArchiveError::InvalidMagic);
+        }
+
+        let version = source.read_u32::<LittleEndian>()?;
+        if version > VERSION {
+            return Err(ArchiveError::UnsupportedVersion(version));
+        }
+
+        let file_size = source.seek(SeekFrom::End(0))?;
+        source.seek(SeekFrom::Start(MAGIC.len() as u64 + 4))?;
+
+        let entry_count = source.read_u32::<LittleEndian>()?;
+        let mut entries = FxHashMap::default();
+        for _ in 0..entry_count {
+            let path_len = source.read_u32::<LittleEndian>()?;
+            let mut path = vec![0; path_len as usize];
+            source.read_exact(&mut path)?;
+            let path = String::from_utf8(path)
+                .map_err(|_| ArchiveError::InvalidEntry("<non UTF-8 path>".to_string()))?;
+
+            let compression = Compression::from_id(source.read_u8()?)
+                .ok_or_else(|| ArchiveError::InvalidEntry(path.clone()))?;
+            let offset = source.read_u64::<LittleEndian>()?;
+            let size = source.read_u64::<LittleEndian>()?;
+            let uncompressed_size = source.read_u64::<LittleEndian>()?;
+
+            if offset.checked_add(size).map_or(true, |end| end > file_size) {
+                return Err(ArchiveError::InvalidEntry(path));
+            }
+
+            entries.insert(
+                path,
+                ArchiveEntry {
+                    offset,
+                    size,
+                    uncompressed_size,
+                    compression,
+                },
+            );
+        }
+
+        Ok(Self {
+            entries,
+            source: Mutex::new(source),
+        })
+    }
+
+    /// Returns an iterator over every entry of the archive.
+    pub fn entries(&self) -> impl Iterator<Item = (&str, &ArchiveEntry)> {
+        self.entries
+            .iter()
+            .map(|(path, entry)| (path.as_str(), entry))
+    }
+
+    /// Returns an entry with the given path, if any.
+    pub fn entry(&self, path: &str) -> Option<&ArchiveEntry> {
+        self.entries.get(path)
+    }
+
+    /// Reads and decompresses (if needed) an entry with the given path.
+    pub fn read_entry(&self, path: &str) -> Result<Vec<u8>, ArchiveError> {
+        let entry = self
+            .entries
+            .get(path)
+            .ok_or_else(|| ArchiveError::NoSuchEntry(path.to_owned()))?;
+
+        let mut data = vec![0; entry.size as usize];
+        {
+            let mut source = self.source.lock();
+            source.seek(SeekFrom::Start(entry.offset))?;
+            source.read_exact(&mut data)?;
+        }
+
+        match entry.compression {
+            Compression::None => Ok(data),
+            Compression::Deflate => miniz_oxide::inflate::decompress_to_vec(&data)
+                .map_err(|e| ArchiveError::Decompression(format!("{e:?}"))),
+        }
+    }
+}
+
+impl FileSystem for Archive {
+    fn contains(&self, path: &str) -> bool {
+        self.entries.contains_key(path)
+    }
+
+    fn read_file(&self, path: &str) -> Result<Vec<u8>, FileLoadError> {
+        Ok(self.read_entry(path)?)
+    }
+}
+
+enum EntrySource {
+    Memory(Vec<u8>),
+    File(PathBuf),
+}
+
+struct PendingEntry {
+    path: String,
+    compression: Compression,
+    source: EntrySource,
+}
+
+// Size of the buffers, that are used to copy and compress entries.
+const BUFFER_SIZE: usize = 64 * 1024;
+
+fn read_chunk(reader: &mut dyn Read, buffer: &mut [u8]) -> Result<usize, ArchiveError> {
+    loop {
+        match reader.read(buffer) {
+            Ok(count) => return Ok(count),
+            Err(e) if e.kind() == ErrorKind::Interrupted => continue,
+            Err(e) => return Err(e.into()),
+        }
+    }
+}
+
+// Compresses the data of the reader into the writer chunk by chunk. Returns uncompressed and compressed
+// sizes of the data.
+fn deflate_stream(
+    reader: &mut dyn Read,
+    writer: &mut dyn Write,
+) -> Result<(u64, u64), ArchiveError> {
+    let flags = create_comp_flags_from_zip_params(ArchiveBuilder::COMPRESSION_LEVEL.into(), 0, 0);
+    let mut compressor = CompressorOxide::new(flags);
+    let mut input = vec![0; BUFFER_SIZE];
+    let mut output = vec![0; BUFFER_SIZE];
+    let mut uncompressed_size = 0;
+    let mut size = 0;
+
+    loop {
+        let count = read_chunk(reader, &mut input)?;
+        uncompressed_size += count as u64;
+
+        let flush = if count == 0 {
+            MZFlush::Finish
+        } else {
+            MZFlush::None
+        };
+
+        let mut remaining = &input[..count];
+        loop {
+            let result = deflate(&mut compressor, remaining, &mut output, flush);
+            remaining = &remaining[result.bytes_consumed..];
+            writer.write_all(&output[..result.bytes_written])?;
+            size += result.bytes_written as u64;
+
+            match result.status {
+                Ok(MZStatus::StreamEnd) => return Ok((uncompressed_size, size)),
+                Ok(_) => (),
+                // No progress could be made without more input.
+                Err(MZError::Buf) if flush != MZFlush::Finish => break,
+                Err(e) => return Err(ArchiveError::Compression(format!("{e:?}"))),
+            }
+
+            if flush != MZFlush::Finish
+                && remaining.is_empty()
+                && result.bytes_written < output.len()
+            {
+                break;
+            }
+        }
+    }
+}
+
+/// Archive builder allows you to create archives (see [`Archive`]). Files added from disk are not read
+/// until the archive is written, every file is then streamed into the archive, so the builder never holds
+/// more than a small buffer of a file in memory.
+///
+/// ```rust,no_run
+/// use fyrox_core::vfs::archive::{ArchiveBuilder, Compression};
+///
+/// let mut builder = ArchiveBuilder::new();
+/// builder.add_directory("data", &mut |path| Compression::for_path(path)).unwrap();
+/// builder.write_to_file("data.fpk").unwrap();
+/// ```
+#[derive(Default)]
+pub struct ArchiveBuilder {
+    entries: Vec<PendingEntry>,
+}
+
+impl ArchiveBuilder {
+    /// Level of Deflate compression, that is used for every compressed entry.
+    pub const COMPRESSION_LEVEL: u8 = 6;
+
+    /// Creates new empty archive builder.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn add_entry(&mut self, entry: PendingEntry) {
+        if let Some(existing) = self.entries.iter_mut().find(|e| e.path == entry.path) {
+            *existing = entry;
+        } else {
+            self.entries.push(entry);
+        }
+    }
+
+    /// Adds a file with the given path and content. An entry with the same path will be replaced.
+    pub fn add_file(&mut self, path: &str, data: &[u8], compression: Compression) {
+        self.add_entry(PendingEntry {
+            path: normalize_path(path),
+            compression,
+            source: EntrySource::Memory(data.to_vec()),
+        });
+    }
+
+    /// Adds a file from disk. The file will be read when the archive is written. An entry with the same
+    /// path will be replaced.
+    pub fn add_file_from_disk<P: AsRef<Path>>(
+        &mut self,
+        path: &str,
+        file_path: P,
+        compression: Compression,
+    ) {
+        self.add_entry(PendingEntry {
+            path: normalize_path(path),
+            compression,
+            source: EntrySource::File(file_path.as_ref().to_path_buf()),
+        });
+    }
+
+    /// Adds every file of the given directory (recursively). Paths of the entries are relative to the
+    /// directory, so the archive should be mounted to the path of the directory. `compression` is used
+    /// to select compression method for each file. The files will be read when the archive is written.
+    pub fn add_directory<P: AsRef<Path>>(
+        &mut self,
+        dir: P,
+        compression: &mut dyn FnMut(&Path) -> Compression,
+    ) -> Result<(), ArchiveError> {
+        self.add_directory_recursive(dir.as_ref(), dir.as_ref(), compression)
+    }
+
+    fn add_directory_recursive(
+        &mut self,
+        root: &Path,
+        dir: &Path,
+        compression: &mut dyn FnMut(&Path) -> Compression,
+    ) -> Result<(), ArchiveError> {
+        let mut paths = std::fs::read_dir(dir)?
+            .map(|entry| entry.map(|e| e.path()))
+            .collect::<Result<Vec<_>, _>>()?;
+        // Make archives reproducible.
+        paths.sort();
+
+        for path in paths {
+            if path.is_dir() {
+                self.add_directory_recursive(root, &path, compression)?;
+            } else {
+                let relative_path = path.strip_prefix(root).unwrap_or(&path);
+                let compression = compression(&path);
+                self.add_file_from_disk(&relative_path.to_string_lossy(), &path, compression);
+            }
+        }
+
+        Ok(())
+    }
+
+    fn write_toc(
+        &self,
+        writer: &mut dyn Write,
+        sizes: &[(u64, u64, u64)],
+    ) -> Result<(), ArchiveError> {
+        for (entry, (offset, size, uncompressed_size)) in self.entries.iter().zip(sizes) {
+            writer.write_u32::<LittleEndian>(entry.path.len() as u32)?;
+            writer.write_all(entry.path.as_bytes())?;
+            writer.write_u8(entry.compression.id())?;
+            writer.write_u64::<LittleEndian>(*offset)?;
+            writer.write_u64::<LittleEndian>(*size)?;
+            writer.write_u64::<LittleEndian>(*uncompressed_size)?;
+        }
+        Ok(())
+    }
+
+    /// Writes the archive. Sizes of the entries are known only after they're written, so the table of
+    /// contents is written twice: at first it is filled with zeros, then the writer is rewound and the
+    /// table is written again.
+    pub fn write<W: Write + Seek>(&self, mut writer: W) -> Result<(), ArchiveError> {
+        let start = writer.stream_position()?;
+
+        writer.write_all(&MAGIC)?;
+        writer.write_u32::<LittleEndian>(VERSION)?;
+        writer.write_u32::<LittleEndian>(self.entries.len() as u32)?;
+
+        let toc_position = writer.stream_position()?;
+        let mut sizes = vec![(0, 0, 0); self.entries.len()];
+        self.write_toc(&mut writer, &sizes)?;
+
+        for (entry, (offset, size, uncompressed_size)) in self.entries.iter().zip(sizes.iter_mut())
+        {
+            *offset = writer.stream_position()? - start;
+
+            let mut reader: Box<dyn Read> = match entry.source {
+                EntrySource::Memory(ref data) => Box::new(data.as_slice()),
+                EntrySource::File(ref path) => Box::new(File::open(path)?),
+            };
+
+            match entry.compression {
+                Compression::None => {
+                    *size = std::io::copy(&mut reader, &mut writer)?;
+                    *uncompressed_size = *size;
+                }
+                Compression::Deflate => {
+                    let (uncompressed, compressed) = deflate_stream(&mut reader, &mut writer)?;
+                    *size = compressed;
+                    *uncompressed_size = uncompressed;
+                }
+            }
+        }
+
+        let end = writer.stream_position()?;
+        writer.seek(SeekFrom::Start(toc_position))?;
+        self.write_toc(&mut writer, &sizes)?;
+        writer.seek(SeekFrom::Start(end))?;
+
+        writer.flush()?;
+
+        Ok(())
+    }
+
+    /// Writes the archive to the given file.
+    pub fn write_to_file<P: AsRef<Path>>(&self, path: P) -> Result<(), ArchiveError> {
+        self.write(BufWriter::new(File::create(path)?))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::vfs::archive::{Archive, ArchiveBuilder, ArchiveError, Compression};
+    use std::io::Cursor;
+
+    #[test]
+    fn test_archive() {
+        let text = "Lorem ipsum dolor sit amet ".repeat(100);
+
+        let mut builder = ArchiveBuilder::new();
+        builder.add_file("textures/a.png", &[1, 2, 3], Compression::None);
+        builder.add_file("./text.txt", text.as_bytes(), Compression::Deflate);
+        let mut data = Cursor::new(Vec::new());
+        builder.write(&mut data).unwrap();
+
+        let archive = Archive::from_memory(data.into_inner()).unwrap();
+        assert_eq!(archive.entries().count(), 2);
+        assert_eq!(archive.read_entry("textures/a.png").unwrap(), vec![1, 2, 3]);
+
+        let entry = archive.entry("text.txt").unwrap();
+        assert_eq!(entry.compression(), Compression::Deflate);
+        assert!(entry.size() < entry.uncompressed_size());
+        assert_eq!(archive.read_entry("text.txt").unwrap(), text.as_bytes());
+
+        assert!(matches!(
+            archive.read_entry("b.png"),
+            Err(ArchiveError::NoSuchEntry(_))
+        ));
+        assert!(matches!(
+            Archive::from_memory(vec![0; 16]),
+            Err(ArchiveError::InvalidMagic)
+        ));
+    }
+
+    #[test]
+    fn test_archive_directory() {
+        let dir = std::env::temp_dir().join(format!("fyrox_archive_{}", std::process::id()));
+        std::fs::create_dir_all(dir.join("nested")).unwrap();
+
+        // Larger than the buffers used for streaming.
+        let big = (0..300_000u32)
+            .map(|i| (i.wrapping_mul(2654435761) >> 24) as u8)
+            .collect::<Vec<_>>();
+        std::fs::write(dir.join("big.bin"), &big).unwrap();
+        std::fs::write(dir.join("nested/small.txt"), b"small").unwrap();
+        std::fs::write(dir.join("image.png"), [1, 2, 3]).unwrap();
+
+        let mut builder = ArchiveBuilder::new();
+        builder
+            .add_directory(&dir, &mut |path| Compression::for_path(path))
+            .unwrap();
+        let mut data = Cursor::new(Vec::new());
+        builder.write(&mut data).unwrap();
+
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        let archive = Archive::from_memory(data.into_inner()).unwrap();
+        assert_eq!(archive.entries().count(), 3);
+        assert_eq!(archive.read_entry("big.bin").unwrap(), big);
+        assert_eq!(
+            archive.entry("big.bin").unwrap().uncompressed_size(),
+            300_000
+        );
+        assert_eq!(archive.read_entry("nested/small.txt").unwrap(), b"small");
+        assert_eq!(
+            archive.entry("image.png").unwrap().compression(),
+            Compression::None
+        );
+        assert_eq!(archive.read_entry("image.png").unwrap(), vec![1, 2, 3]);
+    }
+}
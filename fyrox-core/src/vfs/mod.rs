@@ -0,0 +1,248 @@
+//! Virtual file system (VFS) allows to load files from archives and other sources transparently, as if they
+//! were regular files of the OS file system. See [`Vfs`] docs for more info.
+
+use crate::{io::FileLoadError, parking_lot::RwLock};
+use once_cell::sync::Lazy;
+use std::{
+    fmt::Debug,
+    path::{Component, Path, PathBuf},
+};
+
+pub mod archive;
+
+/// A source of files, that could be mounted to the virtual file system. Paths passed to the methods are
+/// relative to a mount point and always use `/` as a separator.
+pub trait FileSystem: Debug + Send + Sync {
+    /// Returns `true` if the file system contains a file with the given path.
+    fn contains(&self, path: &str) -> bool;
+
+    /// Reads entire file with the given path.
+    fn read_file(&self, path: &str) -> Result<Vec<u8>, FileLoadError>;
+}
+
+/// A file system, that reads files from a directory of the OS file system. It could be used to mount
+/// loose files (for example, a mod that is not packed in an archive) to any mount point.
+#[derive(Debug)]
+pub struct DirectoryFileSystem {
+    root: PathBuf,
+}
+
+impl DirectoryFileSystem {
+    /// Creates new directory file system with the given root directory.
+    pub fn new<P: AsRef<Path>>(root: P) -> Self {
+        Self {
+            root: root.as_ref().to_path_buf(),
+        }
+    }
+}
+
+impl FileSystem for DirectoryFileSystem {
+    fn contains(&self, path: &str) -> bool {
+        self.root.join(path).is_file()
+    }
+
+    fn read_file(&self, path: &str) -> Result<Vec<u8>, FileLoadError> {
+        Ok(std::fs::read(self.root.join(path))?)
+    }
+}
+
+/// Unique identifier of a mount, it could be used to unmount a file system.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct MountId(u64);
+
+#[derive(Debug)]
+struct Mount {
+    id: MountId,
+    point: String,
+    file_system: Box<dyn FileSystem>,
+}
+
+impl Mount {
+    // Returns a path relative to the mount point, if the path is inside of it.
+    fn relative_path<'a>(&self, path: &'a str) -> Option<&'a str> {
+        if self.point.is_empty() {
+            Some(path)
+        } else {
+            path.strip_prefix(self.point.as_str())
+                .and_then(|rest| rest.strip_prefix('/'))
+        }
+    }
+}
+
+/// Virtual file system is a set of file systems (archives, directories, etc.) mounted to specific paths
+/// (mount points). Every file request made via [`crate::io::load_file`] (which is used by every resource
+/// loader of the engine) is first checked in the mounted file systems, and only then in the OS file system.
+/// The engine uses single global instance of the virtual file system - [`VFS`].
+///
+/// ## Overrides
+///
+/// File systems are searched in reverse order of mounting, which means that a file system that was mounted
+/// later overrides files of the previously mounted file systems. It could be used to implement patches and
+/// mods: mount the base archive of a game first and then mount archives (or directories) of patches and mods
+/// on top of it.
+///
+/// ## Example
+///
+/// ```rust,no_run
+/// use fyrox_core::vfs::{archive::Archive, DirectoryFileSystem, VFS};
+///
+/// let mut vfs = VFS.write();
+///
+/// // Every request to `data/...` will be redirected to the archive.
+/// vfs.mount("data", Archive::open("data.fpk").unwrap());
+///
+/// // Loose files of the mod will override the files from the archive.
+/// vfs.mount("data", DirectoryFileSystem::new("mods/my_mod"));
+/// ```
+#[derive(Debug, Default)]
+pub struct Vfs {
+    mounts: Vec<Mount>,
+    id_counter: u64,
+}
+
+/// Global instance of the virtual file system, that is used by [`crate::io::load_file`] and
+/// [`crate::io::exists`].
+pub static VFS: Lazy<RwLock<Vfs>> = Lazy::new(Default::default);
+
+/// Converts a path to the form that is used in the virtual file system: `.` components are removed, `..`
+/// components are resolved (where possible) and `/` is used as a separator.
+pub fn normalize_path<P: AsRef<Path>>(path: P) -> String {
+    // Paths could be written on Windows with `\` as a separator.
+    let path = path.as_ref().to_string_lossy().replace('\\', "/");
+
+    let mut components: Vec<String> = Vec::new();
+    for component in Path::new(&path).components() {
+        match component {
+            Component::CurDir => (),
+            Component::ParentDir => {
+                if components.last().map_or(false, |c| c != "..") {
+                    components.pop();
+                } else {
+                    components.push("..".to_owned());
+                }
+            }
+            Component::Prefix(prefix) => {
+                components.push(prefix.as_os_str().to_string_lossy().into_owned())
+            }
+            Component::RootDir => components.push(String::new()),
+            Component::Normal(name) => components.push(name.to_string_lossy().into_owned()),
+        }
+    }
+    components.join("/")
+}
+
+impl Vfs {
+    /// Creates new empty virtual file system.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Mounts the given file system to the given mount point (for example, `data`). Empty mount point means
+    /// that the file system is mounted to the working directory. Files of the new file system will override
+    /// files with the same paths of previously mounted file systems.
+    pub fn mount<P, F>(&mut self, point: P, file_system: F) -> MountId
+    where
+        P: AsRef<Path>,
+        F: FileSystem + 'static,
+    {
+        let id = MountId(self.id_counter);
+        self.id_counter += 1;
+        self.mounts.push(Mount {
+            id,
+            point: normalize_path(point),
+            file_system: Box::new(file_system),
+        });
+        id
+    }
+
+    /// Unmounts a file system with the given id. Returns `false` if there is no such mount.
+    pub fn unmount(&mut self, id: MountId) -> bool {
+        if let Some(position) = self.mounts.iter().position(|m| m.id == id) {
+            self.mounts.remove(position);
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Unmounts every file system.
+    pub fn unmount_all(&mut self) {
+        self.mounts.clear();
+    }
+
+    /// Returns `true` if there is no mounted file systems.
+    pub fn is_empty(&self) -> bool {
+        self.mounts.is_empty()
+    }
+
+    fn find<'a>(&self, path: &'a str) -> Option<(&dyn FileSystem, &'a str)> {
+        self.mounts.iter().rev().find_map(|mount| {
+            mount
+                .relative_path(path)
+                .filter(|relative_path| mount.file_system.contains(relative_path))
+                .map(|relative_path| (&*mount.file_system, relative_path))
+        })
+    }
+
+    /// Returns `true` if any of the mounted file systems contains the given file.
+    pub fn exists<P: AsRef<Path>>(&self, path: P) -> bool {
+        !self.mounts.is_empty() && self.find(&normalize_path(path)).is_some()
+    }
+
+    /// Reads a file from the mounted file systems. Returns `None` if there is no such file in the mounted
+    /// file systems.
+    pub fn read_file<P: AsRef<Path>>(&self, path: P) -> Option<Result<Vec<u8>, FileLoadError>> {
+        if self.mounts.is_empty() {
+            return None;
+        }
+
+        let path = normalize_path(path);
+        self.find(&path)
+            .map(|(file_system, relative_path)| file_system.read_file(relative_path))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::vfs::{
+        archive::{Archive, ArchiveBuilder, Compression},
+        normalize_path, Vfs,
+    };
+    use std::io::Cursor;
+
+    fn archive(files: &[(&str, &[u8])]) -> Archive {
+        let mut builder = ArchiveBuilder::new();
+        for (path, data) in files {
+            builder.add_file(path, data, Compression::Deflate);
+        }
+        let mut data = Cursor::new(Vec::new());
+        builder.write(&mut data).unwrap();
+        Archive::from_memory(data.into_inner()).unwrap()
+    }
+
+    #[test]
+    fn test_normalize_path() {
+        assert_eq!(normalize_path("./data/../data/a.png"), "data/a.png");
+        assert_eq!(
+            normalize_path("data\\textures/a.png"),
+            "data/textures/a.png"
+        );
+        assert_eq!(normalize_path("../a.png"), "../a.png");
+    }
+
+    #[test]
+    fn test_vfs_overrides() {
+        let mut vfs = Vfs::new();
+        vfs.mount("data", archive(&[("a.txt", b"base"), ("b.txt", b"base")]));
+        let patch = vfs.mount("data", archive(&[("b.txt", b"patch")]));
+
+        assert_eq!(vfs.read_file("data/a.txt").unwrap().unwrap(), b"base");
+        assert_eq!(vfs.read_file("./data/b.txt").unwrap().unwrap(), b"patch");
+        assert!(vfs.read_file("data/c.txt").is_none());
+        assert!(vfs.read_file("a.txt").is_none());
+        assert!(vfs.exists("data/a.txt"));
+
+        assert!(vfs.unmount(patch));
+        assert_eq!(vfs.read_file("data/b.txt").unwrap().unwrap(), b"base");
+    }
+}
@@ -28,10 +28,11 @@ use crate::{
 use base64::Engine;
 use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
 use fxhash::FxHashMap;
+use once_cell::sync::Lazy;
 use std::{
-    any::Any,
+    any::{Any, TypeId},
     cell::{Cell, RefCell},
-    collections::{hash_map::Entry, HashMap, HashSet},
+    collections::{hash_map::Entry, BTreeMap, HashMap, HashSet},
     fmt::{Display, Formatter},
     fs::File,
     hash::{BuildHasher, Hash},
@@ -532,6 +533,16 @@ pub enum VisitError {
     UnexpectedRcNullIndex,
     PoisonedMutex,
     FileLoadError(FileLoadError),
+    /// Data was saved by a newer version of a type than the current one.
+    UnsupportedVersion {
+        version: u32,
+        supported_version: u32,
+    },
+    /// There is no migration of the data of a type from the given version to the next one.
+    MissingMigration {
+        type_name: &'static str,
+        version: u32,
+    },
 }
 
 impl Display for VisitError {
@@ -554,6 +565,21 @@ impl Display for VisitError {
             Self::UnexpectedRcNullIndex => write!(f, "unexpected rc null index"),
             Self::PoisonedMutex => write!(f, "attempt to lock poisoned mutex"),
             Self::FileLoadError(e) => write!(f, "file load error: {:?}", e),
+            Self::UnsupportedVersion {
+                version,
+                supported_version,
+            } => write!(
+                f,
+                "unsupported version {} (max supported version is {})",
+                version, supported_version
+            ),
+            Self::MissingMigration { type_name, version } => write!(
+                f,
+                "there is no migration of {} data from version {} to version {}",
+                type_name,
+                version,
+                version + 1
+            ),
         }
     }
 }
@@ -1078,6 +1104,14 @@ pub trait Visit {
     fn visit(&mut self, name: &str, visitor: &mut Visitor) -> VisitResult;
 }
 
+/// A function that transforms the data of a region of some version of a type to the data of the next
+/// version. See [`Visitor::register_migration`].
+pub type VisitMigration = Arc<dyn Fn(&mut Visitor) -> VisitResult + Send + Sync>;
+
+static MIGRATIONS: Lazy<
+    crate::parking_lot::RwLock<FxHashMap<TypeId, BTreeMap<u32, VisitMigration>>>,
+> = Lazy::new(Default::default);
+
 impl Default for Visitor {
     fn default() -> Self {
         Self::new()
@@ -1087,6 +1121,8 @@ impl Default for Visitor {
 impl Visitor {
     const MAGIC: &'static str = "RG3D";
 
+    const VERSION_FIELD: &'static str = "__Version";
+
     pub fn new() -> Self {
         let mut nodes = Pool::new();
         let root = nodes.spawn(VisitorNode::new("__ROOT__", Handle::NONE));
@@ -1160,6 +1196,203 @@ impl Visitor {
             .map(|n| n.name.as_str())
     }
 
+    /// Registers a migration for the type `T`, that transforms the data of a region of the type from
+    /// `from_version` to `from_version + 1`. Migrations are applied by [`Self::visit_version`] in order
+    /// of versions, before the type reads its data. A migration is called with the visitor positioned at
+    /// the region of the type and could use [`Self::rename_field`], [`Self::rename_region`],
+    /// [`Self::remove_field`], [`Self::remove_region`], [`Self::write_value`] and regular reading to
+    /// restructure the data. Registering a migration for the same version again replaces it.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use fyrox_core::visitor::prelude::*;
+    ///
+    /// // Version 0 had `value: u32` field, version 1 renamed it.
+    /// #[derive(Visit, Default)]
+    /// #[visit(version = 1)]
+    /// struct Foo {
+    ///     amount: u32,
+    /// }
+    ///
+    /// Visitor::register_migration::<Foo, _>(0, |visitor| {
+    ///     visitor.rename_field("Value", "Amount");
+    ///     Ok(())
+    /// });
+    /// ```
+    pub fn register_migration<T, F>(from_version: u32, migration: F)
+    where
+        T: 'static,
+        F: Fn(&mut Visitor) -> VisitResult + Send + Sync + 'static,
+    {
+        MIGRATIONS
+            .write()
+            .entry(TypeId::of::<T>())
+            .or_default()
+            .insert(from_version, Arc::new(migration));
+    }
+
+    /// Returns version of the current region. Regions without a version (the data of unversioned types or
+    /// the data that was saved before the type got a version) have version 0.
+    pub fn region_version(&self) -> u32 {
+        self.nodes
+            .borrow(self.current_node)
+            .fields
+            .iter()
+            .find_map(|field| match field.kind {
+                FieldKind::U32(version) if field.name == Self::VERSION_FIELD => Some(version),
+                _ => None,
+            })
+            .unwrap_or_default()
+    }
+
+    /// Writes the given version of the type `T` to the current region, or, when reading, upgrades the data
+    /// of the current region from its version to the given one using migrations registered for the type
+    /// (see [`Self::register_migration`]). Data of a newer version than the given one and data that cannot be
+    /// upgraded, because a migration of some version is not registered, cause an error. It is used by
+    /// `#[derive(Visit)]` for types with `#[visit(version = N)]` attribute.
+    pub fn visit_version<T: 'static>(&mut self, version: u32) -> VisitResult {
+        if !self.reading {
+            let mut version = version;
+            return version.visit(Self::VERSION_FIELD, self);
+        }
+
+        let stored_version = self.region_version();
+        if stored_version > version {
+            return Err(VisitError::UnsupportedVersion {
+                version: stored_version,
+                supported_version: version,
+            });
+        }
+
+        if stored_version < version {
+            // Clone the migrations to not hold the lock while migrating, migrations could visit other
+            // versioned types.
+            let migrations = MIGRATIONS
+                .read()
+                .get(&TypeId::of::<T>())
+                .map(|migrations| {
+                    migrations
+                        .range(stored_version..version)
+                        .map(|(from_version, migration)| (*from_version, migration.clone()))
+                        .collect::<Vec<_>>()
+                })
+                .unwrap_or_default();
+
+            // Every step must be covered, otherwise the type would read the data of an older version
+            // as if it was the current one.
+            let mut next_version = stored_version;
+            for (from_version, _) in migrations.iter() {
+                if *from_version != next_version {
+                    break;
+                }
+                next_version += 1;
+            }
+            if next_version != version {
+                return Err(VisitError::MissingMigration {
+                    type_name: std::any::type_name::<T>(),
+                    version: next_version,
+                });
+            }
+
+            for (_, migration) in migrations {
+                migration(self)?;
+            }
+
+            // Store the new version, so the data won't be migrated again if the region is read once more.
+            self.write_value(Self::VERSION_FIELD, &mut version.clone())?;
+        }
+
+        Ok(())
+    }
+
+    /// Returns `true` if the current region has a field with the given name.
+    pub fn has_field(&self, name: &str) -> bool {
+        self.nodes
+            .borrow(self.current_node)
+            .fields
+            .iter()
+            .any(|field| field.name == name)
+    }
+
+    /// Returns `true` if the current region has a child region with the given name.
+    pub fn has_region(&self, name: &str) -> bool {
+        self.find_region(name).is_some()
+    }
+
+    fn find_region(&self, name: &str) -> Option<Handle<VisitorNode>> {
+        self.nodes
+            .borrow(self.current_node)
+            .children
+            .iter()
+            .find(|child| self.nodes.borrow(**child).name == name)
+            .cloned()
+    }
+
+    /// Renames a field of the current region. Returns `false` if there is no such field.
+    pub fn rename_field(&mut self, name: &str, new_name: &str) -> bool {
+        match self.find_field(name) {
+            Some(field) => {
+                field.name = new_name.to_owned();
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Removes a field of the current region. Returns `false` if there is no such field.
+    pub fn remove_field(&mut self, name: &str) -> bool {
+        let fields = &mut self.current_node().fields;
+        match fields.iter().position(|field| field.name == name) {
+            Some(position) => {
+                fields.remove(position);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Renames a child region of the current region. Returns `false` if there is no such region.
+    pub fn rename_region(&mut self, name: &str, new_name: &str) -> bool {
+        match self.find_region(name) {
+            Some(region) => {
+                self.nodes.borrow_mut(region).name = new_name.to_owned();
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Removes a child region (with all its descendants) of the current region. Returns `false` if there
+    /// is no such region.
+    pub fn remove_region(&mut self, name: &str) -> bool {
+        match self.find_region(name) {
+            Some(region) => {
+                self.current_node()
+                    .children
+                    .retain(|child| *child != region);
+                let mut stack = vec![region];
+                while let Some(handle) = stack.pop() {
+                    stack.extend(self.nodes.free(handle).children);
+                }
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Writes the given value to the current region, replacing a field or a region with the same name,
+    /// regardless of whether the visitor is reading or not. It is used by migrations to store values in
+    /// a new form.
+    pub fn write_value<T: Visit + ?Sized>(&mut self, name: &str, value: &mut T) -> VisitResult {
+        self.remove_field(name);
+        self.remove_region(name);
+        let reading = std::mem::replace(&mut self.reading, false);
+        let result = value.visit(name, self);
+        self.reading = reading;
+        result
+    }
+
     fn leave_region(&mut self) -> VisitResult {
         self.current_node = self.nodes.borrow(self.current_node).parent;
         if self.current_node.is_none() {
@@ -1290,6 +1523,35 @@ impl Visitor {
         visitor.current_node = visitor.root;
         Ok(visitor)
     }
+
+    /// Returns `true` if the given data starts with the signature of the binary format of the visitor.
+    /// It could be used to check whether a file could be loaded by the visitor without reading it entirely.
+    pub fn is_supported(data: &[u8]) -> bool {
+        data.starts_with(Self::MAGIC.as_bytes())
+    }
+
+    /// Calls the given closure for every string-like value (`String`, `PathBuf`, etc.) stored in the
+    /// visitor. It allows to search for references to external files (such as resource paths) without
+    /// deserializing the data back to the objects.
+    pub fn for_each_string(&self, func: &mut dyn FnMut(&str)) {
+        for node in self.nodes.iter() {
+            let length = node.fields.iter().find_map(|field| match field.kind {
+                FieldKind::U32(length) if field.name == "Length" => Some(length as usize),
+                _ => None,
+            });
+            let data = node.fields.iter().find_map(|field| match field.kind {
+                FieldKind::Data(ref data) if field.name == "Data" => Some(data),
+                _ => None,
+            });
+            if let (Some(length), Some(data)) = (length, data) {
+                if data.len() == length {
+                    if let Ok(string) = std::str::from_utf8(data) {
+                        func(string);
+                    }
+                }
+            }
+        }
+    }
 }
 
 impl<T> Visit for RefCell<T>
@@ -1809,8 +2071,13 @@ impl<T: Visit> Visit for Range<T> {
 
 #[cfg(test)]
 mod test {
-    use crate::visitor::{Data, Visit, VisitResult, Visitor};
-    use std::{fs::File, io::Write, path::Path, rc::Rc};
+    use crate::visitor::{Data, Visit, VisitError, VisitResult, Visitor};
+    use std::{
+        fs::File,
+        io::Write,
+        path::{Path, PathBuf},
+        rc::Rc,
+    };
 
     #[derive(Visit, Default)]
     pub struct Model {
@@ -1913,4 +2180,125 @@ mod test {
             objects.visit("Objects", &mut visitor).unwrap();
         }
     }
+
+    #[test]
+    fn visitor_strings_test() {
+        let mut visitor = Visitor::new();
+        let mut name = "Foo".to_owned();
+        name.visit("Name", &mut visitor).unwrap();
+        let mut path = PathBuf::from("data/textures/foo.png");
+        path.visit("Path", &mut visitor).unwrap();
+        let mut number = 123u32;
+        number.visit("Number", &mut visitor).unwrap();
+
+        let data = visitor.save_binary_to_vec().unwrap();
+        assert!(Visitor::is_supported(&data));
+
+        let visitor = Visitor::load_from_memory(data).unwrap();
+        let mut strings = Vec::new();
+        visitor.for_each_string(&mut |string| strings.push(string.to_owned()));
+        strings.sort();
+        assert_eq!(strings, ["Foo", "data/textures/foo.png"]);
+    }
+
+    #[test]
+    fn visitor_migration_test() {
+        #[derive(Visit, Default)]
+        struct OldFoo {
+            value: u32,
+        }
+
+        #[derive(Visit, Default, Debug, PartialEq)]
+        #[visit(version = 2)]
+        struct Foo {
+            amount: u32,
+            scale: f32,
+        }
+
+        Visitor::register_migration::<Foo, _>(0, |visitor| {
+            assert!(visitor.rename_field("Value", "Amount"));
+            Ok(())
+        });
+        Visitor::register_migration::<Foo, _>(1, |visitor| {
+            let mut amount = 0u32;
+            amount.visit("Amount", visitor)?;
+            visitor.write_value("Scale", &mut (amount as f32 * 0.5))
+        });
+
+        let mut visitor = Visitor::new();
+        OldFoo { value: 10 }.visit("Foo", &mut visitor).unwrap();
+        let data = visitor.save_binary_to_vec().unwrap();
+
+        let mut visitor = Visitor::load_from_memory(data).unwrap();
+        let mut foo = Foo::default();
+        foo.visit("Foo", &mut visitor).unwrap();
+        assert_eq!(
+            foo,
+            Foo {
+                amount: 10,
+                scale: 5.0
+            }
+        );
+
+        // Migrated data is not migrated again.
+        let mut same_foo = Foo::default();
+        same_foo.visit("Foo", &mut visitor).unwrap();
+        assert_eq!(foo, same_foo);
+
+        // Data of the current version is read as is.
+        let mut visitor = Visitor::new();
+        foo.visit("Foo", &mut visitor).unwrap();
+        let data = visitor.save_binary_to_vec().unwrap();
+        let mut visitor = Visitor::load_from_memory(data.clone()).unwrap();
+        let mut loaded_foo = Foo::default();
+        loaded_foo.visit("Foo", &mut visitor).unwrap();
+        assert_eq!(foo, loaded_foo);
+
+        let mut visitor = Visitor::new();
+        loaded_foo.visit("Foo", &mut visitor).unwrap();
+        assert_eq!(visitor.save_binary_to_vec().unwrap(), data);
+
+        // Data of a newer version is rejected.
+        let mut visitor = Visitor::new();
+        {
+            let mut region = visitor.enter_region("Foo").unwrap();
+            region.visit_version::<Foo>(3).unwrap();
+        }
+        let data = visitor.save_binary_to_vec().unwrap();
+        let mut visitor = Visitor::load_from_memory(data).unwrap();
+        assert!(matches!(
+            Foo::default().visit("Foo", &mut visitor),
+            Err(VisitError::UnsupportedVersion {
+                version: 3,
+                supported_version: 2
+            })
+        ));
+    }
+
+    #[test]
+    fn visitor_missing_migration_test() {
+        #[derive(Visit, Default)]
+        struct OldBar {
+            value: u32,
+        }
+
+        #[derive(Visit, Default)]
+        #[visit(version = 2)]
+        struct Bar {
+            amount: u32,
+        }
+
+        // There is no migration from version 0, so the data of version 0 must not be read as version 1.
+        Visitor::register_migration::<Bar, _>(1, |_| Ok(()));
+
+        let mut visitor = Visitor::new();
+        OldBar { value: 10 }.visit("Bar", &mut visitor).unwrap();
+        let data = visitor.save_binary_to_vec().unwrap();
+
+        let mut visitor = Visitor::load_from_memory(data).unwrap();
+        assert!(matches!(
+            Bar::default().visit("Bar", &mut visitor),
+            Err(VisitError::MissingMigration { version: 0, .. })
+        ));
+    }
 }
@@ -0,0 +1,353 @@
+//! Dependency graph of resources. It allows to find out which resources are used by other resources
+//! (for example, which textures are used by a scene), which resources are not used at all and which
+//! references point to non-existent files. See [`DependencyGraph`] docs for more info.
+
+use crate::core::{vfs::normalize_path, visitor::Visitor};
+use std::{
+    collections::{BTreeMap, BTreeSet, VecDeque},
+    fs::File,
+    io::Read,
+    path::Path,
+};
+
+/// Extensions of the files, that are treated as resources by [`DependencyGraph::scan`].
+pub const RESOURCE_EXTENSIONS: &[&str] = &[
+    "rgs", "fbx", "png", "jpg", "jpeg", "tga", "bmp", "dds", "gif", "tif", "tiff", "hdr", "ogg",
+    "wav", "shader", "curve",
+];
+
+// Resources in foreign (or text) formats, that could reference other resources. References are
+// extracted from them by searching for strings that look like file paths.
+const DOCUMENT_EXTENSIONS: &[&str] = &["fbx", "shader"];
+
+#[derive(Default, Debug)]
+struct Asset {
+    dependencies: BTreeSet<String>,
+    dependents: BTreeSet<String>,
+    missing: BTreeSet<String>,
+}
+
+/// Dependency graph of the resources of a project. It is built by scanning the files of a directory:
+/// the data of every file saved by [`Visitor`] (scenes, models, materials inside of them, etc.) is searched
+/// for the paths of other resources. References from documents of other formats (FBX, shaders) are found
+/// by searching for strings that look like file paths; since FBX models reference textures by file name
+/// only, such references are matched with every resource with the same file name.
+///
+/// Paths of the resources are normalized (see [`normalize_path`]) and relative to the working directory
+/// of the game, so the scanned directory should be given the same way the game references its resources
+/// (usually it is `.` or `data`).
+///
+/// ## Limitations
+///
+/// Resources could be loaded from code by a path, that is built at runtime, there is no way to find such
+/// references. Such resources should be passed as roots to [`DependencyGraph::unused_assets`].
+///
+/// ## Example
+///
+/// ```rust,no_run
+/// use fyrox_resource::dependency::DependencyGraph;
+///
+/// let graph = DependencyGraph::scan("data").unwrap();
+///
+/// for dependent in graph.dependents("data/textures/wall.png") {
+///     println!("{dependent} uses the texture");
+/// }
+///
+/// let scenes = graph.scenes().collect::<Vec<_>>();
+/// for unused in graph.unused_assets(&scenes) {
+///     println!("{unused} is not used and could be deleted");
+/// }
+/// ```
+#[derive(Default, Debug)]
+pub struct DependencyGraph {
+    assets: BTreeMap<String, Asset>,
+}
+
+fn extension(path: &str) -> Option<String> {
+    Path::new(path)
+        .extension()
+        .map(|ext| ext.to_string_lossy().to_lowercase())
+}
+
+fn file_name(path: &str) -> &str {
+    path.rsplit(|c| c == '/' || c == '\\')
+        .next()
+        .unwrap_or(path)
+}
+
+// Extracts strings of printable characters from arbitrary data.
+fn embedded_strings(data: &[u8]) -> impl Iterator<Item = &str> {
+    data.split(|&byte| !(b' '..=b'~').contains(&byte) || byte == b'"' || byte == b'\'')
+        .filter_map(|run| std::str::from_utf8(run).ok())
+        .map(|string| string.trim())
+        // Skip strings with characters, that are not allowed in file paths.
+        .filter(|string| !string.is_empty() && !string.contains(['|', '*', '?', '<', '>']))
+}
+
+fn collect_files(dir: &Path, files: &mut Vec<String>) -> std::io::Result<()> {
+    for entry in std::fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            // Skip hidden directories, such as `.git`.
+            if !path
+                .file_name()
+                .map_or(false, |name| name.to_string_lossy().starts_with('.'))
+            {
+                collect_files(&path, files)?;
+            }
+        } else {
+            files.push(normalize_path(&path));
+        }
+    }
+    Ok(())
+}
+
+// Returns references found in the file and a flag, that tells whether the references are exact paths.
+fn read_references(path: &str) -> std::io::Result<(Vec<String>, bool)> {
+    let mut file = File::open(path)?;
+    let mut magic = [0; 4];
+    let magic_len = file.read(&mut magic)?;
+
+    let is_visitor = Visitor::is_supported(&magic[..magic_len]);
+    let is_document =
+        extension(path).map_or(false, |ext| DOCUMENT_EXTENSIONS.contains(&ext.as_str()));
+    if !is_visitor && !is_document {
+        return Ok((Vec::new(), true));
+    }
+
+    let mut data = magic[..magic_len].to_vec();
+    file.read_to_end(&mut data)?;
+
+    if is_visitor {
+        if let Ok(visitor) = Visitor::load_from_memory(data.clone()) {
+            let mut references = Vec::new();
+            visitor.for_each_string(&mut |string| references.push(string.to_owned()));
+            return Ok((references, true));
+        }
+    }
+
+    Ok((
+        embedded_strings(&data).map(|s| s.to_owned()).collect(),
+        false,
+    ))
+}
+
+impl DependencyGraph {
+    /// Scans the given directory recursively and builds dependency graph of every resource in it. Resources
+    /// are detected by their extensions, see [`RESOURCE_EXTENSIONS`].
+    pub fn scan<P: AsRef<Path>>(dir: P) -> std::io::Result<Self> {
+        Self::scan_with_extensions(dir, RESOURCE_EXTENSIONS)
+    }
+
+    /// Same as [`Self::scan`], but allows to specify extensions of the resources. It could be useful if
+    /// a game uses resources of custom types.
+    pub fn scan_with_extensions<P: AsRef<Path>>(
+        dir: P,
+        extensions: &[&str],
+    ) -> std::io::Result<Self> {
+        let mut files = Vec::new();
+        collect_files(dir.as_ref(), &mut files)?;
+
+        let mut graph = Self::default();
+        for path in files {
+            if extension(&path).map_or(false, |ext| extensions.contains(&ext.as_str())) {
+                graph.assets.insert(path, Asset::default());
+            }
+        }
+
+        let mut by_file_name: BTreeMap<&str, Vec<&str>> = BTreeMap::new();
+        for path in graph.assets.keys() {
+            by_file_name
+                .entry(file_name(path))
+                .or_default()
+                .push(path.as_str());
+        }
+
+        let mut edges = Vec::new();
+        let mut missing = Vec::new();
+        for path in graph.assets.keys() {
+            let (references, exact) = read_references(path)?;
+            let dir = Path::new(path).parent().unwrap_or_else(|| Path::new(""));
+
+            for reference in references {
+                if !extension(&reference).map_or(false, |ext| extensions.contains(&ext.as_str())) {
+                    continue;
+                }
+
+                let candidates = [
+                    normalize_path(&reference),
+                    normalize_path(dir.join(reference.replace('\\', "/"))),
+                ];
+                if let Some(dependency) = candidates
+                    .iter()
+                    .find(|candidate| graph.assets.contains_key(candidate.as_str()))
+                {
+                    edges.push((path.clone(), dependency.clone()));
+                } else if let Some(same_name) =
+                    by_file_name.get(file_name(&reference)).filter(|_| !exact)
+                {
+                    for dependency in same_name {
+                        edges.push((path.clone(), dependency.to_string()));
+                    }
+                } else {
+                    missing.push((path.clone(), reference));
+                }
+            }
+        }
+
+        for (dependent, dependency) in edges {
+            if dependent != dependency {
+                graph
+                    .assets
+                    .get_mut(&dependent)
+                    .unwrap()
+                    .dependencies
+                    .insert(dependency.clone());
+                graph
+                    .assets
+                    .get_mut(&dependency)
+                    .unwrap()
+                    .dependents
+                    .insert(dependent);
+            }
+        }
+        for (dependent, reference) in missing {
+            graph
+                .assets
+                .get_mut(&dependent)
+                .unwrap()
+                .missing
+                .insert(reference);
+        }
+
+        Ok(graph)
+    }
+
+    /// Returns `true` if the graph contains the given resource.
+    pub fn contains<P: AsRef<Path>>(&self, path: P) -> bool {
+        self.assets.contains_key(&normalize_path(path))
+    }
+
+    /// Returns an iterator over paths of every resource of the graph.
+    pub fn assets(&self) -> impl Iterator<Item = &str> {
+        self.assets.keys().map(|path| path.as_str())
+    }
+
+    /// Returns an iterator over paths of every scene of the graph.
+    pub fn scenes(&self) -> impl Iterator<Item = &str> {
+        self.assets()
+            .filter(|path| extension(path).map_or(false, |ext| ext == "rgs"))
+    }
+
+    /// Returns an iterator over paths of the resources, that are directly used by the given resource.
+    pub fn dependencies<P: AsRef<Path>>(&self, path: P) -> impl Iterator<Item = &str> {
+        self.assets
+            .get(&normalize_path(path))
+            .into_iter()
+            .flat_map(|asset| asset.dependencies.iter().map(|path| path.as_str()))
+    }
+
+    /// Returns an iterator over paths of the resources, that directly use the given resource (reverse
+    /// dependencies). A resource without dependents could be moved or deleted without breaking other
+    /// resources.
+    pub fn dependents<P: AsRef<Path>>(&self, path: P) -> impl Iterator<Item = &str> {
+        self.assets
+            .get(&normalize_path(path))
+            .into_iter()
+            .flat_map(|asset| asset.dependents.iter().map(|path| path.as_str()))
+    }
+
+    /// Returns an iterator over pairs `(resource, reference)`, where `reference` is a path to a resource
+    /// file, that does not exist.
+    pub fn missing_references(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.assets.iter().flat_map(|(path, asset)| {
+            asset
+                .missing
+                .iter()
+                .map(move |reference| (path.as_str(), reference.as_str()))
+        })
+    }
+
+    /// Returns paths of the resources, that are not used (directly or indirectly) by any of the given
+    /// roots. Roots are the resources that are loaded by the game directly (usually scenes).
+    pub fn unused_assets<P: AsRef<Path>>(&self, roots: &[P]) -> Vec<&str> {
+        let mut used = BTreeSet::new();
+        let mut queue = roots.iter().map(normalize_path).collect::<VecDeque<_>>();
+        while let Some(path) = queue.pop_front() {
+            if let Some(asset) = self.assets.get(&path) {
+                if used.insert(path) {
+                    queue.extend(asset.dependencies.iter().cloned());
+                }
+            }
+        }
+
+        self.assets().filter(|path| !used.contains(*path)).collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        core::visitor::{Visit, Visitor},
+        dependency::DependencyGraph,
+    };
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_dependency_graph() {
+        let dir =
+            std::env::temp_dir().join(format!("fyrox_dependency_test_{}", std::process::id()));
+        let textures = dir.join("textures");
+        std::fs::create_dir_all(&textures).unwrap();
+
+        std::fs::write(textures.join("wall.png"), b"").unwrap();
+        std::fs::write(textures.join("floor.png"), b"").unwrap();
+        std::fs::write(textures.join("unused.png"), b"").unwrap();
+
+        // Textures of FBX models are referenced by file name only.
+        std::fs::write(
+            dir.join("model.fbx"),
+            b"Kaydara FBX Binary\x00\x1a\x00\x10\x00\x00\x00C:\\Work\\floor.png\x00",
+        )
+        .unwrap();
+
+        let mut visitor = Visitor::new();
+        let prefix = dir.to_string_lossy().replace('\\', "/");
+        let mut paths = vec![
+            PathBuf::from(format!("{prefix}/textures/wall.png")),
+            PathBuf::from(format!("{prefix}/model.fbx")),
+            PathBuf::from(format!("{prefix}/textures/missing.png")),
+        ];
+        paths.visit("Paths", &mut visitor).unwrap();
+        visitor.save_binary(dir.join("scene.rgs")).unwrap();
+
+        let graph = DependencyGraph::scan(&dir).unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        let path = |name: &str| format!("{prefix}/{name}");
+
+        assert!(graph.contains(path("model.fbx")));
+        assert_eq!(
+            graph.dependencies(path("scene.rgs")).collect::<Vec<_>>(),
+            [path("model.fbx"), path("textures/wall.png")]
+        );
+        assert_eq!(
+            graph
+                .dependents(path("textures/floor.png"))
+                .collect::<Vec<_>>(),
+            [path("model.fbx")]
+        );
+        assert_eq!(
+            graph.missing_references().collect::<Vec<_>>(),
+            [(
+                path("scene.rgs").as_str(),
+                path("textures/missing.png").as_str()
+            )]
+        );
+
+        let scenes = graph.scenes().collect::<Vec<_>>();
+        assert_eq!(scenes, [path("scene.rgs")]);
+        assert_eq!(graph.unused_assets(&scenes), [path("textures/unused.png")]);
+        assert_eq!(graph.unused_assets::<&str>(&[]).len(), 5);
+    }
+}
@@ -78,10 +78,11 @@ impl GenericBuffer {
             }
             DataSource::RawStreaming(_) => Err(source),
             _ => {
-                let external_source_path = if let DataSource::File { path, .. } = &source {
-                    path.clone()
-                } else {
-                    Default::default()
+                let external_source_path = match &source {
+                    DataSource::File { path, .. } | DataSource::VirtualFile { path, .. } => {
+                        path.clone()
+                    }
+                    _ => Default::default(),
                 };
 
                 // Store cursor to handle errors.
@@ -42,6 +42,16 @@ pub enum DataSource {
         data: Cursor<Vec<u8>>,
     },
 
+    /// Data source is a file from the virtual file system (see [`fyrox_core::vfs::Vfs`]), the file is
+    /// loaded in memory entirely.
+    VirtualFile {
+        /// Path to file.
+        path: PathBuf,
+
+        /// File data.
+        data: Cursor<Vec<u8>>,
+    },
+
     /// Data source is a memory block. Memory block must be in valid format (wav or vorbis/ogg). This variant can
     /// be used together with virtual file system.
     Memory(Cursor<Vec<u8>>),
@@ -103,6 +113,12 @@ impl DataSource {
     where
         P: AsRef<Path>,
     {
+        // Files from the virtual file system are always loaded entirely.
+        #[cfg(not(target_arch = "wasm32"))]
+        if fyrox_core::vfs::VFS.read().exists(path.as_ref()) {
+            return Self::from_virtual_file(path).await;
+        }
+
         Ok(DataSource::File {
             path: path.as_ref().to_path_buf(),
 
@@ -117,6 +133,19 @@ impl DataSource {
         })
     }
 
+    /// Tries to create new `VirtualFile` data source from given path, the file is loaded using the virtual
+    /// file system (see [`fyrox_core::vfs::Vfs`]) or from disk, if the file system has no such file. May fail
+    /// if file does not exists.
+    pub async fn from_virtual_file<P>(path: P) -> Result<Self, FileLoadError>
+    where
+        P: AsRef<Path>,
+    {
+        Ok(DataSource::VirtualFile {
+            path: path.as_ref().to_path_buf(),
+            data: Cursor::new(fyrox_core::io::load_file(path.as_ref()).await?),
+        })
+    }
+
     /// Creates new data source from given memory block. This function does not checks if this is valid source or
     /// not. Data source validity will be checked on first use.
     pub fn from_memory(data: Vec<u8>) -> Self {
@@ -128,6 +157,7 @@ impl Read for DataSource {
     fn read(&mut self, buf: &mut [u8]) -> Result<usize, std::io::Error> {
         match self {
             DataSource::File { data, .. } => data.read(buf),
+            DataSource::VirtualFile { data, .. } => data.read(buf),
             DataSource::Memory(b) => b.read(buf),
             DataSource::Raw { .. } => unreachable!("Raw data source does not supports Read trait!"),
             DataSource::RawStreaming { .. } => {
@@ -141,6 +171,7 @@ impl Seek for DataSource {
     fn seek(&mut self, pos: SeekFrom) -> Result<u64, std::io::Error> {
         match self {
             DataSource::File { data, .. } => data.seek(pos),
+            DataSource::VirtualFile { data, .. } => data.seek(pos),
             DataSource::Memory(b) => b.seek(pos),
             DataSource::Raw { .. } => unreachable!("Raw data source does not supports Seek trait!"),
             DataSource::RawStreaming { .. } => {
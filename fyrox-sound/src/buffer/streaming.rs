@@ -66,7 +66,7 @@ impl StreamingSource {
     #[inline]
     fn new(data_source: DataSource) -> Result<Self, DataSource> {
         match data_source {
-            DataSource::File { .. } | DataSource::Memory(_) => {
+            DataSource::File { .. } | DataSource::VirtualFile { .. } | DataSource::Memory(_) => {
                 Ok(Self::Decoder(Decoder::new(data_source)?))
             }
             DataSource::RawStreaming(raw) => Ok(Self::Raw(raw)),
@@ -160,10 +160,9 @@ impl StreamingBuffer {
     /// This function will return Err if data source is `Raw`. It makes no sense to stream raw data which
     /// is already loaded into memory. Use Generic source instead!
     pub fn new(source: DataSource) -> Result<Self, DataSource> {
-        let external_source_path = if let DataSource::File { path, .. } = &source {
-            path.clone()
-        } else {
-            Default::default()
+        let external_source_path = match &source {
+            DataSource::File { path, .. } | DataSource::VirtualFile { path, .. } => path.clone(),
+            _ => Default::default(),
         };
 
         let mut streaming_source = StreamingSource::new(source)?;
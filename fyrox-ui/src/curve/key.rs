@@ -1,6 +1,6 @@
 use crate::core::{
     algebra::Vector2,
-    curve::{Curve, CurveKey, CurveKeyKind},
+    curve::{Curve, CurveKey, CurveKeyKind, CurveWrapMode},
     uuid::Uuid,
 };
 use std::cmp::Ordering;
@@ -26,6 +26,8 @@ impl From<&CurveKey> for CurveKeyView {
 pub struct KeyContainer {
     id: Uuid,
     keys: Vec<CurveKeyView>,
+    pre_infinity: CurveWrapMode,
+    post_infinity: CurveWrapMode,
 }
 
 impl From<&Curve> for KeyContainer {
@@ -37,6 +39,8 @@ impl From<&Curve> for KeyContainer {
                 .map(CurveKeyView::from)
                 .collect::<Vec<_>>(),
             id: curve.id(),
+            pre_infinity: curve.pre_infinity(),
+            post_infinity: curve.post_infinity(),
         }
     }
 }
@@ -102,6 +106,8 @@ impl KeyContainer {
                 .collect::<Vec<_>>(),
         );
         curve.set_id(self.id);
+        curve.set_pre_infinity(self.pre_infinity);
+        curve.set_post_infinity(self.post_infinity);
         curve
     }
 }
@@ -15,7 +15,7 @@ use crate::{
     message::{MessageDirection, UiMessage},
     stack_panel::StackPanelBuilder,
     widget::{Widget, WidgetBuilder, WidgetMessage},
-    BuildContext, Control, HorizontalAlignment, Thickness, UiNode, UserInterface,
+    BuildContext, Control, HorizontalAlignment, Orientation, Thickness, UiNode, UserInterface,
     VerticalAlignment,
 };
 use fyrox_core::reflect::Reflect;
@@ -31,6 +31,7 @@ use std::{
 pub struct Item {
     inspector: Handle<UiNode>,
     remove: Handle<UiNode>,
+    move_up: Handle<UiNode>,
 }
 
 pub trait CollectionItem: Clone + Reflect + Debug + Default + 'static {}
@@ -119,6 +120,19 @@ impl<T: CollectionItem> Control for CollectionEditor<T> {
                     MessageDirection::FromWidget,
                     index,
                 ));
+            } else if let Some(index) = self
+                .items
+                .iter()
+                .position(|i| i.move_up == message.destination())
+            {
+                if index > 0 {
+                    ui.send_message(CollectionChanged::move_item(
+                        self.handle,
+                        MessageDirection::FromWidget,
+                        index,
+                        index - 1,
+                    ));
+                }
             }
         } else if let Some(msg) = message.data::<CollectionEditorMessage>() {
             if message.destination == self.handle {
@@ -186,11 +200,21 @@ fn create_item_views(
         .iter()
         .enumerate()
         .map(|(n, item)| {
+            let header = StackPanelBuilder::new(
+                WidgetBuilder::new()
+                    .with_horizontal_alignment(HorizontalAlignment::Right)
+                    .on_column(1)
+                    .with_child(item.move_up)
+                    .with_child(item.remove),
+            )
+            .with_orientation(Orientation::Horizontal)
+            .build(ctx);
+
             make_expander_container(
                 layer_index,
                 &format!("Item {}", n),
                 &format!("Item {} of the collection", n),
-                item.remove,
+                header,
                 item.inspector,
                 ctx,
             )
@@ -227,19 +251,31 @@ where
                 .with_context(inspector_context)
                 .build(ctx);
 
+            let move_up = ButtonBuilder::new(
+                WidgetBuilder::new()
+                    .with_margin(Thickness::uniform(1.0))
+                    .with_vertical_alignment(VerticalAlignment::Center)
+                    .with_width(16.0)
+                    .with_height(16.0),
+            )
+            .with_text("^")
+            .build(ctx);
+
             let remove = ButtonBuilder::new(
                 WidgetBuilder::new()
                     .with_margin(Thickness::uniform(1.0))
                     .with_vertical_alignment(VerticalAlignment::Center)
-                    .with_horizontal_alignment(HorizontalAlignment::Right)
-                    .on_column(1)
                     .with_width(16.0)
                     .with_height(16.0),
             )
             .with_text("-")
             .build(ctx);
 
-            Item { inspector, remove }
+            Item {
+                inspector,
+                remove,
+                move_up,
+            }
         })
         .collect::<Vec<_>>()
 }
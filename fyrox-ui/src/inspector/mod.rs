@@ -46,12 +46,20 @@ pub enum CollectionChanged {
         index: usize,
         property: PropertyChanged,
     },
+    /// An item in the collection should be moved to a new position.
+    Move {
+        /// Current index of an item in the collection.
+        from: usize,
+        /// New index of the item.
+        to: usize,
+    },
 }
 
 impl CollectionChanged {
     define_constructor!(CollectionChanged:Add => fn add(ObjectValue), layout: false);
     define_constructor!(CollectionChanged:Remove => fn remove(usize), layout: false);
     define_constructor!(CollectionChanged:ItemChanged => fn item_changed(index: usize, property: PropertyChanged), layout: false);
+    define_constructor!(CollectionChanged:Move => fn move_item(from: usize, to: usize), layout: false);
 }
 
 #[derive(Debug, Clone)]
@@ -85,6 +93,13 @@ pub enum PropertyAction {
         /// Index of an item.
         index: usize,
     },
+    /// An item of a collection property needs to be moved to a new position.
+    MoveItem {
+        /// Current index of an item.
+        from: usize,
+        /// New index of the item.
+        to: usize,
+    },
     /// Revert value to parent.
     Revert,
 }
@@ -103,6 +118,7 @@ impl PropertyAction {
                     value: value.clone().into_box_reflect(),
                 },
                 CollectionChanged::Remove(index) => Self::RemoveItem { index },
+                CollectionChanged::Move { from, to } => Self::MoveItem { from, to },
                 CollectionChanged::ItemChanged { ref property, .. } => {
                     Self::from_field_kind(&property.value)
                 }
@@ -178,6 +194,30 @@ impl PropertyAction {
                     result_callback(Err(Self::RemoveItem { index }))
                 }
             }),
+            PropertyAction::MoveItem { from, to } => target.resolve_path_mut(path, &mut |result| {
+                if let Ok(field) = result {
+                    field.as_list_mut(&mut |result| {
+                        if let Some(list) = result {
+                            if let Some(item) = list.reflect_remove(from) {
+                                match list.reflect_insert(to, item) {
+                                    Ok(_) => result_callback(Ok(None)),
+                                    Err(item) => {
+                                        // Put the item back to keep the collection intact.
+                                        let _ = list.reflect_insert(from, item);
+                                        result_callback(Err(Self::MoveItem { from, to }))
+                                    }
+                                }
+                            } else {
+                                result_callback(Err(Self::MoveItem { from, to }))
+                            }
+                        } else {
+                            result_callback(Err(Self::MoveItem { from, to }))
+                        }
+                    })
+                } else {
+                    result_callback(Err(Self::MoveItem { from, to }))
+                }
+            }),
             PropertyAction::Revert => {
                 // Unsupported due to lack of context (a reference to parent entity).
                 result_callback(Err(Self::Revert))
@@ -302,6 +342,7 @@ impl PropertyChanged {
             FieldKind::Collection(ref collection_changed) => match **collection_changed {
                 CollectionChanged::Add(_) => false,
                 CollectionChanged::Remove(_) => false,
+                CollectionChanged::Move { .. } => false,
                 CollectionChanged::ItemChanged { ref property, .. } => property.is_inheritable(),
             },
             FieldKind::Inspectable(ref inspectable) => inspectable.is_inheritable(),
@@ -398,6 +398,7 @@ impl MachineLayer {
         &mut self,
         animations: &AnimationContainer,
         parameters: &ParameterContainer,
+        consumed_triggers: &mut Vec<String>,
         dt: f32,
     ) -> &AnimationPose {
         self.final_pose.reset();
@@ -409,6 +410,11 @@ impl MachineLayer {
             }
 
             if self.active_transition.is_none() {
+                let normalized_time = self
+                    .states
+                    .try_borrow(self.active_state)
+                    .and_then(|s| s.normalized_time(&self.nodes, animations));
+
                 // Find transition.
                 for (handle, transition) in self.transitions.pair_iter_mut() {
                     if transition.dest() == self.active_state
@@ -417,7 +423,15 @@ impl MachineLayer {
                         continue;
                     }
 
-                    if transition.condition.calculate_value(parameters) {
+                    if transition
+                        .condition
+                        .calculate_value_with_time(parameters, normalized_time)
+                    {
+                        // Triggers are consumed by the transition.
+                        transition
+                            .condition
+                            .collect_triggers(parameters, consumed_triggers);
+
                         self.events.push(Event::StateLeave(self.active_state));
                         if self.debug {
                             Log::writeln(
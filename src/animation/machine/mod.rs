@@ -289,13 +289,74 @@ impl Machine {
     pub fn evaluate_pose(&mut self, animations: &AnimationContainer, dt: f32) -> &AnimationPose {
         self.final_pose.reset();
 
+        let mut consumed_triggers = Vec::new();
         for layer in self.layers.iter_mut() {
             let weight = layer.weight();
-            let pose = layer.evaluate_pose(animations, &self.parameters, dt);
+            let pose =
+                layer.evaluate_pose(animations, &self.parameters, &mut consumed_triggers, dt);
 
             self.final_pose.blend_with(pose, weight);
         }
 
+        for trigger in consumed_triggers {
+            if let Some(Parameter::Trigger(value)) = self.parameters.get_mut(&trigger) {
+                *value = false;
+            }
+        }
+
         &self.final_pose
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::animation::{
+        machine::{
+            transition::{CompareNode, ComparisonOperator, LogicNode},
+            Machine, MachineLayer, Parameter, PoseNode, State, Transition,
+        },
+        AnimationContainer,
+    };
+
+    #[test]
+    fn test_trigger_consumption() {
+        let mut layer = MachineLayer::new();
+        let idle_node = layer.add_node(PoseNode::make_play_animation(Default::default()));
+        let idle = layer.add_state(State::new("Idle", idle_node));
+        let jump_node = layer.add_node(PoseNode::make_play_animation(Default::default()));
+        let jump = layer.add_state(State::new("Jump", jump_node));
+        layer.add_transition(Transition::new("IdleToJump", idle, jump, 0.0, "Jump"));
+        layer.set_entry_state(idle);
+
+        let mut machine = Machine::new();
+        // Replace the default layer.
+        machine.layers_mut()[0] = layer;
+        machine.set_parameter("Jump", Parameter::Trigger(true));
+
+        machine.evaluate_pose(&AnimationContainer::new(), 0.1);
+
+        assert_eq!(machine.layers()[0].active_state(), jump);
+        assert_eq!(
+            machine.parameters().get("Jump"),
+            Some(&Parameter::Trigger(false))
+        );
+    }
+
+    #[test]
+    fn test_numeric_conditions() {
+        let mut machine = Machine::new();
+        machine.set_parameter("Speed", Parameter::Weight(1.5));
+
+        let condition = |operator, value| {
+            LogicNode::Compare(CompareNode {
+                parameter: "Speed".to_string(),
+                operator,
+                value,
+            })
+        };
+
+        assert!(condition(ComparisonOperator::Greater, 1.0).calculate_value(machine.parameters()));
+        assert!(!condition(ComparisonOperator::Less, 1.0).calculate_value(machine.parameters()));
+        assert!(condition(ComparisonOperator::Equal, 1.5).calculate_value(machine.parameters()));
+    }
+}
@@ -13,6 +13,7 @@ use crate::{
         reflect::prelude::*,
         visitor::prelude::*,
     },
+    resource::animation::AnimationResource,
 };
 use std::{
     cell::{Ref, RefCell},
@@ -30,6 +31,12 @@ pub struct PlayAnimation {
     /// A handle to animation.
     pub animation: Handle<Animation>,
 
+    /// An optional animation resource. If set, the node will use an animation from the animation player that is backed
+    /// by the resource (it will be created automatically if there is no such animation) and the `animation` field will be
+    /// overwritten.
+    #[visit(optional)]
+    pub animation_resource: Option<AnimationResource>,
+
     /// Output pose, it contains a filtered (see [`crate::animation::machine::LayerMask`] for more info) pose from
     /// the animation specified by the `animation` field.
     #[visit(skip)]
@@ -57,9 +64,38 @@ impl PlayAnimation {
         Self {
             base: Default::default(),
             animation,
+            animation_resource: None,
+            output_pose: Default::default(),
+        }
+    }
+
+    /// Creates new PlayAnimation node that plays an animation from the given animation resource.
+    pub fn from_resource(resource: AnimationResource) -> Self {
+        Self {
+            base: Default::default(),
+            animation: Default::default(),
+            animation_resource: Some(resource),
             output_pose: Default::default(),
         }
     }
+
+    /// Finds (or adds) an animation backed by the animation resource of the node (if any) in the given container and
+    /// updates the animation handle of the node.
+    pub(crate) fn resolve_animation_resource(&mut self, animations: &mut AnimationContainer) {
+        if let Some(resource) = self.animation_resource.as_ref() {
+            self.animation = match animations.find_by_resource(resource) {
+                Some((handle, _)) => handle,
+                None => {
+                    let mut animation = Animation::default();
+                    if let Some(name) = resource.state().path().file_stem() {
+                        animation.set_name(name.to_string_lossy());
+                    }
+                    animation.set_resource(Some(resource.clone()));
+                    animations.add(animation)
+                }
+            };
+        }
+    }
 }
 
 impl EvaluatePose for PlayAnimation {
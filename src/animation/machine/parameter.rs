@@ -24,6 +24,26 @@ pub enum Parameter {
 
     /// A sampling point. Usually it is used together with BlendSpace nodes.
     SamplingPoint(Vector2<f32>),
+
+    /// Trigger parameter is used the same way as Rule parameter, but it is reset to `false` automatically when
+    /// a transition that uses it becomes active. It is useful for one-shot actions (jump, attack, etc.), so there
+    /// is no need to reset a flag manually from code.
+    Trigger(bool),
+}
+
+impl Parameter {
+    /// Returns numeric value of the parameter, if it has one. `Weight` and `Index` parameters are numeric, `Rule`
+    /// and `Trigger` parameters are converted to `0.0` or `1.0`.
+    pub fn numeric_value(&self) -> Option<f32> {
+        match self {
+            Parameter::Weight(weight) => Some(*weight),
+            Parameter::Index(index) => Some(*index as f32),
+            Parameter::Rule(value) | Parameter::Trigger(value) => {
+                Some(if *value { 1.0 } else { 0.0 })
+            }
+            Parameter::SamplingPoint(_) => None,
+        }
+    }
 }
 
 impl Default for Parameter {
@@ -51,6 +51,37 @@ impl State {
         nodes.try_borrow(self.root).map(|root| root.pose())
     }
 
+    /// Returns normalized time position (`0.0` - beginning, `1.0` - end) of the first animation played by the
+    /// state (if any). It is used by exit time conditions of transitions.
+    pub fn normalized_time(
+        &self,
+        nodes: &Pool<PoseNode>,
+        animations: &AnimationContainer,
+    ) -> Option<f32> {
+        let mut stack = vec![self.root];
+        while let Some(handle) = stack.pop() {
+            match nodes.try_borrow(handle) {
+                Some(PoseNode::PlayAnimation(play_animation)) => {
+                    if let Some(animation) = animations.try_get(play_animation.animation) {
+                        let time_slice = animation.time_slice();
+                        let length = animation.length();
+                        return Some(if length > 0.0 {
+                            (animation.time_position() - time_slice.start) / length
+                        } else {
+                            1.0
+                        });
+                    }
+                }
+                Some(node) => {
+                    // Keep the order of children, so the first one will be checked first.
+                    stack.extend(node.children().into_iter().rev());
+                }
+                None => (),
+            }
+        }
+        None
+    }
+
     pub(super) fn update(
         &mut self,
         nodes: &Pool<PoseNode>,
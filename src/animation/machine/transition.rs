@@ -244,6 +244,78 @@ impl Reflect for NotNode {
     }
 }
 
+/// An operator that is used to compare a numeric parameter with a value. See [`CompareNode`] docs.
+#[derive(
+    Copy, Clone, Debug, PartialEq, Eq, Visit, Reflect, AsRefStr, EnumString, EnumVariantNames,
+)]
+pub enum ComparisonOperator {
+    /// `parameter > value`
+    Greater,
+    /// `parameter >= value`
+    GreaterOrEqual,
+    /// `parameter < value`
+    Less,
+    /// `parameter <= value`
+    LessOrEqual,
+    /// `parameter == value`
+    Equal,
+    /// `parameter != value`
+    NotEqual,
+}
+
+impl Default for ComparisonOperator {
+    fn default() -> Self {
+        Self::Greater
+    }
+}
+
+impl ComparisonOperator {
+    /// Compares two values using the operator.
+    pub fn compare(self, lhs: f32, rhs: f32) -> bool {
+        match self {
+            ComparisonOperator::Greater => lhs > rhs,
+            ComparisonOperator::GreaterOrEqual => lhs >= rhs,
+            ComparisonOperator::Less => lhs < rhs,
+            ComparisonOperator::LessOrEqual => lhs <= rhs,
+            ComparisonOperator::Equal => (lhs - rhs).abs() <= f32::EPSILON,
+            ComparisonOperator::NotEqual => (lhs - rhs).abs() > f32::EPSILON,
+        }
+    }
+}
+
+/// Compares a value of a numeric parameter (see [`Parameter::numeric_value`]) with a constant. Output value will be
+/// `false` if the parameter is not found or is not numeric.
+#[derive(Default, Debug, Clone, PartialEq, Visit, Reflect)]
+pub struct CompareNode {
+    /// Name of a numeric parameter.
+    pub parameter: String,
+    /// Comparison operator.
+    pub operator: ComparisonOperator,
+    /// A value to compare the parameter with.
+    pub value: f32,
+}
+
+/// Checks whether a value of a numeric parameter (see [`Parameter::numeric_value`]) is in `[min; max]` range. Output
+/// value will be `false` if the parameter is not found or is not numeric.
+#[derive(Default, Debug, Clone, PartialEq, Visit, Reflect)]
+pub struct InRangeNode {
+    /// Name of a numeric parameter.
+    pub parameter: String,
+    /// Lower bound of the range (inclusive).
+    pub min: f32,
+    /// Upper bound of the range (inclusive).
+    pub max: f32,
+}
+
+/// Exit time condition. Output value will be `true` if normalized time position (`0.0` - beginning, `1.0` - end) of
+/// the animation played by the source state of a transition is greater or equal than the given value. If the source
+/// state blends multiple animations, the first one is used.
+#[derive(Default, Debug, Clone, PartialEq, Visit, Reflect)]
+pub struct ExitTimeNode {
+    /// Normalized time position in `[0; 1]` range.
+    pub normalized_time: f32,
+}
+
 /// A node responsible for logical operations evaluation. It can have any number of descendant nodes.
 ///
 /// # Examples
@@ -268,9 +340,30 @@ impl Reflect for NotNode {
 ///
 /// assert_eq!(transition_logic.calculate_value(&parameters), true);
 /// ```
+///
+/// Numeric parameters could be compared with constants, which removes the need to mirror them in Rule parameters:
+///
+/// ```rust
+/// use fyrox::animation::machine::{
+///     transition::{CompareNode, ComparisonOperator, LogicNode},
+///     Parameter, ParameterContainer,
+/// };
+///
+/// let mut parameters = ParameterContainer::default();
+/// parameters.add("Speed", Parameter::Weight(3.5));
+///
+/// // Speed > 2.0
+/// let transition_logic = LogicNode::Compare(CompareNode {
+///     parameter: "Speed".to_string(),
+///     operator: ComparisonOperator::Greater,
+///     value: 2.0,
+/// });
+///
+/// assert_eq!(transition_logic.calculate_value(&parameters), true);
+/// ```
 #[derive(Debug, Visit, Clone, Reflect, PartialEq, AsRefStr, EnumString, EnumVariantNames)]
 pub enum LogicNode {
-    /// Fetches a value of `Rule` parameter and returns its value. `false` if the parameter is not found.
+    /// Fetches a value of `Rule` or `Trigger` parameter and returns its value. `false` if the parameter is not found.
     Parameter(String),
     /// Calculates logical AND between two arguments. Output value will be `true` iff both of the arguments is `true`.
     And(AndNode),
@@ -280,6 +373,12 @@ pub enum LogicNode {
     Xor(XorNode),
     /// Calculates logical NOT of an argument. Output value will be `true` if the value of the argument is `false`.
     Not(NotNode),
+    /// Compares a numeric parameter with a constant.
+    Compare(CompareNode),
+    /// Checks whether a numeric parameter is in a range.
+    InRange(InRangeNode),
+    /// Checks whether the animation of the source state has reached a specific normalized time position.
+    ExitTime(ExitTimeNode),
 }
 
 impl Default for LogicNode {
@@ -289,32 +388,87 @@ impl Default for LogicNode {
 }
 
 impl LogicNode {
-    /// Calculates final value of the logic node.
+    /// Calculates final value of the logic node. Exit time conditions are always `false`, because there is no
+    /// information about source state of a transition, use [`Self::calculate_value_with_time`] to take them into
+    /// account.
     pub fn calculate_value(&self, parameters: &ParameterContainer) -> bool {
+        self.calculate_value_with_time(parameters, None)
+    }
+
+    /// Calculates final value of the logic node. `normalized_time` is a normalized time position of the animation
+    /// of the source state of a transition, it is used by exit time conditions (they're `false` if it is `None`).
+    pub fn calculate_value_with_time(
+        &self,
+        parameters: &ParameterContainer,
+        normalized_time: Option<f32>,
+    ) -> bool {
         match self {
-            LogicNode::Parameter(rule_name) => parameters.get(rule_name).map_or(false, |p| {
-                if let Parameter::Rule(rule_value) = p {
-                    *rule_value
-                } else {
-                    false
-                }
-            }),
+            LogicNode::Parameter(rule_name) => {
+                parameters.get(rule_name).map_or(false, |p| match p {
+                    Parameter::Rule(rule_value) | Parameter::Trigger(rule_value) => *rule_value,
+                    _ => false,
+                })
+            }
             LogicNode::And(and) => {
-                let lhs_value = and.lhs.calculate_value(parameters);
-                let rhs_value = and.rhs.calculate_value(parameters);
+                let lhs_value = and
+                    .lhs
+                    .calculate_value_with_time(parameters, normalized_time);
+                let rhs_value = and
+                    .rhs
+                    .calculate_value_with_time(parameters, normalized_time);
                 lhs_value & rhs_value
             }
             LogicNode::Or(or) => {
-                let lhs_value = or.lhs.calculate_value(parameters);
-                let rhs_value = or.rhs.calculate_value(parameters);
+                let lhs_value = or
+                    .lhs
+                    .calculate_value_with_time(parameters, normalized_time);
+                let rhs_value = or
+                    .rhs
+                    .calculate_value_with_time(parameters, normalized_time);
                 lhs_value | rhs_value
             }
             LogicNode::Xor(or) => {
-                let lhs_value = or.lhs.calculate_value(parameters);
-                let rhs_value = or.rhs.calculate_value(parameters);
+                let lhs_value = or
+                    .lhs
+                    .calculate_value_with_time(parameters, normalized_time);
+                let rhs_value = or
+                    .rhs
+                    .calculate_value_with_time(parameters, normalized_time);
                 lhs_value ^ rhs_value
             }
-            LogicNode::Not(node) => !node.lhs.calculate_value(parameters),
+            LogicNode::Not(node) => !node
+                .lhs
+                .calculate_value_with_time(parameters, normalized_time),
+            LogicNode::Compare(compare) => parameters
+                .get(&compare.parameter)
+                .and_then(|p| p.numeric_value())
+                .map_or(false, |v| compare.operator.compare(v, compare.value)),
+            LogicNode::InRange(range) => parameters
+                .get(&range.parameter)
+                .and_then(|p| p.numeric_value())
+                .map_or(false, |v| v >= range.min && v <= range.max),
+            LogicNode::ExitTime(exit_time) => {
+                normalized_time.map_or(false, |t| t >= exit_time.normalized_time)
+            }
+        }
+    }
+
+    /// Collects names of every `Trigger` parameter used by the node and its descendants.
+    pub fn collect_triggers(&self, parameters: &ParameterContainer, triggers: &mut Vec<String>) {
+        match self {
+            LogicNode::Parameter(name) => {
+                if let Some(Parameter::Trigger(_)) = parameters.get(name) {
+                    triggers.push(name.clone());
+                }
+            }
+            LogicNode::And(AndNode { lhs, rhs })
+            | LogicNode::Or(OrNode { lhs, rhs })
+            | LogicNode::Xor(XorNode { lhs, rhs }) => {
+                lhs.collect_triggers(parameters, triggers);
+                rhs.collect_triggers(parameters, triggers);
+            }
+            LogicNode::Not(NotNode { lhs }) => lhs.collect_triggers(parameters, triggers),
+            LogicNode::Compare(_) | LogicNode::InRange(_) | LogicNode::ExitTime(_) => {}
         }
     }
 }
@@ -5,6 +5,7 @@
 
 use crate::{
     animation::track::Track,
+    asset::ResourceState,
     core::{
         algebra::{UnitQuaternion, Vector3},
         math::wrapf,
@@ -13,6 +14,7 @@ use crate::{
         uuid::Uuid,
         visitor::{Visit, VisitResult, Visitor},
     },
+    resource::animation::AnimationResource,
     scene::{
         graph::{Graph, NodePool},
         node::Node,
@@ -32,6 +34,7 @@ pub use signal::{AnimationEvent, AnimationSignal};
 pub mod container;
 pub mod machine;
 pub mod pose;
+pub mod recorder;
 pub mod signal;
 pub mod spritesheet;
 pub mod track;
@@ -175,34 +178,73 @@ pub mod value;
 /// The code above creates a simple animation that moves a node along X axis in various ways. The usage of the animation
 /// is only for the sake of completeness of the example. In the real games you need to add the animation to an animation
 /// player scene node and it will do the job for you.
-#[derive(Debug, Reflect, Visit, PartialEq)]
+#[derive(Debug, Reflect, PartialEq)]
 pub struct Animation {
-    #[visit(optional)]
     name: String,
     tracks: Vec<Track>,
     time_position: f32,
-    #[visit(optional)]
     time_slice: Range<f32>,
     speed: f32,
     looped: bool,
     enabled: bool,
     signals: Vec<AnimationSignal>,
 
-    #[visit(optional)]
     root_motion_settings: Option<RootMotionSettings>,
 
+    // A source of tracks and signals of the animation. See [`Animation::set_resource`] docs.
+    resource: Option<AnimationResource>,
+
     #[reflect(hidden)]
-    #[visit(skip)]
     root_motion: Option<RootMotion>,
 
     // Non-serialized
     #[reflect(hidden)]
-    #[visit(skip)]
     pose: AnimationPose,
     // Non-serialized
     #[reflect(hidden)]
-    #[visit(skip)]
     events: VecDeque<AnimationEvent>,
+    // Non-serialized. Revision of the resource from which the tracks were taken the last time.
+    #[reflect(hidden)]
+    synced_revision: Uuid,
+}
+
+// Optional regions could be missing in data of older versions, but errors on write must not be hidden.
+fn visit_optional<T: Visit>(value: &mut T, name: &str, visitor: &mut Visitor) -> VisitResult {
+    let result = value.visit(name, visitor);
+    if visitor.is_reading() {
+        Ok(())
+    } else {
+        result
+    }
+}
+
+impl Visit for Animation {
+    fn visit(&mut self, name: &str, visitor: &mut Visitor) -> VisitResult {
+        let mut region = visitor.enter_region(name)?;
+
+        visit_optional(&mut self.name, "Name", &mut region)?;
+        visit_optional(&mut self.resource, "Resource", &mut region)?;
+
+        // Tracks and signals of resource-backed animations are taken from the resource on the first update, there
+        // is no need to store them.
+        if self.resource.is_none() {
+            self.tracks.visit("Tracks", &mut region)?;
+            self.signals.visit("Signals", &mut region)?;
+        }
+
+        self.time_position.visit("TimePosition", &mut region)?;
+        visit_optional(&mut self.time_slice, "TimeSlice", &mut region)?;
+        self.speed.visit("Speed", &mut region)?;
+        self.looped.visit("Looped", &mut region)?;
+        self.enabled.visit("Enabled", &mut region)?;
+        visit_optional(
+            &mut self.root_motion_settings,
+            "RootMotionSettings",
+            &mut region,
+        )?;
+
+        Ok(())
+    }
 }
 
 /// Root motion settings. It allows you to set a node (root) from which the motion will be taken
@@ -266,6 +308,8 @@ impl Clone for Animation {
             events: Default::default(),
             time_slice: self.time_slice.clone(),
             root_motion: self.root_motion.clone(),
+            resource: self.resource.clone(),
+            synced_revision: self.synced_revision,
         }
     }
 }
@@ -281,6 +325,54 @@ impl Animation {
         self.name.as_ref()
     }
 
+    /// Sets an animation resource that will be used as a source of tracks, signals and time slice of the animation.
+    /// Tracks of the resource are bound to the nodes by their names, see [`Self::sync_with_resource`] for more info.
+    /// Animation player does the synchronization automatically. Resource-backed animations do not store their tracks
+    /// and signals in a scene, so any manual changes of them will be lost on save. Settings that are specific to a
+    /// particular usage of the clip (speed, looping, enabled, root motion settings, etc.) are still stored in the
+    /// animation itself.
+    pub fn set_resource(&mut self, resource: Option<AnimationResource>) {
+        self.resource = resource;
+        // Force synchronization on next update.
+        self.synced_revision = Uuid::nil();
+    }
+
+    /// Returns a reference to the animation resource that is used as a source of tracks (if any).
+    pub fn resource(&self) -> Option<&AnimationResource> {
+        self.resource.as_ref()
+    }
+
+    /// Copies tracks, signals and time slice from the animation resource (if any) to the animation. Synchronization
+    /// is performed only if the resource is fully loaded and was changed (loaded, reloaded or replaced) since the
+    /// last synchronization, so it is cheap to call this method every frame. `find_node` is used to find a node by
+    /// its name; tracks with unknown target nodes will have [`Handle::NONE`] target and will be ignored. Returns `true`
+    /// if the tracks were updated, `false` - otherwise.
+    pub fn sync_with_resource<F>(&mut self, find_node: F) -> bool
+    where
+        F: FnMut(&str) -> Handle<Node>,
+    {
+        let instance = match self.resource.as_ref() {
+            Some(resource) => match *resource.state() {
+                ResourceState::Ok(ref data) if data.revision != self.synced_revision => {
+                    self.synced_revision = data.revision;
+                    data.instantiate(find_node)
+                }
+                _ => return false,
+            },
+            None => return false,
+        };
+
+        self.tracks = instance.tracks;
+        self.signals = instance.signals;
+        self.time_slice = instance.time_slice;
+        self.time_position = self.time_position.clamp(
+            self.time_slice.start,
+            self.time_slice.end.max(self.time_slice.start),
+        );
+
+        true
+    }
+
     /// Adds new track to the animation. Animation can have unlimited number of tracks, each track is responsible
     /// for animation of a single scene node.
     pub fn add_track(&mut self, track: Track) {
@@ -796,6 +888,8 @@ impl Default for Animation {
             events: Default::default(),
             time_slice: Default::default(),
             root_motion: None,
+            resource: None,
+            synced_revision: Default::default(),
         }
     }
 }
@@ -929,6 +1023,17 @@ impl AnimationContainer {
         utils::find_by_name_mut(self.pool.pair_iter_mut(), name)
     }
 
+    /// Tries to find an animation that uses the given animation resource as a source of its tracks.
+    #[inline]
+    pub fn find_by_resource(
+        &self,
+        resource: &AnimationResource,
+    ) -> Option<(Handle<Animation>, &Animation)> {
+        self.pool
+            .pair_iter()
+            .find(|(_, animation)| animation.resource.as_ref() == Some(resource))
+    }
+
     /// Removes every animation from the container that does not satisfy a particular condition represented by the given
     /// closue.
     #[inline]
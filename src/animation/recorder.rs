@@ -0,0 +1,337 @@
+//! Animation recorder samples properties of scene nodes over time and bakes them into an animation. See
+//! [`AnimationRecorder`] docs for more info.
+
+use crate::{
+    animation::{
+        container::{TrackDataContainer, TrackValueKind},
+        track::Track,
+        value::{TrackValue, ValueBinding},
+        Animation,
+    },
+    core::{
+        algebra::Vector3,
+        curve::{Curve, CurveKey, CurveKeyKind},
+        math::lerpf,
+        pool::Handle,
+        reflect::prelude::*,
+    },
+    scene::{graph::Graph, node::Node},
+};
+use std::f32::consts::{PI, TAU};
+
+struct RecordedTrack {
+    target: Handle<Node>,
+    binding: ValueBinding,
+    kind: Option<TrackValueKind>,
+    // Each sample is a time position and a set of values for each curve of the track.
+    samples: Vec<(f32, Vec<f32>)>,
+}
+
+impl RecordedTrack {
+    fn new(target: Handle<Node>, binding: ValueBinding) -> Self {
+        Self {
+            target,
+            binding,
+            kind: None,
+            samples: Default::default(),
+        }
+    }
+
+    fn sample(&self, graph: &Graph) -> Option<TrackValue> {
+        let node = graph.try_get(self.target)?;
+        match self.binding {
+            ValueBinding::Position => {
+                Some(TrackValue::Vector3(**node.local_transform().position()))
+            }
+            ValueBinding::Scale => Some(TrackValue::Vector3(**node.local_transform().scale())),
+            ValueBinding::Rotation => Some(TrackValue::UnitQuaternion(
+                **node.local_transform().rotation(),
+            )),
+            ValueBinding::Property {
+                ref name,
+                value_type,
+            } => {
+                let mut value = None;
+                node.as_reflect(&mut |node| {
+                    node.resolve_path(name, &mut |result| {
+                        if let Ok(property) = result {
+                            value = TrackValue::from_reflect(property, value_type);
+                        }
+                    })
+                });
+                value
+            }
+        }
+    }
+
+    fn push(&mut self, time: f32, value: TrackValue) {
+        let (kind, components) = match value {
+            TrackValue::Real(v) => (TrackValueKind::Real, vec![v]),
+            TrackValue::Vector2(v) => (TrackValueKind::Vector2, vec![v.x, v.y]),
+            TrackValue::Vector3(v) => (TrackValueKind::Vector3, vec![v.x, v.y, v.z]),
+            TrackValue::Vector4(v) => (TrackValueKind::Vector4, vec![v.x, v.y, v.z, v.w]),
+            TrackValue::UnitQuaternion(q) => {
+                let (x, y, z) = q.euler_angles();
+                let mut angles = Vector3::new(x, y, z);
+
+                // Euler angles are wrapped to [-pi; pi] range, this produces huge jumps in the curves when
+                // an angle crosses the boundary. Unwrap each angle relative to the previous sample, so the
+                // curves will be continuous.
+                if let Some((_, prev)) = self.samples.last() {
+                    for (angle, prev) in angles.iter_mut().zip(prev.iter()) {
+                        *angle += ((*prev - *angle + PI) / TAU).floor() * TAU;
+                    }
+                }
+
+                (
+                    TrackValueKind::UnitQuaternion,
+                    vec![angles.x, angles.y, angles.z],
+                )
+            }
+        };
+
+        match self.kind {
+            Some(existing_kind) if existing_kind != kind => {
+                // The type of the property has changed, ignore such samples.
+                return;
+            }
+            _ => self.kind = Some(kind),
+        }
+
+        self.samples.push((time, components));
+    }
+
+    fn build(&self, tolerance: f32) -> Option<Track> {
+        let kind = self.kind?;
+
+        let mut container = TrackDataContainer::new(kind);
+        for (component, curve) in container.curves_mut().iter_mut().enumerate() {
+            let points = self
+                .samples
+                .iter()
+                .map(|(time, values)| (*time, values[component]))
+                .collect::<Vec<_>>();
+
+            let mut new_curve = Curve::from(
+                reduce_keys(&points, tolerance)
+                    .into_iter()
+                    .map(|(time, value)| CurveKey::new(time, value, CurveKeyKind::Linear))
+                    .collect::<Vec<_>>(),
+            );
+            new_curve.set_name(curve.name());
+            new_curve.set_id(curve.id());
+            *curve = new_curve;
+        }
+
+        let mut track = Track::new(container, self.binding.clone());
+        track.set_target(self.target);
+        Some(track)
+    }
+}
+
+/// Removes every point that could be restored with the given tolerance by linear interpolation between its
+/// neighbours. First and last points are always kept.
+fn reduce_keys(points: &[(f32, f32)], tolerance: f32) -> Vec<(f32, f32)> {
+    if points.len() <= 2 {
+        return points.to_vec();
+    }
+
+    let mut result = vec![points[0]];
+    let mut anchor = 0;
+    for candidate in 2..points.len() {
+        let (t0, v0) = points[anchor];
+        let (t1, v1) = points[candidate];
+
+        // Check whether every point between the anchor and the candidate could be restored by linear
+        // interpolation. If not - the point right before the candidate must be kept.
+        let representable = points[(anchor + 1)..candidate].iter().all(|(t, v)| {
+            let k = if t1 > t0 { (t - t0) / (t1 - t0) } else { 0.0 };
+            (lerpf(v0, v1, k) - v).abs() <= tolerance
+        });
+
+        if !representable {
+            anchor = candidate - 1;
+            result.push(points[anchor]);
+        }
+    }
+    result.push(*points.last().unwrap());
+
+    result
+}
+
+/// Animation recorder samples properties of scene nodes over time and bakes them into an [`Animation`]. It
+/// could be used to bake any motion that is produced by physics (ragdolls, destruction) or scripts (cutscenes)
+/// into a regular animation, that could then be edited in the animation editor and played using animation
+/// player.
+///
+/// The recorder works with the same bindings as animation tracks (see [`ValueBinding`]), which means that it
+/// could record local position, rotation, scale and any numeric property accessible via reflection.
+///
+/// Every sample becomes a linear key on a respective curve. To keep the output compact, the recorder performs
+/// key reduction when baking: every key that could be restored with the given tolerance by interpolating its
+/// neighbours is removed.
+///
+/// # Example
+///
+/// ```rust
+/// use fyrox::{
+///     animation::{recorder::AnimationRecorder, Animation},
+///     core::pool::Handle,
+///     scene::{graph::Graph, node::Node},
+/// };
+///
+/// fn bake(graph: &mut Graph, ragdoll_bones: &[Handle<Node>]) -> Animation {
+///     let mut recorder = AnimationRecorder::new().with_tolerance(0.001);
+///     for bone in ragdoll_bones {
+///         recorder.add_node(*bone);
+///     }
+///
+///     let dt = 1.0 / 60.0;
+///     for _ in 0..120 {
+///         // Update the scene here, so physics will move the bones.
+///         recorder.record(graph, dt);
+///     }
+///
+///     recorder.bake()
+/// }
+/// ```
+pub struct AnimationRecorder {
+    tracks: Vec<RecordedTrack>,
+    time: f32,
+    tolerance: f32,
+}
+
+impl Default for AnimationRecorder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AnimationRecorder {
+    /// Creates new animation recorder without any recorded bindings.
+    pub fn new() -> Self {
+        Self {
+            tracks: Default::default(),
+            time: 0.0,
+            tolerance: 0.0001,
+        }
+    }
+
+    /// Sets maximum allowed deviation of baked curves from the recorded values. Larger values produce fewer
+    /// keys, zero tolerance keeps every key except ones that lie exactly on a line.
+    pub fn with_tolerance(mut self, tolerance: f32) -> Self {
+        self.set_tolerance(tolerance);
+        self
+    }
+
+    /// Sets maximum allowed deviation of baked curves from the recorded values.
+    pub fn set_tolerance(&mut self, tolerance: f32) {
+        self.tolerance = tolerance.max(0.0);
+    }
+
+    /// Returns maximum allowed deviation of baked curves from the recorded values.
+    pub fn tolerance(&self) -> f32 {
+        self.tolerance
+    }
+
+    /// Adds position, rotation and scale bindings of the given node.
+    pub fn add_node(&mut self, node: Handle<Node>) {
+        self.add_binding(node, ValueBinding::Position);
+        self.add_binding(node, ValueBinding::Rotation);
+        self.add_binding(node, ValueBinding::Scale);
+    }
+
+    /// Adds a binding of the given node to record. Duplicate bindings are ignored.
+    pub fn add_binding(&mut self, node: Handle<Node>, binding: ValueBinding) {
+        if !self
+            .tracks
+            .iter()
+            .any(|t| t.target == node && t.binding == binding)
+        {
+            self.tracks.push(RecordedTrack::new(node, binding));
+        }
+    }
+
+    /// Returns total amount of recorded time in seconds.
+    pub fn time(&self) -> f32 {
+        self.time
+    }
+
+    /// Samples every binding at current time position and then advances time position by `dt`. Bindings that
+    /// cannot be sampled (deleted nodes, invalid property paths, type mismatches) are skipped.
+    pub fn record(&mut self, graph: &Graph, dt: f32) {
+        for track in self.tracks.iter_mut() {
+            if let Some(value) = track.sample(graph) {
+                track.push(self.time, value);
+            }
+        }
+
+        self.time += dt;
+    }
+
+    /// Discards every recorded sample and rewinds time position to the beginning. Bindings remain untouched.
+    pub fn reset(&mut self) {
+        for track in self.tracks.iter_mut() {
+            track.samples.clear();
+            track.kind = None;
+        }
+        self.time = 0.0;
+    }
+
+    /// Creates a new animation from the recorded samples. Every binding with at least one sample produces a
+    /// separate track. The recorder could be used further after baking, new samples will be appended to the
+    /// existing ones.
+    pub fn bake(&self) -> Animation {
+        let mut animation = Animation::default();
+        for track in self.tracks.iter() {
+            if let Some(track) = track.build(self.tolerance) {
+                animation.add_track(track);
+            }
+        }
+        animation.fit_length_to_content();
+        animation
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        animation::recorder::{reduce_keys, AnimationRecorder},
+        core::algebra::Vector3,
+        scene::{base::BaseBuilder, graph::Graph, pivot::PivotBuilder},
+    };
+
+    #[test]
+    fn test_key_reduction() {
+        let points = [(0.0, 0.0), (1.0, 1.0), (2.0, 2.0), (3.0, 1.0), (4.0, 0.0)];
+        assert_eq!(
+            reduce_keys(&points, 0.001),
+            vec![(0.0, 0.0), (2.0, 2.0), (4.0, 0.0)]
+        );
+    }
+
+    #[test]
+    fn test_record_position() {
+        let mut graph = Graph::new();
+        let node = PivotBuilder::new(BaseBuilder::new()).build(&mut graph);
+
+        let mut recorder = AnimationRecorder::new();
+        recorder.add_node(node);
+
+        for i in 0..10 {
+            graph[node]
+                .local_transform_mut()
+                .set_position(Vector3::new(i as f32, 0.0, 0.0));
+            recorder.record(&graph, 0.1);
+        }
+
+        let animation = recorder.bake();
+        assert_eq!(animation.tracks().len(), 3);
+
+        let position = &animation.tracks()[0];
+        assert_eq!(position.target(), node);
+        // Linear motion must be reduced to two keys.
+        assert_eq!(position.data_container().curves_ref()[0].keys().len(), 2);
+        assert!((animation.length() - 0.9).abs() < 0.001);
+    }
+}
@@ -0,0 +1,567 @@
+//! Sprite sheet animation import from JSON files produced by external tools - Aseprite and TexturePacker. See
+//! [`ImportedSpriteSheet`] docs for more info.
+
+use crate::{
+    animation::spritesheet::{
+        signal::Signal, FrameParameters, SpriteSheetAnimation, SpriteSheetFramesContainer,
+    },
+    core::{
+        algebra::Vector2,
+        io::{self, FileLoadError},
+        math::Rect,
+    },
+    resource::texture::Texture,
+};
+use serde::Deserialize;
+use std::{
+    fmt::{Display, Formatter},
+    path::{Path, PathBuf},
+};
+
+/// An error that may occur during sprite sheet import.
+#[derive(Debug)]
+pub enum SpriteSheetImportError {
+    /// An i/o error has occurred.
+    Io(FileLoadError),
+
+    /// The file is not a valid JSON or has unexpected structure.
+    Json(serde_json::Error),
+
+    /// A frame tag refers to a frame that does not exist.
+    InvalidFrameTag(String),
+
+    /// A frame is rotated in the image. Rotated frames are not supported, disable rotation in the export settings.
+    RotatedFrame(String),
+
+    /// A frame is trimmed. Trimmed frames are not supported, disable trimming in the export settings.
+    TrimmedFrame(String),
+}
+
+impl Display for SpriteSheetImportError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SpriteSheetImportError::Io(v) => {
+                write!(f, "A file load error has occurred {v:?}")
+            }
+            SpriteSheetImportError::Json(v) => {
+                write!(f, "Unable to parse sprite sheet description. Reason: {v}")
+            }
+            SpriteSheetImportError::InvalidFrameTag(v) => {
+                write!(f, "Frame tag {v} refers to a frame that does not exist!")
+            }
+            SpriteSheetImportError::RotatedFrame(v) => {
+                write!(f, "Frame {v} is rotated, rotated frames are not supported!")
+            }
+            SpriteSheetImportError::TrimmedFrame(v) => {
+                write!(f, "Frame {v} is trimmed, trimmed frames are not supported!")
+            }
+        }
+    }
+}
+
+impl From<FileLoadError> for SpriteSheetImportError {
+    fn from(e: FileLoadError) -> Self {
+        Self::Io(e)
+    }
+}
+
+impl From<serde_json::Error> for SpriteSheetImportError {
+    fn from(e: serde_json::Error) -> Self {
+        Self::Json(e)
+    }
+}
+
+#[derive(Deserialize, Default, Copy, Clone)]
+struct JsonRect {
+    x: u32,
+    y: u32,
+    w: u32,
+    h: u32,
+}
+
+#[derive(Deserialize, Default, Copy, Clone)]
+struct JsonSize {
+    w: u32,
+    h: u32,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct JsonFrame {
+    #[serde(default)]
+    filename: String,
+    frame: JsonRect,
+    #[serde(default)]
+    rotated: bool,
+    #[serde(default)]
+    sprite_source_size: Option<JsonRect>,
+    #[serde(default)]
+    source_size: Option<JsonSize>,
+    #[serde(default)]
+    duration: u32,
+}
+
+#[derive(Deserialize)]
+struct JsonFrameTag {
+    name: String,
+    from: usize,
+    to: usize,
+    #[serde(default)]
+    direction: String,
+    #[serde(default)]
+    data: String,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+struct JsonMeta {
+    #[serde(default)]
+    app: String,
+    #[serde(default)]
+    image: String,
+    #[serde(default)]
+    size: Option<JsonSize>,
+    #[serde(default)]
+    frame_tags: Vec<JsonFrameTag>,
+}
+
+#[derive(Deserialize)]
+struct JsonAtlas {
+    // Could be either an array of frames or a map `filename -> frame`.
+    frames: serde_json::Value,
+    #[serde(default)]
+    meta: JsonMeta,
+}
+
+impl JsonAtlas {
+    fn parse(json: &str) -> Result<(Self, Vec<JsonFrame>), SpriteSheetImportError> {
+        let mut atlas = serde_json::from_str::<JsonAtlas>(json)?;
+
+        let frames = match atlas.frames.take() {
+            serde_json::Value::Object(map) => map
+                .into_iter()
+                .map(|(name, value)| {
+                    serde_json::from_value::<JsonFrame>(value).map(|mut frame| {
+                        frame.filename = name;
+                        frame
+                    })
+                })
+                .collect::<Result<Vec<_>, _>>()?,
+            value => serde_json::from_value::<Vec<JsonFrame>>(value)?,
+        };
+
+        Ok((atlas, frames))
+    }
+
+    fn image_size(&self, frames: &[JsonFrame]) -> Vector2<u32> {
+        match self.meta.size {
+            Some(size) => Vector2::new(size.w, size.h),
+            None => frames.iter().fold(Vector2::new(0, 0), |size, frame| {
+                Vector2::new(
+                    size.x.max(frame.frame.x + frame.frame.w),
+                    size.y.max(frame.frame.y + frame.frame.h),
+                )
+            }),
+        }
+    }
+}
+
+fn frame_parameters(frame: &JsonFrame) -> Result<FrameParameters, SpriteSheetImportError> {
+    let JsonRect { x, y, w, h } = frame.frame;
+
+    // Texture coordinates of a frame are described by a rectangle, which cannot express rotation. Trimmed
+    // frames would require to offset and resize every sprite that uses the animation.
+    if frame.rotated {
+        return Err(SpriteSheetImportError::RotatedFrame(frame.filename.clone()));
+    }
+
+    let source_size = frame.source_size.unwrap_or(JsonSize { w, h });
+    let sprite_source_size = frame
+        .sprite_source_size
+        .unwrap_or(JsonRect { x: 0, y: 0, w, h });
+    if source_size.w != w
+        || source_size.h != h
+        || sprite_source_size.x != 0
+        || sprite_source_size.y != 0
+        || sprite_source_size.w != w
+        || sprite_source_size.h != h
+    {
+        return Err(SpriteSheetImportError::TrimmedFrame(frame.filename.clone()));
+    }
+
+    Ok(FrameParameters {
+        bounds: Rect::new(x, y, w, h),
+        duration: frame.duration,
+    })
+}
+
+fn make_animation(
+    frames: impl Iterator<Item = FrameParameters>,
+    image_size: Vector2<u32>,
+) -> SpriteSheetAnimation {
+    let mut container = SpriteSheetFramesContainer::default();
+    container.set_image_size(image_size);
+    for (i, parameters) in frames.enumerate() {
+        container.push_with_parameters(Vector2::new(i as u32, 0), parameters);
+    }
+    container.set_size(Vector2::new(container.len() as u32, 1));
+
+    let mut animation = SpriteSheetAnimation::with_container(container);
+    if animation
+        .frames()
+        .frame_parameters
+        .iter()
+        .any(|p| p.duration > 0)
+    {
+        // Durations define the actual frame rate, the speed is just a multiplier.
+        animation.set_speed(1.0);
+    }
+    animation
+}
+
+// Strips extension and trailing frame number from a file name: `run_01.png` -> `run`.
+fn animation_name(filename: &str) -> &str {
+    let stem = Path::new(filename)
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .unwrap_or(filename);
+    let name = stem.trim_end_matches(|c: char| c.is_ascii_digit());
+    let name = name.trim_end_matches(|c: char| c == '_' || c == '-' || c == ' ' || c == '.');
+    if name.is_empty() {
+        stem
+    } else {
+        name
+    }
+}
+
+// Parses signal id from tag user data: `signal` or `signal=<id>`.
+fn parse_signal(data: &str) -> Option<Option<u64>> {
+    let data = data.trim();
+    if data == "signal" {
+        Some(None)
+    } else {
+        data.strip_prefix("signal=")
+            .map(|id| id.trim().parse::<u64>().ok())
+    }
+}
+
+/// A result of sprite sheet import. It contains a set of named animations, that share the same image, and a set of
+/// named signals.
+///
+/// # Aseprite
+///
+/// Use `File -> Export Sprite Sheet` with `JSON Data` option enabled (both `Hash` and `Array` formats are supported)
+/// and `Tags` checkbox enabled in the `Meta` section. Every frame tag produces a separate animation (`forward`,
+/// `reverse` and `pingpong` directions are supported), frames without tags produce a single animation named
+/// `Default`. Per-frame durations are preserved (see [`FrameParameters::duration`]).
+///
+/// A frame tag with `signal` or `signal=<id>` user data does not produce an animation, instead it adds a signal to
+/// every animation that contains the first frame of the tag. The name of the tag becomes the name of the signal.
+/// Signals without explicit id get ids that are greater than any explicit id in the file.
+///
+/// # TexturePacker
+///
+/// Use `JSON (Hash)` or `JSON (Array)` data format. Frames are grouped into animations by their file names without
+/// extensions and trailing numbers, for example `run_01.png` and `run_02.png` will be put in `run` animation.
+///
+/// # Limitations
+///
+/// Rotated and trimmed frames are not supported, the import fails with [`SpriteSheetImportError::RotatedFrame`]
+/// or [`SpriteSheetImportError::TrimmedFrame`] errors respectively. Disable rotation and trimming in the export
+/// settings of the tool.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// # use fyrox::{
+/// #     animation::spritesheet::import::ImportedSpriteSheet,
+/// #     engine::resource_manager::ResourceManager,
+/// # };
+/// async fn import(resource_manager: ResourceManager) {
+///     let mut sprite_sheet = ImportedSpriteSheet::from_file("data/hero.json")
+///         .await
+///         .unwrap();
+///
+///     sprite_sheet.set_texture(Some(resource_manager.request_texture(&sprite_sheet.image)));
+///
+///     let run = sprite_sheet.find("run").unwrap();
+/// }
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct ImportedSpriteSheet {
+    /// A path of the image that contains every frame. The path is relative to the directory of the imported file.
+    pub image: PathBuf,
+
+    /// Size of the image in pixels.
+    pub image_size: Vector2<u32>,
+
+    /// A set of named animations.
+    pub animations: Vec<(String, SpriteSheetAnimation)>,
+
+    /// A set of named signals (name -> id). Every animation could have any of these signals.
+    pub signals: Vec<(String, u64)>,
+}
+
+impl ImportedSpriteSheet {
+    /// Loads a JSON file and imports sprite sheet animations from it. The format of the file is detected
+    /// automatically. The path of the image will be resolved relative to the directory of the file.
+    pub async fn from_file<P: AsRef<Path>>(path: P) -> Result<Self, SpriteSheetImportError> {
+        let path = path.as_ref();
+        let data = io::load_file(path).await?;
+        let json = String::from_utf8_lossy(&data);
+
+        let mut sprite_sheet = Self::from_json(&json)?;
+        if let Some(directory) = path.parent() {
+            sprite_sheet.image = directory.join(&sprite_sheet.image);
+        }
+        Ok(sprite_sheet)
+    }
+
+    /// Imports sprite sheet animations from a JSON string. The format of the data is detected automatically.
+    pub fn from_json(json: &str) -> Result<Self, SpriteSheetImportError> {
+        let (atlas, frames) = JsonAtlas::parse(json)?;
+        if atlas.meta.app.to_lowercase().contains("aseprite") {
+            Self::from_aseprite_atlas(atlas, frames)
+        } else {
+            Self::from_texture_packer_atlas(atlas, frames)
+        }
+    }
+
+    /// Imports sprite sheet animations from a JSON string exported by Aseprite.
+    pub fn from_aseprite_json(json: &str) -> Result<Self, SpriteSheetImportError> {
+        let (atlas, frames) = JsonAtlas::parse(json)?;
+        Self::from_aseprite_atlas(atlas, frames)
+    }
+
+    /// Imports sprite sheet animations from a JSON string exported by TexturePacker.
+    pub fn from_texture_packer_json(json: &str) -> Result<Self, SpriteSheetImportError> {
+        let (atlas, frames) = JsonAtlas::parse(json)?;
+        Self::from_texture_packer_atlas(atlas, frames)
+    }
+
+    fn from_aseprite_atlas(
+        atlas: JsonAtlas,
+        frames: Vec<JsonFrame>,
+    ) -> Result<Self, SpriteSheetImportError> {
+        let image_size = atlas.image_size(&frames);
+        let parameters = frames
+            .iter()
+            .map(frame_parameters)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        // Ids of signals without explicit id must not collide with explicit ids.
+        let mut next_signal_id = atlas
+            .meta
+            .frame_tags
+            .iter()
+            .filter_map(|tag| parse_signal(&tag.data).flatten())
+            .max()
+            .map_or(0, |id| id + 1);
+
+        let mut signals = Vec::new();
+        let mut signal_frames = Vec::new();
+        let mut animations = Vec::new();
+
+        for tag in atlas.meta.frame_tags.iter() {
+            if tag.from > tag.to || tag.to >= parameters.len() {
+                return Err(SpriteSheetImportError::InvalidFrameTag(tag.name.clone()));
+            }
+
+            if let Some(id) = parse_signal(&tag.data) {
+                let id = id.unwrap_or_else(|| {
+                    next_signal_id += 1;
+                    next_signal_id - 1
+                });
+                signals.push((tag.name.clone(), id));
+                signal_frames.push((id, tag.from));
+                continue;
+            }
+
+            let range = tag.from..=tag.to;
+            let indices = match tag.direction.as_str() {
+                "reverse" => range.rev().collect::<Vec<_>>(),
+                "pingpong" => range
+                    .clone()
+                    .chain(
+                        range
+                            .rev()
+                            .skip(1)
+                            .take((tag.to - tag.from).saturating_sub(1)),
+                    )
+                    .collect::<Vec<_>>(),
+                _ => range.collect::<Vec<_>>(),
+            };
+
+            animations.push((tag.name.clone(), indices));
+        }
+
+        if animations.is_empty() {
+            animations.push(("Default".to_string(), (0..parameters.len()).collect()));
+        }
+
+        Ok(Self {
+            image: PathBuf::from(&atlas.meta.image),
+            image_size,
+            animations: animations
+                .into_iter()
+                .map(|(name, indices)| {
+                    let mut animation =
+                        make_animation(indices.iter().map(|i| parameters[*i]), image_size);
+
+                    for (id, signal_frame) in signal_frames.iter() {
+                        if let Some(frame) = indices.iter().position(|i| i == signal_frame) {
+                            animation.add_signal(Signal {
+                                id: *id,
+                                frame: frame as u32,
+                                enabled: true,
+                            });
+                        }
+                    }
+
+                    (name, animation)
+                })
+                .collect(),
+            signals,
+        })
+    }
+
+    fn from_texture_packer_atlas(
+        atlas: JsonAtlas,
+        frames: Vec<JsonFrame>,
+    ) -> Result<Self, SpriteSheetImportError> {
+        let image_size = atlas.image_size(&frames);
+
+        let mut groups: Vec<(String, Vec<FrameParameters>)> = Vec::new();
+        for frame in frames.iter() {
+            let name = animation_name(&frame.filename);
+            let parameters = frame_parameters(frame)?;
+            match groups.iter_mut().find(|(group, _)| group == name) {
+                Some((_, group_frames)) => group_frames.push(parameters),
+                None => groups.push((name.to_string(), vec![parameters])),
+            }
+        }
+
+        Ok(Self {
+            image: PathBuf::from(&atlas.meta.image),
+            image_size,
+            animations: groups
+                .into_iter()
+                .map(|(name, frames)| (name, make_animation(frames.into_iter(), image_size)))
+                .collect(),
+            signals: Default::default(),
+        })
+    }
+
+    /// Tries to find an animation by its name.
+    pub fn find(&self, name: &str) -> Option<&SpriteSheetAnimation> {
+        self.animations
+            .iter()
+            .find_map(|(n, animation)| if n == name { Some(animation) } else { None })
+    }
+
+    /// Tries to find a signal id by its name.
+    pub fn find_signal(&self, name: &str) -> Option<u64> {
+        self.signals
+            .iter()
+            .find_map(|(n, id)| if n == name { Some(*id) } else { None })
+    }
+
+    /// Sets the texture for every animation.
+    pub fn set_texture(&mut self, texture: Option<Texture>) {
+        for (_, animation) in self.animations.iter_mut() {
+            animation.set_texture(texture.clone());
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        animation::spritesheet::import::{ImportedSpriteSheet, SpriteSheetImportError},
+        core::math::Rect,
+    };
+
+    const ASEPRITE_JSON: &str = r#"{
+        "frames": {
+            "hero 0.aseprite": { "frame": { "x": 0, "y": 0, "w": 16, "h": 16 }, "rotated": false, "trimmed": false,
+                "spriteSourceSize": { "x": 0, "y": 0, "w": 16, "h": 16 }, "sourceSize": { "w": 16, "h": 16 }, "duration": 100 },
+            "hero 1.aseprite": { "frame": { "x": 16, "y": 0, "w": 16, "h": 16 }, "rotated": false, "trimmed": false,
+                "spriteSourceSize": { "x": 0, "y": 0, "w": 16, "h": 16 }, "sourceSize": { "w": 16, "h": 16 }, "duration": 200 },
+            "hero 2.aseprite": { "frame": { "x": 32, "y": 0, "w": 16, "h": 16 }, "rotated": false, "trimmed": false,
+                "spriteSourceSize": { "x": 0, "y": 0, "w": 16, "h": 16 }, "sourceSize": { "w": 16, "h": 16 }, "duration": 100 }
+        },
+        "meta": {
+            "app": "http://www.aseprite.org/",
+            "image": "hero.png",
+            "size": { "w": 64, "h": 16 },
+            "frameTags": [
+                { "name": "run", "from": 0, "to": 2, "direction": "forward" },
+                { "name": "idle", "from": 2, "to": 2, "direction": "forward" },
+                { "name": "land", "from": 0, "to": 0, "direction": "forward", "data": "signal" },
+                { "name": "step", "from": 1, "to": 1, "direction": "forward", "data": "signal=7" }
+            ]
+        }
+    }"#;
+
+    #[test]
+    fn test_aseprite_import() {
+        let sprite_sheet = ImportedSpriteSheet::from_json(ASEPRITE_JSON).unwrap();
+
+        assert_eq!(sprite_sheet.animations.len(), 2);
+        assert_eq!(sprite_sheet.find_signal("step"), Some(7));
+        assert_eq!(sprite_sheet.find_signal("land"), Some(8));
+
+        let run = sprite_sheet.find("run").unwrap();
+        assert_eq!(run.frames().len(), 3);
+        assert_eq!(run.frames().frame_parameters(1).unwrap().duration, 200);
+        assert_eq!(run.frame_uv_rect(1), Some(Rect::new(0.25, 0.0, 0.25, 1.0)));
+
+        let idle = sprite_sheet.find("idle").unwrap();
+        assert_eq!(idle.frames().len(), 1);
+    }
+
+    #[test]
+    fn test_texture_packer_import() {
+        let json = r#"{
+            "frames": [
+                { "filename": "run_01.png", "frame": { "x": 0, "y": 0, "w": 16, "h": 32 }, "rotated": false },
+                { "filename": "run_02.png", "frame": { "x": 32, "y": 0, "w": 16, "h": 16 }, "rotated": false },
+                { "filename": "jump_01.png", "frame": { "x": 48, "y": 0, "w": 16, "h": 16 }, "rotated": false }
+            ],
+            "meta": { "app": "https://www.codeandweb.com/texturepacker", "image": "atlas.png", "size": { "w": 64, "h": 64 } }
+        }"#;
+
+        let sprite_sheet = ImportedSpriteSheet::from_json(json).unwrap();
+
+        assert_eq!(sprite_sheet.animations.len(), 2);
+        let run = sprite_sheet.find("run").unwrap();
+        assert_eq!(run.frames().len(), 2);
+
+        assert_eq!(
+            run.frames().frame_parameters(0).unwrap().bounds,
+            Rect::new(0, 0, 16, 32)
+        );
+    }
+
+    #[test]
+    fn test_unsupported_frames() {
+        let rotated = r#"{
+            "frames": [{ "filename": "run_01.png", "frame": { "x": 0, "y": 0, "w": 16, "h": 32 }, "rotated": true }],
+            "meta": { "app": "https://www.codeandweb.com/texturepacker", "image": "atlas.png" }
+        }"#;
+        assert!(matches!(
+            ImportedSpriteSheet::from_json(rotated),
+            Err(SpriteSheetImportError::RotatedFrame(name)) if name == "run_01.png"
+        ));
+
+        let trimmed = r#"{
+            "frames": { "run_01.png": { "frame": { "x": 0, "y": 0, "w": 8, "h": 16 },
+                "spriteSourceSize": { "x": 4, "y": 0, "w": 8, "h": 16 }, "sourceSize": { "w": 16, "h": 16 } } },
+            "meta": { "app": "https://www.codeandweb.com/texturepacker", "image": "atlas.png" }
+        }"#;
+        assert!(matches!(
+            ImportedSpriteSheet::from_json(trimmed),
+            Err(SpriteSheetImportError::TrimmedFrame(name)) if name == "run_01.png"
+        ));
+    }
+}
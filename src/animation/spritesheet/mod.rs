@@ -11,6 +11,7 @@ use crate::{
 use std::collections::vec_deque::VecDeque;
 use strum_macros::{AsRefStr, EnumString, EnumVariantNames};
 
+pub mod import;
 pub mod signal;
 
 /// Animation playback status.
@@ -49,6 +50,19 @@ impl Default for Event {
     }
 }
 
+/// Optional parameters of a frame, that are used when frames are packed into an atlas by an external tool (Aseprite,
+/// TexturePacker, etc.) and cannot be described by a uniform grid.
+#[derive(Reflect, Visit, Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct FrameParameters {
+    /// Bounds of the frame in the image (in pixels). Zero-sized bounds means that the frame position in the grid
+    /// should be used instead.
+    pub bounds: Rect<u32>,
+
+    /// Duration of the frame in milliseconds. Zero means that the animation speed should be used instead. See
+    /// [`SpriteSheetAnimation::set_speed`] docs for more info.
+    pub duration: u32,
+}
+
 /// Container for a sprite sheet animation frames.
 #[derive(Reflect, Visit, Clone, Debug, PartialEq, Eq)]
 pub struct SpriteSheetFramesContainer {
@@ -56,19 +70,57 @@ pub struct SpriteSheetFramesContainer {
     frames: Vec<Vector2<u32>>,
     #[visit(optional)]
     texture: Option<Texture>,
+    // Either empty or has the same length as frames.
+    #[visit(optional)]
+    frame_parameters: Vec<FrameParameters>,
+    #[visit(optional)]
+    image_size: Vector2<u32>,
 }
 
 impl SpriteSheetFramesContainer {
     /// Adds a frame to the container.
     pub fn push(&mut self, bounds: Vector2<u32>) {
+        if !self.frame_parameters.is_empty() {
+            self.frame_parameters.push(Default::default());
+        }
         self.frames.push(bounds)
     }
 
+    /// Adds a frame with explicit parameters to the container. Position of the frame in the grid is used only if
+    /// the bounds of the frame are zero-sized. Do not forget to set image size (see [`Self::set_image_size`]),
+    /// otherwise the bounds will be ignored.
+    pub fn push_with_parameters(&mut self, position: Vector2<u32>, parameters: FrameParameters) {
+        self.frame_parameters
+            .resize(self.frames.len(), Default::default());
+        self.frame_parameters.push(parameters);
+        self.frames.push(position);
+    }
+
     /// Removes a frame from the container.
     pub fn remove(&mut self, index: usize) -> Vector2<u32> {
+        if index < self.frame_parameters.len() {
+            self.frame_parameters.remove(index);
+        }
         self.frames.remove(index)
     }
 
+    /// Tries to get a reference to parameters of a frame with given index. Returns `None` if the frame does not have
+    /// explicit parameters.
+    pub fn frame_parameters(&self, index: usize) -> Option<&FrameParameters> {
+        self.frame_parameters.get(index)
+    }
+
+    /// Sets size of the source image (in pixels). It is used to calculate texture coordinates of frames with
+    /// explicit parameters.
+    pub fn set_image_size(&mut self, image_size: Vector2<u32>) {
+        self.image_size = image_size;
+    }
+
+    /// Returns size of the source image (in pixels).
+    pub fn image_size(&self) -> Vector2<u32> {
+        self.image_size
+    }
+
     /// Returns total amount of frames in the container.
     pub fn len(&self) -> usize {
         self.frames.len()
@@ -123,6 +175,8 @@ impl Default for SpriteSheetFramesContainer {
             size: Vector2::new(1, 1),
             frames: vec![],
             texture: None,
+            frame_parameters: vec![],
+            image_size: Vector2::default(),
         }
     }
 }
@@ -130,8 +184,8 @@ impl Default for SpriteSheetFramesContainer {
 /// Sprite sheet animation is an animation based on key frames, where each key frame is packed into single image. Usually, all key
 /// frames have the same size, but this is not mandatory.
 #[derive(Visit, Reflect, Clone, Debug)]
+#[visit(version = 1)]
 pub struct SpriteSheetAnimation {
-    #[visit(rename = "Frames")]
     frames_container: SpriteSheetFramesContainer,
     current_frame: f32,
     speed: f32,
@@ -161,6 +215,15 @@ impl Default for SpriteSheetAnimation {
     }
 }
 
+/// Registers migrations of the data of sprite sheet animations.
+pub(crate) fn register_migrations() {
+    // Version 1 stores the frames container under its own name.
+    Visitor::register_migration::<SpriteSheetAnimation, _>(0, |visitor| {
+        visitor.rename_region("Frames", "FramesContainer");
+        Ok(())
+    });
+}
+
 /// Sprite sheet source image parameters defines how to interpret an image. It defines size of each frame,
 /// total size of an image, frame range to use, etc.
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -291,6 +354,8 @@ impl SpriteSheetAnimation {
                 frames,
                 size: Vector2::new(width_in_frames, height_in_frames),
                 texture: None,
+                frame_parameters: vec![],
+                image_size: Vector2::new(width, height),
             },
             ..Default::default()
         }
@@ -351,7 +416,15 @@ impl SpriteSheetAnimation {
             return;
         }
 
-        let next_frame = self.current_frame + self.speed * dt;
+        // Frames with explicit duration are played at their own rate, the speed is used as a multiplier.
+        let frame_rate = match self.frames_container.frame_parameters(self.current_frame()) {
+            Some(parameters) if parameters.duration > 0 => {
+                1000.0 / parameters.duration as f32 * self.speed
+            }
+            _ => self.speed,
+        };
+
+        let next_frame = self.current_frame + frame_rate * dt;
 
         for signal in self.signals.iter_mut().filter(|s| s.enabled) {
             let signal_frame = signal.frame as f32;
@@ -398,6 +471,19 @@ impl SpriteSheetAnimation {
         assert_ne!(self.frames_container.size.x, 0);
         assert_ne!(self.frames_container.size.y, 0);
 
+        let image_size = self.frames_container.image_size;
+        if let Some(parameters) = self.frames_container.frame_parameters(i) {
+            let bounds = parameters.bounds;
+            if bounds.size.x > 0 && bounds.size.y > 0 && image_size.x > 0 && image_size.y > 0 {
+                return Some(Rect::new(
+                    bounds.position.x as f32 / image_size.x as f32,
+                    bounds.position.y as f32 / image_size.y as f32,
+                    bounds.size.x as f32 / image_size.x as f32,
+                    bounds.size.y as f32 / image_size.y as f32,
+                ));
+            }
+        }
+
         self.frames_container.get(i).map(|pos| Rect {
             position: Vector2::new(
                 pos.x as f32 / self.frames_container.size.x as f32,
@@ -436,7 +522,9 @@ impl SpriteSheetAnimation {
     }
 
     /// Sets playback speed in frames per second. The speed can be negative, in this case animation
-    /// will play in reverse.
+    /// will play in reverse. Frames with explicit duration (see [`FrameParameters::duration`]) are played
+    /// at their own rate, and the speed is used as a multiplier for it (`1.0` - normal speed, `2.0` - twice
+    /// as fast, etc.).
     pub fn set_speed(&mut self, speed: f32) {
         self.speed = speed;
     }
@@ -506,10 +594,12 @@ impl SpriteSheetAnimation {
 #[cfg(test)]
 mod test {
     use crate::animation::spritesheet::{
-        signal::Signal, Event, ImageParameters, SpriteSheetAnimation, Status,
+        signal::Signal, Event, ImageParameters, SpriteSheetAnimation, SpriteSheetFramesContainer,
+        Status,
     };
     use fyrox_core::algebra::Vector2;
     use fyrox_core::math::Rect;
+    use fyrox_core::visitor::prelude::*;
 
     #[test]
     fn test_sprite_sheet_one_row() {
@@ -712,4 +802,64 @@ mod test {
         // Only two should appear.
         assert_eq!(animation.pop_event(), None);
     }
+
+    #[test]
+    fn test_sprite_sheet_old_data_migration() {
+        crate::engine::register_visit_migrations();
+
+        // Layout of the data before the version was introduced.
+        #[derive(Visit)]
+        struct OldSpriteSheetAnimation {
+            #[visit(rename = "Frames")]
+            frames_container: SpriteSheetFramesContainer,
+            current_frame: f32,
+            speed: f32,
+            status: Status,
+            looping: bool,
+            signals: Vec<Signal>,
+        }
+
+        let mut frames_container = SpriteSheetFramesContainer::default();
+        frames_container.set_size(Vector2::new(2, 1));
+        frames_container.push(Vector2::new(0, 0));
+        frames_container.push(Vector2::new(1, 0));
+
+        let mut old = OldSpriteSheetAnimation {
+            frames_container: frames_container.clone(),
+            current_frame: 1.0,
+            speed: 5.0,
+            status: Status::Playing,
+            looping: false,
+            signals: vec![Signal {
+                id: 1,
+                frame: 1,
+                enabled: true,
+            }],
+        };
+
+        let mut visitor = Visitor::new();
+        old.visit("Animation", &mut visitor).unwrap();
+        let data = visitor.save_binary_to_vec().unwrap();
+
+        let mut visitor = Visitor::load_from_memory(data).unwrap();
+        let mut animation = SpriteSheetAnimation::default();
+        animation.visit("Animation", &mut visitor).unwrap();
+
+        assert_eq!(animation.frames(), &frames_container);
+        assert_eq!(animation.current_frame(), 1);
+        assert_eq!(animation.speed(), 5.0);
+        assert_eq!(animation.status(), Status::Playing);
+        assert!(!animation.is_looping());
+        assert_eq!(animation.signals.len(), 1);
+
+        // Migrated data is saved in the new layout and read as is.
+        let mut visitor = Visitor::new();
+        animation.visit("Animation", &mut visitor).unwrap();
+        let data = visitor.save_binary_to_vec().unwrap();
+
+        let mut visitor = Visitor::load_from_memory(data).unwrap();
+        let mut loaded = SpriteSheetAnimation::default();
+        loaded.visit("Animation", &mut visitor).unwrap();
+        assert_eq!(loaded.frames(), &frames_container);
+    }
 }
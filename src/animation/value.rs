@@ -3,7 +3,7 @@
 
 use crate::{
     core::{
-        algebra::{UnitQuaternion, Vector2, Vector3, Vector4},
+        algebra::{Scalar, UnitQuaternion, Vector2, Vector3, Vector4},
         math::lerpf,
         num_traits::AsPrimitive,
         reflect::{prelude::*, SetFieldByPathError},
@@ -286,6 +286,137 @@ impl TrackValue {
     }
 }
 
+fn downcast_copy<T: Reflect + Copy>(value: &dyn Reflect) -> Option<T> {
+    let mut result = None;
+    value.downcast_ref::<T>(&mut |v| result = v.copied());
+    result
+}
+
+trait ToReal: Copy {
+    fn to_real(self) -> f32;
+}
+
+impl ToReal for bool {
+    fn to_real(self) -> f32 {
+        if self {
+            1.0
+        } else {
+            0.0
+        }
+    }
+}
+
+macro_rules! impl_to_real {
+    ($($ty:ty),*) => {
+        $(
+            impl ToReal for $ty {
+                fn to_real(self) -> f32 {
+                    self as f32
+                }
+            }
+        )*
+    };
+}
+
+impl_to_real!(f32, f64, u64, i64, u32, i32, u16, i16, u8, i8);
+
+fn real<T: Reflect + ToReal>(value: &dyn Reflect) -> Option<TrackValue> {
+    downcast_copy::<T>(value).map(|v| TrackValue::Real(v.to_real()))
+}
+
+fn vec2<T: Reflect + ToReal + Scalar + Copy>(value: &dyn Reflect) -> Option<TrackValue>
+where
+    Vector2<T>: Reflect + Copy,
+{
+    downcast_copy::<Vector2<T>>(value)
+        .map(|v| TrackValue::Vector2(Vector2::new(v.x.to_real(), v.y.to_real())))
+}
+
+fn vec3<T: Reflect + ToReal + Scalar + Copy>(value: &dyn Reflect) -> Option<TrackValue>
+where
+    Vector3<T>: Reflect + Copy,
+{
+    downcast_copy::<Vector3<T>>(value)
+        .map(|v| TrackValue::Vector3(Vector3::new(v.x.to_real(), v.y.to_real(), v.z.to_real())))
+}
+
+fn vec4<T: Reflect + ToReal + Scalar + Copy>(value: &dyn Reflect) -> Option<TrackValue>
+where
+    Vector4<T>: Reflect + Copy,
+{
+    downcast_copy::<Vector4<T>>(value).map(|v| {
+        TrackValue::Vector4(Vector4::new(
+            v.x.to_real(),
+            v.y.to_real(),
+            v.z.to_real(),
+            v.w.to_real(),
+        ))
+    })
+}
+
+impl TrackValue {
+    /// Tries to convert a value of a property to a track value. It is the inverse operation of
+    /// [`Self::numeric_type_cast`]: `value_type` must match the actual type of the property, boolean values are
+    /// converted to `0.0` or `1.0`.
+    pub fn from_reflect(value: &dyn Reflect, value_type: ValueType) -> Option<Self> {
+        match value_type {
+            ValueType::Bool => real::<bool>(value),
+            ValueType::F32 => real::<f32>(value),
+            ValueType::F64 => real::<f64>(value),
+            ValueType::U64 => real::<u64>(value),
+            ValueType::I64 => real::<i64>(value),
+            ValueType::U32 => real::<u32>(value),
+            ValueType::I32 => real::<i32>(value),
+            ValueType::U16 => real::<u16>(value),
+            ValueType::I16 => real::<i16>(value),
+            ValueType::U8 => real::<u8>(value),
+            ValueType::I8 => real::<i8>(value),
+
+            ValueType::Vector2Bool => vec2::<bool>(value),
+            ValueType::Vector2F32 => vec2::<f32>(value),
+            ValueType::Vector2F64 => vec2::<f64>(value),
+            ValueType::Vector2U64 => vec2::<u64>(value),
+            ValueType::Vector2I64 => vec2::<i64>(value),
+            ValueType::Vector2U32 => vec2::<u32>(value),
+            ValueType::Vector2I32 => vec2::<i32>(value),
+            ValueType::Vector2U16 => vec2::<u16>(value),
+            ValueType::Vector2I16 => vec2::<i16>(value),
+            ValueType::Vector2U8 => vec2::<u8>(value),
+            ValueType::Vector2I8 => vec2::<i8>(value),
+
+            ValueType::Vector3Bool => vec3::<bool>(value),
+            ValueType::Vector3F32 => vec3::<f32>(value),
+            ValueType::Vector3F64 => vec3::<f64>(value),
+            ValueType::Vector3U64 => vec3::<u64>(value),
+            ValueType::Vector3I64 => vec3::<i64>(value),
+            ValueType::Vector3U32 => vec3::<u32>(value),
+            ValueType::Vector3I32 => vec3::<i32>(value),
+            ValueType::Vector3U16 => vec3::<u16>(value),
+            ValueType::Vector3I16 => vec3::<i16>(value),
+            ValueType::Vector3U8 => vec3::<u8>(value),
+            ValueType::Vector3I8 => vec3::<i8>(value),
+
+            ValueType::Vector4Bool => vec4::<bool>(value),
+            ValueType::Vector4F32 => vec4::<f32>(value),
+            ValueType::Vector4F64 => vec4::<f64>(value),
+            ValueType::Vector4U64 => vec4::<u64>(value),
+            ValueType::Vector4I64 => vec4::<i64>(value),
+            ValueType::Vector4U32 => vec4::<u32>(value),
+            ValueType::Vector4I32 => vec4::<i32>(value),
+            ValueType::Vector4U16 => vec4::<u16>(value),
+            ValueType::Vector4I16 => vec4::<i16>(value),
+            ValueType::Vector4U8 => vec4::<u8>(value),
+            ValueType::Vector4I8 => vec4::<i8>(value),
+
+            ValueType::UnitQuaternionF32 => {
+                downcast_copy::<UnitQuaternion<f32>>(value).map(TrackValue::UnitQuaternion)
+            }
+            ValueType::UnitQuaternionF64 => downcast_copy::<UnitQuaternion<f64>>(value)
+                .map(|q| TrackValue::UnitQuaternion(q.cast::<f32>())),
+        }
+    }
+}
+
 /// Value binding tells the animation system to which of the many properties to set track's value. It has special
 /// cases for the most used properties and a generic one for arbitrary properties. Arbitrary properties are set using
 /// reflection system, while the special cases handles bindings to standard properties (such as position, scaling, or
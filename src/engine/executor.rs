@@ -34,6 +34,7 @@ pub struct Executor {
     event_loop: EventLoop<()>,
     engine: Engine,
     desired_update_rate: f32,
+    max_frame_time: f32,
     loader: Option<AsyncSceneLoader>,
     headless: bool,
 }
@@ -62,12 +63,38 @@ impl Executor {
     /// Default update rate in frames per second.
     pub const DEFAULT_UPDATE_RATE: f32 = 60.0;
 
+    /// Default maximum amount of time (in seconds) that could be simulated per frame.
+    pub const DEFAULT_MAX_FRAME_TIME: f32 = 0.25;
+
+    /// Path to an archive with packed resources of the game. If the archive exists, it is mounted to
+    /// [`Self::ARCHIVE_MOUNT_POINT`] of the virtual file system automatically.
+    pub const ARCHIVE_PATH: &str = "data.fpk";
+
+    /// Mount point of the archive with packed resources of the game.
+    pub const ARCHIVE_MOUNT_POINT: &str = "data";
+
     /// Creates new game executor using specified set of parameters. Much more flexible version of
     /// [`Executor::new`].
     pub fn from_params(
         event_loop: EventLoop<()>,
         graphics_context_params: GraphicsContextParams,
     ) -> Self {
+        #[cfg(not(target_arch = "wasm32"))]
+        if std::path::Path::new(Self::ARCHIVE_PATH).exists() {
+            match crate::core::vfs::archive::Archive::open(Self::ARCHIVE_PATH) {
+                Ok(archive) => {
+                    crate::core::vfs::VFS
+                        .write()
+                        .mount(Self::ARCHIVE_MOUNT_POINT, archive);
+                }
+                Err(e) => Log::err(format!(
+                    "Unable to open {} archive. Reason: {}",
+                    Self::ARCHIVE_PATH,
+                    e
+                )),
+            }
+        }
+
         let serialization_context = Arc::new(SerializationContext::new());
         let engine = Engine::new(EngineInitParams {
             graphics_context_params,
@@ -80,6 +107,7 @@ impl Executor {
             event_loop,
             engine,
             desired_update_rate: Self::DEFAULT_UPDATE_RATE,
+            max_frame_time: Self::DEFAULT_MAX_FRAME_TIME,
             loader: None,
             headless: false,
         }
@@ -123,6 +151,24 @@ impl Executor {
         self.desired_update_rate
     }
 
+    /// Sets the maximum amount of time (in seconds) that could be simulated per frame. If a frame took more
+    /// time (for example, because of loading a heavy level), the rest of the time will be dropped. It prevents
+    /// the executor from running too many update steps in a row.
+    pub fn set_max_frame_time(&mut self, max_frame_time: f32) {
+        self.max_frame_time = max_frame_time.abs();
+    }
+
+    /// Returns the maximum amount of time (in seconds) that could be simulated per frame.
+    pub fn max_frame_time(&self) -> f32 {
+        self.max_frame_time
+    }
+
+    /// Enables or disables interpolation of node transforms between fixed update steps, see
+    /// [`Engine::set_transform_interpolation`] for more info.
+    pub fn set_transform_interpolation(&mut self, enabled: bool) {
+        self.engine.set_transform_interpolation(enabled)
+    }
+
     /// Adds new plugin constructor to the executor, the plugin will be enabled only on [`Executor::run`].
     pub fn add_plugin_constructor<P>(&mut self, plugin: P)
     where
@@ -153,6 +199,7 @@ impl Executor {
 
         let mut previous = Instant::now();
         let fixed_time_step = 1.0 / self.desired_update_rate;
+        let max_frame_time = self.max_frame_time;
         let mut lag = 0.0;
 
         event_loop.run(move |event, window_target, control_flow| {
@@ -212,15 +259,22 @@ impl Executor {
                         }
                     }
 
-                    let elapsed = previous.elapsed();
+                    // Clamp the elapsed time to prevent "spiral of death", when the engine cannot catch up with
+                    // the real time, because each frame requires more and more update steps.
+                    let elapsed = previous.elapsed().as_secs_f32().min(max_frame_time);
                     previous = Instant::now();
-                    lag += elapsed.as_secs_f32();
+                    lag += elapsed;
 
                     while lag >= fixed_time_step {
                         engine.update(fixed_time_step, control_flow, &mut lag, Default::default());
                         lag -= fixed_time_step;
                     }
 
+                    // Variable-rate part of the frame. `lag` could be modified by plugins, so clamp the
+                    // interpolation factor.
+                    let alpha = (lag / fixed_time_step).clamp(0.0, 1.0);
+                    engine.frame(elapsed, alpha, control_flow, &mut lag);
+
                     if let GraphicsContext::Initialized(ref ctx) = engine.graphics_context {
                         ctx.window.request_redraw();
                     }
@@ -9,7 +9,7 @@ pub mod resource_manager;
 
 use crate::{
     asset::ResourceState,
-    core::{algebra::Vector2, futures::executor::block_on, instant, pool::Handle},
+    core::{algebra::Vector2, futures::executor::block_on, instant, pool::Handle, profiler},
     engine::{
         error::EngineError,
         resource_manager::{container::event::ResourceEvent, ResourceManager, ResourceWaitContext},
@@ -21,20 +21,27 @@ use crate::{
     renderer::{framework::error::FrameworkError, Renderer},
     resource::{model::Model, texture::TextureKind},
     scene::{
+        animation::dispatch_animation_events,
         base::NodeScriptMessage,
-        graph::GraphUpdateSwitches,
+        camera::Camera,
+        graph::{interpolation::TransformInterpolator, Graph, GraphUpdateSwitches},
         node::{constructor::NodeConstructorContainer, Node},
         sound::SoundEngine,
         Scene, SceneContainer,
     },
     script::{
-        constructor::ScriptConstructorContainer, RoutingStrategy, Script, ScriptContext,
-        ScriptDeinitContext, ScriptMessage, ScriptMessageContext, ScriptMessageKind,
+        constructor::ScriptConstructorContainer, task::TaskPoolHandler, RoutingStrategy, Script,
+        ScriptContext, ScriptDeinitContext, ScriptMessage, ScriptMessageContext, ScriptMessageKind,
         ScriptMessageSender,
     },
     utils::log::Log,
     window::{Window, WindowBuilder},
 };
+#[cfg(not(target_arch = "wasm32"))]
+use crate::{
+    core::{uuid::Uuid, visitor::prelude::*},
+    plugin::dynamic::{DynamicPlugin, DynamicPluginError},
+};
 use fxhash::{FxHashMap, FxHashSet};
 #[cfg(not(target_arch = "wasm32"))]
 use glutin::{
@@ -57,12 +64,12 @@ use std::{
     ops::Deref,
     sync::{
         mpsc::{channel, Receiver},
-        Arc,
+        Arc, Once,
     },
     time::Duration,
 };
 #[cfg(not(target_arch = "wasm32"))]
-use std::{ffi::CString, num::NonZeroU32};
+use std::{ffi::CString, num::NonZeroU32, path::Path};
 use winit::{
     dpi::{Position, Size},
     event_loop::EventLoopWindowTarget,
@@ -85,8 +92,11 @@ impl Default for SerializationContext {
 }
 
 impl SerializationContext {
-    /// Creates default serialization context.
+    /// Creates default serialization context. It also registers migrations of the data of the engine
+    /// types (see [`Visitor::register_migration`](crate::core::visitor::Visitor::register_migration)).
     pub fn new() -> Self {
+        register_visit_migrations();
+
         Self {
             node_constructors: NodeConstructorContainer::new(),
             script_constructors: ScriptConstructorContainer::new(),
@@ -94,6 +104,12 @@ impl SerializationContext {
     }
 }
 
+/// Registers migrations of the data of the engine types, it is done only once.
+pub(crate) fn register_visit_migrations() {
+    static REGISTER: Once = Once::new();
+    REGISTER.call_once(crate::animation::spritesheet::register_migrations);
+}
+
 /// Performance statistics.
 #[derive(Debug, Default)]
 pub struct PerformanceStatistics {
@@ -211,6 +227,15 @@ pub struct Engine {
 
     plugins_enabled: bool,
 
+    // An override scene that was used to create plugin instances, it is used to re-create plugins
+    // after hot reloading.
+    plugins_override_scene: Handle<Scene>,
+
+    // A set of plugins loaded from dynamic libraries, they refer to respective plugin constructors
+    // by index.
+    #[cfg(not(target_arch = "wasm32"))]
+    dynamic_plugins: Vec<DynamicPlugin>,
+
     // Amount of time (in seconds) that passed from creation of the engine.
     elapsed_time: f32,
 
@@ -219,6 +244,10 @@ pub struct Engine {
     pub serialization_context: Arc<SerializationContext>,
 
     script_processor: ScriptProcessor,
+
+    transform_interpolation: bool,
+
+    transform_interpolators: FxHashMap<Handle<Scene>, TransformInterpolator>,
 }
 
 /// Performs dispatch of script messages.
@@ -260,6 +289,19 @@ impl ScriptMessageDispatcher {
         }
     }
 
+    // Drops every queued message and removes subscriptions of the given nodes. Messages and subscriptions
+    // could refer to types of a dynamic plugin, so they must be removed before its library is unloaded.
+    #[cfg(not(target_arch = "wasm32"))]
+    fn clear_for_reload(&mut self, nodes: &[Handle<Node>]) {
+        while self.message_receiver.try_recv().is_ok() {}
+
+        for node in nodes {
+            self.unsubscribe(*node);
+        }
+
+        self.type_groups.retain(|_, group| !group.is_empty());
+    }
+
     fn dispatch_messages(
         &self,
         scene: &mut Scene,
@@ -269,87 +311,57 @@ impl ScriptMessageDispatcher {
         elapsed_time: f32,
         message_sender: &ScriptMessageSender,
     ) {
+        let mut context = ScriptMessageContext {
+            dt,
+            elapsed_time,
+            plugins,
+            handle: Default::default(),
+            script_index: 0,
+            scene,
+            resource_manager,
+            message_sender,
+        };
+
         while let Ok(message) = self.message_receiver.try_recv() {
             let mut payload = message.payload;
             if let Some(receivers) = self.type_groups.get(&payload.deref().type_id()) {
-                match message.kind {
-                    ScriptMessageKind::Targeted(target) => {
-                        if receivers.contains(&target) {
-                            let mut context = ScriptMessageContext {
-                                dt,
-                                elapsed_time,
-                                plugins,
-                                handle: target,
-                                scene,
-                                resource_manager,
-                                message_sender,
-                            };
-
-                            process_node_message(&mut context, &mut |s, ctx| {
-                                s.on_message(&mut *payload, ctx)
-                            })
-                        }
+                // Every script of a receiver gets the message, `process_node_message` sets the index of
+                // each script in the context.
+                let mut deliver = |context: &mut ScriptMessageContext, node: Handle<Node>| {
+                    if receivers.contains(&node) {
+                        context.handle = node;
+                        process_node_message(context, &mut |s, ctx| {
+                            s.on_message(&mut *payload, ctx)
+                        });
                     }
+                };
+
+                match message.kind {
+                    ScriptMessageKind::Targeted(target) => deliver(&mut context, target),
                     ScriptMessageKind::Hierarchical { root, routing } => match routing {
                         RoutingStrategy::Up => {
                             let mut node = root;
-                            while let Some(node_ref) = scene.graph.try_get(node) {
-                                let parent = node_ref.parent();
-
-                                let mut context = ScriptMessageContext {
-                                    dt,
-                                    elapsed_time,
-                                    plugins,
-                                    handle: node,
-                                    scene,
-                                    resource_manager,
-                                    message_sender,
-                                };
-
-                                if receivers.contains(&node) {
-                                    process_node_message(&mut context, &mut |s, ctx| {
-                                        s.on_message(&mut *payload, ctx)
-                                    });
-                                }
-
+                            while let Some(parent) =
+                                context.scene.graph.try_get(node).map(|n| n.parent())
+                            {
+                                deliver(&mut context, node);
                                 node = parent;
                             }
                         }
                         RoutingStrategy::Down => {
-                            for node in scene.graph.traverse_handle_iter(root).collect::<Vec<_>>() {
-                                let mut context = ScriptMessageContext {
-                                    dt,
-                                    elapsed_time,
-                                    plugins,
-                                    handle: node,
-                                    scene,
-                                    resource_manager,
-                                    message_sender,
-                                };
-
-                                if receivers.contains(&node) {
-                                    process_node_message(&mut context, &mut |s, ctx| {
-                                        s.on_message(&mut *payload, ctx)
-                                    });
-                                }
+                            for node in context
+                                .scene
+                                .graph
+                                .traverse_handle_iter(root)
+                                .collect::<Vec<_>>()
+                            {
+                                deliver(&mut context, node);
                             }
                         }
                     },
                     ScriptMessageKind::Global => {
                         for &node in receivers {
-                            let mut context = ScriptMessageContext {
-                                dt,
-                                elapsed_time,
-                                plugins,
-                                handle: node,
-                                scene,
-                                resource_manager,
-                                message_sender,
-                            };
-
-                            process_node_message(&mut context, &mut |s, ctx| {
-                                s.on_message(&mut *payload, ctx)
-                            });
+                            deliver(&mut context, node);
                         }
                     }
                 }
@@ -362,6 +374,7 @@ pub(crate) struct ScriptedScene {
     handle: Handle<Scene>,
     message_sender: ScriptMessageSender,
     message_dispatcher: ScriptMessageDispatcher,
+    task_pool: TaskPoolHandler,
 }
 
 #[derive(Default)]
@@ -389,6 +402,7 @@ impl ScriptProcessor {
             handle: scene,
             message_sender: ScriptMessageSender { sender: tx },
             message_dispatcher: ScriptMessageDispatcher::new(rx),
+            task_pool: TaskPoolHandler::new(resource_manager.state().task_pool()),
         });
 
         let graph = &mut scenes[scene].graph;
@@ -406,6 +420,48 @@ impl ScriptProcessor {
             .push(resource_manager.state().containers_mut().get_wait_context());
     }
 
+    fn handle_fixed_update(
+        &mut self,
+        scenes: &mut SceneContainer,
+        plugins: &mut Vec<Box<dyn Plugin>>,
+        resource_manager: &ResourceManager,
+        dt: f32,
+        elapsed_time: f32,
+    ) {
+        if !self.wait_list.is_empty() {
+            return;
+        }
+
+        for scripted_scene in self.scripted_scenes.iter_mut() {
+            let scene = match scenes.try_get_mut(scripted_scene.handle) {
+                Some(scene) if scene.enabled => scene,
+                _ => continue,
+            };
+
+            let mut context = ScriptContext {
+                dt,
+                elapsed_time,
+                plugins,
+                handle: Default::default(),
+                script_index: 0,
+                scene,
+                resource_manager,
+                message_sender: &scripted_scene.message_sender,
+                message_dispatcher: &mut scripted_scene.message_dispatcher,
+                task_pool: &mut scripted_scene.task_pool,
+            };
+
+            for (handle, script_index) in collect_started_scripts(&context.scene.graph) {
+                context.handle = handle;
+                context.script_index = script_index;
+
+                process_script(&mut context, &mut |script, context| {
+                    script.on_fixed_update(context);
+                });
+            }
+        }
+    }
+
     fn handle_scripts(
         &mut self,
         scenes: &mut SceneContainer,
@@ -432,16 +488,55 @@ impl ScriptProcessor {
                 continue 'scene_loop;
             }
 
-            // Fill in initial handles to nodes to update.
-            let mut update_queue = VecDeque::new();
-            for (handle, node) in scene.graph.pair_iter() {
-                if let Some(script) = node.script.as_ref() {
-                    if script.initialized && script.started {
-                        update_queue.push_back(handle);
-                    }
+            // Animation events are sent as script messages, they'll be delivered right after the first
+            // update pass.
+            dispatch_animation_events(&mut scene.graph, &scripted_scene.message_sender);
+
+            // Tasks of deleted nodes must never be completed.
+            scripted_scene.task_pool.cancel_orphaned_tasks(&scene.graph);
+
+            // Deliver results of completed tasks before the update, so the scripts could use the results
+            // on the current frame.
+            let mut context = ScriptContext {
+                dt,
+                elapsed_time,
+                plugins,
+                handle: Default::default(),
+                script_index: 0,
+                scene,
+                resource_manager,
+                message_sender: &scripted_scene.message_sender,
+                message_dispatcher: &mut scripted_scene.message_dispatcher,
+                task_pool: &mut scripted_scene.task_pool,
+            };
+            for task in context.task_pool.take_completed_tasks() {
+                // Results of the tasks of disabled nodes are delivered when the nodes are enabled.
+                if context
+                    .scene
+                    .graph
+                    .try_get(task.node)
+                    .map_or(false, |node| !node.is_globally_enabled())
+                {
+                    context.task_pool.postpone_task(task);
+                    continue;
+                }
+
+                if let Some(script_index) = task.script_index(&context.scene.graph) {
+                    context.handle = task.node;
+                    context.script_index = script_index;
+
+                    let mut task = Some(task);
+                    process_script(&mut context, &mut |script, context| {
+                        if let Some(task) = task.take() {
+                            task.complete(script, context);
+                        }
+                    });
                 }
             }
 
+            // Fill in initial handles to nodes to update.
+            let mut update_queue = VecDeque::from(collect_started_scripts(&scene.graph));
+
             // We'll gather all scripts queued for destruction and destroy them all at once at the
             // end of the frame.
             let mut destruction_queue = VecDeque::new();
@@ -454,10 +549,12 @@ impl ScriptProcessor {
                     elapsed_time,
                     plugins,
                     handle: Default::default(),
+                    script_index: 0,
                     scene,
                     resource_manager,
                     message_sender: &scripted_scene.message_sender,
                     message_dispatcher: &mut scripted_scene.message_dispatcher,
+                    task_pool: &mut scripted_scene.task_pool,
                 };
 
                 'init_loop: for init_loop_iteration in 0..max_iterations {
@@ -477,12 +574,16 @@ impl ScriptProcessor {
                                     }
 
                                     // `on_start` must be called even if the script was initialized.
-                                    start_queue.push_back(handle);
+                                    start_queue.push_back((handle, context.script_index));
                                 });
                             }
-                            NodeScriptMessage::DestroyScript { handle, script } => {
+                            NodeScriptMessage::DestroyScript {
+                                handle,
+                                script,
+                                script_index,
+                            } => {
                                 // Destruction is delayed to the end of the frame.
-                                destruction_queue.push_back((handle, script));
+                                destruction_queue.push_back((handle, script_index, script));
                             }
                         }
                     }
@@ -494,15 +595,16 @@ impl ScriptProcessor {
                         // Call `on_start` for every recently initialized node and go to next
                         // iteration of init loop. This is needed because `on_start` can spawn
                         // some other nodes that must be initialized before update.
-                        while let Some(node) = start_queue.pop_front() {
+                        while let Some((node, script_index)) = start_queue.pop_front() {
                             context.handle = node;
+                            context.script_index = script_index;
 
-                            process_node(&mut context, &mut |script, context| {
+                            process_script(&mut context, &mut |script, context| {
                                 if !script.started {
                                     script.on_start(context);
                                     script.started = true;
 
-                                    update_queue.push_back(node);
+                                    update_queue.push_back((node, script_index));
                                 }
                             });
                         }
@@ -520,10 +622,20 @@ impl ScriptProcessor {
                 if update_queue.is_empty() {
                     break 'update_loop;
                 } else {
-                    while let Some(handle) = update_queue.pop_front() {
+                    // Recently started scripts are added to the end of the queue, so the queue must be
+                    // sorted again to keep the execution priority.
+                    let graph = &context.scene.graph;
+                    update_queue
+                        .make_contiguous()
+                        .sort_by_key(|(handle, script_index)| {
+                            script_priority(graph, *handle, *script_index)
+                        });
+
+                    while let Some((handle, script_index)) = update_queue.pop_front() {
                         context.handle = handle;
+                        context.script_index = script_index;
 
-                        process_node(&mut context, &mut |script, context| {
+                        process_script(&mut context, &mut |script, context| {
                             script.on_update(context);
                         });
                     }
@@ -549,6 +661,60 @@ impl ScriptProcessor {
                 }
             }
 
+            // Late update pass, at this point every script was updated. Transform modification flags are
+            // reset for the pass to find out whether it has changed any transform, the flags are restored
+            // after the pass.
+            let modified_transforms = scene
+                .graph
+                .pair_iter()
+                .filter(|(_, node)| node.transform_modified.replace(false))
+                .map(|(handle, _)| handle)
+                .collect::<Vec<_>>();
+            let node_count = scene.graph.node_count();
+
+            let mut context = ScriptContext {
+                dt,
+                elapsed_time,
+                plugins,
+                handle: Default::default(),
+                script_index: 0,
+                scene,
+                resource_manager,
+                message_sender: &scripted_scene.message_sender,
+                message_dispatcher: &mut scripted_scene.message_dispatcher,
+                task_pool: &mut scripted_scene.task_pool,
+            };
+            for (handle, script_index) in collect_started_scripts(&context.scene.graph) {
+                context.handle = handle;
+                context.script_index = script_index;
+
+                process_script(&mut context, &mut |script, context| {
+                    script.on_late_update(context);
+                });
+            }
+
+            let late_update_changed_hierarchy = node_count != scene.graph.node_count()
+                || scene
+                    .graph
+                    .linear_iter()
+                    .any(|node| node.transform_modified.get());
+
+            for handle in modified_transforms {
+                if let Some(node) = scene.graph.try_get(handle) {
+                    node.transform_modified.set(true);
+                }
+            }
+
+            // Make the changes made by late update visible on the current frame.
+            if late_update_changed_hierarchy {
+                scene.graph.update_hierarchical_data();
+                for node in scene.graph.linear_iter_mut() {
+                    if let Some(camera) = node.cast_mut::<Camera>() {
+                        camera.calculate_view_matrix();
+                    }
+                }
+            }
+
             // As the last step, destroy queued scripts.
             let mut context = ScriptDeinitContext {
                 elapsed_time,
@@ -556,17 +722,32 @@ impl ScriptProcessor {
                 resource_manager,
                 scene,
                 node_handle: Default::default(),
+                script_index: 0,
                 message_sender: &scripted_scene.message_sender,
             };
-            while let Some((handle, mut script)) = destruction_queue.pop_front() {
+            while let Some((handle, script_index, mut script)) = destruction_queue.pop_front() {
                 context.node_handle = handle;
+                context.script_index = script_index;
 
-                // Unregister self in message dispatcher.
-                scripted_scene.message_dispatcher.unsubscribe(handle);
+                // Unregister the node in message dispatcher, if there's no more scripts that could
+                // receive messages.
+                if context
+                    .scene
+                    .graph
+                    .try_get(handle)
+                    .map_or(true, |node| node.scripts().next().is_none())
+                {
+                    scripted_scene.message_dispatcher.unsubscribe(handle);
+                }
 
                 // `on_deinit` could also spawn new nodes, but we won't take those into account on
                 // this frame. They'll be correctly handled on next frame.
                 script.on_deinit(&mut context);
+
+                // The record of the script could be removed now.
+                if let Some(node) = context.scene.graph.try_get_mut(handle) {
+                    node.finish_script_destruction(script_index);
+                }
             }
         }
 
@@ -579,6 +760,7 @@ impl ScriptProcessor {
                     resource_manager,
                     scene: &mut detached_scene,
                     node_handle: Default::default(),
+                    script_index: 0,
                     message_sender: &scripted_scene.message_sender,
                 };
 
@@ -586,16 +768,27 @@ impl ScriptProcessor {
                 for node_index in 0..context.scene.graph.capacity() {
                     context.node_handle = context.scene.graph.handle_from_index(node_index);
 
-                    if let Some(mut script) = context
+                    let script_count = context
                         .scene
                         .graph
-                        .try_get_mut(context.node_handle)
-                        .and_then(|node| node.script.take())
-                    {
-                        // A script could not be initialized in case if we added a scene, and then immediately
-                        // removed it. Calling `on_deinit` in this case would be a violation of API contract.
-                        if script.initialized {
-                            script.on_deinit(&mut context)
+                        .try_get(context.node_handle)
+                        .map_or(0, |node| node.scripts.len());
+
+                    for script_index in 0..script_count {
+                        context.script_index = script_index;
+
+                        if let Some(mut script) = context
+                            .scene
+                            .graph
+                            .try_get_mut(context.node_handle)
+                            .and_then(|node| node.scripts.get_mut(script_index))
+                            .and_then(|record| record.script.take())
+                        {
+                            // A script could not be initialized in case if we added a scene, and then immediately
+                            // removed it. Calling `on_deinit` in this case would be a violation of API contract.
+                            if script.initialized {
+                                script.on_deinit(&mut context)
+                            }
                         }
                     }
                 }
@@ -604,6 +797,39 @@ impl ScriptProcessor {
     }
 }
 
+// Returns execution priority of a script, or zero if there is no such script.
+fn script_priority(graph: &Graph, handle: Handle<Node>, script_index: usize) -> i32 {
+    graph
+        .try_get(handle)
+        .and_then(|node| node.script_at(script_index))
+        .map_or(0, |script| script.execution_priority())
+}
+
+// Collects handles of nodes and indices of every initialized and started script, sorted by execution
+// priority of the scripts. Scripts with the same priority keep their order in the graph.
+fn collect_started_scripts(graph: &Graph) -> Vec<(Handle<Node>, usize)> {
+    let mut scripts = Vec::new();
+
+    for (handle, node) in graph.pair_iter() {
+        for (script_index, record) in node.scripts.iter().enumerate() {
+            if let Some(script) = record
+                .script
+                .as_ref()
+                .filter(|script| script.initialized && script.started && !record.should_be_deleted)
+            {
+                scripts.push((script.execution_priority(), handle, script_index));
+            }
+        }
+    }
+
+    scripts.sort_by_key(|(priority, _, _)| *priority);
+
+    scripts
+        .into_iter()
+        .map(|(_, handle, script_index)| (handle, script_index))
+        .collect()
+}
+
 struct ResourceGraphVertex {
     resource: Model,
     children: Vec<ResourceGraphVertex>,
@@ -708,8 +934,8 @@ pub struct EngineInitParams {
 }
 
 macro_rules! define_process_node {
-    ($name:ident, $ctx_type:ty) => {
-        fn $name<T>(context: &mut $ctx_type, func: &mut T)
+    ($name:ident, $script_name:ident, $ctx_type:ty) => {
+        fn $script_name<T>(context: &mut $ctx_type, func: &mut T)
         where
             T: FnMut(&mut Script, &mut $ctx_type),
         {
@@ -721,11 +947,19 @@ macro_rules! define_process_node {
                         return;
                     }
 
-                    if let Some(script) = node.script.take() {
-                        script
-                    } else {
-                        // No script.
-                        return;
+                    match node.scripts.get_mut(context.script_index) {
+                        Some(record) if !record.should_be_deleted => {
+                            if let Some(script) = record.script.take() {
+                                script
+                            } else {
+                                // No script.
+                                return;
+                            }
+                        }
+                        _ => {
+                            // No script or it is deleted.
+                            return;
+                        }
                     }
                 }
                 None => {
@@ -739,14 +973,44 @@ macro_rules! define_process_node {
             // Put the script back to the node. We must do a checked borrow, because it is possible
             // that the node is already destroyed by script logic.
             if let Some(node) = context.scene.graph.try_get_mut(context.handle) {
-                node.script = Some(script);
+                if let Some(record) = node.scripts.get_mut(context.script_index) {
+                    if record.should_be_deleted {
+                        // The script was removed by script logic while it was taken out of the node.
+                        node.send_script_for_destruction(script, context.script_index);
+                    } else {
+                        record.script = Some(script);
+                    }
+                }
+            }
+        }
+
+        fn $name<T>(context: &mut $ctx_type, func: &mut T)
+        where
+            T: FnMut(&mut Script, &mut $ctx_type),
+        {
+            // Scripts could add more scripts to the node, so the amount of scripts must be checked on
+            // every iteration.
+            let mut script_index = 0;
+            while context
+                .scene
+                .graph
+                .try_get(context.handle)
+                .map_or(false, |node| script_index < node.scripts.len())
+            {
+                context.script_index = script_index;
+                $script_name(context, func);
+                script_index += 1;
             }
         }
     };
 }
 
-define_process_node!(process_node, ScriptContext);
-define_process_node!(process_node_message, ScriptMessageContext);
+define_process_node!(process_node, process_script, ScriptContext);
+define_process_node!(
+    process_node_message,
+    process_script_message,
+    ScriptMessageContext
+);
 
 pub(crate) fn process_scripts<T>(
     scene: &mut Scene,
@@ -754,6 +1018,7 @@ pub(crate) fn process_scripts<T>(
     resource_manager: &ResourceManager,
     message_sender: &ScriptMessageSender,
     message_dispatcher: &mut ScriptMessageDispatcher,
+    task_pool: &mut TaskPoolHandler,
     dt: f32,
     elapsed_time: f32,
     mut func: T,
@@ -765,10 +1030,12 @@ pub(crate) fn process_scripts<T>(
         elapsed_time,
         plugins,
         handle: Default::default(),
+        script_index: 0,
         scene,
         resource_manager,
         message_sender,
         message_dispatcher,
+        task_pool,
     };
 
     for node_index in 0..context.scene.graph.capacity() {
@@ -846,8 +1113,13 @@ impl Engine {
             serialization_context,
             script_processor: Default::default(),
             plugins_enabled: false,
+            plugins_override_scene: Default::default(),
+            #[cfg(not(target_arch = "wasm32"))]
+            dynamic_plugins: Default::default(),
             plugin_constructors: Default::default(),
             elapsed_time: 0.0,
+            transform_interpolation: false,
+            transform_interpolators: Default::default(),
         })
     }
 
@@ -1129,6 +1401,9 @@ impl Engine {
         lag: &mut f32,
         switches: FxHashMap<Handle<Scene>, GraphUpdateSwitches>,
     ) {
+        #[cfg(not(target_arch = "wasm32"))]
+        self.handle_dynamic_plugins();
+
         if let GraphicsContext::Initialized(ctx) = &mut self.graphics_context {
             let inner_size = ctx.window.inner_size();
             let window_size = Vector2::new(inner_size.width as f32, inner_size.height as f32);
@@ -1137,6 +1412,24 @@ impl Engine {
             ctx.renderer.update_caches(dt);
             self.handle_model_events();
 
+            if self.transform_interpolation {
+                let scenes = &self.scenes;
+                self.transform_interpolators
+                    .retain(|handle, _| scenes.is_valid_handle(*handle));
+
+                for (handle, interpolator) in self.transform_interpolators.iter_mut() {
+                    interpolator.begin_step(&mut self.scenes[*handle].graph);
+                }
+            }
+
+            self.script_processor.handle_fixed_update(
+                &mut self.scenes,
+                &mut self.plugins,
+                &self.resource_manager,
+                dt,
+                self.elapsed_time,
+            );
+
             for (handle, scene) in self.scenes.pair_iter_mut().filter(|(_, s)| s.enabled) {
                 let frame_size = scene.render_target.as_ref().map_or(window_size, |rt| {
                     if let TextureKind::Rectangle { width, height } = rt.data_ref().kind() {
@@ -1155,6 +1448,15 @@ impl Engine {
 
             self.update_plugins(dt, control_flow, lag);
             self.handle_scripts(dt);
+
+            if self.transform_interpolation {
+                for (handle, scene) in self.scenes.pair_iter().filter(|(_, s)| s.enabled) {
+                    self.transform_interpolators
+                        .entry(handle)
+                        .or_default()
+                        .end_step(&scene.graph);
+                }
+            }
         }
     }
 
@@ -1174,6 +1476,86 @@ impl Engine {
         }
     }
 
+    /// Performs variable-rate part of the frame, it must be called once per rendered frame right before
+    /// [`Self::render`]. It calls [`Plugin::on_frame`] and [`crate::script::ScriptTrait::on_frame`] and
+    /// interpolates transforms of scene nodes, if transform interpolation is enabled (see
+    /// [`Self::set_transform_interpolation`]).
+    ///
+    /// ## Parameters
+    ///
+    /// `dt` - the time (in seconds) that passed since the previous frame.
+    ///
+    /// `alpha` - an interpolation factor between two most recent fixed update steps, usually it is
+    /// `lag / fixed_time_step`.
+    pub fn frame(&mut self, dt: f32, alpha: f32, control_flow: &mut ControlFlow, lag: &mut f32) {
+        if self.plugins_enabled {
+            let mut context = PluginContext {
+                scenes: &mut self.scenes,
+                resource_manager: &self.resource_manager,
+                graphics_context: &mut self.graphics_context,
+                dt,
+                lag,
+                user_interface: &mut self.user_interface,
+                serialization_context: &self.serialization_context,
+                performance_statistics: &self.performance_statistics,
+            };
+
+            for plugin in self.plugins.iter_mut() {
+                plugin.on_frame(&mut context, control_flow);
+            }
+        }
+
+        for scripted_scene in self.script_processor.scripted_scenes.iter_mut() {
+            if let Some(scene) = self.scenes.try_get_mut(scripted_scene.handle) {
+                if scene.enabled {
+                    process_scripts(
+                        scene,
+                        &mut self.plugins,
+                        &self.resource_manager,
+                        &scripted_scene.message_sender,
+                        &mut scripted_scene.message_dispatcher,
+                        &mut scripted_scene.task_pool,
+                        dt,
+                        self.elapsed_time,
+                        |script, context| {
+                            if script.initialized && script.started {
+                                script.on_frame(context);
+                            }
+                        },
+                    )
+                }
+            }
+        }
+
+        if self.transform_interpolation {
+            for (handle, interpolator) in self.transform_interpolators.iter_mut() {
+                if let Some(scene) = self.scenes.try_get_mut(*handle) {
+                    interpolator.interpolate(&mut scene.graph, alpha);
+                }
+            }
+        }
+    }
+
+    /// Enables or disables interpolation of node transforms between two most recent fixed update steps. It
+    /// makes motion smoother when frame rate differs from the update rate, at the cost of one update step
+    /// of visual latency. See [`TransformInterpolator`] docs for more info. Disabled by default.
+    pub fn set_transform_interpolation(&mut self, enabled: bool) {
+        self.transform_interpolation = enabled;
+        if !enabled {
+            for (handle, interpolator) in self.transform_interpolators.iter_mut() {
+                if let Some(scene) = self.scenes.try_get_mut(*handle) {
+                    interpolator.begin_step(&mut scene.graph);
+                }
+            }
+            self.transform_interpolators.clear();
+        }
+    }
+
+    /// Returns `true` if transform interpolation is enabled, `false` - otherwise.
+    pub fn is_transform_interpolation_enabled(&self) -> bool {
+        self.transform_interpolation
+    }
+
     /// Returns true if the scene is registered for script processing.
     pub fn has_scripted_scene(&self, scene: Handle<Scene>) -> bool {
         self.script_processor.has_scripted_scene(scene)
@@ -1344,6 +1726,7 @@ impl Engine {
                     &self.resource_manager,
                     &scripted_scene.message_sender,
                     &mut scripted_scene.message_dispatcher,
+                    &mut scripted_scene.task_pool,
                     dt,
                     self.elapsed_time,
                     |script, context| {
@@ -1408,6 +1791,8 @@ impl Engine {
             }
         }
 
+        profiler::mark_frame();
+
         Ok(())
     }
 
@@ -1415,6 +1800,7 @@ impl Engine {
     pub(crate) fn enable_plugins(&mut self, override_scene: Handle<Scene>, enabled: bool) {
         if self.plugins_enabled != enabled {
             self.plugins_enabled = enabled;
+            self.plugins_override_scene = override_scene;
 
             if self.plugins_enabled {
                 // Create and initialize instances.
@@ -1464,73 +1850,518 @@ impl Engine {
 
         self.plugin_constructors.push(Box::new(constructor));
     }
-}
 
-impl Drop for Engine {
-    fn drop(&mut self) {
-        // Destroy all scenes first and correctly destroy all script instances.
-        // This will ensure that any `on_destroy` logic will be executed before
-        // engine destroyed.
-        let scenes = self
-            .scenes
-            .pair_iter()
-            .map(|(h, _)| h)
-            .collect::<Vec<Handle<Scene>>>();
-
-        for handle in scenes {
-            self.scenes.remove(handle);
-        }
+    /// Loads a plugin from a dynamic library at the given path and adds its constructor to the engine.
+    /// If `reload_when_changed` is `true`, the engine will reload the plugin automatically every time
+    /// when the library file was changed (for example, when the game was re-compiled). See [`DynamicPlugin`]
+    /// docs for more info.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn add_dynamic_plugin<P>(
+        &mut self,
+        path: P,
+        reload_when_changed: bool,
+    ) -> Result<(), DynamicPluginError>
+    where
+        P: AsRef<Path>,
+    {
+        let (mut dynamic_plugin, constructor) = DynamicPlugin::load(
+            path.as_ref(),
+            reload_when_changed,
+            self.plugin_constructors.len(),
+        )?;
 
-        // Finally disable plugins.
-        self.enable_plugins(Default::default(), false);
-    }
-}
+        dynamic_plugin.script_types = self.register_dynamic_plugin(constructor.as_ref())?;
 
-#[cfg(test)]
-mod test {
-    use crate::script::{ScriptMessageContext, ScriptMessagePayload};
-    use crate::{
-        core::{pool::Handle, reflect::prelude::*, uuid::Uuid, visitor::prelude::*},
-        engine::{resource_manager::ResourceManager, ScriptProcessor},
-        impl_component_provider,
-        scene::{base::BaseBuilder, node::Node, pivot::PivotBuilder, Scene, SceneContainer},
-        script::{Script, ScriptContext, ScriptDeinitContext, ScriptTrait},
-    };
-    use std::sync::mpsc::{self, Sender, TryRecvError};
+        self.plugin_constructors.push(constructor);
+        self.dynamic_plugins.push(dynamic_plugin);
 
-    #[derive(PartialEq, Eq, Clone, Debug)]
-    enum Event {
-        Initialized(Handle<Node>),
-        Started(Handle<Node>),
-        Updated(Handle<Node>),
-        Destroyed(Handle<Node>),
-        EventReceived(Handle<Node>),
+        Ok(())
     }
 
-    #[derive(Debug, Clone, Reflect, Visit)]
-    struct MyScript {
-        #[reflect(hidden)]
-        #[visit(skip)]
-        sender: Sender<Event>,
-        spawned: bool,
+    /// Returns a reference to the set of plugins loaded from dynamic libraries.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn dynamic_plugins(&self) -> &[DynamicPlugin] {
+        &self.dynamic_plugins
     }
 
-    impl_component_provider!(MyScript);
+    /// Returns a reference to the set of plugins loaded from dynamic libraries.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn dynamic_plugins_mut(&mut self) -> &mut [DynamicPlugin] {
+        &mut self.dynamic_plugins
+    }
 
-    impl ScriptTrait for MyScript {
-        fn on_init(&mut self, ctx: &mut ScriptContext) {
-            self.sender.send(Event::Initialized(ctx.handle)).unwrap();
+    /// Forces reloading of every dynamic plugin. State of the plugins and their scripts will be preserved.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn reload_dynamic_plugins(&mut self) {
+        for index in 0..self.dynamic_plugins.len() {
+            self.reload_dynamic_plugin_logged(index);
+        }
+    }
 
-            // Spawn new entity with script.
-            let handle =
-                PivotBuilder::new(BaseBuilder::new().with_script(Script::new(MySubScript {
-                    sender: self.sender.clone(),
-                })))
-                .build(&mut ctx.scene.graph);
-            assert_eq!(handle, Handle::new(2, 1));
+    #[cfg(not(target_arch = "wasm32"))]
+    fn handle_dynamic_plugins(&mut self) {
+        for index in 0..self.dynamic_plugins.len() {
+            if self.dynamic_plugins[index].poll_changes() {
+                self.reload_dynamic_plugin_logged(index);
+            }
         }
+    }
 
-        fn on_start(&mut self, ctx: &mut ScriptContext) {
+    #[cfg(not(target_arch = "wasm32"))]
+    fn reload_dynamic_plugin_logged(&mut self, index: usize) {
+        let path = self.dynamic_plugins[index].path().to_path_buf();
+
+        match self.reload_dynamic_plugin(index) {
+            Ok(_) => Log::info(format!(
+                "Plugin {} was reloaded successfully!",
+                path.display()
+            )),
+            Err(e) => Log::err(format!(
+                "Unable to reload plugin {}. Reason: {}",
+                path.display(),
+                e
+            )),
+        }
+    }
+
+    // Registers the plugin and returns a list of script types registered by it.
+    #[cfg(not(target_arch = "wasm32"))]
+    fn register_dynamic_plugin(
+        &self,
+        constructor: &dyn PluginConstructor,
+    ) -> Result<Vec<Uuid>, DynamicPluginError> {
+        let context = &self.serialization_context;
+
+        let node_constructors_count = context.node_constructors.len();
+        let scripts_before = context
+            .script_constructors
+            .map()
+            .keys()
+            .cloned()
+            .collect::<FxHashSet<_>>();
+
+        constructor.register(PluginRegistrationContext {
+            serialization_context: context,
+        });
+
+        if context.node_constructors.len() != node_constructors_count {
+            return Err(DynamicPluginError::CustomNodesUnsupported);
+        }
+
+        Ok(context
+            .script_constructors
+            .map()
+            .keys()
+            .filter(|type_uuid| !scripts_before.contains(type_uuid))
+            .cloned()
+            .collect())
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn reload_dynamic_plugin(&mut self, index: usize) -> Result<(), DynamicPluginError> {
+        // Load the new library first, the old one will continue to work if this fails.
+        let dynamic_plugin = &mut self.dynamic_plugins[index];
+        let new_constructor = dynamic_plugin.load_library()?;
+        let constructor_index = dynamic_plugin.constructor_index;
+        let script_types = std::mem::take(&mut dynamic_plugin.script_types);
+
+        // Serialize and destroy every script instance created by the old library. Scripts in loaded
+        // models must be reloaded too, because they're used as prefabs.
+        let mut scene_scripts = Vec::new();
+        for (handle, scene) in self.scenes.pair_iter_mut() {
+            let (saved_scripts, mut instances) = take_scripts(&mut scene.graph, &script_types);
+
+            if let Some(scripted_scene) = self
+                .script_processor
+                .scripted_scenes
+                .iter_mut()
+                .find(|s| s.handle == handle)
+            {
+                let mut context = ScriptDeinitContext {
+                    elapsed_time: self.elapsed_time,
+                    plugins: &mut self.plugins,
+                    resource_manager: &self.resource_manager,
+                    scene,
+                    node_handle: Default::default(),
+                    script_index: 0,
+                    message_sender: &scripted_scene.message_sender,
+                };
+
+                for (node, script_index, script) in instances.iter_mut() {
+                    if script.initialized {
+                        context.node_handle = *node;
+                        context.script_index = *script_index;
+                        script.on_deinit(&mut context);
+                    }
+                }
+
+                // Tasks, messages and subscriptions could refer to the code and types of the old library.
+                scripted_scene.task_pool.cancel_script_tasks(&instances);
+                let nodes = instances
+                    .iter()
+                    .map(|(node, _, _)| *node)
+                    .collect::<Vec<_>>();
+                scripted_scene.message_dispatcher.clear_for_reload(&nodes);
+            }
+
+            scene_scripts.push((handle, saved_scripts));
+        }
+
+        let mut model_scripts = Vec::new();
+        for model in self.resource_manager.state().containers().models.iter() {
+            if let ResourceState::Ok(ref mut data) = *model.state() {
+                // Scripts of models are never initialized, so they're just dropped.
+                let (scripts, _) = take_scripts(&mut data.get_scene_mut().graph, &script_types);
+                if !scripts.is_empty() {
+                    model_scripts.push((model.clone(), scripts));
+                }
+            }
+        }
+
+        // Serialize and destroy the plugin instance.
+        let plugin_state = if self.plugins_enabled && constructor_index < self.plugins.len() {
+            let mut plugin = self.plugins.remove(constructor_index);
+            let mut visitor = Visitor::new();
+            match plugin
+                .visit_state(&mut visitor)
+                .and_then(|_| visitor.save_binary_to_vec())
+            {
+                Ok(data) => Some(data),
+                Err(e) => {
+                    Log::err(format!("Unable to save plugin state. Reason: {:?}", e));
+                    Some(Vec::new())
+                }
+            }
+        } else {
+            None
+        };
+
+        // Replace the constructor and register new types.
+        for type_uuid in script_types.iter() {
+            self.serialization_context
+                .script_constructors
+                .remove(*type_uuid);
+        }
+        let script_types = match self.register_dynamic_plugin(new_constructor.as_ref()) {
+            Ok(script_types) => script_types,
+            Err(e) => {
+                Log::err(format!("Unable to register reloaded plugin. Reason: {}", e));
+                Default::default()
+            }
+        };
+        drop(std::mem::replace(
+            &mut self.plugin_constructors[constructor_index],
+            new_constructor,
+        ));
+
+        // Every object of the old library is destroyed at this point, so it can be unloaded safely.
+        let dynamic_plugin = &mut self.dynamic_plugins[index];
+        dynamic_plugin.unload_previous();
+        dynamic_plugin.script_types = script_types;
+
+        // Restore the state. Scripts of scripted scenes were deinitialized, so they must be initialized
+        // again.
+        for (handle, scripts) in scene_scripts {
+            restore_scripts(
+                &mut self.scenes[handle].graph,
+                scripts,
+                &self.serialization_context,
+                self.script_processor.has_scripted_scene(handle),
+            );
+        }
+
+        for (model, scripts) in model_scripts {
+            if let ResourceState::Ok(ref mut data) = *model.state() {
+                restore_scripts(
+                    &mut data.get_scene_mut().graph,
+                    scripts,
+                    &self.serialization_context,
+                    false,
+                );
+            }
+        }
+
+        if let Some(plugin_state) = plugin_state {
+            let mut plugin = self.plugin_constructors[constructor_index]
+                .create_instance_for_hot_reload(
+                    self.plugins_override_scene,
+                    PluginContext {
+                        scenes: &mut self.scenes,
+                        resource_manager: &self.resource_manager,
+                        graphics_context: &mut self.graphics_context,
+                        dt: 0.0,
+                        lag: &mut 0.0,
+                        user_interface: &mut self.user_interface,
+                        serialization_context: &self.serialization_context,
+                        performance_statistics: &self.performance_statistics,
+                    },
+                );
+
+            if !plugin_state.is_empty() {
+                if let Err(e) = Visitor::load_from_memory(plugin_state)
+                    .and_then(|mut visitor| plugin.visit_state(&mut visitor))
+                {
+                    Log::err(format!("Unable to restore plugin state. Reason: {:?}", e));
+                }
+            }
+
+            self.plugins.insert(constructor_index, plugin);
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+struct SavedScript {
+    node: Handle<Node>,
+    script_index: usize,
+    type_uuid: Uuid,
+    data: Vec<u8>,
+    started: bool,
+}
+
+// Script instances taken from the graph along with handles of their nodes and their indices.
+#[cfg(not(target_arch = "wasm32"))]
+type ScriptInstances = Vec<(Handle<Node>, usize, Script)>;
+
+// Serializes and removes every script of the given types from the graph. Records of the scripts are
+// kept in the nodes, so the restored scripts will have the same indices. Returns serialized scripts and
+// script instances (including the ones that are waiting for destruction), that must be destroyed before
+// the library of the scripts is unloaded.
+#[cfg(not(target_arch = "wasm32"))]
+fn take_scripts(graph: &mut Graph, script_types: &[Uuid]) -> (Vec<SavedScript>, ScriptInstances) {
+    let mut saved_scripts = Vec::new();
+    let mut instances = Vec::new();
+
+    // Scripts that are waiting for destruction must be destroyed by the old library.
+    let messages = graph.script_message_receiver.try_iter().collect::<Vec<_>>();
+    for message in messages {
+        match message {
+            NodeScriptMessage::DestroyScript {
+                script,
+                handle,
+                script_index,
+            } if script_types.contains(&script.id()) => {
+                // The script is destroyed right after this call, before the graph is updated.
+                if let Some(node) = graph.try_get_mut(handle) {
+                    node.finish_script_destruction(script_index);
+                }
+                instances.push((handle, script_index, script));
+            }
+            message => Log::verify(graph.script_message_sender.send(message)),
+        }
+    }
+
+    for (handle, node) in graph.pair_iter_mut() {
+        for script_index in 0..node.scripts.len() {
+            let record = &mut node.scripts[script_index];
+
+            if record.should_be_deleted
+                || !record
+                    .script
+                    .as_ref()
+                    .map_or(false, |script| script_types.contains(&script.id()))
+            {
+                continue;
+            }
+
+            if let Some(mut script) = record.script.take() {
+                let mut visitor = Visitor::new();
+                match script
+                    .visit("Script", &mut visitor)
+                    .and_then(|_| visitor.save_binary_to_vec())
+                {
+                    Ok(data) => saved_scripts.push(SavedScript {
+                        node: handle,
+                        script_index,
+                        type_uuid: script.id(),
+                        data,
+                        started: script.started,
+                    }),
+                    Err(e) => {
+                        record.should_be_deleted = true;
+
+                        Log::err(format!(
+                            "Unable to save state of script {} of node {}. Reason: {:?}",
+                            script.id(),
+                            node.name(),
+                            e
+                        ))
+                    }
+                }
+
+                instances.push((handle, script_index, script));
+            }
+        }
+    }
+
+    (saved_scripts, instances)
+}
+
+// Re-creates scripts using the actual script constructors and restores their state. If `reinitialize` is
+// `true`, the scripts will be initialized and started again.
+#[cfg(not(target_arch = "wasm32"))]
+fn restore_scripts(
+    graph: &mut Graph,
+    saved_scripts: Vec<SavedScript>,
+    serialization_context: &SerializationContext,
+    reinitialize: bool,
+) {
+    for saved_script in saved_scripts {
+        let node = match graph.try_get_mut(saved_script.node) {
+            Some(node) => node,
+            None => continue,
+        };
+
+        let script = serialization_context
+            .script_constructors
+            .try_create(&saved_script.type_uuid);
+
+        let mut script = match script {
+            Some(script) => script,
+            None => {
+                Log::warn(format!(
+                    "Script {} of node {} was removed from the plugin!",
+                    saved_script.type_uuid,
+                    node.name()
+                ));
+                if let Some(record) = node.scripts.get_mut(saved_script.script_index) {
+                    record.should_be_deleted = true;
+                }
+                continue;
+            }
+        };
+
+        let result = Visitor::load_from_memory(saved_script.data)
+            .and_then(|mut visitor| script.visit("Script", &mut visitor));
+
+        let name = node.name_owned();
+        if let Some(record) = node.scripts.get_mut(saved_script.script_index) {
+            match result {
+                Ok(_) => {
+                    if reinitialize {
+                        script.initialized = false;
+                        script.started = false;
+                    } else {
+                        script.started = saved_script.started;
+                    }
+                    record.script = Some(script);
+
+                    if reinitialize {
+                        Log::verify(graph.script_message_sender.send(
+                            NodeScriptMessage::InitializeScript {
+                                handle: saved_script.node,
+                            },
+                        ));
+                    }
+                }
+                Err(e) => {
+                    record.should_be_deleted = true;
+
+                    Log::err(format!(
+                        "Unable to restore state of script {} of node {}. Reason: {:?}",
+                        saved_script.type_uuid, name, e
+                    ))
+                }
+            }
+        }
+    }
+}
+
+impl Drop for Engine {
+    fn drop(&mut self) {
+        // Destroy all scenes first and correctly destroy all script instances.
+        // This will ensure that any `on_destroy` logic will be executed before
+        // engine destroyed.
+        let scenes = self
+            .scenes
+            .pair_iter()
+            .map(|(h, _)| h)
+            .collect::<Vec<Handle<Scene>>>();
+
+        for handle in scenes {
+            self.scenes.remove(handle);
+        }
+
+        // Finally disable plugins.
+        self.enable_plugins(Default::default(), false);
+
+        // Plugin constructors must be destroyed before dynamic plugins, since their code lives in
+        // dynamic libraries.
+        self.plugin_constructors.clear();
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::script::{ScriptMessageContext, ScriptMessagePayload};
+    use crate::{
+        animation::{
+            machine::{self, Machine, MachineLayer, Parameter, PoseNode, State, Transition},
+            signal::AnimationEvent,
+            Animation, AnimationContainer,
+        },
+        core::{
+            algebra::Vector3, futures, pool::Handle, reflect::prelude::*, uuid::Uuid,
+            visitor::prelude::*,
+        },
+        engine::{resource_manager::ResourceManager, ScriptProcessor},
+        impl_component_provider,
+        scene::{
+            animation::{
+                absm::{AbsmEventMessage, AnimationBlendingStateMachineBuilder},
+                AnimationEventMessage, AnimationPlayer, AnimationPlayerBuilder,
+            },
+            base::BaseBuilder,
+            node::Node,
+            pivot::PivotBuilder,
+            Scene, SceneContainer,
+        },
+        script::{task, Script, ScriptContext, ScriptDeinitContext, ScriptTrait},
+    };
+    use std::{
+        sync::mpsc::{self, Sender, TryRecvError},
+        time::Duration,
+    };
+
+    #[derive(PartialEq, Eq, Clone, Debug)]
+    enum Event {
+        Initialized(Handle<Node>),
+        Started(Handle<Node>),
+        Updated(Handle<Node>),
+        Destroyed(Handle<Node>),
+        EventReceived(Handle<Node>),
+        FixedUpdated(Handle<Node>),
+        LateUpdated(Handle<Node>),
+        TaskCompleted(Handle<Node>, u32),
+        MessageReceived(Handle<Node>, usize),
+    }
+
+    #[derive(Debug, Clone, Reflect, Visit)]
+    struct MyScript {
+        #[reflect(hidden)]
+        #[visit(skip)]
+        sender: Sender<Event>,
+        spawned: bool,
+    }
+
+    impl_component_provider!(MyScript);
+
+    impl ScriptTrait for MyScript {
+        fn on_init(&mut self, ctx: &mut ScriptContext) {
+            self.sender.send(Event::Initialized(ctx.handle)).unwrap();
+
+            // Spawn new entity with script.
+            let handle =
+                PivotBuilder::new(BaseBuilder::new().with_script(Script::new(MySubScript {
+                    sender: self.sender.clone(),
+                })))
+                .build(&mut ctx.scene.graph);
+            assert_eq!(handle, Handle::new(2, 1));
+        }
+
+        fn on_start(&mut self, ctx: &mut ScriptContext) {
             self.sender.send(Event::Started(ctx.handle)).unwrap();
 
             // Spawn new entity with script.
@@ -1816,4 +2647,705 @@ mod test {
             }
         }
     }
+
+    #[derive(Debug, Clone, Reflect, Visit)]
+    struct ScriptListeningToAnimationEvents {
+        #[reflect(hidden)]
+        #[visit(skip)]
+        sender: Sender<AnimationEventMessage>,
+    }
+
+    impl_component_provider!(ScriptListeningToAnimationEvents);
+
+    impl ScriptTrait for ScriptListeningToAnimationEvents {
+        fn on_start(&mut self, ctx: &mut ScriptContext) {
+            ctx.message_dispatcher
+                .subscribe_to::<AnimationEventMessage>(ctx.handle);
+        }
+
+        fn on_message(
+            &mut self,
+            message: &mut dyn ScriptMessagePayload,
+            _ctx: &mut ScriptMessageContext,
+        ) {
+            let message = message.downcast_ref::<AnimationEventMessage>().unwrap();
+            self.sender.send(message.clone()).unwrap();
+        }
+
+        fn id(&self) -> Uuid {
+            Uuid::new_v4()
+        }
+    }
+
+    #[test]
+    fn test_animation_events_dispatch() {
+        let resource_manager = ResourceManager::new(Default::default());
+        let mut scene = Scene::new();
+
+        let (tx, rx) = mpsc::channel();
+
+        let mut animations = AnimationContainer::new();
+        let animation = animations.add(Animation::default());
+
+        let animation_player = AnimationPlayerBuilder::new(BaseBuilder::new())
+            .with_animations(animations)
+            .with_dispatch_events(true)
+            .build(&mut scene.graph);
+
+        PivotBuilder::new(
+            BaseBuilder::new()
+                .with_children(&[animation_player])
+                .with_script(Script::new(ScriptListeningToAnimationEvents { sender: tx })),
+        )
+        .build(&mut scene.graph);
+
+        let event = AnimationEvent {
+            signal_id: Uuid::new_v4(),
+            name: "Footstep".to_string(),
+        };
+
+        scene.graph[animation_player]
+            .query_component_mut::<AnimationPlayer>()
+            .unwrap()
+            .animations_mut()
+            .get_value_mut_silent()
+            .get_mut(animation)
+            .events_mut()
+            .push_back(event.clone());
+
+        let mut scene_container = SceneContainer::new(Default::default());
+
+        let scene_handle = scene_container.add(scene);
+
+        let mut script_processor = ScriptProcessor::default();
+
+        script_processor.register_scripted_scene(
+            scene_handle,
+            &mut scene_container,
+            &resource_manager,
+        );
+
+        script_processor.handle_scripts(
+            &mut scene_container,
+            &mut Default::default(),
+            &resource_manager,
+            0.0,
+            0.0,
+        );
+
+        assert_eq!(
+            rx.try_recv(),
+            Ok(AnimationEventMessage {
+                animation_player,
+                animation,
+                event,
+            })
+        );
+        assert_eq!(rx.try_recv(), Err(TryRecvError::Empty));
+    }
+
+    #[derive(Debug, Clone, Reflect, Visit)]
+    struct ScriptListeningToAbsmEvents {
+        #[reflect(hidden)]
+        #[visit(skip)]
+        sender: Sender<AbsmEventMessage>,
+    }
+
+    impl_component_provider!(ScriptListeningToAbsmEvents);
+
+    impl ScriptTrait for ScriptListeningToAbsmEvents {
+        fn on_start(&mut self, ctx: &mut ScriptContext) {
+            ctx.message_dispatcher
+                .subscribe_to::<AbsmEventMessage>(ctx.handle);
+        }
+
+        fn on_message(
+            &mut self,
+            message: &mut dyn ScriptMessagePayload,
+            _ctx: &mut ScriptMessageContext,
+        ) {
+            let message = message.downcast_ref::<AbsmEventMessage>().unwrap();
+            self.sender.send(message.clone()).unwrap();
+        }
+
+        fn id(&self) -> Uuid {
+            Uuid::new_v4()
+        }
+    }
+
+    #[test]
+    fn test_absm_events_dispatch() {
+        let resource_manager = ResourceManager::new(Default::default());
+        let mut scene = Scene::new();
+
+        let (tx, rx) = mpsc::channel();
+
+        let mut layer = MachineLayer::new();
+        let idle_node = layer.add_node(PoseNode::make_play_animation(Default::default()));
+        let idle = layer.add_state(State::new("Idle", idle_node));
+        let walk_node = layer.add_node(PoseNode::make_play_animation(Default::default()));
+        let walk = layer.add_state(State::new("Walk", walk_node));
+        layer.add_transition(Transition::new("IdleToWalk", idle, walk, 0.0, "Walk"));
+        layer.set_entry_state(idle);
+
+        let mut machine = Machine::new();
+        // Replace the default layer.
+        machine.layers_mut()[0] = layer;
+        machine.set_parameter("Walk", Parameter::Rule(true));
+
+        // Fill the events queue of the layer.
+        machine.evaluate_pose(&AnimationContainer::new(), 0.1);
+
+        let absm = AnimationBlendingStateMachineBuilder::new(BaseBuilder::new())
+            .with_machine(machine)
+            .with_dispatch_events(true)
+            .build(&mut scene.graph);
+
+        PivotBuilder::new(
+            BaseBuilder::new()
+                .with_children(&[absm])
+                .with_script(Script::new(ScriptListeningToAbsmEvents { sender: tx })),
+        )
+        .build(&mut scene.graph);
+
+        let mut scene_container = SceneContainer::new(Default::default());
+
+        let scene_handle = scene_container.add(scene);
+
+        let mut script_processor = ScriptProcessor::default();
+
+        script_processor.register_scripted_scene(
+            scene_handle,
+            &mut scene_container,
+            &resource_manager,
+        );
+
+        script_processor.handle_scripts(
+            &mut scene_container,
+            &mut Default::default(),
+            &resource_manager,
+            0.0,
+            0.0,
+        );
+
+        let messages = rx.try_iter().collect::<Vec<_>>();
+        let (message, state) = messages
+            .iter()
+            .find_map(|message| match message.event {
+                machine::Event::StateEnter(state) => Some((message, state)),
+                _ => None,
+            })
+            .expect("State enter event must be dispatched!");
+        assert_eq!(message.absm, absm);
+        assert_eq!(message.layer, 0);
+        assert_eq!(message.name, if state == idle { "Idle" } else { "Walk" });
+    }
+
+    #[derive(Debug, Clone, Reflect, Visit)]
+    struct PrioritizedScript {
+        priority: i32,
+        #[reflect(hidden)]
+        #[visit(skip)]
+        sender: Sender<Event>,
+    }
+
+    impl_component_provider!(PrioritizedScript);
+
+    impl ScriptTrait for PrioritizedScript {
+        fn on_update(&mut self, ctx: &mut ScriptContext) {
+            self.sender.send(Event::Updated(ctx.handle)).unwrap();
+        }
+
+        fn on_fixed_update(&mut self, ctx: &mut ScriptContext) {
+            self.sender.send(Event::FixedUpdated(ctx.handle)).unwrap();
+        }
+
+        fn on_late_update(&mut self, ctx: &mut ScriptContext) {
+            self.sender.send(Event::LateUpdated(ctx.handle)).unwrap();
+        }
+
+        fn execution_priority(&self) -> i32 {
+            self.priority
+        }
+
+        fn id(&self) -> Uuid {
+            Uuid::new_v4()
+        }
+    }
+
+    #[test]
+    fn test_execution_priority() {
+        let resource_manager = ResourceManager::new(Default::default());
+        let mut scene = Scene::new();
+
+        let (tx, rx) = mpsc::channel();
+
+        let late = PivotBuilder::new(BaseBuilder::new().with_script(Script::new(
+            PrioritizedScript {
+                priority: 10,
+                sender: tx.clone(),
+            },
+        )))
+        .build(&mut scene.graph);
+
+        let early = PivotBuilder::new(BaseBuilder::new().with_script(Script::new(
+            PrioritizedScript {
+                priority: -1,
+                sender: tx,
+            },
+        )))
+        .build(&mut scene.graph);
+
+        let mut scene_container = SceneContainer::new(Default::default());
+
+        let scene_handle = scene_container.add(scene);
+
+        let mut script_processor = ScriptProcessor::default();
+
+        script_processor.register_scripted_scene(
+            scene_handle,
+            &mut scene_container,
+            &resource_manager,
+        );
+
+        let mut plugins = Default::default();
+
+        // Scripts are not started yet, so there must be no fixed update.
+        script_processor.handle_fixed_update(
+            &mut scene_container,
+            &mut plugins,
+            &resource_manager,
+            0.0,
+            0.0,
+        );
+        assert_eq!(rx.try_recv(), Err(TryRecvError::Empty));
+
+        script_processor.handle_scripts(
+            &mut scene_container,
+            &mut plugins,
+            &resource_manager,
+            0.0,
+            0.0,
+        );
+
+        assert_eq!(rx.try_recv(), Ok(Event::Updated(early)));
+        assert_eq!(rx.try_recv(), Ok(Event::Updated(late)));
+        assert_eq!(rx.try_recv(), Ok(Event::LateUpdated(early)));
+        assert_eq!(rx.try_recv(), Ok(Event::LateUpdated(late)));
+        assert_eq!(rx.try_recv(), Err(TryRecvError::Empty));
+
+        script_processor.handle_fixed_update(
+            &mut scene_container,
+            &mut plugins,
+            &resource_manager,
+            0.0,
+            0.0,
+        );
+
+        assert_eq!(rx.try_recv(), Ok(Event::FixedUpdated(early)));
+        assert_eq!(rx.try_recv(), Ok(Event::FixedUpdated(late)));
+        assert_eq!(rx.try_recv(), Err(TryRecvError::Empty));
+    }
+
+    #[derive(Debug, Clone, Default, Reflect, Visit)]
+    struct FollowScript {
+        target: Handle<Node>,
+    }
+
+    impl_component_provider!(FollowScript);
+
+    impl ScriptTrait for FollowScript {
+        fn on_late_update(&mut self, ctx: &mut ScriptContext) {
+            let position = ctx.scene.graph[self.target].global_position();
+            ctx.scene.graph[ctx.handle]
+                .local_transform_mut()
+                .set_position(position);
+        }
+
+        fn id(&self) -> Uuid {
+            Uuid::new_v4()
+        }
+    }
+
+    #[test]
+    fn test_late_update_transform() {
+        let resource_manager = ResourceManager::new(Default::default());
+        let mut scene = Scene::new();
+
+        let target = PivotBuilder::new(BaseBuilder::new()).build(&mut scene.graph);
+        let follower =
+            PivotBuilder::new(BaseBuilder::new().with_script(Script::new(FollowScript { target })))
+                .build(&mut scene.graph);
+
+        let mut scene_container = SceneContainer::new(Default::default());
+        let scene_handle = scene_container.add(scene);
+
+        let mut script_processor = ScriptProcessor::default();
+        script_processor.register_scripted_scene(
+            scene_handle,
+            &mut scene_container,
+            &resource_manager,
+        );
+
+        let mut plugins = Default::default();
+
+        let graph = &mut scene_container[scene_handle].graph;
+        graph[target]
+            .local_transform_mut()
+            .set_position(Vector3::new(1.0, 2.0, 3.0));
+        graph.update_hierarchical_data();
+
+        script_processor.handle_scripts(
+            &mut scene_container,
+            &mut plugins,
+            &resource_manager,
+            0.0,
+            0.0,
+        );
+
+        // The change made in late update must be visible on the same frame and the modification flags
+        // of the transforms must be preserved.
+        let graph = &scene_container[scene_handle].graph;
+        assert_eq!(
+            graph[follower].global_position(),
+            Vector3::new(1.0, 2.0, 3.0)
+        );
+        assert!(graph[target].transform_modified.get());
+        assert!(graph[follower].transform_modified.get());
+    }
+
+    #[derive(Debug, Clone, Reflect, Visit)]
+    struct HealthScript {
+        health: f32,
+        #[reflect(hidden)]
+        #[visit(skip)]
+        sender: Sender<Event>,
+    }
+
+    impl_component_provider!(HealthScript);
+
+    impl ScriptTrait for HealthScript {
+        fn on_start(&mut self, ctx: &mut ScriptContext) {
+            ctx.message_dispatcher.subscribe_to::<MyMessage>(ctx.handle);
+        }
+
+        fn on_message(
+            &mut self,
+            _message: &mut dyn ScriptMessagePayload,
+            ctx: &mut ScriptMessageContext,
+        ) {
+            self.sender
+                .send(Event::MessageReceived(ctx.handle, ctx.script_index))
+                .unwrap();
+        }
+
+        fn on_deinit(&mut self, ctx: &mut ScriptDeinitContext) {
+            assert_eq!(ctx.script_index, 0);
+            self.sender.send(Event::Destroyed(ctx.node_handle)).unwrap();
+        }
+
+        fn id(&self) -> Uuid {
+            Uuid::new_v4()
+        }
+    }
+
+    #[derive(Debug, Clone, Reflect, Visit)]
+    struct DamageScript {
+        #[reflect(hidden)]
+        #[visit(skip)]
+        sender: Sender<Event>,
+    }
+
+    impl_component_provider!(DamageScript);
+
+    impl ScriptTrait for DamageScript {
+        fn on_update(&mut self, ctx: &mut ScriptContext) {
+            // The script itself is taken out of the node while it is processed.
+            assert!(ctx.sibling_script::<DamageScript>().is_none());
+            assert_eq!(ctx.script_index, 1);
+
+            if let Some(health) = ctx.sibling_script_mut::<HealthScript>() {
+                health.health -= 10.0;
+                self.sender.send(Event::Updated(ctx.handle)).unwrap();
+                ctx.message_sender
+                    .send_to_target(ctx.handle, MyMessage::Foo(10));
+            }
+        }
+
+        fn on_message(
+            &mut self,
+            _message: &mut dyn ScriptMessagePayload,
+            ctx: &mut ScriptMessageContext,
+        ) {
+            self.sender
+                .send(Event::MessageReceived(ctx.handle, ctx.script_index))
+                .unwrap();
+        }
+
+        fn id(&self) -> Uuid {
+            Uuid::new_v4()
+        }
+    }
+
+    #[test]
+    fn test_multiple_scripts() {
+        let resource_manager = ResourceManager::new(Default::default());
+        let mut scene = Scene::new();
+
+        let (tx, rx) = mpsc::channel();
+
+        let node = PivotBuilder::new(
+            BaseBuilder::new()
+                .with_script(Script::new(HealthScript {
+                    health: 100.0,
+                    sender: tx.clone(),
+                }))
+                .with_script(Script::new(DamageScript { sender: tx })),
+        )
+        .build(&mut scene.graph);
+
+        let mut scene_container = SceneContainer::new(Default::default());
+
+        let scene_handle = scene_container.add(scene);
+
+        let mut script_processor = ScriptProcessor::default();
+
+        script_processor.register_scripted_scene(
+            scene_handle,
+            &mut scene_container,
+            &resource_manager,
+        );
+
+        let mut plugins = Default::default();
+
+        script_processor.handle_scripts(
+            &mut scene_container,
+            &mut plugins,
+            &resource_manager,
+            0.0,
+            0.0,
+        );
+
+        // Every script of the node receives the message with its own index.
+        assert_eq!(rx.try_recv(), Ok(Event::Updated(node)));
+        assert_eq!(rx.try_recv(), Ok(Event::MessageReceived(node, 0)));
+        assert_eq!(rx.try_recv(), Ok(Event::MessageReceived(node, 1)));
+        assert_eq!(rx.try_recv(), Err(TryRecvError::Empty));
+
+        let graph = &mut scene_container[scene_handle].graph;
+        assert_eq!(graph[node].script_count(), 2);
+        assert_eq!(
+            graph[node].try_get_script::<HealthScript>().unwrap().health,
+            90.0
+        );
+
+        // Indices of the scripts must remain the same until the next graph update.
+        graph[node].remove_script(0);
+        assert!(graph[node].script_at(0).is_none());
+        assert!(graph[node].script_at(1).is_some());
+
+        // The record must not be removed while the script is waiting for destruction, otherwise the
+        // index of the script would be invalid.
+        graph.update(Default::default(), 0.0, Default::default());
+        assert_eq!(graph[node].script_count(), 2);
+
+        script_processor.handle_scripts(
+            &mut scene_container,
+            &mut plugins,
+            &resource_manager,
+            0.0,
+            0.0,
+        );
+
+        assert_eq!(rx.try_recv(), Ok(Event::Destroyed(node)));
+        assert_eq!(rx.try_recv(), Err(TryRecvError::Empty));
+
+        let graph = &mut scene_container[scene_handle].graph;
+        graph.update(Default::default(), 0.0, Default::default());
+        assert_eq!(graph[node].script_count(), 1);
+        assert!(graph[node].try_get_script::<DamageScript>().is_some());
+    }
+
+    #[derive(Debug, Clone, Reflect, Visit)]
+    enum TaskKind {
+        Ready,
+        Timer,
+        Endless,
+    }
+
+    #[derive(Debug, Clone, Reflect, Visit)]
+    struct TaskScript {
+        #[reflect(hidden)]
+        #[visit(skip)]
+        sender: Sender<Event>,
+        kind: TaskKind,
+    }
+
+    impl_component_provider!(TaskScript);
+
+    impl ScriptTrait for TaskScript {
+        fn on_start(&mut self, ctx: &mut ScriptContext) {
+            match self.kind {
+                TaskKind::Ready => {
+                    ctx.spawn_task(
+                        futures::future::ready(42u32),
+                        |result, script: &mut TaskScript, ctx| {
+                            script
+                                .sender
+                                .send(Event::TaskCompleted(ctx.handle, result))
+                                .unwrap();
+                        },
+                    );
+                }
+                TaskKind::Timer => {
+                    ctx.spawn_task(
+                        task::wait_for(Duration::from_millis(20)),
+                        |_, script: &mut TaskScript, ctx| {
+                            script
+                                .sender
+                                .send(Event::TaskCompleted(ctx.handle, 0))
+                                .unwrap();
+                        },
+                    );
+                }
+                TaskKind::Endless => {
+                    ctx.spawn_task(
+                        futures::future::pending::<()>(),
+                        |_, _script: &mut TaskScript, _ctx| unreachable!(),
+                    );
+                }
+            }
+        }
+
+        fn id(&self) -> Uuid {
+            Uuid::new_v4()
+        }
+    }
+
+    #[test]
+    fn test_script_tasks() {
+        let resource_manager = ResourceManager::new(Default::default());
+        let mut scene = Scene::new();
+
+        let (tx, rx) = mpsc::channel();
+
+        let node = PivotBuilder::new(BaseBuilder::new().with_script(Script::new(TaskScript {
+            sender: tx.clone(),
+            kind: TaskKind::Ready,
+        })))
+        .build(&mut scene.graph);
+
+        let timer_node =
+            PivotBuilder::new(BaseBuilder::new().with_script(Script::new(TaskScript {
+                sender: tx.clone(),
+                kind: TaskKind::Timer,
+            })))
+            .build(&mut scene.graph);
+
+        let endless_node =
+            PivotBuilder::new(BaseBuilder::new().with_script(Script::new(TaskScript {
+                sender: tx,
+                kind: TaskKind::Endless,
+            })))
+            .build(&mut scene.graph);
+
+        let mut scene_container = SceneContainer::new(Default::default());
+
+        let scene_handle = scene_container.add(scene);
+
+        let mut script_processor = ScriptProcessor::default();
+
+        script_processor.register_scripted_scene(
+            scene_handle,
+            &mut scene_container,
+            &resource_manager,
+        );
+
+        let mut plugins = Default::default();
+
+        // Spawn the tasks and disable the node with the timer, its result must be kept until the node
+        // is enabled.
+        script_processor.handle_scripts(
+            &mut scene_container,
+            &mut plugins,
+            &resource_manager,
+            0.0,
+            0.0,
+        );
+        let graph = &mut scene_container[scene_handle].graph;
+        graph[timer_node].set_enabled(false);
+        graph.update_hierarchical_data();
+
+        // Tasks are executed on other threads, so wait until the result is delivered.
+        let mut result = Err(TryRecvError::Empty);
+        for _ in 0..500 {
+            script_processor.handle_scripts(
+                &mut scene_container,
+                &mut plugins,
+                &resource_manager,
+                0.0,
+                0.0,
+            );
+
+            result = rx.try_recv();
+            if result.is_ok() {
+                break;
+            }
+
+            std::thread::sleep(Duration::from_millis(10));
+        }
+        assert_eq!(result, Ok(Event::TaskCompleted(node, 42)));
+
+        // Let the timer expire.
+        std::thread::sleep(Duration::from_millis(100));
+        for _ in 0..3 {
+            script_processor.handle_scripts(
+                &mut scene_container,
+                &mut plugins,
+                &resource_manager,
+                0.0,
+                0.0,
+            );
+        }
+        assert_eq!(rx.try_recv(), Err(TryRecvError::Empty));
+        assert_eq!(
+            script_processor.scripted_scenes[0].task_pool.task_count(),
+            2
+        );
+
+        let graph = &mut scene_container[scene_handle].graph;
+        graph[timer_node].set_enabled(true);
+        graph.update_hierarchical_data();
+
+        script_processor.handle_scripts(
+            &mut scene_container,
+            &mut plugins,
+            &resource_manager,
+            0.0,
+            0.0,
+        );
+        assert_eq!(rx.try_recv(), Ok(Event::TaskCompleted(timer_node, 0)));
+        assert_eq!(
+            script_processor.scripted_scenes[0].task_pool.task_count(),
+            1
+        );
+
+        // Deletion of the node must cancel its tasks.
+        scene_container[scene_handle]
+            .graph
+            .remove_node(endless_node);
+
+        script_processor.handle_scripts(
+            &mut scene_container,
+            &mut plugins,
+            &resource_manager,
+            0.0,
+            0.0,
+        );
+        assert_eq!(
+            script_processor.scripted_scenes[0].task_pool.task_count(),
+            0
+        );
+        assert_eq!(rx.try_recv(), Err(TryRecvError::Empty));
+    }
 }
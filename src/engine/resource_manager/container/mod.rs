@@ -3,19 +3,22 @@
 
 use crate::{
     asset::{Resource, ResourceData, ResourceLoadError, ResourceState},
-    core::{futures::future::JoinAll, variable::InheritableVariable, VecExtensions},
+    core::{
+        futures::future::JoinAll, profiler, reflect::prelude::*, variable::InheritableVariable,
+        VecExtensions,
+    },
     engine::resource_manager::{
         container::{
             entry::{TimedEntry, DEFAULT_RESOURCE_LIFETIME},
             event::{ResourceEvent, ResourceEventBroadcaster},
         },
-        loader::ResourceLoader,
+        loader::{BoxedLoaderFuture, ResourceLoader},
         options::ImportOptions,
         task::TaskPool,
     },
     utils::log::Log,
 };
-use std::{future::Future, ops::Deref, path::Path, sync::Arc};
+use std::{any::Any, future::Future, ops::Deref, path::Path, sync::Arc};
 
 pub mod entry;
 pub mod event;
@@ -24,6 +27,50 @@ pub(crate) trait Container {
     fn try_reload_resource_from_path(&mut self, path: &Path) -> bool;
 }
 
+/// Type-erased resource container, it is used to store containers of custom resource types. See
+/// [`crate::engine::resource_manager::ResourceManager::register_resource_type`] for more info.
+pub(crate) trait CustomContainer: Container {
+    fn as_any(&self) -> &dyn Any;
+
+    fn as_any_mut(&mut self) -> &mut dyn Any;
+
+    fn as_container_mut(&mut self) -> &mut dyn Container;
+
+    /// Returns a boxed resource of the type of the container.
+    fn request_any(&mut self, path: &Path) -> Box<dyn Any>;
+
+    fn update(&mut self, dt: f32);
+
+    fn destroy_unused(&mut self);
+
+    fn len(&self) -> usize;
+
+    fn count_pending_resources(&self) -> usize;
+
+    fn count_loaded_resources(&self) -> usize;
+
+    /// Returns a set of checks, each of them tells whether a respective resource is loaded (or failed
+    /// to load).
+    fn loaded_checks(&self) -> Vec<Box<dyn Fn() -> bool + Send>>;
+
+    /// Reloads every resource and returns futures that could be used to wait until the resources are
+    /// reloaded.
+    fn reload_resources(&mut self) -> Vec<BoxedLoaderFuture>;
+
+    /// Restores a "shallow" resource after deserialization, if the entity is a resource of the type of
+    /// the container. Returns a future that could be used to wait until the resource is loaded.
+    fn try_restore_entity(&mut self, entity: &mut dyn Reflect) -> Option<BoxedLoaderFuture>;
+}
+
+fn wait_future<T>(resource: T) -> BoxedLoaderFuture
+where
+    T: Future + Send + 'static,
+{
+    Box::pin(async move {
+        let _ = resource.await;
+    })
+}
+
 /// Generic container for any resource in the engine. Main purpose of the container is to
 /// track resources life time and remove unused timed-out resources. It also provides useful
 /// methods to search resources, count loaded or pending, wait until all resources are loading,
@@ -214,6 +261,22 @@ where
         self.resources.iter().map(|t| t.value.clone()).collect()
     }
 
+    fn spawn_loading(&self, resource: T, reload: bool) {
+        // Do not waste time on formatting if the timeline is not recorded.
+        let scope_name = profiler::is_timeline_enabled()
+            .then(|| format!("Load {}", resource.state().path().display()));
+        let loading = self.loader.load(
+            resource,
+            self.default_import_options.clone(),
+            self.event_broadcaster.clone(),
+            reload,
+        );
+        self.task_pool.spawn_task(async move {
+            let _scope = scope_name.map(profiler::timeline_scope);
+            loading.await
+        });
+    }
+
     /// Tries to load a resources at a given path.
     pub fn request<P: AsRef<Path>>(&mut self, path: P) -> T {
         match self.find(path.as_ref()) {
@@ -224,12 +287,7 @@ where
                 )));
                 self.push(resource.clone());
 
-                self.task_pool.spawn_task(self.loader.load(
-                    resource.clone(),
-                    self.default_import_options.clone(),
-                    self.event_broadcaster.clone(),
-                    false,
-                ));
+                self.spawn_loading(resource.clone(), false);
 
                 resource
             }
@@ -241,12 +299,7 @@ where
         if !resource.is_loading() {
             resource.state().switch_to_pending_state();
 
-            self.task_pool.spawn_task(self.loader.load(
-                resource,
-                self.default_import_options.clone(),
-                self.event_broadcaster.clone(),
-                true,
-            ));
+            self.spawn_loading(resource, true);
         }
     }
 
@@ -261,12 +314,7 @@ where
 
         for resource in resources.iter().cloned() {
             resource.state().switch_to_pending_state();
-            self.task_pool.spawn_task(self.loader.load(
-                resource,
-                self.default_import_options.clone(),
-                self.event_broadcaster.clone(),
-                true,
-            ));
+            self.spawn_loading(resource, true);
         }
 
         resources
@@ -322,3 +370,81 @@ where
         }
     }
 }
+
+impl<T, R, E, O> CustomContainer for ResourceContainer<T, O>
+where
+    T: Deref<Target = Resource<R, E>>
+        + Clone
+        + Send
+        + Future
+        + From<Resource<R, E>>
+        + Reflect
+        + 'static,
+    R: ResourceData,
+    E: ResourceLoadError,
+    O: ImportOptions + 'static,
+{
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn as_container_mut(&mut self) -> &mut dyn Container {
+        self
+    }
+
+    fn request_any(&mut self, path: &Path) -> Box<dyn Any> {
+        Box::new(self.request(path))
+    }
+
+    fn update(&mut self, dt: f32) {
+        ResourceContainer::update(self, dt)
+    }
+
+    fn destroy_unused(&mut self) {
+        ResourceContainer::destroy_unused(self)
+    }
+
+    fn len(&self) -> usize {
+        ResourceContainer::len(self)
+    }
+
+    fn count_pending_resources(&self) -> usize {
+        ResourceContainer::count_pending_resources(self)
+    }
+
+    fn count_loaded_resources(&self) -> usize {
+        ResourceContainer::count_loaded_resources(self)
+    }
+
+    fn loaded_checks(&self) -> Vec<Box<dyn Fn() -> bool + Send>> {
+        self.iter()
+            .cloned()
+            .map(|resource| {
+                Box::new(move || !matches!(*resource.state(), ResourceState::Pending { .. }))
+                    as Box<dyn Fn() -> bool + Send>
+            })
+            .collect()
+    }
+
+    fn reload_resources(&mut self) -> Vec<BoxedLoaderFuture> {
+        ResourceContainer::reload_resources(self)
+            .into_iter()
+            .map(wait_future)
+            .collect()
+    }
+
+    fn try_restore_entity(&mut self, entity: &mut dyn Reflect) -> Option<BoxedLoaderFuture> {
+        let mut future = None;
+        entity.downcast_mut::<T>(&mut |result| {
+            if let Some(resource) = result {
+                self.try_restore_resource(resource);
+                future = Some(wait_future(resource.clone()));
+            }
+        });
+        future
+    }
+}
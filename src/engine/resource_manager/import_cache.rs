@@ -0,0 +1,245 @@
+//! Import cache stores processed (imported) forms of resources, so the expensive import step (FBX
+//! parsing, texture compression, mip-map generation, etc.) is done only once. See [`ImportCache`] docs
+//! for more info.
+
+use crate::{
+    core::{
+        parking_lot::RwLock,
+        visitor::{Visit, VisitResult, Visitor},
+    },
+    utils::log::Log,
+};
+use serde::Serialize;
+use sha1_smol::Sha1;
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+/// Version of the data stored in the cache. It must be incremented every time when native format of any
+/// cached resource changes, so every outdated entry will be invalidated.
+const VERSION: u32 = 0;
+
+const EXTENSION: &str = "cache";
+
+/// A key of an entry in the import cache. It is unique for every combination of source file content and
+/// its import options. The key consists of SHA-1 digests of the path of the source file and of its content
+/// (with import options), the digests are stable across application runs and platforms.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ImportCacheKey {
+    source: String,
+    content: String,
+}
+
+impl ImportCacheKey {
+    fn file_name(&self) -> String {
+        format!("{}-{}.{}", self.source, self.content, EXTENSION)
+    }
+
+    // The full key is stored in every entry, so an entry is never used for another key.
+    fn visit(&mut self, visitor: &mut Visitor) -> VisitResult {
+        let mut region = visitor.enter_region("Key")?;
+        self.source.visit("Source", &mut region)?;
+        self.content.visit("Content", &mut region)
+    }
+}
+
+/// Import cache is a directory that stores processed (imported) forms of resources in engine's native
+/// format: compressed textures with mip-maps, models converted from FBX, etc. Every entry is keyed by a
+/// hash of the content of a source file and its import options (`.options` file), so any change of the
+/// source or its import options invalidates the entry and the resource is imported again. Outdated entries
+/// are removed when a new entry for the same source file is stored.
+///
+/// The cache is disabled by default, it could be enabled by setting a directory for it:
+///
+/// ```rust,no_run
+/// # use fyrox::engine::resource_manager::ResourceManager;
+/// fn enable_import_cache(resource_manager: &ResourceManager) {
+///     resource_manager
+///         .state()
+///         .import_cache()
+///         .set_directory(Some(".cache/import".into()));
+/// }
+/// ```
+///
+/// The cache is shared between every loader of the resource manager, cloning the cache produces a new
+/// reference to the same cache. The editor enables the cache automatically (`.cache/import` directory of a
+/// project).
+///
+/// ## Limitations
+///
+/// Models converted from FBX store paths of the textures that were found at conversion time, so the cache
+/// should be cleared (see [`ImportCache::clear`]) if the textures were moved.
+#[derive(Clone, Default, Debug)]
+pub struct ImportCache {
+    directory: Arc<RwLock<Option<PathBuf>>>,
+}
+
+impl ImportCache {
+    /// Creates new disabled import cache.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets a directory for the cache, `None` disables the cache. The directory will be created on first
+    /// write.
+    pub fn set_directory(&self, directory: Option<PathBuf>) {
+        *self.directory.write() = directory;
+    }
+
+    /// Returns current directory of the cache, `None` means that the cache is disabled.
+    pub fn directory(&self) -> Option<PathBuf> {
+        self.directory.read().clone()
+    }
+
+    /// Returns `true` if the cache is enabled.
+    pub fn is_enabled(&self) -> bool {
+        self.directory.read().is_some()
+    }
+
+    /// Removes every entry of the cache.
+    pub fn clear(&self) -> std::io::Result<()> {
+        if let Some(directory) = self.directory() {
+            if directory.exists() {
+                for entry in std::fs::read_dir(directory)? {
+                    let path = entry?.path();
+                    if path.extension().map_or(false, |ext| ext == EXTENSION) {
+                        std::fs::remove_file(path)?;
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Creates a key for the given source file with the given content and import options. Returns `None`
+    /// if the cache is disabled.
+    pub fn key<O: Serialize>(
+        &self,
+        source_path: &Path,
+        source_data: &[u8],
+        import_options: &O,
+    ) -> Option<ImportCacheKey> {
+        if !self.is_enabled() {
+            return None;
+        }
+
+        let source = Sha1::from(source_path.to_string_lossy().as_bytes())
+            .digest()
+            .to_string();
+
+        let mut hasher = Sha1::new();
+        hasher.update(&VERSION.to_le_bytes());
+        hasher.update(&(source_data.len() as u64).to_le_bytes());
+        hasher.update(source_data);
+        hasher.update(ron::to_string(import_options).ok()?.as_bytes());
+        let content = hasher.digest().to_string();
+
+        Some(ImportCacheKey { source, content })
+    }
+
+    /// Tries to load an entry from the cache using the given function. Returns `None` if there is no such
+    /// entry or it is corrupted.
+    pub fn load<T, F>(&self, key: &ImportCacheKey, func: F) -> Option<T>
+    where
+        F: FnOnce(&mut Visitor) -> Option<T>,
+    {
+        let path = self.directory()?.join(key.file_name());
+        let data = std::fs::read(path).ok()?;
+        let mut visitor = Visitor::load_from_memory(data).ok()?;
+
+        let mut stored_key = ImportCacheKey {
+            source: Default::default(),
+            content: Default::default(),
+        };
+        stored_key.visit(&mut visitor).ok()?;
+        if &stored_key != key {
+            return None;
+        }
+
+        func(&mut visitor)
+    }
+
+    /// Stores an entry in the cache using the given function and removes outdated entries of the same
+    /// source file. Errors are not fatal, they're just written to the log.
+    pub fn store<F>(&self, key: &ImportCacheKey, func: F)
+    where
+        F: FnOnce(&mut Visitor) -> VisitResult,
+    {
+        let directory = match self.directory() {
+            Some(directory) => directory,
+            None => return,
+        };
+
+        if let Err(e) = std::fs::create_dir_all(&directory) {
+            Log::warn(format!(
+                "Unable to create import cache directory {}. Reason: {:?}",
+                directory.display(),
+                e
+            ));
+            return;
+        }
+
+        // Remove outdated entries of the source.
+        let prefix = format!("{}-", key.source);
+        if let Ok(entries) = std::fs::read_dir(&directory) {
+            for entry in entries.flatten() {
+                if entry.file_name().to_string_lossy().starts_with(&prefix) {
+                    let _ = std::fs::remove_file(entry.path());
+                }
+            }
+        }
+
+        let mut visitor = Visitor::new();
+        let path = directory.join(key.file_name());
+        let mut stored_key = key.clone();
+        if let Err(e) = stored_key
+            .visit(&mut visitor)
+            .and_then(|_| func(&mut visitor))
+            .and_then(|_| visitor.save_binary(&path))
+        {
+            Log::warn(format!(
+                "Unable to write import cache entry {}. Reason: {:?}",
+                path.display(),
+                e
+            ));
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{core::visitor::Visit, engine::resource_manager::import_cache::ImportCache};
+    use std::path::Path;
+
+    #[test]
+    fn test_import_cache() {
+        let directory =
+            std::env::temp_dir().join(format!("fyrox_import_cache_{}", std::process::id()));
+
+        let cache = ImportCache::new();
+        assert!(cache.key(Path::new("foo.png"), b"data", &()).is_none());
+
+        cache.set_directory(Some(directory.clone()));
+        let key = cache.key(Path::new("foo.png"), b"data", &()).unwrap();
+        cache.store(&key, |visitor| 123u32.visit("Value", visitor));
+
+        let value = cache.load(&key, |visitor| {
+            let mut value = 0u32;
+            value.visit("Value", visitor).ok().map(|_| value)
+        });
+        assert_eq!(value, Some(123));
+
+        // Changed source invalidates the entry and removes it.
+        let new_key = cache.key(Path::new("foo.png"), b"new data", &()).unwrap();
+        assert!(cache.load(&new_key, |_| Some(())).is_none());
+        cache.store(&new_key, |visitor| 321u32.visit("Value", visitor));
+        assert!(cache.load(&key, |_| Some(())).is_none());
+        assert!(cache.load(&new_key, |_| Some(())).is_some());
+
+        cache.clear().unwrap();
+        assert!(cache.load(&new_key, |_| Some(())).is_none());
+
+        std::fs::remove_dir_all(directory).unwrap();
+    }
+}
@@ -5,6 +5,7 @@ use crate::engine::resource_manager::{
 };
 use std::{future::Future, pin::Pin};
 
+pub mod animation;
 pub mod curve;
 pub mod model;
 pub mod shader;
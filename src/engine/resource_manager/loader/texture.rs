@@ -1,18 +1,67 @@
 //! Texture loader.
 
 use crate::{
-    core::instant,
+    core::{instant, io},
     engine::resource_manager::{
         container::event::ResourceEventBroadcaster,
+        import_cache::ImportCache,
         loader::{BoxedLoaderFuture, ResourceLoader},
         options::try_get_import_settings,
     },
-    resource::texture::{Texture, TextureData, TextureImportOptions},
+    resource::texture::{Texture, TextureData, TextureError, TextureImportOptions},
     utils::log::Log,
 };
+use std::path::Path;
 
 /// Default implementation for texture loading.
-pub struct TextureLoader;
+#[derive(Default)]
+pub struct TextureLoader {
+    /// Import cache to store compressed textures with mip-maps, so the processing is done only once.
+    pub import_cache: ImportCache,
+}
+
+impl TextureLoader {
+    /// Creates new texture loader that uses the given import cache. Use [`Default::default`] to create a
+    /// loader with disabled import cache.
+    pub fn new(import_cache: ImportCache) -> Self {
+        Self { import_cache }
+    }
+}
+
+async fn load_texture(
+    path: &Path,
+    import_options: &TextureImportOptions,
+    import_cache: &ImportCache,
+) -> Result<TextureData, TextureError> {
+    let data = io::load_file(path).await?;
+
+    let key = import_cache.key(path, &data, import_options);
+    if let Some(key) = key.as_ref() {
+        if let Some(mut texture) = import_cache.load(key, |visitor| {
+            let mut texture = TextureData::default();
+            texture
+                .visit_with_content("Texture", visitor)
+                .ok()
+                .map(|_| texture)
+        }) {
+            texture.set_path(path);
+            return Ok(texture);
+        }
+    }
+
+    let gen_mip_maps = import_options.minification_filter.is_using_mip_mapping();
+    let mut texture =
+        TextureData::load_from_memory(&data, import_options.compression, gen_mip_maps)?;
+    texture.set_path(path);
+
+    if let Some(key) = key.as_ref() {
+        import_cache.store(key, |visitor| {
+            texture.visit_with_content("Texture", visitor)
+        });
+    }
+
+    Ok(texture)
+}
 
 impl ResourceLoader<Texture, TextureImportOptions> for TextureLoader {
     fn load(
@@ -22,6 +71,8 @@ impl ResourceLoader<Texture, TextureImportOptions> for TextureLoader {
         event_broadcaster: ResourceEventBroadcaster<Texture>,
         reload: bool,
     ) -> BoxedLoaderFuture {
+        let import_cache = self.import_cache.clone();
+
         Box::pin(async move {
             let path = texture.state().path().to_path_buf();
 
@@ -29,11 +80,8 @@ impl ResourceLoader<Texture, TextureImportOptions> for TextureLoader {
                 .await
                 .unwrap_or(default_import_options);
 
-            let gen_mip_maps = import_options.minification_filter.is_using_mip_mapping();
-
             let time = instant::Instant::now();
-            match TextureData::load_from_file(&path, import_options.compression, gen_mip_maps).await
-            {
+            match load_texture(&path, &import_options, &import_cache).await {
                 Ok(mut raw_texture) => {
                     Log::info(format!(
                         "Texture {:?} is loaded in {:?}!",
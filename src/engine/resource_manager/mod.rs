@@ -6,32 +6,39 @@ use crate::{
         futures::future::join_all,
         make_relative_path,
         parking_lot::{Mutex, MutexGuard},
+        reflect::prelude::*,
     },
     engine::{
         resource_manager::{
-            container::{Container, ResourceContainer},
+            container::{Container, CustomContainer, ResourceContainer},
+            import_cache::ImportCache,
             loader::{
+                animation::AnimationLoader,
                 curve::CurveLoader,
                 model::ModelLoader,
                 shader::ShaderLoader,
                 sound::{SoundBufferImportOptions, SoundBufferLoader},
                 texture::TextureLoader,
-                ResourceLoader,
+                BoxedLoaderFuture, ResourceLoader,
             },
+            options::ImportOptions,
             task::TaskPool,
         },
         SerializationContext,
     },
     material::shader::{Shader, ShaderImportOptions},
     resource::{
+        animation::{AnimationImportOptions, AnimationResource},
         curve::{CurveImportOptions, CurveResource},
         model::{Model, ModelImportOptions},
         texture::{Texture, TextureError, TextureImportOptions, TextureState},
     },
     utils::{log::Log, watcher::FileSystemWatcher},
 };
+use fxhash::FxHashMap;
 use fyrox_sound::buffer::SoundBufferResource;
 use std::{
+    any::TypeId,
     fmt::{Debug, Display, Formatter},
     future::Future,
     ops::Deref,
@@ -40,9 +47,16 @@ use std::{
 };
 
 pub mod container;
+pub mod import_cache;
 pub mod loader;
 pub mod options;
-mod task;
+pub mod task;
+
+#[cfg(not(target_arch = "wasm32"))]
+type BoxedCustomContainer = Box<dyn CustomContainer + Send>;
+
+#[cfg(target_arch = "wasm32")]
+type BoxedCustomContainer = Box<dyn CustomContainer>;
 
 /// Storage of resource containers.
 pub struct ContainersStorage {
@@ -60,6 +74,12 @@ pub struct ContainersStorage {
 
     /// Container for curve resources.
     pub curves: ResourceContainer<CurveResource, CurveImportOptions>,
+
+    /// Container for animation resources.
+    pub animations: ResourceContainer<AnimationResource, AnimationImportOptions>,
+
+    /// Containers for custom resource types, see [`ResourceManager::register_resource_type`].
+    custom: FxHashMap<TypeId, BoxedCustomContainer>,
 }
 
 impl ContainersStorage {
@@ -103,6 +123,53 @@ impl ContainersStorage {
         self.curves.set_loader(loader);
     }
 
+    /// Sets a custom animation loader.
+    pub fn set_animation_loader<L>(&mut self, loader: L)
+    where
+        L: 'static + ResourceLoader<AnimationResource, AnimationImportOptions>,
+    {
+        self.animations.set_loader(loader);
+    }
+
+    /// Returns a reference to a container of a custom resource type. Returns `None` if the type is
+    /// not registered or registered with different import options.
+    pub fn custom<T, O>(&self) -> Option<&ResourceContainer<T, O>>
+    where
+        T: Clone + 'static,
+        O: ImportOptions + 'static,
+    {
+        self.custom
+            .get(&TypeId::of::<T>())
+            .and_then(|container| container.as_any().downcast_ref())
+    }
+
+    /// Returns a reference to a container of a custom resource type. Returns `None` if the type is
+    /// not registered or registered with different import options.
+    pub fn custom_mut<T, O>(&mut self) -> Option<&mut ResourceContainer<T, O>>
+    where
+        T: Clone + 'static,
+        O: ImportOptions + 'static,
+    {
+        self.custom
+            .get_mut(&TypeId::of::<T>())
+            .and_then(|container| container.as_any_mut().downcast_mut())
+    }
+
+    /// Returns `true` if the given custom resource type is registered.
+    pub fn is_custom_registered<T: 'static>(&self) -> bool {
+        self.custom.contains_key(&TypeId::of::<T>())
+    }
+
+    /// Restores a "shallow" resource of a custom type after deserialization.
+    pub(crate) fn try_restore_custom_resource(
+        &mut self,
+        entity: &mut dyn Reflect,
+    ) -> Option<BoxedLoaderFuture> {
+        self.custom
+            .values_mut()
+            .find_map(|container| container.try_restore_entity(entity))
+    }
+
     /// Wait until all resources are loaded (or failed to load).
     pub fn get_wait_context(&self) -> ResourceWaitContext {
         ResourceWaitContext {
@@ -111,6 +178,12 @@ impl ContainersStorage {
             shaders: self.shaders.resources(),
             textures: self.textures.resources(),
             sound_buffers: self.sound_buffers.resources(),
+            animations: self.animations.resources(),
+            custom: self
+                .custom
+                .values()
+                .flat_map(|container| container.loaded_checks())
+                .collect(),
         }
     }
 }
@@ -124,6 +197,8 @@ pub struct ResourceWaitContext {
     shaders: Vec<Shader>,
     textures: Vec<Texture>,
     sound_buffers: Vec<SoundBufferResource>,
+    animations: Vec<AnimationResource>,
+    custom: Vec<Box<dyn Fn() -> bool + Send>>,
 }
 
 impl ResourceWaitContext {
@@ -155,6 +230,8 @@ impl ResourceWaitContext {
             && check_container(&self.shaders)
             && check_container(&self.textures)
             && check_container(&self.sound_buffers)
+            && check_container(&self.animations)
+            && self.custom.iter().all(|is_loaded| is_loaded())
     }
 }
 
@@ -162,6 +239,8 @@ impl ResourceWaitContext {
 pub struct ResourceManagerState {
     containers_storage: Option<ContainersStorage>,
     watcher: Option<FileSystemWatcher>,
+    task_pool: Arc<TaskPool>,
+    import_cache: ImportCache,
 }
 
 /// See module docs.
@@ -208,10 +287,14 @@ impl ResourceManager {
             state: Arc::new(Mutex::new(ResourceManagerState::new())),
         };
 
-        let task_pool = Arc::new(TaskPool::new());
+        let task_pool = resource_manager.state().task_pool();
+        let import_cache = resource_manager.state().import_cache().clone();
 
         resource_manager.state().containers_storage = Some(ContainersStorage {
-            textures: ResourceContainer::new(task_pool.clone(), Box::new(TextureLoader)),
+            textures: ResourceContainer::new(
+                task_pool.clone(),
+                Box::new(TextureLoader::new(import_cache)),
+            ),
             models: ResourceContainer::new(
                 task_pool.clone(),
                 Box::new(ModelLoader {
@@ -221,7 +304,14 @@ impl ResourceManager {
             ),
             sound_buffers: ResourceContainer::new(task_pool.clone(), Box::new(SoundBufferLoader)),
             shaders: ResourceContainer::new(task_pool.clone(), Box::new(ShaderLoader)),
-            curves: ResourceContainer::new(task_pool, Box::new(CurveLoader)),
+            curves: ResourceContainer::new(task_pool.clone(), Box::new(CurveLoader)),
+            animations: ResourceContainer::new(
+                task_pool,
+                Box::new(AnimationLoader {
+                    resource_manager: resource_manager.clone(),
+                }),
+            ),
+            custom: Default::default(),
         });
 
         resource_manager
@@ -351,6 +441,149 @@ impl ResourceManager {
         self.state().containers_mut().curves.request(path)
     }
 
+    /// Tries to load a new animation resource from given path or get instance of existing, if any.
+    /// The path could point either to a native animation file or to a model file (FBX, RGS), in the
+    /// latter case the first animation of the model will be extracted. This method is asynchronous,
+    /// it immediately returns an animation resource which can be shared across multiple places, the
+    /// loading may fail, but it is internal state of the animation resource.
+    ///
+    /// # Async/.await
+    ///
+    /// Each animation resource implements Future trait and can be used in async contexts.
+    pub fn request_animation<P: AsRef<Path>>(&self, path: P) -> AnimationResource {
+        self.state().containers_mut().animations.request(path)
+    }
+
+    /// Registers a custom resource type with its loader. After registration, resources of the type could
+    /// be requested using [`Self::request_resource`], they are hot-reloaded (if there is a file system
+    /// watcher), destroyed when unused and restored after deserialization of a scene (the same as built-in
+    /// resources). Registering the same resource type again replaces its loader.
+    ///
+    /// A resource type is usually defined using [`crate::asset::define_new_resource`] macro, its import
+    /// options could be loaded by the loader using [`options::try_get_import_settings`].
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use fyrox::{
+    ///     asset::{define_new_resource, Resource, ResourceData},
+    ///     core::{reflect::prelude::*, visitor::prelude::*},
+    ///     engine::resource_manager::{
+    ///         container::event::ResourceEventBroadcaster,
+    ///         loader::{BoxedLoaderFuture, ResourceLoader},
+    ///         options::ImportOptions,
+    ///         ResourceManager,
+    ///     },
+    /// };
+    /// use serde::{Deserialize, Serialize};
+    /// use std::{
+    ///     borrow::Cow,
+    ///     path::{Path, PathBuf},
+    /// };
+    ///
+    /// #[derive(Debug, Visit, Default)]
+    /// pub struct DialogState {
+    ///     path: PathBuf,
+    ///     lines: Vec<String>,
+    /// }
+    ///
+    /// impl ResourceData for DialogState {
+    ///     fn path(&self) -> Cow<Path> {
+    ///         Cow::Borrowed(&self.path)
+    ///     }
+    ///
+    ///     fn set_path(&mut self, path: PathBuf) {
+    ///         self.path = path;
+    ///     }
+    /// }
+    ///
+    /// define_new_resource!(
+    ///     #[derive(Reflect)]
+    ///     #[reflect(hide_all)]
+    ///     Dialog<DialogState, std::io::Error>
+    /// );
+    ///
+    /// #[derive(Serialize, Deserialize, Default, Clone)]
+    /// pub struct DialogImportOptions {}
+    ///
+    /// impl ImportOptions for DialogImportOptions {}
+    ///
+    /// struct DialogLoader;
+    ///
+    /// impl ResourceLoader<Dialog, DialogImportOptions> for DialogLoader {
+    ///     fn load(
+    ///         &self,
+    ///         dialog: Dialog,
+    ///         _default_import_options: DialogImportOptions,
+    ///         event_broadcaster: ResourceEventBroadcaster<Dialog>,
+    ///         reload: bool,
+    ///     ) -> BoxedLoaderFuture {
+    ///         Box::pin(async move {
+    ///             let path = dialog.state().path().to_path_buf();
+    ///             match std::fs::read_to_string(&path) {
+    ///                 Ok(text) => {
+    ///                     let lines = text.lines().map(|l| l.to_owned()).collect();
+    ///                     dialog.state().commit_ok(DialogState { path, lines });
+    ///                     event_broadcaster.broadcast_loaded_or_reloaded(dialog, reload);
+    ///                 }
+    ///                 Err(error) => dialog.state().commit_error(path, error),
+    ///             }
+    ///         })
+    ///     }
+    /// }
+    ///
+    /// fn register(resource_manager: &ResourceManager) {
+    ///     resource_manager.register_resource_type(DialogLoader);
+    ///
+    ///     let dialog = resource_manager
+    ///         .request_resource::<Dialog, _>("data/intro.dialog")
+    ///         .unwrap();
+    /// }
+    /// ```
+    pub fn register_resource_type<T, R, E, O, L>(&self, loader: L)
+    where
+        T: Deref<Target = Resource<R, E>>
+            + Clone
+            + Send
+            + Future
+            + From<Resource<R, E>>
+            + Reflect
+            + 'static,
+        R: ResourceData,
+        E: ResourceLoadError,
+        O: ImportOptions + Send + 'static,
+        L: ResourceLoader<T, O> + 'static,
+    {
+        let mut state = self.state();
+        let task_pool = state.task_pool();
+        let containers = state.containers_mut();
+        if let Some(container) = containers.custom_mut::<T, O>() {
+            container.set_loader(loader);
+        } else {
+            containers.custom.insert(
+                TypeId::of::<T>(),
+                Box::new(ResourceContainer::new(task_pool, Box::new(loader))),
+            );
+        }
+    }
+
+    /// Tries to load a resource of a custom type from given path or get instance of existing, if any.
+    /// Returns `None` if the type is not registered, see [`Self::register_resource_type`]. This method
+    /// is asynchronous, it immediately returns a resource which can be shared across multiple places,
+    /// the loading may fail, but it is internal state of the resource.
+    pub fn request_resource<T, P>(&self, path: P) -> Option<T>
+    where
+        T: 'static,
+        P: AsRef<Path>,
+    {
+        self.state()
+            .containers_mut()
+            .custom
+            .get_mut(&TypeId::of::<T>())
+            .and_then(|container| container.request_any(path.as_ref()).downcast::<T>().ok())
+            .map(|resource| *resource)
+    }
+
     /// Reloads every loaded texture. This method is asynchronous, internally it uses thread pool
     /// to run reload on separate thread per texture.
     pub async fn reload_textures(&self) {
@@ -379,6 +612,13 @@ impl ResourceManager {
         join_all(resources).await;
     }
 
+    /// Reloads every loaded animation resource. This method is asynchronous, internally it uses thread pool
+    /// to run reload on separate thread per resource.
+    pub async fn reload_animations(&self) {
+        let resources = self.state().containers_mut().animations.reload_resources();
+        join_all(resources).await;
+    }
+
     /// Reloads every loaded sound buffer. This method is asynchronous, internally it uses thread pool
     /// to run reload on separate thread per sound buffer.
     pub async fn reload_sound_buffers(&self) {
@@ -390,6 +630,19 @@ impl ResourceManager {
         join_all(resources).await;
     }
 
+    /// Reloads every loaded resource of custom types. This method is asynchronous, internally it uses thread
+    /// pool to run reload on separate thread per resource.
+    pub async fn reload_custom_resources(&self) {
+        let resources = self
+            .state()
+            .containers_mut()
+            .custom
+            .values_mut()
+            .flat_map(|container| container.reload_resources())
+            .collect::<Vec<_>>();
+        join_all(resources).await;
+    }
+
     /// Reloads all loaded resources. Normally it should never be called, because it is **very** heavy
     /// method! This method is asynchronous, it uses all available CPU power to reload resources as
     /// fast as possible.
@@ -400,6 +653,8 @@ impl ResourceManager {
             self.reload_sound_buffers(),
             self.reload_shaders(),
             self.reload_curve_resources(),
+            self.reload_animations(),
+            self.reload_custom_resources(),
         );
     }
 }
@@ -409,9 +664,23 @@ impl ResourceManagerState {
         Self {
             containers_storage: None,
             watcher: None,
+            task_pool: Arc::new(TaskPool::new()),
+            import_cache: Default::default(),
         }
     }
 
+    /// Returns a task pool that is used to load resources. It could be used to run any other
+    /// asynchronous tasks as well.
+    pub fn task_pool(&self) -> Arc<TaskPool> {
+        self.task_pool.clone()
+    }
+
+    /// Returns the import cache, that is used by the loaders to store processed forms of resources. The
+    /// cache is disabled by default, see [`ImportCache`] docs for more info.
+    pub fn import_cache(&self) -> &ImportCache {
+        &self.import_cache
+    }
+
     /// Sets resource watcher which will track any modifications in file system and forcing
     /// the manager to reload changed resources. By default there is no watcher, since it
     /// may be an undesired effect to reload resources at runtime. This is very useful thing
@@ -442,6 +711,12 @@ impl ResourceManagerState {
             + containers.models.count_pending_resources()
             + containers.shaders.count_pending_resources()
             + containers.curves.count_pending_resources()
+            + containers.animations.count_pending_resources()
+            + containers
+                .custom
+                .values()
+                .map(|container| container.count_pending_resources())
+                .sum::<usize>()
     }
 
     /// Returns total amount of loaded resources.
@@ -452,6 +727,12 @@ impl ResourceManagerState {
             + containers.models.count_loaded_resources()
             + containers.shaders.count_loaded_resources()
             + containers.curves.count_loaded_resources()
+            + containers.animations.count_loaded_resources()
+            + containers
+                .custom
+                .values()
+                .map(|container| container.count_loaded_resources())
+                .sum::<usize>()
     }
 
     /// Returns total amount of registered resources.
@@ -462,6 +743,12 @@ impl ResourceManagerState {
             + containers.models.len()
             + containers.shaders.len()
             + containers.curves.len()
+            + containers.animations.len()
+            + containers
+                .custom
+                .values()
+                .map(|container| container.len())
+                .sum::<usize>()
     }
 
     /// Returns percentage of loading progress. This method is useful to show progress on
@@ -485,6 +772,10 @@ impl ResourceManagerState {
         containers.textures.destroy_unused();
         containers.shaders.destroy_unused();
         containers.curves.destroy_unused();
+        containers.animations.destroy_unused();
+        for container in containers.custom.values_mut() {
+            container.destroy_unused();
+        }
     }
 
     /// Update resource containers and do hot-reloading.
@@ -501,6 +792,10 @@ impl ResourceManagerState {
         containers.sound_buffers.update(dt);
         containers.shaders.update(dt);
         containers.curves.update(dt);
+        containers.animations.update(dt);
+        for container in containers.custom.values_mut() {
+            container.update(dt);
+        }
 
         if let Some(watcher) = self.watcher.as_ref() {
             if let Some(evt) = watcher.try_get_event() {
@@ -514,7 +809,15 @@ impl ResourceManagerState {
                                 &mut containers.sound_buffers as &mut dyn Container,
                                 &mut containers.shaders as &mut dyn Container,
                                 &mut containers.curves as &mut dyn Container,
-                            ] {
+                                &mut containers.animations as &mut dyn Container,
+                            ]
+                            .into_iter()
+                            .chain(
+                                containers
+                                    .custom
+                                    .values_mut()
+                                    .map(|container| container.as_container_mut()),
+                            ) {
                                 if container.try_reload_resource_from_path(&relative_path) {
                                     Log::info(format!(
                                         "File {} was changed, trying to reload a respective resource...",
@@ -531,3 +834,107 @@ impl ResourceManagerState {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        asset::{define_new_resource, Resource, ResourceData},
+        core::{futures::executor::block_on, reflect::prelude::*, visitor::prelude::*},
+        engine::resource_manager::{
+            container::event::ResourceEventBroadcaster,
+            loader::{BoxedLoaderFuture, ResourceLoader},
+            options::ImportOptions,
+            ResourceManager,
+        },
+    };
+    use serde::{Deserialize, Serialize};
+    use std::{
+        borrow::Cow,
+        path::{Path, PathBuf},
+        sync::{
+            atomic::{AtomicUsize, Ordering},
+            Arc,
+        },
+    };
+
+    #[derive(Debug, Visit, Default)]
+    pub struct DialogState {
+        path: PathBuf,
+        lines: Vec<String>,
+    }
+
+    impl ResourceData for DialogState {
+        fn path(&self) -> Cow<Path> {
+            Cow::Borrowed(&self.path)
+        }
+
+        fn set_path(&mut self, path: PathBuf) {
+            self.path = path;
+        }
+    }
+
+    define_new_resource!(
+        #[derive(Reflect)]
+        #[reflect(hide_all)]
+        Dialog<DialogState, std::io::Error>
+    );
+
+    #[derive(Serialize, Deserialize, Default, Clone)]
+    pub struct DialogImportOptions {}
+
+    impl ImportOptions for DialogImportOptions {}
+
+    struct DialogLoader {
+        load_count: Arc<AtomicUsize>,
+    }
+
+    impl ResourceLoader<Dialog, DialogImportOptions> for DialogLoader {
+        fn load(
+            &self,
+            dialog: Dialog,
+            _default_import_options: DialogImportOptions,
+            event_broadcaster: ResourceEventBroadcaster<Dialog>,
+            reload: bool,
+        ) -> BoxedLoaderFuture {
+            self.load_count.fetch_add(1, Ordering::SeqCst);
+            Box::pin(async move {
+                let path = dialog.state().path().to_path_buf();
+                dialog.state().commit_ok(DialogState {
+                    path,
+                    lines: vec!["Hello".to_string()],
+                });
+                event_broadcaster.broadcast_loaded_or_reloaded(dialog, reload);
+            })
+        }
+    }
+
+    #[test]
+    fn test_custom_resource_type() {
+        let resource_manager = ResourceManager::new(Default::default());
+
+        // Unregistered types cannot be requested.
+        assert!(resource_manager
+            .request_resource::<Dialog, _>("intro.dialog")
+            .is_none());
+
+        let load_count = Arc::new(AtomicUsize::new(0));
+        resource_manager.register_resource_type(DialogLoader {
+            load_count: load_count.clone(),
+        });
+
+        let a = resource_manager
+            .request_resource::<Dialog, _>("intro.dialog")
+            .unwrap();
+        let b = resource_manager
+            .request_resource::<Dialog, _>("intro.dialog")
+            .unwrap();
+
+        // Both requests must return the same shared resource, which is loaded only once.
+        assert_eq!(a.key(), b.key());
+        assert_eq!(load_count.load(Ordering::SeqCst), 1);
+
+        let a = block_on(a).unwrap();
+        assert_eq!(a.data_ref().lines, ["Hello"]);
+        assert_eq!(block_on(b).unwrap().key(), a.key());
+    }
+}
@@ -1,13 +1,24 @@
+//! Task pool is used to run asynchronous tasks (resource loading, etc.) in background.
+
 #[cfg(not(target_arch = "wasm32"))]
 use crate::core::futures::executor::ThreadPool;
 use std::future::Future;
 
+/// Task pool runs asynchronous tasks using a thread pool on every platform except WebAssembly,
+/// where tasks are executed by the browser's event loop.
 pub struct TaskPool {
     #[cfg(not(target_arch = "wasm32"))]
     thread_pool: ThreadPool,
 }
 
+impl Default for TaskPool {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl TaskPool {
+    /// Creates new task pool.
     pub fn new() -> Self {
         Self {
             #[cfg(not(target_arch = "wasm32"))]
@@ -15,6 +26,7 @@ impl TaskPool {
         }
     }
 
+    /// Spawns a new task, the task will be executed in background.
     #[cfg(target_arch = "wasm32")]
     pub fn spawn_task<F>(&self, future: F)
     where
@@ -23,6 +35,7 @@ impl TaskPool {
         crate::core::wasm_bindgen_futures::spawn_local(future);
     }
 
+    /// Spawns a new task, the task will be executed in background.
     #[cfg(not(target_arch = "wasm32"))]
     pub fn spawn_task<F>(&self, future: F)
     where
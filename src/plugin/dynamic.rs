@@ -0,0 +1,405 @@
+//! Dynamic plugins are plugins that are loaded from dynamic libraries (`.dll`, `.so`, `.dylib`). Such plugins
+//! could be reloaded while the game (or the editor) is running, which significantly reduces iteration times.
+//! See [`DynamicPlugin`] docs for more info.
+
+use crate::{core::uuid::Uuid, plugin::PluginConstructor, utils::log::Log};
+use std::{
+    fmt::{Display, Formatter},
+    path::{Path, PathBuf},
+    time::{Duration, Instant, SystemTime},
+};
+
+/// Name of the symbol that must be exported by every dynamic plugin library. The symbol must be a function
+/// with the following signature `fn() -> Box<dyn PluginConstructor>`. The easiest way to export it is to
+/// use the following code in your game crate (which must have `crate-type = ["cdylib"]` in its manifest):
+///
+/// ```rust,no_run
+/// # use fyrox::{
+/// #     core::pool::Handle,
+/// #     plugin::{Plugin, PluginConstructor, PluginContext},
+/// #     scene::Scene,
+/// # };
+/// # struct MyPlugin;
+/// # impl Plugin for MyPlugin {}
+/// struct MyPluginConstructor;
+///
+/// impl PluginConstructor for MyPluginConstructor {
+///     fn create_instance(&self, _override_scene: Handle<Scene>, _context: PluginContext) -> Box<dyn Plugin> {
+///         Box::new(MyPlugin)
+///     }
+/// }
+///
+/// #[no_mangle]
+/// pub fn fyrox_plugin() -> Box<dyn PluginConstructor> {
+///     Box::new(MyPluginConstructor)
+/// }
+/// ```
+pub const PLUGIN_ENTRY_POINT: &[u8] = b"fyrox_plugin";
+
+/// Signature of the plugin entry point. See [`PLUGIN_ENTRY_POINT`] for more info.
+pub type PluginEntryPoint = fn() -> Box<dyn PluginConstructor>;
+
+/// An error that may occur during dynamic plugin loading.
+#[derive(Debug)]
+pub enum DynamicPluginError {
+    /// An i/o error has occurred.
+    Io(std::io::Error),
+
+    /// Dynamic library cannot be loaded.
+    Library(libloading::Error),
+
+    /// Plugin registers custom scene nodes, such plugins cannot be reloaded, because scene nodes are
+    /// not serialized during the reloading.
+    CustomNodesUnsupported,
+}
+
+impl Display for DynamicPluginError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DynamicPluginError::Io(v) => {
+                write!(f, "An i/o error has occurred {v:?}")
+            }
+            DynamicPluginError::Library(v) => {
+                write!(f, "Unable to load dynamic library. Reason: {v:?}")
+            }
+            DynamicPluginError::CustomNodesUnsupported => {
+                write!(
+                    f,
+                    "Dynamic plugins cannot register custom scene nodes, use scripts instead!"
+                )
+            }
+        }
+    }
+}
+
+impl From<std::io::Error> for DynamicPluginError {
+    fn from(e: std::io::Error) -> Self {
+        Self::Io(e)
+    }
+}
+
+impl From<libloading::Error> for DynamicPluginError {
+    fn from(e: libloading::Error) -> Self {
+        Self::Library(e)
+    }
+}
+
+/// Dynamic plugin is a plugin, that is loaded from a dynamic library. The engine tracks changes of the
+/// library file and reloads the plugin when the file was changed (if enabled). During reloading, the
+/// engine serializes the state of every script instance (which type was registered by the plugin) and
+/// the state of the plugin itself (see [`crate::plugin::Plugin::visit_state`]), then unloads the old
+/// library, loads the new one and restores the state.
+///
+/// # Important notes
+///
+/// Rust does not have stable ABI, so the library must be compiled using exactly the same compiler and
+/// the same version of the engine as the host executable. Any type that is shared between the host and
+/// the plugin must have the same layout on both sides.
+///
+/// Any object that was created by the plugin and stored outside of scripts or the plugin itself
+/// (for example, boxed trait objects in user interface, closures in resource loaders, etc.) will become
+/// invalid after reloading. Make sure, that your plugin keeps all such objects in its own state or in
+/// scripts.
+///
+/// # Scripts
+///
+/// Every script instance of the plugin in a running scene is deinitialized
+/// ([`crate::script::ScriptTrait::on_deinit`]) before reloading. A new instance is then created by the
+/// new library, its state is restored and it is initialized and started again (`on_init` and `on_start`
+/// are called), so the scripts could re-create objects that cannot survive reloading. Queued script
+/// messages are dropped, message subscriptions of the nodes with the scripts are removed (subscribe in
+/// `on_start` to restore them) and tasks of the scripts are cancelled.
+///
+/// # Library files
+///
+/// The original library file is never loaded directly, instead it is copied to a new file with unique
+/// name next to the original one. This prevents the library from being locked by the OS (on Windows)
+/// and prevents the OS from returning cached library on repeated loading. The copies are removed when
+/// they're unloaded, the copies left from previous runs (the last copy cannot be removed on some
+/// platforms while it is loaded) are removed when the plugin is loaded.
+pub struct DynamicPlugin {
+    path: PathBuf,
+    loaded_path: PathBuf,
+    // Must be dropped only after every object created by the plugin was destroyed.
+    library: Option<libloading::Library>,
+    previous: Option<(libloading::Library, PathBuf)>,
+    modification_time: Option<SystemTime>,
+    // Modification time of the file and the time when the change was noticed, it is used to wait
+    // until the compiler finishes writing the file.
+    pending_change: Option<(SystemTime, Instant)>,
+    reload_when_changed: bool,
+    generation: usize,
+    pub(crate) constructor_index: usize,
+    pub(crate) script_types: Vec<Uuid>,
+}
+
+impl DynamicPlugin {
+    pub(crate) fn load(
+        path: &Path,
+        reload_when_changed: bool,
+        constructor_index: usize,
+    ) -> Result<(Self, Box<dyn PluginConstructor>), DynamicPluginError> {
+        remove_stale_copies(path);
+
+        let mut plugin = Self {
+            path: path.to_path_buf(),
+            loaded_path: Default::default(),
+            library: None,
+            previous: None,
+            modification_time: None,
+            pending_change: None,
+            reload_when_changed,
+            generation: 0,
+            constructor_index,
+            script_types: Default::default(),
+        };
+
+        let constructor = plugin.load_library()?;
+
+        Ok((plugin, constructor))
+    }
+
+    /// Returns a path to the source library file.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Returns `true` if the plugin will be reloaded automatically on every change of the source library
+    /// file.
+    pub fn is_reload_when_changed(&self) -> bool {
+        self.reload_when_changed
+    }
+
+    /// Enables or disables automatic reloading of the plugin.
+    pub fn set_reload_when_changed(&mut self, reload_when_changed: bool) {
+        self.reload_when_changed = reload_when_changed;
+    }
+
+    fn file_modification_time(&self) -> Option<SystemTime> {
+        std::fs::metadata(&self.path)
+            .and_then(|m| m.modified())
+            .ok()
+    }
+
+    /// Returns `true` if the source library was changed since last (re)loading.
+    pub fn is_changed(&self) -> bool {
+        let modification_time = self.file_modification_time();
+        modification_time.is_some() && modification_time != self.modification_time
+    }
+
+    // Returns `true` if the source library was changed and the file was not modified for some time after
+    // that, so it is safe to load it.
+    pub(crate) fn poll_changes(&mut self) -> bool {
+        const DEBOUNCE_TIME: Duration = Duration::from_millis(500);
+
+        if !self.reload_when_changed || !self.is_changed() {
+            self.pending_change = None;
+            return false;
+        }
+
+        let modification_time = match self.file_modification_time() {
+            Some(modification_time) => modification_time,
+            None => return false,
+        };
+
+        match self.pending_change {
+            Some((pending_time, noticed_at)) if pending_time == modification_time => {
+                noticed_at.elapsed() >= DEBOUNCE_TIME
+            }
+            _ => {
+                self.pending_change = Some((modification_time, Instant::now()));
+                false
+            }
+        }
+    }
+
+    // Copies the source library to a new file with unique name and returns the path of the copy.
+    fn copy_library(&mut self) -> Result<PathBuf, DynamicPluginError> {
+        self.generation += 1;
+
+        let loaded_path = hot_reload_path(&self.path, self.generation);
+
+        self.modification_time = self.file_modification_time();
+        self.pending_change = None;
+
+        std::fs::copy(&self.path, &loaded_path)?;
+
+        Ok(loaded_path)
+    }
+
+    // Loads a new copy of the library. The current library (if any) is kept loaded, it must be unloaded
+    // by calling `unload_previous` when every object created by it is destroyed.
+    pub(crate) fn load_library(
+        &mut self,
+    ) -> Result<Box<dyn PluginConstructor>, DynamicPluginError> {
+        let loaded_path = self.copy_library()?;
+
+        // SAFETY: Loading of arbitrary library is inherently unsafe, the library is trusted by the user
+        // (see docs for the struct).
+        let result = unsafe {
+            libloading::Library::new(&loaded_path).and_then(|library| {
+                let constructor = {
+                    let entry_point = library.get::<PluginEntryPoint>(PLUGIN_ENTRY_POINT)?;
+                    entry_point()
+                };
+                Ok((library, constructor))
+            })
+        };
+
+        let (library, constructor) = match result {
+            Ok(result) => result,
+            Err(e) => {
+                remove_copy(&loaded_path);
+                return Err(e.into());
+            }
+        };
+
+        self.unload_previous();
+
+        if let Some(previous) = self.library.replace(library) {
+            self.previous = Some((
+                previous,
+                std::mem::replace(&mut self.loaded_path, loaded_path),
+            ));
+        } else {
+            self.loaded_path = loaded_path;
+        }
+
+        Ok(constructor)
+    }
+
+    pub(crate) fn unload_previous(&mut self) {
+        if let Some((library, path)) = self.previous.take() {
+            drop(library);
+            remove_copy(&path);
+        }
+    }
+}
+
+// Returns a path of a copy of the library with the given generation: `game.dll` -> `game_hot_reload_1.dll`.
+fn hot_reload_path(path: &Path, generation: usize) -> PathBuf {
+    let mut file_name = path.file_stem().unwrap_or_default().to_os_string();
+    file_name.push(format!("_hot_reload_{}", generation));
+    let mut loaded_path = path.with_file_name(file_name);
+    if let Some(extension) = path.extension() {
+        loaded_path.set_extension(extension);
+    }
+    loaded_path
+}
+
+fn remove_copy(path: &Path) {
+    if let Err(e) = std::fs::remove_file(path) {
+        Log::warn(format!(
+            "Unable to remove temporary plugin library {}. Reason: {:?}",
+            path.display(),
+            e
+        ));
+    }
+}
+
+// Removes copies of the library, that were left from previous runs.
+fn remove_stale_copies(path: &Path) {
+    let prefix = format!(
+        "{}_hot_reload_",
+        path.file_stem().unwrap_or_default().to_string_lossy()
+    );
+
+    let directory = match path.parent() {
+        Some(directory) if directory.as_os_str().is_empty() => Path::new("."),
+        Some(directory) => directory,
+        None => return,
+    };
+
+    if let Ok(entries) = std::fs::read_dir(directory) {
+        for entry in entries.flatten() {
+            let entry_path = entry.path();
+            let is_copy = entry_path.extension() == path.extension()
+                && entry_path
+                    .file_stem()
+                    .and_then(|stem| stem.to_str())
+                    .and_then(|stem| stem.strip_prefix(&prefix))
+                    .map_or(false, |generation| generation.parse::<usize>().is_ok());
+            if is_copy {
+                remove_copy(&entry_path);
+            }
+        }
+    }
+}
+
+impl Drop for DynamicPlugin {
+    fn drop(&mut self) {
+        self.unload_previous();
+
+        // The current library is intentionally leaked, there still could be objects created by the
+        // plugin (for example, script constructors in a shared serialization context or scripts in
+        // resources) that will be destroyed after the engine. Unloading the library before that will
+        // cause a crash.
+        if let Some(library) = self.library.take() {
+            std::mem::forget(library);
+
+            // The file of a loaded library could be removed on every platform except Windows, on Windows
+            // it will be removed on the next run.
+            #[cfg(not(windows))]
+            remove_copy(&self.loaded_path);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::plugin::dynamic::{hot_reload_path, remove_stale_copies, DynamicPlugin};
+    use std::path::Path;
+
+    #[test]
+    fn test_library_copies() {
+        let directory =
+            std::env::temp_dir().join(format!("fyrox_dynamic_plugin_{}", std::process::id()));
+        std::fs::create_dir_all(&directory).unwrap();
+
+        let path = directory.join("game.so");
+        std::fs::write(&path, b"library").unwrap();
+
+        // Copies from a previous run and unrelated files.
+        std::fs::write(hot_reload_path(&path, 3), b"stale").unwrap();
+        std::fs::write(directory.join("game_hot_reload_3.txt"), b"other").unwrap();
+        std::fs::write(directory.join("other_hot_reload_1.so"), b"other").unwrap();
+
+        assert_eq!(
+            hot_reload_path(Path::new("bin/game.dll"), 2),
+            Path::new("bin/game_hot_reload_2.dll")
+        );
+
+        remove_stale_copies(&path);
+        assert!(!hot_reload_path(&path, 3).exists());
+        assert!(directory.join("game_hot_reload_3.txt").exists());
+        assert!(directory.join("other_hot_reload_1.so").exists());
+        assert!(path.exists());
+
+        let mut plugin = DynamicPlugin {
+            path: path.clone(),
+            loaded_path: Default::default(),
+            library: None,
+            previous: None,
+            modification_time: None,
+            pending_change: None,
+            reload_when_changed: true,
+            generation: 0,
+            constructor_index: 0,
+            script_types: Default::default(),
+        };
+
+        let first = plugin.copy_library().unwrap();
+        let second = plugin.copy_library().unwrap();
+        assert_eq!(plugin.generation, 2);
+        assert_eq!(first, hot_reload_path(&path, 1));
+        assert_eq!(second, hot_reload_path(&path, 2));
+        assert_eq!(std::fs::read(&second).unwrap(), b"library");
+        assert!(!plugin.is_changed());
+
+        remove_stale_copies(&path);
+        assert!(!first.exists());
+        assert!(!second.exists());
+
+        std::fs::remove_dir_all(&directory).unwrap();
+    }
+}
@@ -2,8 +2,11 @@
 
 #![warn(missing_docs)]
 
+#[cfg(not(target_arch = "wasm32"))]
+pub mod dynamic;
+
 use crate::{
-    core::pool::Handle,
+    core::{pool::Handle, visitor::prelude::*},
     engine::{
         resource_manager::ResourceManager, GraphicsContext, PerformanceStatistics,
         SerializationContext,
@@ -40,6 +43,18 @@ pub trait PluginConstructor {
         #[allow(unused_variables)] override_scene: Handle<Scene>,
         context: PluginContext,
     ) -> Box<dyn Plugin>;
+
+    /// The method is called when the engine re-creates plugin instance after hot reloading of a dynamic
+    /// plugin (see [`dynamic::DynamicPlugin`]). The state of the previous instance will be restored right
+    /// after this method using [`Plugin::visit_state`], so there is no need to create any scenes here. By
+    /// default, it calls [`Self::create_instance`].
+    fn create_instance_for_hot_reload(
+        &self,
+        override_scene: Handle<Scene>,
+        context: PluginContext,
+    ) -> Box<dyn Plugin> {
+        self.create_instance(override_scene, context)
+    }
 }
 
 /// Contains plugin environment for the registration stage.
@@ -182,6 +197,16 @@ pub trait Plugin: BasePlugin {
     ) {
     }
 
+    /// Called once per rendered frame, right before rendering. Unlike [`Self::update`], the rate of calls is
+    /// not stable - it depends on the frame rate, `context.dt` contains the time (in seconds) that passed since
+    /// the previous frame.
+    fn on_frame(
+        &mut self,
+        #[allow(unused_variables)] context: &mut PluginContext,
+        #[allow(unused_variables)] control_flow: &mut ControlFlow,
+    ) {
+    }
+
     /// The method is called when the main window receives an event from the OS. The main use of
     /// the method is to respond to some external events, for example an event from keyboard or
     /// gamepad. See [`Event`] docs for more info.
@@ -219,4 +244,13 @@ pub trait Plugin: BasePlugin {
         #[allow(unused_variables)] control_flow: &mut ControlFlow,
     ) {
     }
+
+    /// The method is used to save and restore the state of the plugin when it is reloaded from a dynamic
+    /// library (see [`dynamic::DynamicPlugin`]). Visit every field that must survive hot reloading, for
+    /// example handles of scenes, that were created by the plugin. It is not called for static plugins
+    /// by the engine, but it is also used to store the state of a plugin in a save game (see
+    /// [`crate::scene::save_game::SaveGame::store_plugin_state`]).
+    fn visit_state(&mut self, #[allow(unused_variables)] visitor: &mut Visitor) -> VisitResult {
+        Ok(())
+    }
 }
@@ -0,0 +1,269 @@
+//! Animation resource holds an [`Animation`] clip that could be shared across multiple animation players.
+//! See [`AnimationResource`] docs for more info.
+
+use crate::{
+    animation::Animation,
+    asset::{define_new_resource, Resource, ResourceData},
+    core::{io::FileLoadError, pool::Handle, reflect::prelude::*, uuid::Uuid, visitor::prelude::*},
+    engine::resource_manager::{options::ImportOptions, ResourceManager},
+    resource::model::ModelLoadError,
+    scene::{animation::AnimationPlayer, node::Node},
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    borrow::Cow,
+    fmt::{Display, Formatter},
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+/// An error that may occur during animation resource loading.
+#[derive(Debug)]
+pub enum AnimationResourceError {
+    /// An i/o error has occurred.
+    Io(FileLoadError),
+
+    /// An error that may occur due to version incompatibilities.
+    Visit(VisitError),
+
+    /// Source model of the animation has failed to load.
+    Model(Option<Arc<ModelLoadError>>),
+
+    /// Source model does not have any animations.
+    NoAnimation,
+}
+
+impl Display for AnimationResourceError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AnimationResourceError::Io(v) => {
+                write!(f, "A file load error has occurred {v:?}")
+            }
+            AnimationResourceError::Visit(v) => {
+                write!(
+                    f,
+                    "An error that may occur due to version incompatibilities. {v:?}"
+                )
+            }
+            AnimationResourceError::Model(v) => {
+                write!(f, "Unable to load source model. Reason: {v:?}")
+            }
+            AnimationResourceError::NoAnimation => {
+                write!(f, "Source model does not contain any animations!")
+            }
+        }
+    }
+}
+
+impl From<FileLoadError> for AnimationResourceError {
+    fn from(e: FileLoadError) -> Self {
+        Self::Io(e)
+    }
+}
+
+impl From<VisitError> for AnimationResourceError {
+    fn from(e: VisitError) -> Self {
+        Self::Visit(e)
+    }
+}
+
+/// State of the [`AnimationResource`].
+#[derive(Debug, Visit, Default)]
+pub struct AnimationResourceState {
+    pub(crate) path: PathBuf,
+
+    /// Actual animation clip. Targets of its tracks are meaningless, because the clip is not bound to any
+    /// scene. Use [`Self::track_targets`] to find the actual nodes.
+    #[visit(skip)]
+    pub animation: Animation,
+
+    /// Names of the nodes animated by respective (by index) tracks of the animation. The names are used to
+    /// bind the clip to a node hierarchy of an animation player.
+    #[visit(skip)]
+    pub track_targets: Vec<String>,
+
+    /// A unique id, that changes every time when the resource is loaded (or reloaded). It is used to find
+    /// out whether the animations that uses the resource should be synchronized with it.
+    #[visit(skip)]
+    pub revision: Uuid,
+}
+
+impl ResourceData for AnimationResourceState {
+    fn path(&self) -> Cow<Path> {
+        Cow::Borrowed(&self.path)
+    }
+
+    fn set_path(&mut self, path: PathBuf) {
+        self.path = path;
+    }
+}
+
+impl AnimationResourceState {
+    /// Creates new animation resource state from an animation and a respective set of names of nodes animated
+    /// by each track of the animation.
+    pub fn new(animation: Animation, track_targets: Vec<String>) -> Self {
+        Self {
+            path: Default::default(),
+            animation,
+            track_targets,
+            revision: Uuid::new_v4(),
+        }
+    }
+
+    /// Load an animation resource from the specific file path. The file could be either a native animation
+    /// file (usually with `anim` extension) or any model file supported by the engine (FBX, RGS). In the latter
+    /// case the first animation of the first animation player of the model will be extracted.
+    pub async fn from_file(
+        path: &Path,
+        resource_manager: ResourceManager,
+    ) -> Result<Self, AnimationResourceError> {
+        let is_model = path.extension().map_or(false, |ext| {
+            let ext = ext.to_string_lossy().to_lowercase();
+            ext == "fbx" || ext == "rgs"
+        });
+
+        let mut state = if is_model {
+            Self::from_model(path, resource_manager).await?
+        } else {
+            let mut visitor = Visitor::load_binary(path).await?;
+            let mut animation = Animation::default();
+            animation.visit("Animation", &mut visitor)?;
+            let mut track_targets = Vec::<String>::new();
+            track_targets.visit("TrackTargets", &mut visitor)?;
+            Self::new(animation, track_targets)
+        };
+
+        state.path = path.to_path_buf();
+
+        Ok(state)
+    }
+
+    async fn from_model(
+        path: &Path,
+        resource_manager: ResourceManager,
+    ) -> Result<Self, AnimationResourceError> {
+        let model = resource_manager
+            .request_model(path)
+            .await
+            .map_err(AnimationResourceError::Model)?;
+
+        let data = model.data_ref();
+        let graph = &data.get_scene().graph;
+
+        let animation = graph
+            .linear_iter()
+            .filter_map(|n| n.query_component_ref::<AnimationPlayer>())
+            .find_map(|p| p.animations().iter().next())
+            .ok_or(AnimationResourceError::NoAnimation)?;
+
+        let mut animation = animation.clone();
+        let mut track_targets = Vec::new();
+        for track in animation.tracks_mut() {
+            track_targets.push(
+                graph
+                    .try_get(track.target())
+                    .map(|n| n.name_owned())
+                    .unwrap_or_default(),
+            );
+            track.set_target(Handle::NONE);
+        }
+
+        Ok(Self::new(animation, track_targets))
+    }
+
+    /// Saves the animation in the native format to the given path.
+    pub fn save(&mut self, path: &Path) -> VisitResult {
+        let mut visitor = Visitor::new();
+        self.animation.visit("Animation", &mut visitor)?;
+        self.track_targets.visit("TrackTargets", &mut visitor)?;
+        visitor.save_binary(path)
+    }
+
+    /// Creates a copy of the animation clip with tracks bound to actual nodes. `find_node` is used to find a
+    /// node by its name, tracks with unknown targets will have [`Handle::NONE`] target.
+    pub fn instantiate<F>(&self, mut find_node: F) -> Animation
+    where
+        F: FnMut(&str) -> Handle<Node>,
+    {
+        let mut animation = self.animation.clone();
+        for (track, name) in animation
+            .tracks_mut()
+            .iter_mut()
+            .zip(self.track_targets.iter())
+        {
+            track.set_target(find_node(name));
+        }
+        animation
+    }
+}
+
+define_new_resource!(
+    /// Animation resource is an animation clip that lives in a separate file and could be shared across
+    /// multiple animation players. Every [`Animation`] in an animation player could be backed by an animation
+    /// resource (see [`Animation::set_resource`]), in this case its tracks and signals are taken from the
+    /// resource and bound to nodes of the player's hierarchy by their names. Such animations are stored in
+    /// scenes without tracks, so a clip is never duplicated in every scene that uses it. The resource supports
+    /// hot reloading, every animation that uses the resource will be synchronized with its new content
+    /// automatically.
+    #[derive(Reflect)]
+    #[reflect(hide_all)]
+    AnimationResource<AnimationResourceState, AnimationResourceError>
+);
+
+/// Import options for animation resource.
+#[derive(Serialize, Deserialize, Default, Clone)]
+pub struct AnimationImportOptions {}
+
+impl ImportOptions for AnimationImportOptions {}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        animation::{
+            container::{TrackDataContainer, TrackValueKind},
+            track::Track,
+            value::ValueBinding,
+            Animation,
+        },
+        asset::{Resource, ResourceState},
+        core::pool::Handle,
+        resource::animation::{AnimationResource, AnimationResourceState},
+        scene::node::Node,
+    };
+
+    #[test]
+    fn test_sync_with_resource() {
+        let mut clip = Animation::default();
+        clip.add_track(Track::new(
+            TrackDataContainer::new(TrackValueKind::Vector3),
+            ValueBinding::Position,
+        ));
+        clip.add_track(Track::new(
+            TrackDataContainer::new(TrackValueKind::Vector3),
+            ValueBinding::Scale,
+        ));
+
+        let resource = AnimationResource::from(Resource::new(ResourceState::Ok(
+            AnimationResourceState::new(clip, vec!["Hips".to_string(), "Unknown".to_string()]),
+        )));
+
+        let hips = Handle::<Node>::new(1, 1);
+        let find_node = |name: &str| {
+            if name == "Hips" {
+                hips
+            } else {
+                Handle::NONE
+            }
+        };
+
+        let mut animation = Animation::default();
+        animation.set_resource(Some(resource));
+        assert!(animation.sync_with_resource(find_node));
+        assert_eq!(animation.tracks().len(), 2);
+        assert_eq!(animation.tracks()[0].target(), hips);
+        assert_eq!(animation.tracks()[1].target(), Handle::NONE);
+
+        // Nothing has changed, so there must be no synchronization.
+        assert!(!animation.sync_with_resource(find_node));
+    }
+}
@@ -5,12 +5,11 @@ mod binary;
 use crate::{
     core::{
         algebra::Vector3,
-        io,
         pool::{Handle, Pool},
     },
     resource::fbx::{document::attribute::FbxAttribute, error::FbxError},
 };
-use std::{io::Cursor, path::Path};
+use std::io::Cursor;
 
 pub struct FbxNode {
     name: String,
@@ -121,9 +120,7 @@ fn is_binary(data: &[u8]) -> bool {
 }
 
 impl FbxDocument {
-    pub async fn new<P: AsRef<Path>>(path: P) -> Result<FbxDocument, FbxError> {
-        let data = io::load_file(path).await?;
-
+    pub fn from_memory(data: Vec<u8>) -> Result<FbxDocument, FbxError> {
         let is_bin = is_binary(&data);
 
         let mut reader = Cursor::new(data);
@@ -954,6 +954,19 @@ pub async fn load_to_scene<P: AsRef<Path>>(
     resource_manager: ResourceManager,
     path: P,
     model_import_options: &ModelImportOptions,
+) -> Result<(), FbxError> {
+    let data = io::load_file(path.as_ref()).await?;
+    load_data_to_scene(scene, resource_manager, path, data, model_import_options).await
+}
+
+/// Same as [`load_to_scene`], but uses the given content of the file instead of reading it. The path is
+/// used to resolve paths of the textures.
+pub(crate) async fn load_data_to_scene<P: AsRef<Path>>(
+    scene: &mut Scene,
+    resource_manager: ResourceManager,
+    path: P,
+    data: Vec<u8>,
+    model_import_options: &ModelImportOptions,
 ) -> Result<(), FbxError> {
     let start_time = Instant::now();
 
@@ -963,7 +976,7 @@ pub async fn load_to_scene<P: AsRef<Path>>(
     );
 
     let now = Instant::now();
-    let fbx = FbxDocument::new(path.as_ref()).await?;
+    let fbx = FbxDocument::from_memory(data)?;
     let parsing_time = now.elapsed().as_millis();
 
     let now = Instant::now();
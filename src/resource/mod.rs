@@ -2,6 +2,7 @@
 
 #![warn(missing_docs)]
 
+pub mod animation;
 pub mod curve;
 pub mod fbx;
 pub mod model;
@@ -23,6 +23,7 @@ use crate::{
     asset::{define_new_resource, Resource, ResourceData},
     core::{
         algebra::{UnitQuaternion, Vector3},
+        io,
         pool::Handle,
         reflect::prelude::*,
         variable::reset_inheritable_properties,
@@ -36,6 +37,7 @@ use crate::{
     scene::{
         animation::AnimationPlayer,
         graph::{map::NodeHandleMap, Graph},
+        mesh::Mesh,
         node::Node,
         Scene, SceneLoader,
     },
@@ -426,6 +428,62 @@ impl From<VisitError> for ModelLoadError {
     }
 }
 
+// Marks every surface of the scene as procedural (or not). Procedural surfaces store their geometry when
+// serialized, it is used to store converted models in the import cache.
+fn set_surfaces_procedural(scene: &Scene, procedural: bool) {
+    for node in scene.graph.linear_iter() {
+        if let Some(mesh) = node.cast::<Mesh>() {
+            for surface in mesh.surfaces() {
+                surface.data().lock().set_procedural(procedural);
+            }
+        }
+    }
+}
+
+async fn load_fbx(
+    path: &Path,
+    serialization_context: Arc<SerializationContext>,
+    resource_manager: ResourceManager,
+    model_import_options: &ModelImportOptions,
+) -> Result<Scene, ModelLoadError> {
+    let data = io::load_file(path).await.map_err(FbxError::from)?;
+
+    let import_cache = resource_manager.state().import_cache().clone();
+    let key = import_cache.key(path, &data, model_import_options);
+
+    if let Some(key) = key.as_ref() {
+        if let Some(loader) = import_cache.load(key, |visitor| {
+            SceneLoader::load("Scene", serialization_context.clone(), visitor).ok()
+        }) {
+            let scene = loader.finish(resource_manager).await;
+            set_surfaces_procedural(&scene, false);
+            return Ok(scene);
+        }
+    }
+
+    let mut scene = Scene::new();
+    if let Some(filename) = path.file_name() {
+        let root = scene.graph.get_root();
+        scene.graph[root].set_name(&filename.to_string_lossy());
+    }
+    fbx::load_data_to_scene(
+        &mut scene,
+        resource_manager,
+        path,
+        data,
+        model_import_options,
+    )
+    .await?;
+
+    if let Some(key) = key.as_ref() {
+        set_surfaces_procedural(&scene, true);
+        import_cache.store(key, |visitor| scene.save("Scene", visitor));
+        set_surfaces_procedural(&scene, false);
+    }
+
+    Ok(scene)
+}
+
 impl ModelData {
     pub(crate) async fn load<P: AsRef<Path>>(
         path: P,
@@ -442,15 +500,10 @@ impl ModelData {
             .to_lowercase();
         let (scene, mapping) = match extension.as_ref() {
             "fbx" => {
-                let mut scene = Scene::new();
-                if let Some(filename) = path.as_ref().file_name() {
-                    let root = scene.graph.get_root();
-                    scene.graph[root].set_name(&filename.to_string_lossy());
-                }
-                fbx::load_to_scene(
-                    &mut scene,
-                    resource_manager,
+                let scene = load_fbx(
                     path.as_ref(),
+                    serialization_context,
+                    resource_manager,
                     &model_import_options,
                 )
                 .await?;
@@ -24,7 +24,7 @@ use crate::{
     asset::{define_new_resource, Resource, ResourceData, ResourceState},
     core::{
         futures::io::Error,
-        io::{self, FileLoadError},
+        io::FileLoadError,
         reflect::prelude::*,
         visitor::{PodVecView, Visit, VisitError, VisitResult, Visitor},
     },
@@ -1191,21 +1191,16 @@ impl TextureData {
         }
     }
 
-    /// Tries to load a texture from a file.
-    ///
-    /// # Notes
-    ///
-    /// It is **not** public because you must use resource manager to load textures from external
-    /// resources.
-    pub(crate) async fn load_from_file<P: AsRef<Path>>(
-        path: P,
-        compression: CompressionOptions,
-        gen_mip_maps: bool,
-    ) -> Result<Self, TextureError> {
-        let data = io::load_file(path.as_ref()).await?;
-        let mut texture = Self::load_from_memory(&data, compression, gen_mip_maps)?;
-        texture.path = path.as_ref().to_path_buf();
-        Ok(texture)
+    /// Visits the texture together with its content, regardless of whether the texture is procedural
+    /// or not. It is used to store processed textures in the import cache.
+    pub(crate) fn visit_with_content(&mut self, name: &str, visitor: &mut Visitor) -> VisitResult {
+        let serialize_content = std::mem::replace(&mut self.serialize_content, true);
+        let result = self.visit(name, visitor);
+        self.serialize_content = serialize_content;
+        if visitor.is_reading() {
+            self.data_hash = data_hash(&self.bytes);
+        }
+        result
     }
 
     /// Creates new texture instance from given parameters.
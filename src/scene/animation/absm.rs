@@ -2,7 +2,7 @@
 //! mixes them in arbitrary way into one animation. See [`AnimationBlendingStateMachine`] docs for more info.
 
 use crate::{
-    animation::machine::Machine,
+    animation::machine::{Event, Machine, PoseNode},
     core::{
         math::aabb::AxisAlignedBoundingBox,
         pool::Handle,
@@ -18,9 +18,31 @@ use crate::{
         node::{Node, NodeTrait, TypeUuidProvider, UpdateContext},
         Scene,
     },
+    script::{RoutingStrategy, ScriptMessageSender},
 };
 use std::ops::{Deref, DerefMut};
 
+/// A script message that is sent by an animation blending state machine (if
+/// [`AnimationBlendingStateMachine::set_dispatch_events`] is enabled) every time when any of its layers produces an
+/// event. The message is routed up in the hierarchy, starting from the state machine node, so any script on the node
+/// or its ancestors can receive it (do not forget to subscribe to the message type).
+#[derive(Clone, Debug, PartialEq)]
+pub struct AbsmEventMessage {
+    /// A handle of the state machine node that produced the event.
+    pub absm: Handle<Node>,
+
+    /// An index of the layer that produced the event.
+    pub layer: usize,
+
+    /// Name of the state (for [`Event::StateEnter`], [`Event::StateLeave`] and [`Event::ActiveStateChanged`]) or
+    /// the transition (for [`Event::ActiveTransitionChanged`]) the event relates to. It is useful to match the event
+    /// against names used in the editor.
+    pub name: String,
+
+    /// The event itself.
+    pub event: Event,
+}
+
 /// Animation blending state machine (ABSM) is a node that takes multiple animations from an animation player and
 /// mixes them in arbitrary way into one animation. Usually, ABSMs are used to animate humanoid characters in games,
 /// by blending multiple states with one or more animations. More info about state machines can be found in
@@ -107,6 +129,8 @@ pub struct AnimationBlendingStateMachine {
     base: Base,
     machine: InheritableVariable<Machine>,
     animation_player: InheritableVariable<Handle<Node>>,
+    #[visit(optional)]
+    dispatch_events: InheritableVariable<bool>,
 }
 
 impl AnimationBlendingStateMachine {
@@ -136,6 +160,64 @@ impl AnimationBlendingStateMachine {
     pub fn animation_player(&self) -> Handle<Node> {
         *self.animation_player
     }
+
+    /// Enables or disables automatic dispatching of layer events to scripts. When enabled, the engine takes every
+    /// event from every layer of the state machine each frame and sends it as [`AbsmEventMessage`] to the scripts
+    /// of the node and its ancestors. The events queue of each layer will be empty after dispatching, so you cannot
+    /// mix this mode with manual polling of the events.
+    pub fn set_dispatch_events(&mut self, dispatch_events: bool) {
+        self.dispatch_events
+            .set_value_and_mark_modified(dispatch_events);
+    }
+
+    /// Returns `true` if the state machine sends layer events to scripts, `false` - otherwise.
+    pub fn is_dispatch_events(&self) -> bool {
+        *self.dispatch_events
+    }
+
+    pub(crate) fn dispatch_events(
+        &mut self,
+        self_handle: Handle<Node>,
+        message_sender: &ScriptMessageSender,
+    ) {
+        if !*self.dispatch_events {
+            return;
+        }
+
+        for (layer_index, layer) in self
+            .machine
+            .get_value_mut_silent()
+            .layers_mut()
+            .iter_mut()
+            .enumerate()
+        {
+            while let Some(event) = layer.pop_event() {
+                let name = match event {
+                    Event::StateEnter(state)
+                    | Event::StateLeave(state)
+                    | Event::ActiveStateChanged { new: state, .. } => {
+                        layer.states().try_borrow(state).map(|s| s.name.clone())
+                    }
+                    Event::ActiveTransitionChanged(transition) => layer
+                        .transitions()
+                        .try_borrow(transition)
+                        .map(|t| t.name().to_owned()),
+                }
+                .unwrap_or_default();
+
+                message_sender.send_hierarchical(
+                    self_handle,
+                    RoutingStrategy::Up,
+                    AbsmEventMessage {
+                        absm: self_handle,
+                        layer: layer_index,
+                        name,
+                        event,
+                    },
+                );
+            }
+        }
+    }
 }
 
 impl TypeUuidProvider for AnimationBlendingStateMachine {
@@ -183,6 +265,16 @@ impl NodeTrait for AnimationBlendingStateMachine {
             // do than instead.
             animation_player.set_auto_apply(false);
 
+            // Make sure that every animation resource used by the machine has a respective animation in the player.
+            let animations = animation_player.animations_mut().get_value_mut_silent();
+            for layer in self.machine.get_value_mut_silent().layers_mut() {
+                for node in layer.nodes_mut().iter_mut() {
+                    if let PoseNode::PlayAnimation(play_animation) = node {
+                        play_animation.resolve_animation_resource(animations);
+                    }
+                }
+            }
+
             let pose = self
                 .machine
                 .get_value_mut_silent()
@@ -215,6 +307,7 @@ pub struct AnimationBlendingStateMachineBuilder {
     base_builder: BaseBuilder,
     machine: Machine,
     animation_player: Handle<Node>,
+    dispatch_events: bool,
 }
 
 impl AnimationBlendingStateMachineBuilder {
@@ -224,6 +317,7 @@ impl AnimationBlendingStateMachineBuilder {
             base_builder,
             machine: Default::default(),
             animation_player: Default::default(),
+            dispatch_events: false,
         }
     }
 
@@ -239,12 +333,20 @@ impl AnimationBlendingStateMachineBuilder {
         self
     }
 
+    /// Enables or disables dispatching of layer events to scripts. See
+    /// [`AnimationBlendingStateMachine::set_dispatch_events`] docs for more info.
+    pub fn with_dispatch_events(mut self, dispatch_events: bool) -> Self {
+        self.dispatch_events = dispatch_events;
+        self
+    }
+
     /// Creates new node.
     pub fn build_node(self) -> Node {
         Node::new(AnimationBlendingStateMachine {
             base: self.base_builder.build_base(),
             machine: self.machine.into(),
             animation_player: self.animation_player.into(),
+            dispatch_events: self.dispatch_events.into(),
         })
     }
 
@@ -2,7 +2,7 @@
 //! See [`AnimationPlayer`] docs for more info.
 
 use crate::{
-    animation::AnimationContainer,
+    animation::{signal::AnimationEvent, Animation, AnimationContainer},
     core::{
         math::aabb::AxisAlignedBoundingBox,
         pool::Handle,
@@ -12,15 +12,52 @@ use crate::{
         visitor::prelude::*,
     },
     scene::{
+        animation::absm::AnimationBlendingStateMachine,
         base::{Base, BaseBuilder},
-        graph::Graph,
+        graph::{Graph, NodePool},
         node::{Node, NodeTrait, TypeUuidProvider, UpdateContext},
     },
+    script::{RoutingStrategy, ScriptMessageSender},
 };
 use std::ops::{Deref, DerefMut};
 
 pub mod absm;
 
+/// A script message that is sent by an animation player (if [`AnimationPlayer::set_dispatch_events`] is enabled)
+/// every time when an animation emits an event. The message is routed up in the hierarchy, starting from the
+/// animation player, so any script on the animation player or its ancestors can receive it. Do not forget to
+/// subscribe to the message type:
+///
+/// ```rust
+/// # use fyrox::{
+/// #     scene::animation::AnimationEventMessage,
+/// #     script::{ScriptContext, ScriptMessageContext, ScriptMessagePayload},
+/// # };
+/// fn on_start(ctx: &mut ScriptContext) {
+///     ctx.message_dispatcher
+///         .subscribe_to::<AnimationEventMessage>(ctx.handle);
+/// }
+///
+/// fn on_message(message: &mut dyn ScriptMessagePayload, ctx: &mut ScriptMessageContext) {
+///     if let Some(message) = message.downcast_ref::<AnimationEventMessage>() {
+///         if message.event.name == "Footstep" {
+///             // Play footstep sound.
+///         }
+///     }
+/// }
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct AnimationEventMessage {
+    /// A handle of the animation player that owns the animation.
+    pub animation_player: Handle<Node>,
+
+    /// A handle of the animation that emitted the event.
+    pub animation: Handle<Animation>,
+
+    /// The event itself. It contains name and id of the signal that emitted the event.
+    pub event: AnimationEvent,
+}
+
 /// Animation player is a node that contains multiple animations. It updates and plays all the animations.
 /// The node could be a source of animations for animation blending state machines. To learn more about
 /// animations, see [`crate::animation::Animation`] docs.
@@ -95,6 +132,8 @@ pub struct AnimationPlayer {
     base: Base,
     animations: InheritableVariable<AnimationContainer>,
     auto_apply: bool,
+    #[visit(optional)]
+    dispatch_events: InheritableVariable<bool>,
 }
 
 impl Default for AnimationPlayer {
@@ -103,6 +142,7 @@ impl Default for AnimationPlayer {
             base: Default::default(),
             animations: Default::default(),
             auto_apply: true,
+            dispatch_events: false.into(),
         }
     }
 }
@@ -137,6 +177,45 @@ impl AnimationPlayer {
     pub fn set_animations(&mut self, animations: AnimationContainer) {
         self.animations.set_value_and_mark_modified(animations);
     }
+
+    /// Enables or disables automatic dispatching of animation events to scripts. When enabled, the engine takes
+    /// every event from every animation of the player each frame and sends it as [`AnimationEventMessage`] to the
+    /// scripts of the player and its ancestors. Keep in mind, that the events queue of each animation will be empty
+    /// after dispatching, so you cannot mix this mode with manual polling of the events.
+    pub fn set_dispatch_events(&mut self, dispatch_events: bool) {
+        self.dispatch_events
+            .set_value_and_mark_modified(dispatch_events);
+    }
+
+    /// Returns `true` if the player sends animation events to scripts, `false` - otherwise.
+    pub fn is_dispatch_events(&self) -> bool {
+        *self.dispatch_events
+    }
+
+    pub(crate) fn dispatch_events(
+        &mut self,
+        self_handle: Handle<Node>,
+        message_sender: &ScriptMessageSender,
+    ) {
+        if !*self.dispatch_events {
+            return;
+        }
+
+        for (animation_handle, animation) in self.animations.get_value_mut_silent().pair_iter_mut()
+        {
+            while let Some(event) = animation.pop_event() {
+                message_sender.send_hierarchical(
+                    self_handle,
+                    RoutingStrategy::Up,
+                    AnimationEventMessage {
+                        animation_player: self_handle,
+                        animation: animation_handle,
+                        event,
+                    },
+                );
+            }
+        }
+    }
 }
 
 impl TypeUuidProvider for AnimationPlayer {
@@ -175,6 +254,12 @@ impl NodeTrait for AnimationPlayer {
     }
 
     fn update(&mut self, context: &mut UpdateContext) {
+        // Bind resource-backed animations to the nodes of the hierarchy the player belongs to.
+        let root = self.parent();
+        for animation in self.animations.get_value_mut_silent().iter_mut() {
+            animation.sync_with_resource(|name| find_node_by_name(context.nodes, root, name));
+        }
+
         self.animations.get_value_mut_silent().update_animations(
             context.nodes,
             self.auto_apply,
@@ -188,6 +273,7 @@ pub struct AnimationPlayerBuilder {
     base_builder: BaseBuilder,
     animations: AnimationContainer,
     auto_apply: bool,
+    dispatch_events: bool,
 }
 
 impl AnimationPlayerBuilder {
@@ -197,6 +283,7 @@ impl AnimationPlayerBuilder {
             base_builder,
             animations: AnimationContainer::new(),
             auto_apply: true,
+            dispatch_events: false,
         }
     }
 
@@ -212,12 +299,20 @@ impl AnimationPlayerBuilder {
         self
     }
 
+    /// Enables or disables dispatching of animation events to scripts. See [`AnimationPlayer::set_dispatch_events`]
+    /// docs for more info.
+    pub fn with_dispatch_events(mut self, dispatch_events: bool) -> Self {
+        self.dispatch_events = dispatch_events;
+        self
+    }
+
     /// Creates an instance of [`AnimationPlayer`] node.
     pub fn build_node(self) -> Node {
         Node::new(AnimationPlayer {
             base: self.base_builder.build_base(),
             animations: self.animations.into(),
             auto_apply: self.auto_apply,
+            dispatch_events: self.dispatch_events.into(),
         })
     }
 
@@ -226,3 +321,28 @@ impl AnimationPlayerBuilder {
         graph.add_node(self.build_node())
     }
 }
+
+fn find_node_by_name(nodes: &NodePool, root: Handle<Node>, name: &str) -> Handle<Node> {
+    let mut stack = vec![root];
+    while let Some(handle) = stack.pop() {
+        if let Some(node) = nodes.try_borrow(handle) {
+            if node.name() == name {
+                return handle;
+            }
+            stack.extend_from_slice(node.children());
+        }
+    }
+    Handle::NONE
+}
+
+/// Sends queued events of every animation player and animation blending state machine in the graph to scripts.
+/// Only nodes with enabled event dispatching are taken into account.
+pub(crate) fn dispatch_animation_events(graph: &mut Graph, message_sender: &ScriptMessageSender) {
+    for (handle, node) in graph.pair_iter_mut() {
+        if let Some(animation_player) = node.query_component_mut::<AnimationPlayer>() {
+            animation_player.dispatch_events(handle, message_sender);
+        } else if let Some(absm) = node.query_component_mut::<AnimationBlendingStateMachine>() {
+            absm.dispatch_events(handle, message_sender);
+        }
+    }
+}
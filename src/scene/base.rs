@@ -270,6 +270,8 @@ pub enum NodeScriptMessage {
         script: Script,
         /// Node handle.
         handle: Handle<Node>,
+        /// Index of the script in the node.
+        script_index: usize,
     },
 }
 
@@ -288,6 +290,51 @@ impl Visit for InstanceId {
     }
 }
 
+/// A script container record of a scene node.
+#[derive(Reflect, Debug, Default)]
+pub struct ScriptRecord {
+    // Script could be `None` if it was moved out of the record for processing or if the record is
+    // in the process of deletion.
+    pub(crate) script: Option<Script>,
+    // The record is marked for deletion, it will be removed from the node on next graph update.
+    // It is needed to keep indices of the scripts stable during script processing.
+    #[reflect(hidden)]
+    pub(crate) should_be_deleted: bool,
+    // The script of the record was sent for destruction, but it wasn't destroyed yet. Records of the
+    // node are not removed until destruction is finished, because destruction messages refer to the
+    // scripts by their indices.
+    #[reflect(hidden)]
+    pub(crate) destruction_pending: bool,
+}
+
+impl Clone for ScriptRecord {
+    fn clone(&self) -> Self {
+        Self {
+            script: self.script.clone(),
+            should_be_deleted: self.should_be_deleted,
+            // Destruction messages are never sent to copies of the record.
+            destruction_pending: false,
+        }
+    }
+}
+
+impl ScriptRecord {
+    /// Creates a new record with the given script.
+    pub fn new(script: Script) -> Self {
+        Self {
+            script: Some(script),
+            should_be_deleted: false,
+            destruction_pending: false,
+        }
+    }
+
+    /// Returns a reference to the script instance. It could be `None` if the script is being processed
+    /// right now.
+    pub fn script(&self) -> Option<&Script> {
+        self.script.as_ref()
+    }
+}
+
 /// Base scene graph node is a simplest possible node, it is used to build more complex ones using composition.
 /// It contains all fundamental properties for each scene graph nodes, like local and global transforms, name,
 /// lifetime, etc. Base node is a building block for all complex node hierarchies - it contains list of children
@@ -357,6 +404,11 @@ pub struct Base {
     #[reflect(hidden)]
     pub(crate) transform_modified: Cell<bool>,
 
+    // Set when local bounds, LOD group or frustum culling flag of the node were changed, so the graph
+    // have to update its spatial index even if the global transform of the node has stayed the same.
+    #[reflect(hidden)]
+    pub(crate) bounding_box_modified: Cell<bool>,
+
     // When `true` it means that this node is instance of `resource`.
     // More precisely - this node is root of whole descendant nodes
     // hierarchy which was instantiated from resource.
@@ -394,14 +446,15 @@ pub struct Base {
     #[reflect(hidden)]
     pub(crate) instance_id: InstanceId,
 
-    // Current script of the scene node.
+    // Scripts of the scene node.
     //
     // # Important notes
     //
-    // WARNING: Setting a new script via reflection will break normal script destruction process!
-    // Use it at your own risk only when you're completely sure what you are doing.
-    #[reflect(setter = "set_script_internal")]
-    pub(crate) script: Option<Script>,
+    // WARNING: Modifying the list of scripts directly via reflection (adding, removing or replacing
+    // items) will break normal script initialization and destruction process! Set the whole list
+    // instead, the setter correctly destroys old scripts and initializes new ones.
+    #[reflect(setter = "set_scripts_internal")]
+    pub(crate) scripts: Vec<ScriptRecord>,
 
     enabled: InheritableVariable<bool>,
 
@@ -411,7 +464,7 @@ pub struct Base {
 
 impl Drop for Base {
     fn drop(&mut self) {
-        self.remove_script();
+        self.remove_all_scripts();
     }
 }
 
@@ -570,6 +623,15 @@ impl Base {
             .transform(&self.global_transform())
     }
 
+    /// Tells the graph that the bounding box of the node was changed and its spatial index must be
+    /// updated on next update tick. Moved nodes are detected automatically, so this method must only be
+    /// called by nodes whose bounds depend on something other than their transform (for example mesh
+    /// surfaces or sprite size).
+    #[inline]
+    pub fn invalidate_bounding_box(&self) {
+        self.bounding_box_modified.set(true);
+    }
+
     /// Set new mobility for the node.
     ///
     /// TODO. Mobility still has no effect, it was designed to be used in combined
@@ -653,12 +715,14 @@ impl Base {
     /// Sets new lod group.
     #[inline]
     pub fn set_lod_group(&mut self, lod_group: Option<LodGroup>) -> Option<LodGroup> {
+        self.bounding_box_modified.set(true);
         std::mem::replace(self.lod_group.get_value_mut_and_mark_modified(), lod_group)
     }
 
     /// Extracts lod group, leaving None in the node.
     #[inline]
     pub fn take_lod_group(&mut self) -> Option<LodGroup> {
+        self.bounding_box_modified.set(true);
         std::mem::take(self.lod_group.get_value_mut_and_mark_modified())
     }
 
@@ -671,6 +735,7 @@ impl Base {
     /// Returns mutable reference to current lod group.
     #[inline]
     pub fn lod_group_mut(&mut self) -> Option<&mut LodGroup> {
+        self.bounding_box_modified.set(true);
         self.lod_group.get_value_mut_and_mark_modified().as_mut()
     }
 
@@ -701,6 +766,7 @@ impl Base {
     /// Sets whether to use frustum culling or not
     #[inline]
     pub fn set_frustum_culling(&mut self, frustum_culling: bool) -> bool {
+        self.bounding_box_modified.set(true);
         self.frustum_culling
             .set_value_and_mark_modified(frustum_culling)
     }
@@ -733,40 +799,82 @@ impl Base {
         self.instance_id
     }
 
-    fn remove_script(&mut self) {
+    pub(crate) fn send_script_for_destruction(&mut self, script: Script, script_index: usize) {
+        // Scripts that were never initialized must not be de-initialized, so they're just dropped.
+        if !script.initialized {
+            return;
+        }
+
         // Send script to the graph to destroy script instances correctly.
-        if let Some(script) = self.script.take() {
-            if let Some(sender) = self.script_message_sender.as_ref() {
-                Log::verify(sender.send(NodeScriptMessage::DestroyScript {
-                    script,
-                    handle: self.self_handle,
-                }));
-            } else {
-                Log::warn(format!(
-                    "There is a script instance on a node {}, but no message sender. \
-                    The script won't be correctly destroyed!",
-                    self.name(),
-                ))
+        if let Some(sender) = self.script_message_sender.as_ref() {
+            Log::verify(sender.send(NodeScriptMessage::DestroyScript {
+                script,
+                handle: self.self_handle,
+                script_index,
+            }));
+
+            if let Some(record) = self.scripts.get_mut(script_index) {
+                record.destruction_pending = true;
             }
+        } else {
+            Log::warn(format!(
+                "There is a script instance on a node {}, but no message sender. \
+                    The script won't be correctly destroyed!",
+                self.name(),
+            ))
         }
     }
 
-    /// Sets new script for the scene node.
-    #[inline]
-    pub fn set_script(&mut self, script: Option<Script>) {
-        self.remove_script();
-        self.script = script;
+    fn request_script_initialization(&self) {
         if let Some(sender) = self.script_message_sender.as_ref() {
-            if self.script.is_some() {
-                Log::verify(sender.send(NodeScriptMessage::InitializeScript {
-                    handle: self.self_handle,
-                }));
+            Log::verify(sender.send(NodeScriptMessage::InitializeScript {
+                handle: self.self_handle,
+            }));
+        }
+    }
+
+    /// Removes a script with the given index from the scene node. The script will be destroyed on
+    /// the current or the next frame. The record of the script is removed on the next update of the
+    /// graph, so indices of other scripts remain the same until then.
+    #[inline]
+    pub fn remove_script(&mut self, index: usize) {
+        if let Some(record) = self.scripts.get_mut(index) {
+            if record.should_be_deleted {
+                return;
+            }
+
+            record.should_be_deleted = true;
+
+            if let Some(script) = record.script.take() {
+                self.send_script_for_destruction(script, index);
             }
         }
     }
 
-    fn set_script_internal(&mut self, script: Option<Script>) -> Option<Script> {
-        std::mem::replace(&mut self.script, script)
+    /// Removes all scripts from the scene node. See [`Self::remove_script`] for more info.
+    #[inline]
+    pub fn remove_all_scripts(&mut self) {
+        for index in 0..self.scripts.len() {
+            self.remove_script(index);
+        }
+    }
+
+    /// Adds new script to the scene node. The script will be initialized on the current or the next
+    /// frame.
+    #[inline]
+    pub fn add_script(&mut self, script: Script) {
+        self.scripts.push(ScriptRecord::new(script));
+        self.request_script_initialization();
+    }
+
+    /// Replaces all scripts of the scene node with the given script. Old scripts will be correctly
+    /// destroyed.
+    #[inline]
+    pub fn set_script(&mut self, script: Option<Script>) {
+        self.remove_all_scripts();
+        if let Some(script) = script {
+            self.add_script(script);
+        }
     }
 
     /// Checks if the node has a script of a particular type. Returns `false` if there is no script
@@ -776,27 +884,25 @@ impl Base {
         self.try_get_script::<T>().is_some()
     }
 
-    /// Tries to cast current script instance (if any) to given type and returns a shared reference
-    /// to it on successful cast.
+    /// Tries to find a script of the given type and returns a shared reference to it.
     #[inline]
     pub fn try_get_script<T: ScriptTrait>(&self) -> Option<&T> {
-        self.script.as_ref().and_then(|s| s.cast::<T>())
+        self.scripts().find_map(|s| s.cast::<T>())
     }
 
-    /// Tries to cast current script instance (if any) to given type and returns a mutable reference
-    /// to it on successful cast.
+    /// Tries to find a script of the given type and returns a mutable reference to it.
     #[inline]
     pub fn try_get_script_mut<T: ScriptTrait>(&mut self) -> Option<&mut T> {
-        self.script.as_mut().and_then(|s| s.cast_mut::<T>())
+        self.scripts_mut().find_map(|s| s.cast_mut::<T>())
     }
 
-    /// Returns shared reference to current script instance.
+    /// Returns shared reference to the first script instance.
     #[inline]
     pub fn script(&self) -> Option<&Script> {
-        self.script.as_ref()
+        self.scripts().next()
     }
 
-    /// Returns mutable reference to current script instance.
+    /// Returns mutable reference to the first script instance.
     ///
     /// # Important notes
     ///
@@ -805,19 +911,98 @@ impl Base {
     /// to replace the script.
     #[inline]
     pub fn script_mut(&mut self) -> Option<&mut Script> {
-        self.script.as_mut()
+        self.scripts_mut().next()
+    }
+
+    /// Returns shared reference to a script instance with the given index.
+    #[inline]
+    pub fn script_at(&self, index: usize) -> Option<&Script> {
+        self.scripts
+            .get(index)
+            .filter(|r| !r.should_be_deleted)
+            .and_then(|r| r.script.as_ref())
+    }
+
+    /// Returns mutable reference to a script instance with the given index. See [`Self::script_mut`]
+    /// for important notes.
+    #[inline]
+    pub fn script_at_mut(&mut self, index: usize) -> Option<&mut Script> {
+        self.scripts
+            .get_mut(index)
+            .filter(|r| !r.should_be_deleted)
+            .and_then(|r| r.script.as_mut())
+    }
+
+    /// Returns an iterator over every script of the node.
+    #[inline]
+    pub fn scripts(&self) -> impl Iterator<Item = &Script> {
+        self.scripts
+            .iter()
+            .filter(|r| !r.should_be_deleted)
+            .filter_map(|r| r.script.as_ref())
+    }
+
+    /// Returns an iterator over every script of the node. See [`Self::script_mut`] for important notes.
+    #[inline]
+    pub fn scripts_mut(&mut self) -> impl Iterator<Item = &mut Script> {
+        self.scripts
+            .iter_mut()
+            .filter(|r| !r.should_be_deleted)
+            .filter_map(|r| r.script.as_mut())
+    }
+
+    /// Returns total amount of script records of the node. Some of the records may be empty, if
+    /// the respective scripts were removed on the current frame.
+    #[inline]
+    pub fn script_count(&self) -> usize {
+        self.scripts.len()
     }
 
-    /// Returns a copy of the current script.
+    /// Returns a copy of the first script.
     #[inline]
     pub fn script_cloned(&self) -> Option<Script> {
-        self.script.clone()
+        self.script().cloned()
     }
 
     /// Internal. Do not use.
     #[inline]
-    pub fn script_inner(&mut self) -> &mut Option<Script> {
-        &mut self.script
+    pub fn script_inner(&mut self) -> &mut Vec<ScriptRecord> {
+        &mut self.scripts
+    }
+
+    // Removes records of deleted scripts, must not be called during script processing. Records are
+    // kept while there are scripts waiting for destruction, so their indices remain valid.
+    pub(crate) fn remove_deleted_script_records(&mut self) {
+        if self.scripts.iter().all(|r| !r.destruction_pending) {
+            self.scripts.retain(|r| !r.should_be_deleted);
+        }
+    }
+
+    // Must be called when a script, that was sent for destruction, is destroyed.
+    pub(crate) fn finish_script_destruction(&mut self, script_index: usize) {
+        if let Some(record) = self.scripts.get_mut(script_index) {
+            record.destruction_pending = false;
+        }
+    }
+
+    // Replaces every script of the node with the scripts from the given list. Old scripts are destroyed
+    // correctly and their copies are returned.
+    fn set_scripts_internal(&mut self, scripts: Vec<ScriptRecord>) -> Vec<ScriptRecord> {
+        let old_scripts = self
+            .scripts()
+            .map(|script| ScriptRecord::new(script.clone()))
+            .collect();
+
+        self.remove_all_scripts();
+        for record in scripts {
+            if !record.should_be_deleted {
+                if let Some(script) = record.script {
+                    self.add_script(script);
+                }
+            }
+        }
+
+        old_scripts
     }
 
     /// Enables or disables scene node. Disabled scene nodes won't be updated (including scripts) or rendered.
@@ -892,6 +1077,44 @@ fn visit_opt_script(name: &str, script: &mut Option<Script>, visitor: &mut Visit
     Ok(())
 }
 
+// Serializes a list of scripts, every script is visited separately, so a failure of one script does
+// not affect the others.
+fn visit_scripts(
+    name: &str,
+    scripts: &mut Vec<ScriptRecord>,
+    visitor: &mut Visitor,
+) -> VisitResult {
+    let mut region = visitor.enter_region(name)?;
+
+    if region.is_reading() {
+        let mut count = 0u32;
+        count.visit("Count", &mut region)?;
+
+        scripts.clear();
+        for i in 0..count {
+            let mut script = None;
+            match visit_opt_script(&format!("Script{}", i), &mut script, &mut region) {
+                Ok(_) => scripts.extend(script.map(ScriptRecord::new)),
+                Err(e) => Log::err(format!("Unable to visit script. Reason: {:?}", e)),
+            }
+        }
+    } else {
+        let mut alive_scripts = scripts
+            .iter_mut()
+            .filter(|r| !r.should_be_deleted && r.script.is_some())
+            .collect::<Vec<_>>();
+
+        let mut count = alive_scripts.len() as u32;
+        count.visit("Count", &mut region)?;
+
+        for (i, record) in alive_scripts.iter_mut().enumerate() {
+            visit_opt_script(&format!("Script{}", i), &mut record.script, &mut region)?;
+        }
+    }
+
+    Ok(())
+}
+
 impl Visit for Base {
     fn visit(&mut self, name: &str, visitor: &mut Visitor) -> VisitResult {
         let mut region = visitor.enter_region(name)?;
@@ -925,11 +1148,20 @@ impl Visit for Base {
         //
         // None of the reasons are fatal and we should still give an ability to load such node
         // to edit or remove it.
-        if let Err(e) = visit_opt_script("Script", &mut self.script, &mut region) {
-            // Do not spam with error messages if there is missing `Script` field. It is ok
-            // for old scenes not to have script at all.
-            if !matches!(e, VisitError::RegionDoesNotExist(_)) {
-                Log::err(format!("Unable to visit script. Reason: {:?}", e))
+        if let Err(e) = visit_scripts("Scripts", &mut self.scripts, &mut region) {
+            if region.is_reading() {
+                // Scenes made before multiple scripts support store a single optional script.
+                let mut script = None;
+                if let Err(e) = visit_opt_script("Script", &mut script, &mut region) {
+                    // Do not spam with error messages if there is missing `Script` field. It is ok
+                    // for old scenes not to have script at all.
+                    if !matches!(e, VisitError::RegionDoesNotExist(_)) {
+                        Log::err(format!("Unable to visit script. Reason: {:?}", e))
+                    }
+                }
+                self.scripts = script.map(ScriptRecord::new).into_iter().collect();
+            } else {
+                Log::err(format!("Unable to visit scripts. Reason: {:?}", e))
             }
         }
 
@@ -951,7 +1183,7 @@ pub struct BaseBuilder {
     tag: String,
     frustum_culling: bool,
     cast_shadows: bool,
-    script: Option<Script>,
+    scripts: Vec<ScriptRecord>,
     instance_id: InstanceId,
     enabled: bool,
 }
@@ -979,7 +1211,7 @@ impl BaseBuilder {
             tag: Default::default(),
             frustum_culling: true,
             cast_shadows: true,
-            script: None,
+            scripts: Default::default(),
             instance_id: InstanceId(Uuid::new_v4()),
             enabled: true,
         }
@@ -1082,10 +1314,10 @@ impl BaseBuilder {
         self
     }
 
-    /// Sets desired script of the node.
+    /// Adds a script to the node, could be called multiple times to add multiple scripts.
     #[inline]
     pub fn with_script(mut self, script: Script) -> Self {
-        self.script = Some(script);
+        self.scripts.push(ScriptRecord::new(script));
         self
     }
 
@@ -1119,10 +1351,11 @@ impl BaseBuilder {
             tag: self.tag.into(),
             properties: Default::default(),
             transform_modified: Cell::new(false),
+            bounding_box_modified: Cell::new(true),
             frustum_culling: self.frustum_culling.into(),
             cast_shadows: self.cast_shadows.into(),
-            script: self.script,
-            instance_id: InstanceId(Uuid::new_v4()),
+            scripts: self.scripts,
+            instance_id: self.instance_id,
             enabled: self.enabled.into(),
             global_enabled: Cell::new(true),
         }
@@ -27,7 +27,7 @@ use crate::{
     resource::texture::{Texture, TextureError, TextureKind, TexturePixelKind, TextureWrapMode},
     scene::{
         base::{Base, BaseBuilder},
-        graph::Graph,
+        graph::{spatial::SpatialIndex, Graph, NodePool},
         node::{Node, NodeTrait, TypeUuidProvider, UpdateContext},
         visibility::VisibilityCache,
     },
@@ -368,6 +368,34 @@ impl Camera {
         self.projection_matrix = self.projection.matrix(frame_size);
     }
 
+    // Re-calculates the visibility cache of the camera, it must be called when the spatial index of
+    // the graph is in sync with the nodes.
+    pub(crate) fn update_visibility_cache(
+        &mut self,
+        nodes: &NodePool,
+        spatial_index: &SpatialIndex,
+    ) {
+        let frustum = Frustum::from(self.view_projection_matrix()).unwrap_or_default();
+        self.visibility_cache.update(
+            nodes,
+            spatial_index,
+            self.global_position(),
+            self.projection().z_near(),
+            self.projection().z_far(),
+            Some(&[&frustum]),
+        );
+    }
+
+    // Re-calculates view matrix only, it is used when the global transform of the camera was changed
+    // outside of the usual update cycle.
+    pub(crate) fn calculate_view_matrix(&mut self) {
+        let pos = self.base.global_position();
+        let look = self.base.look_vector();
+        let up = self.base.up_vector();
+
+        self.view_matrix = Matrix4::look_at_rh(&Point3::from(pos), &Point3::from(pos + look), &up);
+    }
+
     /// Sets new viewport in resolution-independent format. In other words
     /// each parameter of viewport defines portion of your current resolution
     /// in percents. In example viewport (0.0, 0.0, 0.5, 1.0) will force camera
@@ -608,16 +636,8 @@ impl NodeTrait for Camera {
     }
 
     fn update(&mut self, context: &mut UpdateContext) {
+        // Visibility cache is updated by the graph, when spatial index is in sync with the nodes.
         self.calculate_matrices(context.frame_size);
-
-        self.visibility_cache.clear();
-        self.visibility_cache.update(
-            context.nodes,
-            self.global_position(),
-            self.projection().z_near(),
-            self.projection().z_far(),
-            Some(&[&Frustum::from(self.view_projection_matrix()).unwrap_or_default()]),
-        );
     }
 }
 
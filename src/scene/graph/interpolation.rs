@@ -0,0 +1,247 @@
+//! Transform interpolation smooths motion of scene nodes, when a scene is updated with fixed time step, but
+//! rendered with variable frame rate. See [`TransformInterpolator`] docs for more info.
+
+use crate::{
+    core::{
+        algebra::{Matrix4, UnitQuaternion, Vector3},
+        pool::Handle,
+    },
+    scene::{
+        camera::Camera,
+        graph::{Graph, NodePool},
+        node::Node,
+    },
+};
+use fxhash::FxHashMap;
+use std::collections::hash_map::Entry;
+
+#[derive(Clone, PartialEq, Debug)]
+struct LocalTransform {
+    position: Vector3<f32>,
+    rotation: UnitQuaternion<f32>,
+    scale: Vector3<f32>,
+}
+
+impl LocalTransform {
+    fn from_node(node: &Node) -> Self {
+        let transform = node.local_transform();
+        Self {
+            position: **transform.position(),
+            rotation: **transform.rotation(),
+            scale: **transform.scale(),
+        }
+    }
+
+    fn apply(&self, node: &mut Node) {
+        node.local_transform_mut()
+            .set_position_rotation_scale_silent(self.position, self.rotation, self.scale);
+    }
+
+    fn lerp(&self, other: &Self, t: f32) -> Self {
+        Self {
+            position: self.position.lerp(&other.position, t),
+            rotation: self.rotation.nlerp(&other.rotation, t),
+            scale: self.scale.lerp(&other.scale, t),
+        }
+    }
+}
+
+#[derive(Debug)]
+struct NodeState {
+    previous: LocalTransform,
+    current: LocalTransform,
+    global_transform: Matrix4<f32>,
+}
+
+/// Transform interpolator stores two most recent states of local transforms of every node of a graph and
+/// blends them before rendering. It is used when a graph is updated with fixed time step (for example, at
+/// 60 Hz), but rendered at higher (or just different) rate. Without interpolation, a moving object will
+/// stutter, because some frames will show the same simulation state twice.
+///
+/// The interpolator changes the transforms *silently*, in other words the changes are never synchronized
+/// with physics and never mark any property as modified. Actual transforms are restored right before the
+/// next update step.
+///
+/// Typical usage is:
+///
+/// 1) Call [`Self::begin_step`] right before a fixed update step of the graph.
+/// 2) Call [`Self::end_step`] right after the fixed update step.
+/// 3) Call [`Self::interpolate`] right before rendering with `alpha = lag / fixed_time_step`.
+#[derive(Default, Debug)]
+pub struct TransformInterpolator {
+    states: FxHashMap<Handle<Node>, NodeState>,
+    interpolated: bool,
+}
+
+impl TransformInterpolator {
+    /// Creates new transform interpolator.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Restores actual transforms of the nodes, if they were interpolated. Must be called right before a fixed
+    /// update step of the graph.
+    pub fn begin_step(&mut self, graph: &mut Graph) {
+        if !self.interpolated {
+            return;
+        }
+
+        self.interpolated = false;
+
+        for (handle, state) in self.states.iter() {
+            if let Some(node) = graph.try_get_mut(*handle) {
+                if state.previous != state.current {
+                    state.current.apply(node);
+                }
+                // Global transform of a node is interpolated even if its local transform is the
+                // same, when any of its ancestors is moving. So every global transform must be
+                // restored.
+                node.global_transform.set(state.global_transform);
+            }
+        }
+
+        update_view_matrices(graph);
+    }
+
+    /// Remembers the new state of every node of the graph. Must be called right after a fixed update step of
+    /// the graph.
+    pub fn end_step(&mut self, graph: &Graph) {
+        self.states
+            .retain(|handle, _| graph.is_valid_handle(*handle));
+
+        for (handle, node) in graph.pair_iter() {
+            let current = LocalTransform::from_node(node);
+
+            match self.states.entry(handle) {
+                Entry::Occupied(entry) => {
+                    let state = entry.into_mut();
+                    state.previous = std::mem::replace(&mut state.current, current);
+                    state.global_transform = node.global_transform();
+                }
+                Entry::Vacant(entry) => {
+                    entry.insert(NodeState {
+                        previous: current.clone(),
+                        current,
+                        global_transform: node.global_transform(),
+                    });
+                }
+            }
+        }
+    }
+
+    /// Blends two most recent states of every node using the given factor (`0.0` - previous state, `1.0` -
+    /// current state) and updates global transforms of the nodes as well as view matrices of cameras.
+    pub fn interpolate(&mut self, graph: &mut Graph, alpha: f32) {
+        let alpha = alpha.clamp(0.0, 1.0);
+
+        let mut any_changed = false;
+        for (handle, state) in self.states.iter() {
+            if state.previous != state.current {
+                if let Some(node) = graph.try_get_mut(*handle) {
+                    state.previous.lerp(&state.current, alpha).apply(node);
+                    any_changed = true;
+                }
+            }
+        }
+
+        if any_changed {
+            self.interpolated = true;
+
+            update_global_transforms(&graph.pool, graph.root);
+            update_view_matrices(graph);
+        }
+    }
+}
+
+fn update_view_matrices(graph: &mut Graph) {
+    for node in graph.linear_iter_mut() {
+        if let Some(camera) = node.cast_mut::<Camera>() {
+            camera.calculate_view_matrix();
+        }
+    }
+}
+
+// Unlike `Graph::update_hierarchical_data`, it does not sync anything with physics or sound.
+fn update_global_transforms(nodes: &NodePool, handle: Handle<Node>) {
+    let node = &nodes[handle];
+
+    let parent_global_transform = nodes
+        .try_borrow(node.parent())
+        .map_or_else(Matrix4::identity, |parent| parent.global_transform());
+
+    node.global_transform
+        .set(parent_global_transform * node.local_transform().matrix());
+
+    for &child in node.children() {
+        update_global_transforms(nodes, child);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        core::algebra::Vector3,
+        scene::{
+            base::BaseBuilder,
+            graph::{interpolation::TransformInterpolator, Graph},
+            pivot::PivotBuilder,
+        },
+    };
+
+    #[test]
+    fn test_interpolation() {
+        let mut graph = Graph::new();
+        let pivot = PivotBuilder::new(BaseBuilder::new()).build(&mut graph);
+        graph.update_hierarchical_data();
+
+        let mut interpolator = TransformInterpolator::new();
+
+        interpolator.begin_step(&mut graph);
+        interpolator.end_step(&graph);
+
+        interpolator.begin_step(&mut graph);
+        graph[pivot]
+            .local_transform_mut()
+            .set_position(Vector3::new(2.0, 0.0, 0.0));
+        graph.update_hierarchical_data();
+        interpolator.end_step(&graph);
+
+        interpolator.interpolate(&mut graph, 0.5);
+        assert_eq!(graph[pivot].global_position(), Vector3::new(1.0, 0.0, 0.0));
+
+        interpolator.begin_step(&mut graph);
+        assert_eq!(
+            **graph[pivot].local_transform().position(),
+            Vector3::new(2.0, 0.0, 0.0)
+        );
+        assert_eq!(graph[pivot].global_position(), Vector3::new(2.0, 0.0, 0.0));
+    }
+
+    #[test]
+    fn test_children_of_moving_node_are_restored() {
+        let mut graph = Graph::new();
+        let child = PivotBuilder::new(BaseBuilder::new()).build(&mut graph);
+        let parent =
+            PivotBuilder::new(BaseBuilder::new().with_children(&[child])).build(&mut graph);
+        graph.update_hierarchical_data();
+
+        let mut interpolator = TransformInterpolator::new();
+
+        interpolator.begin_step(&mut graph);
+        interpolator.end_step(&graph);
+
+        interpolator.begin_step(&mut graph);
+        graph[parent]
+            .local_transform_mut()
+            .set_position(Vector3::new(2.0, 0.0, 0.0));
+        graph.update_hierarchical_data();
+        interpolator.end_step(&graph);
+
+        interpolator.interpolate(&mut graph, 0.5);
+        assert_eq!(graph[child].global_position(), Vector3::new(1.0, 0.0, 0.0));
+
+        // The child itself wasn't moved, but its global position must be restored too.
+        interpolator.begin_step(&mut graph);
+        assert_eq!(graph[child].global_position(), Vector3::new(2.0, 0.0, 0.0));
+    }
+}
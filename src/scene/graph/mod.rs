@@ -44,6 +44,7 @@ use crate::{
             event::{GraphEvent, GraphEventBroadcaster},
             map::NodeHandleMap,
             physics::{PhysicsPerformanceStatistics, PhysicsWorld},
+            spatial::SpatialIndex,
         },
         mesh::Mesh,
         node::{container::NodeContainer, Node, SyncContext, UpdateContext},
@@ -64,8 +65,10 @@ use std::{
 };
 
 pub mod event;
+pub mod interpolation;
 pub mod map;
 pub mod physics;
+pub mod spatial;
 
 /// Graph performance statistics. Allows you to find out "hot" parts of the scene graph, which
 /// parts takes the most time to update.
@@ -115,6 +118,9 @@ pub struct Graph {
     #[reflect(hidden)]
     stack: Vec<Handle<Node>>,
 
+    #[reflect(hidden)]
+    spatial_index: SpatialIndex,
+
     /// Backing physics "world". It is responsible for the physics simulation.
     pub physics: PhysicsWorld,
 
@@ -149,6 +155,7 @@ impl Default for Graph {
             root: Handle::NONE,
             pool: Pool::new(),
             stack: Vec::new(),
+            spatial_index: Default::default(),
             sound_context: Default::default(),
             performance_statistics: Default::default(),
             event_broadcaster: Default::default(),
@@ -252,6 +259,7 @@ impl Graph {
         Self {
             physics: Default::default(),
             stack: Vec::new(),
+            spatial_index: Default::default(),
             root,
             pool,
             physics2d: Default::default(),
@@ -270,8 +278,9 @@ impl Graph {
     pub fn add_node(&mut self, mut node: Node) -> Handle<Node> {
         let children = node.children.clone();
         node.children.clear();
-        let has_script = node.script.is_some();
+        let has_script = !node.scripts.is_empty();
         let handle = self.pool.spawn(node);
+        self.spatial_index.invalidate(handle);
         if self.root.is_some() {
             self.link_nodes(handle, self.root);
         }
@@ -373,6 +382,7 @@ impl Graph {
             }
 
             // Remove associated entities.
+            self.spatial_index.remove(handle);
             let mut node = self.pool.free(handle);
             node.on_removed_from_graph(self);
 
@@ -547,9 +557,7 @@ impl Graph {
     where
         S: ScriptTrait,
     {
-        self.find(root_node, &mut |n| {
-            n.script().and_then(|s| s.cast::<S>()).is_some()
-        })
+        self.find(root_node, &mut |n| n.try_get_script::<S>().is_some())
     }
 
     /// Searches node using specified compare closure starting from root. Returns a tuple with a handle and
@@ -961,6 +969,7 @@ impl Graph {
         sound_context: &mut SoundContext,
         physics: &mut PhysicsWorld,
         physics2d: &mut dim2::physics::PhysicsWorld,
+        spatial_index: &mut SpatialIndex,
         node_handle: Handle<Node>,
     ) {
         let node = &nodes[node_handle];
@@ -990,6 +999,14 @@ impl Graph {
             },
         );
 
+        // Bounds of the node must be re-inserted in the spatial index only if the node was moved or
+        // its bounds were changed.
+        if node.bounding_box_modified.replace(false)
+            || node.global_transform.get() != new_global_transform
+        {
+            spatial_index.invalidate(node_handle);
+        }
+
         node.global_transform.set(new_global_transform);
         node.global_visibility
             .set(parent_visibility && node.visibility());
@@ -1001,6 +1018,7 @@ impl Graph {
                 sound_context,
                 physics,
                 physics2d,
+                spatial_index,
                 child,
             );
         }
@@ -1015,6 +1033,7 @@ impl Graph {
             &mut self.sound_context,
             &mut self.physics,
             &mut self.physics2d,
+            &mut self.spatial_index,
             node_handle,
         );
     }
@@ -1030,10 +1049,26 @@ impl Graph {
             &mut self.sound_context,
             &mut self.physics,
             &mut self.physics2d,
+            &mut self.spatial_index,
             self.root,
         );
     }
 
+    /// Returns a reference to the spatial index of the graph, that could be used to quickly find nodes by their
+    /// world-space bounding boxes. See [`SpatialIndex`] docs for more info.
+    #[inline]
+    pub fn spatial_index(&self) -> &SpatialIndex {
+        &self.spatial_index
+    }
+
+    /// Updates the spatial index of the graph using current world-space bounding boxes of nodes. Normally you
+    /// not need to call this method directly, it will be called automatically on each frame. It could be useful
+    /// if you need to do spatial queries right after you've created or moved some nodes (do not forget to call
+    /// [`Self::update_hierarchical_data`] first).
+    pub fn update_spatial_index(&mut self) {
+        self.spatial_index.sync(&self.pool);
+    }
+
     /// Checks whether given node handle is valid or not.
     pub fn is_valid_handle(&self, node_handle: Handle<Node>) -> bool {
         self.pool.is_valid_handle(node_handle)
@@ -1085,6 +1120,11 @@ impl Graph {
                 }
             }
 
+            // Some nodes (like meshes) calculate their world-space bounds in their update method.
+            if node.bounding_box_modified.replace(false) {
+                self.spatial_index.invalidate(handle);
+            }
+
             self.pool.put_back(ticket, node);
 
             if !is_alive && delete_dead_nodes {
@@ -1100,6 +1140,12 @@ impl Graph {
     /// Update switches allows you to disable update for parts of the update pipeline, it could be useful for editors
     /// where you need to have preview mode to update only specific set of nodes, etc.
     pub fn update(&mut self, frame_size: Vector2<f32>, dt: f32, switches: GraphUpdateSwitches) {
+        // Remove records of deleted scripts. It is safe to do it here, because scripts are never
+        // processed during graph update.
+        for node in self.pool.iter_mut() {
+            node.remove_deleted_script_records();
+        }
+
         let last_time = instant::Instant::now();
         self.update_hierarchical_data();
         self.performance_statistics.hierarchical_properties_time =
@@ -1138,6 +1184,36 @@ impl Graph {
                 );
             }
         }
+
+        // Must be done after nodes update, because some nodes (like meshes) calculate their world-space bounds
+        // in their update method.
+        self.update_spatial_index();
+
+        // Frustum culling uses the spatial index, so it must be done after the index is synced.
+        if let Some(overrides) = switches.node_overrides.as_ref() {
+            for handle in overrides {
+                self.update_camera_visibility_cache(*handle);
+            }
+        } else {
+            for i in 0..self.pool.get_capacity() {
+                self.update_camera_visibility_cache(self.pool.handle_from_index(i));
+            }
+        }
+    }
+
+    fn update_camera_visibility_cache(&mut self, handle: Handle<Node>) {
+        if !self.pool.try_borrow(handle).map_or(false, |node| {
+            node.is_globally_enabled() && node.cast::<Camera>().is_some()
+        }) {
+            return;
+        }
+
+        if let Some((ticket, mut node)) = self.pool.try_take_reserve(handle) {
+            if let Some(camera) = node.cast_mut::<Camera>() {
+                camera.update_visibility_cache(&self.pool, &self.spatial_index);
+            }
+            self.pool.put_back(ticket, node);
+        }
     }
 
     /// Returns capacity of internal pool. Can be used to iterate over all **potentially**
@@ -1216,6 +1292,7 @@ impl Graph {
 
     pub(crate) fn take_reserve_internal(&mut self, handle: Handle<Node>) -> (Ticket<Node>, Node) {
         let (ticket, mut node) = self.pool.take_reserve(handle);
+        self.spatial_index.invalidate(handle);
         node.on_removed_from_graph(self);
         (ticket, node)
     }
@@ -1228,7 +1305,9 @@ impl Graph {
     }
 
     pub(crate) fn put_back_internal(&mut self, ticket: Ticket<Node>, node: Node) -> Handle<Node> {
-        self.pool.put_back(ticket, node)
+        let handle = self.pool.put_back(ticket, node);
+        self.spatial_index.invalidate(handle);
+        handle
     }
 
     /// Makes node handle vacant again.
@@ -1262,7 +1341,7 @@ impl Graph {
     /// parent.
     pub fn put_sub_graph_back(&mut self, sub_graph: SubGraph) -> Handle<Node> {
         for (ticket, node) in sub_graph.descendants {
-            self.pool.put_back(ticket, node);
+            self.put_back_internal(ticket, node);
         }
 
         let (ticket, node) = sub_graph.root;
@@ -1498,8 +1577,20 @@ mod test {
     use crate::scene::base::BaseBuilder;
     use crate::scene::pivot::PivotBuilder;
     use crate::{
-        core::pool::Handle,
-        scene::{graph::Graph, node::Node, pivot::Pivot},
+        core::{
+            algebra::{Matrix4, UnitQuaternion, Vector2, Vector3},
+            math::frustum::Frustum,
+            pool::Handle,
+        },
+        scene::{
+            camera::CameraBuilder,
+            graph::Graph,
+            node::Node,
+            pivot::Pivot,
+            sprite::{Sprite, SpriteBuilder},
+            transform::TransformBuilder,
+            visibility::VisibilityCache,
+        },
     };
 
     #[test]
@@ -1568,4 +1659,146 @@ mod test {
         assert_eq!(result.0, a);
         assert_eq!(result.1, "A");
     }
+
+    #[test]
+    fn test_spatial_index() {
+        let mut graph = Graph::new();
+
+        let sprite = |graph: &mut Graph, position: Vector3<f32>| {
+            SpriteBuilder::new(
+                BaseBuilder::new().with_local_transform(
+                    TransformBuilder::new()
+                        .with_local_position(position)
+                        .build(),
+                ),
+            )
+            .build(graph)
+        };
+        let a = sprite(&mut graph, Vector3::new(0.0, 0.0, 0.0));
+        let b = sprite(&mut graph, Vector3::new(10.0, 0.0, 0.0));
+        PivotBuilder::new(BaseBuilder::new()).build(&mut graph);
+
+        graph.update_hierarchical_data();
+        graph.update_spatial_index();
+        // Pivots have no bounds and are not added to the index.
+        assert_eq!(graph.spatial_index().len(), 2);
+
+        let mut nodes = Vec::new();
+        graph
+            .spatial_index()
+            .sphere_query(Vector3::new(10.0, 0.0, 0.0), 0.5, &mut nodes);
+        assert_eq!(nodes, [b]);
+
+        graph[a]
+            .local_transform_mut()
+            .set_position(Vector3::new(10.0, 0.5, 0.0));
+        graph.remove_node(b);
+        graph.update_hierarchical_data();
+        graph.update_spatial_index();
+        graph
+            .spatial_index()
+            .sphere_query(Vector3::new(10.0, 0.0, 0.0), 0.5, &mut nodes);
+        assert_eq!(nodes, [a]);
+
+        // Bounds change without movement must be picked up as well.
+        graph[a].cast_mut::<Sprite>().unwrap().set_size(3.0);
+        graph.update_hierarchical_data();
+        graph.update_spatial_index();
+        assert_eq!(
+            graph.spatial_index().bounds(a).unwrap().max,
+            Vector3::new(13.0, 3.5, 3.0)
+        );
+    }
+
+    #[test]
+    fn test_frustum_culling() {
+        let mut graph = Graph::new();
+
+        let sprite = |graph: &mut Graph, position: Vector3<f32>| {
+            SpriteBuilder::new(
+                BaseBuilder::new().with_local_transform(
+                    TransformBuilder::new()
+                        .with_local_position(position)
+                        .build(),
+                ),
+            )
+            .build(graph)
+        };
+        let front = sprite(&mut graph, Vector3::new(0.0, 0.0, 5.0));
+        let back = sprite(&mut graph, Vector3::new(0.0, 0.0, -5.0));
+        let camera = CameraBuilder::new(BaseBuilder::new()).build(&mut graph);
+
+        graph.update(Vector2::new(800.0, 600.0), 1.0, Default::default());
+        let visibility_cache = &graph[camera].as_camera().visibility_cache;
+        assert!(visibility_cache.is_visible(front));
+        assert!(!visibility_cache.is_visible(back));
+
+        // Turn the camera around.
+        graph[camera]
+            .local_transform_mut()
+            .set_rotation(UnitQuaternion::from_axis_angle(
+                &Vector3::y_axis(),
+                std::f32::consts::PI,
+            ));
+        graph.update(Vector2::new(800.0, 600.0), 1.0, Default::default());
+        let visibility_cache = &graph[camera].as_camera().visibility_cache;
+        assert!(!visibility_cache.is_visible(front));
+        assert!(visibility_cache.is_visible(back));
+    }
+
+    #[test]
+    fn test_frustum_culling_multiple_frustums() {
+        let mut graph = Graph::new();
+
+        let sprite = |graph: &mut Graph, position: Vector3<f32>| {
+            SpriteBuilder::new(
+                BaseBuilder::new().with_local_transform(
+                    TransformBuilder::new()
+                        .with_local_position(position)
+                        .build(),
+                ),
+            )
+            .build(graph)
+        };
+        let left = sprite(&mut graph, Vector3::new(-10.0, 0.0, 0.0));
+        let middle = sprite(&mut graph, Vector3::new(0.0, 0.0, 0.0));
+        let right = sprite(&mut graph, Vector3::new(10.0, 0.0, 0.0));
+        let pivot = PivotBuilder::new(BaseBuilder::new()).build(&mut graph);
+
+        // Sync the spatial index.
+        graph.update(Vector2::new(800.0, 600.0), 1.0, Default::default());
+
+        // Two disjoint frustums around the left and the right sprites.
+        let frustum = |center: f32| {
+            Frustum::from(Matrix4::new_orthographic(
+                center - 2.0,
+                center + 2.0,
+                -2.0,
+                2.0,
+                -2.0,
+                2.0,
+            ))
+            .unwrap()
+        };
+        let left_frustum = frustum(-10.0);
+        let right_frustum = frustum(10.0);
+
+        let mut visibility_cache = VisibilityCache::default();
+        visibility_cache.update(
+            &graph.pool,
+            &graph.spatial_index,
+            Default::default(),
+            0.0,
+            1.0,
+            Some(&[&left_frustum, &right_frustum]),
+        );
+        assert!(visibility_cache.is_visible(left));
+        assert!(!visibility_cache.is_visible(middle));
+        assert!(visibility_cache.is_visible(right));
+        // Pivot has no bounds, so it is not in the index and is tested on its own.
+        assert!(graph
+            .spatial_index
+            .unculled_nodes()
+            .any(|node| node == pivot));
+    }
 }
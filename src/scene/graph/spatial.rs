@@ -0,0 +1,222 @@
+//! Scene-wide spatial index that allows you to quickly find nodes by their world-space bounding
+//! boxes. See [`SpatialIndex`] docs for more info.
+
+use crate::{
+    core::{
+        algebra::Vector3,
+        bvh::{BvhNode, DynamicBvh},
+        math::{aabb::AxisAlignedBoundingBox, frustum::Frustum, ray::Ray},
+        pool::Handle,
+    },
+    scene::{graph::NodePool, node::Node},
+};
+use fxhash::{FxHashMap, FxHashSet};
+use std::collections::hash_map::Entry;
+
+/// Spatial index is a dynamic bounding volume hierarchy of world-space bounding boxes of scene
+/// nodes. It is maintained by the graph automatically on every [`super::Graph::update`] call, so
+/// the results of the queries reflect the state of the graph after last update. Only nodes with
+/// valid local bounding box (meshes, sprites, decals, particle systems, etc.) are added to the
+/// index.
+///
+/// The index is updated incrementally - only nodes that were added, moved or whose bounds were
+/// changed (see [`crate::scene::base::Base::invalidate_bounding_box`]) since the last update are
+/// processed, so static parts of the scene cost nothing.
+///
+/// Every query has `O(log(n))` complexity in common case, which makes it a good replacement for
+/// linear search on large scenes (for example frustum culling or proximity queries).
+///
+/// # Example
+///
+/// ```rust
+/// use fyrox::{
+///     core::{algebra::Vector3, pool::Handle},
+///     scene::{node::Node, Scene},
+/// };
+///
+/// fn find_nodes_near(scene: &Scene, position: Vector3<f32>) -> Vec<Handle<Node>> {
+///     let mut nodes = Vec::new();
+///     scene
+///         .graph
+///         .spatial_index()
+///         .sphere_query(position, 5.0, &mut nodes);
+///     nodes
+/// }
+/// ```
+#[derive(Clone, Debug)]
+pub struct SpatialIndex {
+    bvh: DynamicBvh<Handle<Node>>,
+    leaves: FxHashMap<Handle<Node>, Handle<BvhNode<Handle<Node>>>>,
+    dirty: FxHashSet<Handle<Node>>,
+    // Nodes that cannot be culled by a frustum query - nodes without valid bounds and nodes with
+    // disabled frustum culling.
+    unculled: FxHashSet<Handle<Node>>,
+    lod_groups: FxHashSet<Handle<Node>>,
+    // Forces the index to walk the entire graph on next sync. It is used for freshly created (or
+    // loaded) graphs, where there is no information about which nodes were changed.
+    rebuild: bool,
+}
+
+impl Default for SpatialIndex {
+    fn default() -> Self {
+        Self {
+            bvh: Default::default(),
+            leaves: Default::default(),
+            dirty: Default::default(),
+            unculled: Default::default(),
+            lod_groups: Default::default(),
+            rebuild: true,
+        }
+    }
+}
+
+fn is_valid(aabb: &AxisAlignedBoundingBox) -> bool {
+    aabb.min.x <= aabb.max.x && aabb.min.y <= aabb.max.y && aabb.min.z <= aabb.max.z
+}
+
+impl SpatialIndex {
+    /// Returns a reference to the inner hierarchy.
+    pub fn bvh(&self) -> &DynamicBvh<Handle<Node>> {
+        &self.bvh
+    }
+
+    /// Returns amount of nodes in the index.
+    pub fn len(&self) -> usize {
+        self.bvh.len()
+    }
+
+    /// Returns `true` if the index has no nodes.
+    pub fn is_empty(&self) -> bool {
+        self.bvh.is_empty()
+    }
+
+    /// Returns world-space bounding box of a node, that was used on last index update.
+    pub fn bounds(&self, node: Handle<Node>) -> Option<&AxisAlignedBoundingBox> {
+        self.leaves
+            .get(&node)
+            .and_then(|leaf| self.bvh.bounds(*leaf))
+    }
+
+    /// Returns an iterator over the nodes that cannot be culled by [`Self::frustum_query`] - nodes
+    /// without valid bounds and nodes with disabled frustum culling. Such nodes must be checked
+    /// one-by-one.
+    pub fn unculled_nodes(&self) -> impl Iterator<Item = Handle<Node>> + '_ {
+        self.unculled.iter().cloned()
+    }
+
+    /// Returns an iterator over the nodes with LOD groups.
+    pub fn lod_group_nodes(&self) -> impl Iterator<Item = Handle<Node>> + '_ {
+        self.lod_groups.iter().cloned()
+    }
+
+    /// Collects every node whose bounding box intersects the given bounding box.
+    pub fn aabb_query(&self, aabb: &AxisAlignedBoundingBox, buffer: &mut Vec<Handle<Node>>) {
+        self.bvh.aabb_query(aabb, buffer)
+    }
+
+    /// Collects every node whose bounding box intersects the given sphere.
+    pub fn sphere_query(
+        &self,
+        position: Vector3<f32>,
+        radius: f32,
+        buffer: &mut Vec<Handle<Node>>,
+    ) {
+        self.bvh.sphere_query(position, radius, buffer)
+    }
+
+    /// Collects every node whose bounding box intersects the given frustum.
+    pub fn frustum_query(&self, frustum: &Frustum, buffer: &mut Vec<Handle<Node>>) {
+        self.bvh.frustum_query(frustum, buffer)
+    }
+
+    /// Collects every node whose bounding box intersects the given ray. Nodes are not sorted by
+    /// distance.
+    pub fn ray_query(&self, ray: &Ray, buffer: &mut Vec<Handle<Node>>) {
+        self.bvh.ray_query(ray, buffer)
+    }
+
+    pub(crate) fn invalidate(&mut self, node: Handle<Node>) {
+        if !self.rebuild {
+            self.dirty.insert(node);
+        }
+    }
+
+    pub(crate) fn remove(&mut self, node: Handle<Node>) {
+        self.dirty.remove(&node);
+        self.forget(node);
+    }
+
+    fn forget(&mut self, node: Handle<Node>) {
+        if let Some(leaf) = self.leaves.remove(&node) {
+            self.bvh.remove(leaf);
+        }
+        self.unculled.remove(&node);
+        self.lod_groups.remove(&node);
+    }
+
+    fn sync_node(&mut self, handle: Handle<Node>, node: &Node) {
+        let bounded = is_valid(&node.local_bounding_box());
+        if bounded {
+            let aabb = node.world_bounding_box();
+            match self.leaves.entry(handle) {
+                Entry::Occupied(entry) => {
+                    self.bvh.set_bounds(*entry.get(), aabb);
+                }
+                Entry::Vacant(entry) => {
+                    entry.insert(self.bvh.insert(aabb, handle));
+                }
+            }
+        } else if let Some(leaf) = self.leaves.remove(&handle) {
+            self.bvh.remove(leaf);
+        }
+
+        if bounded && node.frustum_culling() {
+            self.unculled.remove(&handle);
+        } else {
+            self.unculled.insert(handle);
+        }
+
+        if node.lod_group().is_some() {
+            self.lod_groups.insert(handle);
+        } else {
+            self.lod_groups.remove(&handle);
+        }
+    }
+
+    pub(crate) fn sync(&mut self, nodes: &NodePool) {
+        if self.rebuild {
+            self.rebuild = false;
+            self.dirty.clear();
+            self.unculled.clear();
+            self.lod_groups.clear();
+
+            let bvh = &mut self.bvh;
+
+            // Remove records of deleted nodes.
+            self.leaves.retain(|node, leaf| {
+                if nodes.is_valid_handle(*node) {
+                    true
+                } else {
+                    bvh.remove(*leaf);
+                    false
+                }
+            });
+
+            for (handle, node) in nodes.pair_iter() {
+                self.sync_node(handle, node);
+            }
+        } else {
+            let dirty = std::mem::take(&mut self.dirty);
+            for &handle in dirty.iter() {
+                if let Some(node) = nodes.try_borrow(handle) {
+                    self.sync_node(handle, node);
+                } else {
+                    self.forget(handle);
+                }
+            }
+            // Keep the allocated memory for the next frame.
+            self.dirty = dirty;
+            self.dirty.clear();
+        }
+    }
+}
@@ -293,26 +293,22 @@ impl NodeTrait for Mesh {
     }
 
     fn update(&mut self, context: &mut UpdateContext) {
-        if self.surfaces.iter().any(|s| !s.bones.is_empty()) {
-            let mut world_aabb = self
-                .local_bounding_box()
-                .transform(&self.global_transform());
+        let mut world_aabb = self
+            .local_bounding_box()
+            .transform(&self.global_transform());
 
-            // Special case for skinned meshes.
-            for surface in self.surfaces.iter() {
-                for &bone in surface.bones() {
-                    if let Some(node) = context.nodes.try_borrow(bone) {
-                        world_aabb.add_point(node.global_position())
-                    }
+        // Special case for skinned meshes.
+        for surface in self.surfaces.iter() {
+            for &bone in surface.bones() {
+                if let Some(node) = context.nodes.try_borrow(bone) {
+                    world_aabb.add_point(node.global_position())
                 }
             }
+        }
 
-            self.world_bounding_box.set(world_aabb)
-        } else {
-            self.world_bounding_box.set(
-                self.local_bounding_box()
-                    .transform(&self.global_transform()),
-            );
+        let old_aabb = self.world_bounding_box.replace(world_aabb);
+        if old_aabb.min != world_aabb.min || old_aabb.max != world_aabb.max {
+            self.invalidate_bounding_box();
         }
     }
 }
@@ -15,7 +15,7 @@ use crate::{
         reflect::prelude::*,
         sparse::AtomicIndex,
         variable::InheritableVariable,
-        visitor::{Visit, VisitResult, Visitor},
+        visitor::prelude::*,
     },
     material::{Material, SharedMaterial},
     scene::{
@@ -34,7 +34,7 @@ use fxhash::FxHasher;
 use std::{error::Error, hash::Hasher, sync::Arc};
 
 /// A target shape for blending.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Visit)]
 pub struct BlendShape {
     /// Weight of the shape.
     pub weight: f32,
@@ -55,7 +55,7 @@ impl Default for BlendShape {
 }
 
 /// A container for multiple blend shapes/
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, Visit)]
 pub struct BlendShapesContainer {
     /// Base shape which will be used as a binding pose to calculate offsets for each blend shape.
     pub base_shape: VertexBuffer,
@@ -985,7 +985,16 @@ impl Visit for SurfaceData {
 
         if self.is_procedural {
             self.vertex_buffer.visit("VertexBuffer", &mut region)?;
-            self.geometry_buffer.visit("GeometryBuffer", &mut region)?
+            self.geometry_buffer.visit("GeometryBuffer", &mut region)?;
+            if region.is_reading() {
+                // Backward compatibility - old surfaces have no blend shapes.
+                let _ = self
+                    .blend_shapes_container
+                    .visit("BlendShapesContainer", &mut region);
+            } else {
+                self.blend_shapes_container
+                    .visit("BlendShapesContainer", &mut region)?;
+            }
         }
 
         Ok(())
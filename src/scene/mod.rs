@@ -22,7 +22,9 @@ pub mod node;
 pub mod particle_system;
 pub mod pivot;
 pub mod rigidbody;
+pub mod save_game;
 pub mod sound;
+pub mod spline;
 pub mod sprite;
 pub mod terrain;
 pub mod transform;
@@ -38,12 +40,17 @@ use crate::{
         sstorage::ImmutableString,
         visitor::{Visit, VisitError, VisitResult, Visitor},
     },
-    engine::{resource_manager::ResourceManager, SerializationContext},
+    engine::{
+        resource_manager::{loader::BoxedLoaderFuture, ResourceManager},
+        SerializationContext,
+    },
     material::{
         shader::{SamplerFallback, Shader, STANDARD_SHADER_NAMES},
         PropertyValue,
     },
-    resource::{curve::CurveResource, model::Model, texture::Texture},
+    resource::{
+        animation::AnimationResource, curve::CurveResource, model::Model, texture::Texture,
+    },
     scene::{
         base::BaseBuilder,
         camera::Camera,
@@ -253,6 +260,8 @@ struct UsedResourcesSet {
     shaders: FxHashSet<Shader>,
     textures: FxHashSet<Texture>,
     sound_buffers: FxHashSet<SoundBufferResource>,
+    animations: FxHashSet<AnimationResource>,
+    custom: Vec<BoxedLoaderFuture>,
 }
 
 impl UsedResourcesSet {
@@ -263,6 +272,8 @@ impl UsedResourcesSet {
         join_all(self.textures).await;
         join_all(self.sound_buffers).await;
         join_all(self.models).await;
+        join_all(self.animations).await;
+        join_all(self.custom).await;
     }
 }
 
@@ -424,6 +435,32 @@ fn restore_resources(
         });
     }
 
+    if !mapped {
+        entity.downcast_mut::<AnimationResource>(&mut |result| {
+            if let Some(animation) = result {
+                resource_manager
+                    .state()
+                    .containers_mut()
+                    .animations
+                    .try_restore_resource(animation);
+
+                used_resources.animations.insert(animation.clone());
+
+                mapped = true;
+            }
+        });
+    }
+
+    if !mapped {
+        if let Some(future) = resource_manager
+            .state()
+            .containers_mut()
+            .try_restore_custom_resource(entity)
+        {
+            used_resources.custom.push(future);
+        }
+    }
+
     entity.fields_mut(&mut |fields| {
         for field in fields {
             // Continue resolving.
@@ -15,6 +15,7 @@ use crate::{
         particle_system::ParticleSystem,
         pivot::Pivot,
         sound::{listener::Listener, Sound},
+        spline::Spline,
         sprite::Sprite,
         terrain::Terrain,
     },
@@ -57,6 +58,7 @@ impl NodeConstructorContainer {
         container.add::<AnimationPlayer>();
         container.add::<AnimationBlendingStateMachine>();
         container.add::<NavigationalMesh>();
+        container.add::<Spline>();
 
         container
     }
@@ -504,4 +504,17 @@ impl Reflect for Node {
     fn field_mut(&mut self, name: &str, func: &mut dyn FnMut(Option<&mut dyn Reflect>)) {
         self.0.deref_mut().field_mut(name, func)
     }
+
+    fn methods_info(&self, func: &mut dyn FnMut(Vec<MethodInfo>)) {
+        self.0.deref().methods_info(func)
+    }
+
+    fn invoke_method(
+        &mut self,
+        name: &str,
+        args: Vec<Box<dyn Reflect>>,
+        func: &mut dyn FnMut(Result<Box<dyn Reflect>, InvokeError>),
+    ) {
+        self.0.deref_mut().invoke_method(name, args, func)
+    }
 }
@@ -200,6 +200,10 @@ impl Visit for ParticleSystemRng {
 
 /// See module docs.
 #[derive(Debug, Visit, Clone, Reflect)]
+#[reflect(method(
+    name = "clear_particles",
+    description = "Removes all generated particles."
+))]
 pub struct ParticleSystem {
     base: Base,
 
@@ -0,0 +1,720 @@
+//! Save games store only the difference between a running scene and its source scene (a level). See
+//! [`SaveGame`] docs for more info.
+
+use crate::{
+    core::{
+        pool::{Handle, PayloadContainer},
+        reflect::prelude::*,
+        visitor::{prelude::*, PodVecView},
+    },
+    engine::{resource_manager::ResourceManager, SerializationContext},
+    plugin::Plugin,
+    scene::{
+        base::{InstanceId, ScriptRecord},
+        graph::{map::NodeHandleMap, Graph},
+        node::{container::NodeContainer, Node},
+        Scene, SceneLoader,
+    },
+    utils::log::Log,
+};
+use fxhash::FxHashMap;
+use std::{
+    fmt::{Display, Formatter},
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+/// An error that may occur during saving or loading of a save game.
+#[derive(Debug)]
+pub enum SaveGameError {
+    /// An error has occurred during serialization or deserialization.
+    Visit(VisitError),
+
+    /// The save game was made for a newer version of the level, than the one that is being loaded.
+    NewerLevelVersion {
+        /// Version of the level at the moment of saving.
+        save_game: u32,
+        /// Version of the level that is being loaded.
+        level: u32,
+    },
+}
+
+impl Display for SaveGameError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SaveGameError::Visit(v) => {
+                write!(f, "A visitor error has occurred. Reason: {v:?}")
+            }
+            SaveGameError::NewerLevelVersion { save_game, level } => {
+                write!(
+                    f,
+                    "The save game was made for level version {save_game}, \
+                    but the current version of the level is {level}!"
+                )
+            }
+        }
+    }
+}
+
+impl From<VisitError> for SaveGameError {
+    fn from(e: VisitError) -> Self {
+        Self::Visit(e)
+    }
+}
+
+// A copy of a node of a running scene. Scripts of the copy are never initialized, so they must be
+// dropped silently.
+#[derive(Default, Debug)]
+struct NodeSnapshot(NodeContainer);
+
+impl NodeSnapshot {
+    fn new(node: &Node) -> Self {
+        let mut copy = node.clone_box();
+        copy.script_message_sender = None;
+        copy.parent = Handle::NONE;
+        copy.children.clear();
+        Self(NodeContainer::new(copy))
+    }
+}
+
+impl Drop for NodeSnapshot {
+    fn drop(&mut self) {
+        if let Some(node) = self.0.as_mut() {
+            node.scripts.clear();
+        }
+    }
+}
+
+impl Visit for NodeSnapshot {
+    fn visit(&mut self, name: &str, visitor: &mut Visitor) -> VisitResult {
+        self.0.visit(name, visitor)
+    }
+}
+
+#[derive(Default, Debug, Visit)]
+struct NodeId {
+    handle: Handle<Node>,
+    instance_id: InstanceId,
+}
+
+#[derive(Default, Debug, Visit)]
+struct ModifiedNode {
+    handle: Handle<Node>,
+    parent: Handle<Node>,
+    properties: Vec<String>,
+    snapshot: NodeSnapshot,
+}
+
+#[derive(Default, Debug, Visit)]
+struct SpawnedNode {
+    handle: Handle<Node>,
+    parent: Handle<Node>,
+    snapshot: NodeSnapshot,
+}
+
+#[derive(Default, Debug)]
+struct PluginState {
+    name: String,
+    data: Vec<u8>,
+}
+
+impl Visit for PluginState {
+    fn visit(&mut self, name: &str, visitor: &mut Visitor) -> VisitResult {
+        let mut region = visitor.enter_region(name)?;
+
+        self.name.visit("Name", &mut region)?;
+        PodVecView::from_pod_vec(&mut self.data).visit("Data", &mut region)?;
+
+        Ok(())
+    }
+}
+
+/// Save game stores the state of a running scene as a difference from its source scene (a level made
+/// in the editor), instead of saving the entire scene with all its static data. The difference
+/// includes:
+///
+/// - Every modified inheritable property (see [`crate::core::variable::InheritableVariable`]) of the
+///   level nodes. Properties are stored by their reflection paths, for example
+///   `base.local_transform.local_position`.
+/// - Parents of the level nodes, if they were changed.
+/// - Scripts of the level nodes (with their entire state).
+/// - Nodes, that were spawned at runtime (for example, instances of prefabs), with their scripts.
+/// - Level nodes, that were destroyed at runtime.
+/// - Optional states of plugins, see [`Self::store_plugin_state`].
+///
+/// A level node is a node of the running scene, that has a node with the same handle and the same
+/// instance id in the source scene. Everything else is considered as spawned at runtime.
+///
+/// ## Example
+///
+/// ```rust,no_run
+/// # use fyrox::{
+/// #     engine::{resource_manager::ResourceManager, SerializationContext},
+/// #     scene::{save_game::{SaveGame, SaveGameError}, Scene, SceneLoader},
+/// # };
+/// # use std::sync::Arc;
+/// const LEVEL_VERSION: u32 = 1;
+///
+/// async fn save(
+///     scene: &Scene,
+///     serialization_context: Arc<SerializationContext>,
+///     resource_manager: ResourceManager,
+/// ) -> Result<(), SaveGameError> {
+///     // Load the source level to compare the running scene with it.
+///     let source = SceneLoader::from_file("data/level.rgs", serialization_context)
+///         .await?
+///         .finish(resource_manager)
+///         .await;
+///
+///     SaveGame::capture(scene, &source, "data/level.rgs", LEVEL_VERSION).save("save.bin")
+/// }
+///
+/// async fn load(
+///     serialization_context: Arc<SerializationContext>,
+///     resource_manager: ResourceManager,
+/// ) -> Result<Scene, SaveGameError> {
+///     SaveGame::load_from_file("save.bin", serialization_context.clone())
+///         .await?
+///         .load_scene(LEVEL_VERSION, serialization_context, resource_manager)
+///         .await
+/// }
+/// ```
+///
+/// ## Versioning
+///
+/// Levels are usually changed after a game was released, so the save game stores a user-defined version
+/// of the level. It is not allowed to load a save game on top of an older version of the level (see
+/// [`SaveGameError::NewerLevelVersion`]). If the level was updated, nodes are matched by their handles
+/// first and, if a node with the same handle and instance id does not exist, by their instance ids. Nodes
+/// that could not be matched are skipped with a warning.
+///
+/// ## Important notes
+///
+/// Save game captures only the graph of a scene, scene-wide settings (for example, rendering options)
+/// are not saved. Scripts of a loaded scene are initialized from scratch, which means that
+/// [`crate::script::ScriptTrait::on_init`] and [`crate::script::ScriptTrait::on_start`] will be called
+/// again. A save game should be captured outside of script callbacks (for example, in a plugin), because
+/// a script that is being processed is temporarily moved out of its node.
+#[derive(Default, Debug)]
+pub struct SaveGame {
+    level_path: PathBuf,
+    level_version: u32,
+    root: Handle<Node>,
+    level_nodes: Vec<NodeId>,
+    modified: Vec<ModifiedNode>,
+    spawned: Vec<SpawnedNode>,
+    destroyed: Vec<NodeId>,
+    plugin_states: Vec<PluginState>,
+}
+
+impl SaveGame {
+    /// Current version of the save game format.
+    pub const VERSION: u32 = 0;
+
+    /// Captures the difference between the given running scene and its source scene. `level_path` is
+    /// a path to the source scene, it is used to load the level back. `level_version` is a user-defined
+    /// version of the level, see [`Self`] docs for more info.
+    pub fn capture<P: AsRef<Path>>(
+        scene: &Scene,
+        source: &Scene,
+        level_path: P,
+        level_version: u32,
+    ) -> Self {
+        let graph = &scene.graph;
+        let source_graph = &source.graph;
+
+        let source_node = |handle: Handle<Node>, node: &Node| {
+            if handle == graph.get_root() {
+                source_graph.try_get(source_graph.get_root())
+            } else {
+                source_graph
+                    .try_get(handle)
+                    .filter(|source_node| source_node.instance_id() == node.instance_id())
+            }
+        };
+
+        let mut save_game = Self {
+            level_path: level_path.as_ref().to_path_buf(),
+            level_version,
+            root: graph.get_root(),
+            ..Default::default()
+        };
+
+        for (handle, node) in graph.pair_iter() {
+            if let Some(source_node) = source_node(handle, node) {
+                if handle != graph.get_root() {
+                    save_game.level_nodes.push(NodeId {
+                        handle,
+                        instance_id: node.instance_id(),
+                    });
+                }
+
+                let mut properties = Vec::new();
+                source_node.as_reflect(&mut |source_node| {
+                    node.as_reflect(&mut |node| {
+                        collect_modified_properties(source_node, node, "", &mut properties)
+                    })
+                });
+
+                if !properties.is_empty()
+                    || node.parent() != source_node.parent()
+                    || node.script_count() != 0
+                    || source_node.script_count() != 0
+                {
+                    save_game.modified.push(ModifiedNode {
+                        handle,
+                        parent: node.parent(),
+                        properties,
+                        snapshot: NodeSnapshot::new(node),
+                    });
+                }
+            } else {
+                save_game.spawned.push(SpawnedNode {
+                    handle,
+                    parent: node.parent(),
+                    snapshot: NodeSnapshot::new(node),
+                });
+            }
+        }
+
+        for (handle, source_node) in source_graph.pair_iter() {
+            if handle != source_graph.get_root()
+                && graph
+                    .try_get(handle)
+                    .map_or(true, |node| node.instance_id() != source_node.instance_id())
+            {
+                save_game.destroyed.push(NodeId {
+                    handle,
+                    instance_id: source_node.instance_id(),
+                });
+            }
+        }
+
+        save_game
+    }
+
+    /// Returns a path to the source scene of the save game.
+    pub fn level_path(&self) -> &Path {
+        &self.level_path
+    }
+
+    /// Returns the version of the level at the moment of saving.
+    pub fn level_version(&self) -> u32 {
+        self.level_version
+    }
+
+    /// Stores the state of the given plugin (see [`Plugin::visit_state`]) under the given name. The state
+    /// of a plugin with the same name will be replaced.
+    pub fn store_plugin_state(&mut self, name: &str, plugin: &mut dyn Plugin) -> VisitResult {
+        let mut visitor = Visitor::new();
+        plugin.visit_state(&mut visitor)?;
+        let data = visitor.save_binary_to_vec()?;
+
+        if let Some(state) = self.plugin_states.iter_mut().find(|s| s.name == name) {
+            state.data = data;
+        } else {
+            self.plugin_states.push(PluginState {
+                name: name.to_owned(),
+                data,
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Restores the state of the given plugin (see [`Plugin::visit_state`]) from the state stored under
+    /// the given name. Returns `false` if there is no such state.
+    pub fn restore_plugin_state(
+        &self,
+        name: &str,
+        plugin: &mut dyn Plugin,
+    ) -> Result<bool, VisitError> {
+        if let Some(state) = self.plugin_states.iter().find(|s| s.name == name) {
+            let mut visitor = Visitor::load_from_memory(state.data.clone())?;
+            plugin.visit_state(&mut visitor)?;
+            Ok(true)
+        } else {
+            Ok(false)
+        }
+    }
+
+    /// Writes the save game to the given file.
+    pub fn save<P: AsRef<Path>>(&mut self, path: P) -> Result<(), SaveGameError> {
+        let mut visitor = Visitor::new();
+        self.visit("SaveGame", &mut visitor)?;
+        visitor.save_binary(path)?;
+        Ok(())
+    }
+
+    /// Reads a save game from the given file. Serialization context is needed to create instances of
+    /// spawned nodes and scripts.
+    pub async fn load_from_file<P: AsRef<Path>>(
+        path: P,
+        serialization_context: Arc<SerializationContext>,
+    ) -> Result<Self, SaveGameError> {
+        let mut visitor = Visitor::load_binary(path).await?;
+        visitor.environment = Some(serialization_context);
+        let mut save_game = Self::default();
+        save_game.visit("SaveGame", &mut visitor)?;
+        Ok(save_game)
+    }
+
+    /// Loads the source scene of the save game and applies the saved state on top of it. `level_version`
+    /// is the current version of the level, see [`Self`] docs for more info.
+    pub async fn load_scene(
+        mut self,
+        level_version: u32,
+        serialization_context: Arc<SerializationContext>,
+        resource_manager: ResourceManager,
+    ) -> Result<Scene, SaveGameError> {
+        if self.level_version > level_version {
+            return Err(SaveGameError::NewerLevelVersion {
+                save_game: self.level_version,
+                level: level_version,
+            });
+        }
+
+        let mut loader = SceneLoader::from_file(&self.level_path, serialization_context).await?;
+
+        self.apply(&mut loader.scene.graph);
+
+        let mut scene = loader.finish(resource_manager).await;
+
+        // Destroyed nodes must be removed after the scene is resolved, otherwise destroyed parts of
+        // prefab instances will be restored.
+        self.remove_destroyed(&mut scene.graph);
+
+        Ok(scene)
+    }
+
+    // Applies everything except destroyed nodes, returns a map of saved handles to handles in the
+    // given graph.
+    fn apply(&mut self, graph: &mut Graph) -> NodeHandleMap {
+        let instance_ids = unique_instance_ids(graph);
+
+        let mut map = NodeHandleMap::default();
+        map.map.insert(self.root, graph.get_root());
+
+        for id in self.level_nodes.iter() {
+            if let Some(handle) = find_node(graph, id, &instance_ids) {
+                map.map.insert(id.handle, handle);
+            } else {
+                Log::warn(format!(
+                    "Unable to find a level node {} with instance id {:?}, its state won't be loaded!",
+                    id.handle, id.instance_id
+                ))
+            }
+        }
+
+        // References to destroyed nodes will become invalid.
+        for id in self.destroyed.iter() {
+            map.map.entry(id.handle).or_insert(Handle::NONE);
+        }
+
+        for spawned in self.spawned.iter_mut() {
+            if let Some(node) = spawned.snapshot.0.take() {
+                let handle = graph.add_node(node);
+                map.map.insert(spawned.handle, handle);
+            }
+        }
+
+        for spawned in self.spawned.iter() {
+            if let Some(&handle) = map.inner().get(&spawned.handle) {
+                map.remap_handles(&mut graph[handle]);
+
+                let mut parent = spawned.parent;
+                if map.try_map(&mut parent) && parent.is_some() {
+                    graph.link_nodes(handle, parent);
+                } else {
+                    Log::warn(format!(
+                        "Unable to find a parent for spawned node {}, it will be attached to the root.",
+                        graph[handle].name()
+                    ));
+                }
+            }
+        }
+
+        for modified in self.modified.iter_mut() {
+            let handle = match map.inner().get(&modified.handle) {
+                Some(handle) if graph.is_valid_handle(*handle) => *handle,
+                _ => continue,
+            };
+
+            let snapshot = match modified.snapshot.0.as_mut() {
+                Some(snapshot) => snapshot,
+                None => continue,
+            };
+
+            map.remap_handles(snapshot);
+
+            let node = &mut graph[handle];
+            for path in modified.properties.iter() {
+                copy_property(snapshot, node, path);
+            }
+            node.scripts = std::mem::take(&mut snapshot.scripts)
+                .into_iter()
+                .filter(|record| record.script.is_some() && !record.should_be_deleted)
+                .collect();
+
+            let mut parent = modified.parent;
+            if map.try_map(&mut parent) && parent.is_some() && graph[handle].parent() != parent {
+                graph.link_nodes(handle, parent);
+            }
+        }
+
+        map
+    }
+
+    fn remove_destroyed(&self, graph: &mut Graph) {
+        let instance_ids = unique_instance_ids(graph);
+
+        for id in self.destroyed.iter() {
+            // A node could be removed already together with its destroyed parent.
+            if let Some(handle) = find_node(graph, id, &instance_ids) {
+                if graph.is_valid_handle(handle) {
+                    graph.remove_node(handle);
+                }
+            }
+        }
+    }
+}
+
+impl Visit for SaveGame {
+    fn visit(&mut self, name: &str, visitor: &mut Visitor) -> VisitResult {
+        let mut region = visitor.enter_region(name)?;
+
+        let mut version = Self::VERSION;
+        version.visit("Version", &mut region)?;
+        if region.is_reading() && version > Self::VERSION {
+            return Err(VisitError::User(format!(
+                "Unsupported save game version {version}!"
+            )));
+        }
+
+        self.level_path.visit("LevelPath", &mut region)?;
+        self.level_version.visit("LevelVersion", &mut region)?;
+        self.root.visit("Root", &mut region)?;
+        self.level_nodes.visit("LevelNodes", &mut region)?;
+        self.modified.visit("Modified", &mut region)?;
+        self.spawned.visit("Spawned", &mut region)?;
+        self.destroyed.visit("Destroyed", &mut region)?;
+        self.plugin_states.visit("PluginStates", &mut region)?;
+
+        Ok(())
+    }
+}
+
+// Collects reflection paths of every inheritable variable that differs in the given entities.
+fn collect_modified_properties(
+    source: &dyn Reflect,
+    current: &dyn Reflect,
+    path: &str,
+    properties: &mut Vec<String>,
+) {
+    let mut handled = false;
+
+    current.as_inheritable_variable(&mut |current_variable| {
+        if let Some(current_variable) = current_variable {
+            handled = true;
+            source.as_inheritable_variable(&mut |source_variable| {
+                if source_variable.map_or(true, |v| !current_variable.value_equals(v)) {
+                    properties.push(path.to_owned());
+                }
+            });
+        }
+    });
+
+    // Scripts are stored as a whole.
+    current.downcast_ref::<Vec<ScriptRecord>>(&mut |scripts| handled |= scripts.is_some());
+
+    if handled {
+        return;
+    }
+
+    current.as_array(&mut |current_array| {
+        if let Some(current_array) = current_array {
+            handled = true;
+            source.as_array(&mut |source_array| match source_array {
+                Some(source_array) if source_array.reflect_len() == current_array.reflect_len() => {
+                    for i in 0..current_array.reflect_len() {
+                        if let (Some(source_item), Some(current_item)) = (
+                            source_array.reflect_index(i),
+                            current_array.reflect_index(i),
+                        ) {
+                            collect_modified_properties(
+                                source_item,
+                                current_item,
+                                &format!("{path}[{i}]"),
+                                properties,
+                            );
+                        }
+                    }
+                }
+                _ => (),
+            });
+        }
+    });
+
+    if handled {
+        return;
+    }
+
+    current.fields_info(&mut |current_fields| {
+        source.fields_info(&mut |source_fields| {
+            for (current_field, source_field) in current_fields.iter().zip(source_fields.iter()) {
+                if current_field.name == source_field.name {
+                    let field_path = if path.is_empty() {
+                        current_field.name.to_owned()
+                    } else {
+                        format!("{}.{}", path, current_field.name)
+                    };
+
+                    collect_modified_properties(
+                        source_field.reflect_value,
+                        current_field.reflect_value,
+                        &field_path,
+                        properties,
+                    );
+                }
+            }
+        })
+    });
+}
+
+fn copy_property(source: &Node, dest: &mut Node, path: &str) {
+    let mut value = None;
+    source.as_reflect(&mut |source| {
+        source.resolve_path(path, &mut |result| {
+            if let Ok(field) = result {
+                field.as_inheritable_variable(&mut |variable| {
+                    value = variable.map(|variable| variable.clone_value_box());
+                })
+            }
+        })
+    });
+
+    if value.is_none() {
+        Log::warn(format!("Unable to read saved property {path}!"));
+        return;
+    }
+
+    let mut succeeded = false;
+    dest.as_reflect_mut(&mut |dest| {
+        if path.ends_with(']') {
+            // Array items cannot be set using `set_field_by_path`, set them directly.
+            dest.resolve_path_mut(path, &mut |result| {
+                if let (Ok(field), Some(value)) = (result, value.take()) {
+                    succeeded = field.set(value).is_ok();
+                }
+            });
+        } else if let Some(value) = value.take() {
+            dest.set_field_by_path(path, value, &mut |result| succeeded = result.is_ok());
+        }
+    });
+
+    if !succeeded {
+        Log::warn(format!("Unable to load saved property {path}!"));
+    }
+}
+
+// Maps every instance id of the graph to a handle of a node, or to `None` if there are multiple
+// nodes with the same instance id.
+fn unique_instance_ids(graph: &Graph) -> FxHashMap<InstanceId, Option<Handle<Node>>> {
+    let mut instance_ids = FxHashMap::default();
+    for (handle, node) in graph.pair_iter() {
+        instance_ids
+            .entry(node.instance_id())
+            .and_modify(|h| *h = None)
+            .or_insert(Some(handle));
+    }
+    instance_ids
+}
+
+fn find_node(
+    graph: &Graph,
+    id: &NodeId,
+    instance_ids: &FxHashMap<InstanceId, Option<Handle<Node>>>,
+) -> Option<Handle<Node>> {
+    if graph
+        .try_get(id.handle)
+        .map_or(false, |node| node.instance_id() == id.instance_id)
+    {
+        Some(id.handle)
+    } else {
+        instance_ids.get(&id.instance_id).cloned().flatten()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        core::{algebra::Vector3, uuid::Uuid, visitor::prelude::*},
+        engine::SerializationContext,
+        scene::{
+            base::{BaseBuilder, InstanceId},
+            pivot::PivotBuilder,
+            save_game::SaveGame,
+            Scene,
+        },
+    };
+    use std::sync::Arc;
+
+    fn make_level() -> Scene {
+        let mut scene = Scene::new();
+        let root = scene.graph.get_root();
+        scene.graph[root].set_instance_id(InstanceId(Uuid::from_u128(0)));
+        for (i, name) in ["A", "B", "C"].into_iter().enumerate() {
+            PivotBuilder::new(
+                BaseBuilder::new()
+                    .with_name(name)
+                    .with_instance_id(InstanceId(Uuid::from_u128(i as u128 + 1))),
+            )
+            .build(&mut scene.graph);
+        }
+        scene
+    }
+
+    #[test]
+    fn test_save_game() {
+        let source = make_level();
+        let mut running = make_level();
+
+        let a = running.graph.find_by_name_from_root("A").unwrap().0;
+        let b = running.graph.find_by_name_from_root("B").unwrap().0;
+        let c = running.graph.find_by_name_from_root("C").unwrap().0;
+
+        running.graph[a].set_name("Renamed");
+        running.graph[a]
+            .local_transform_mut()
+            .set_position(Vector3::new(1.0, 2.0, 3.0));
+        running.graph.remove_node(b);
+        let spawned =
+            PivotBuilder::new(BaseBuilder::new().with_name("Spawned")).build(&mut running.graph);
+        running.graph.link_nodes(spawned, c);
+
+        let mut save_game = SaveGame::capture(&running, &source, "level.rgs", 1);
+
+        let mut visitor = Visitor::new();
+        save_game.visit("SaveGame", &mut visitor).unwrap();
+        let data = visitor.save_binary_to_vec().unwrap();
+
+        let mut visitor = Visitor::load_from_memory(data).unwrap();
+        visitor.environment = Some(Arc::new(SerializationContext::new()));
+        let mut loaded = SaveGame::default();
+        loaded.visit("SaveGame", &mut visitor).unwrap();
+        assert_eq!(loaded.level_version(), 1);
+
+        let mut level = make_level();
+        loaded.apply(&mut level.graph);
+        loaded.remove_destroyed(&mut level.graph);
+
+        assert_eq!(level.graph[a].name(), "Renamed");
+        assert_eq!(
+            **level.graph[a].local_transform().position(),
+            Vector3::new(1.0, 2.0, 3.0)
+        );
+        assert!(!level.graph.is_valid_handle(b));
+        let (spawned, _) = level.graph.find_by_name_from_root("Spawned").unwrap();
+        assert_eq!(level.graph[spawned].parent(), c);
+    }
+}
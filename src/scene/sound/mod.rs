@@ -46,6 +46,9 @@ pub mod listener;
 
 /// Sound source.
 #[derive(Visit, Reflect, Debug)]
+#[reflect(method(name = "play", description = "Starts playing the sound."))]
+#[reflect(method(name = "pause", description = "Pauses the sound."))]
+#[reflect(method(name = "stop", description = "Stops the sound and rewinds it."))]
 pub struct Sound {
     base: Base,
 
@@ -0,0 +1,360 @@
+//! Contains all structures and methods to create and manage splines.
+//!
+//! For more info see [`Spline`].
+
+use crate::{
+    core::{
+        algebra::{Matrix4, Point3, Vector3},
+        math::{
+            aabb::AxisAlignedBoundingBox,
+            spline::{
+                self, ArcLengthTable, BezierSpline, CatmullRomSpline, Spline as _, SplinePoint,
+            },
+        },
+        pool::Handle,
+        reflect::prelude::*,
+        uuid::{uuid, Uuid},
+        variable::InheritableVariable,
+        visitor::prelude::*,
+    },
+    scene::{
+        base::{Base, BaseBuilder},
+        graph::Graph,
+        node::{Node, NodeTrait, TypeUuidProvider},
+    },
+};
+use std::ops::{Deref, DerefMut};
+use strum_macros::{AsRefStr, EnumString, EnumVariantNames};
+
+/// Defines how the control points of a spline are interpolated.
+#[derive(
+    Copy, Clone, PartialEq, Eq, Debug, Visit, Reflect, AsRefStr, EnumString, EnumVariantNames,
+)]
+pub enum SplineKind {
+    /// The spline passes through every control point, control handles are ignored. See
+    /// [`CatmullRomSpline`] for more info.
+    CatmullRom,
+    /// Chain of cubic Bezier curves, the shape of the spline is defined by control handles of the
+    /// points. See [`BezierSpline`] for more info.
+    Bezier,
+}
+
+impl Default for SplineKind {
+    fn default() -> Self {
+        Self::CatmullRom
+    }
+}
+
+/// A curve built from the control points of a [`Spline`] node. It implements [`spline::Spline`] trait,
+/// so it could be used for any queries - positions, tangents, closest points, etc.
+#[derive(Clone, Debug, PartialEq)]
+pub enum SplineCurve {
+    /// Catmull-Rom spline.
+    CatmullRom(CatmullRomSpline),
+    /// Cubic Bezier spline.
+    Bezier(BezierSpline),
+}
+
+impl spline::Spline for SplineCurve {
+    fn segment_count(&self) -> usize {
+        match self {
+            SplineCurve::CatmullRom(spline) => spline.segment_count(),
+            SplineCurve::Bezier(spline) => spline.segment_count(),
+        }
+    }
+
+    fn segment_position(&self, segment: usize, t: f32) -> Vector3<f32> {
+        match self {
+            SplineCurve::CatmullRom(spline) => spline.segment_position(segment, t),
+            SplineCurve::Bezier(spline) => spline.segment_position(segment, t),
+        }
+    }
+
+    fn segment_derivative(&self, segment: usize, t: f32) -> Vector3<f32> {
+        match self {
+            SplineCurve::CatmullRom(spline) => spline.segment_derivative(segment, t),
+            SplineCurve::Bezier(spline) => spline.segment_derivative(segment, t),
+        }
+    }
+}
+
+/// Spline is a smooth path defined by a set of control points in local coordinates of the node. It
+/// could be used to lay out camera rails, patrol paths, roads, etc. Spline has no visual representation
+/// in the game, the editor shows it as a set of lines.
+///
+/// Use [`Spline::curve`] to get the curve in local coordinates or [`Spline::world_curve`] to get it in
+/// world coordinates (useful to do closest point queries, etc.). Since the parameterization of the curve
+/// is not uniform, use [`ArcLengthTable`] to move along the spline with constant speed.
+///
+/// # Example
+///
+/// ```rust
+/// use fyrox::{
+///     core::{algebra::Vector3, math::spline::{ArcLengthTable, Spline as _}, pool::Handle},
+///     scene::{
+///         base::BaseBuilder,
+///         graph::Graph,
+///         node::Node,
+///         spline::{Spline, SplineBuilder},
+///     },
+/// };
+///
+/// fn create_patrol_path(graph: &mut Graph) -> Handle<Node> {
+///     SplineBuilder::new(BaseBuilder::new().with_name("PatrolPath"))
+///         .with_positions(&[
+///             Vector3::new(0.0, 0.0, 0.0),
+///             Vector3::new(5.0, 0.0, 0.0),
+///             Vector3::new(5.0, 0.0, 5.0),
+///         ])
+///         .with_closed(true)
+///         .build(graph)
+/// }
+///
+/// fn position_at_distance(spline: &Spline, distance: f32) -> Vector3<f32> {
+///     let curve = spline.world_curve();
+///     let table = ArcLengthTable::new(&curve, 16);
+///     curve.position(table.parameter(distance % table.length()))
+/// }
+/// ```
+#[derive(Debug, Reflect, Clone, Visit)]
+pub struct Spline {
+    base: Base,
+
+    #[reflect(setter = "set_kind")]
+    kind: InheritableVariable<SplineKind>,
+
+    #[reflect(setter = "set_points")]
+    points: InheritableVariable<Vec<SplinePoint>>,
+
+    #[reflect(setter = "set_closed")]
+    closed: InheritableVariable<bool>,
+}
+
+impl Deref for Spline {
+    type Target = Base;
+
+    fn deref(&self) -> &Self::Target {
+        &self.base
+    }
+}
+
+impl DerefMut for Spline {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.base
+    }
+}
+
+impl Default for Spline {
+    fn default() -> Self {
+        SplineBuilder::new(BaseBuilder::new()).build_spline()
+    }
+}
+
+impl TypeUuidProvider for Spline {
+    fn type_uuid() -> Uuid {
+        uuid!("8f1b2a0e-5c7d-4e39-9a64-3d2f6b1c7e05")
+    }
+}
+
+impl Spline {
+    /// Sets new kind of the spline and returns the old one.
+    pub fn set_kind(&mut self, kind: SplineKind) -> SplineKind {
+        self.invalidate_bounding_box();
+        self.kind.set_value_and_mark_modified(kind)
+    }
+
+    /// Returns current kind of the spline.
+    pub fn kind(&self) -> SplineKind {
+        *self.kind
+    }
+
+    /// Sets new control points of the spline and returns the old ones. Points are defined in local
+    /// coordinates of the node.
+    pub fn set_points(&mut self, points: Vec<SplinePoint>) -> Vec<SplinePoint> {
+        self.invalidate_bounding_box();
+        self.points.set_value_and_mark_modified(points)
+    }
+
+    /// Returns current control points of the spline.
+    pub fn points(&self) -> &[SplinePoint] {
+        &self.points
+    }
+
+    /// Sets whether the last point of the spline should be connected with the first one or not.
+    /// Returns the old value.
+    pub fn set_closed(&mut self, closed: bool) -> bool {
+        self.invalidate_bounding_box();
+        self.closed.set_value_and_mark_modified(closed)
+    }
+
+    /// Returns `true` if the last point of the spline is connected with the first one.
+    pub fn is_closed(&self) -> bool {
+        *self.closed
+    }
+
+    /// Returns the curve in local coordinates of the node.
+    pub fn curve(&self) -> SplineCurve {
+        self.make_curve(&Matrix4::identity())
+    }
+
+    /// Returns the curve in world coordinates. Keep in mind that world transform of the node is
+    /// calculated on graph update, so the result will be valid only after at least one update.
+    pub fn world_curve(&self) -> SplineCurve {
+        self.make_curve(&self.global_transform())
+    }
+
+    /// Returns length of the spline in world coordinates.
+    pub fn world_length(&self) -> f32 {
+        ArcLengthTable::new(&self.world_curve(), 16).length()
+    }
+
+    fn make_curve(&self, transform: &Matrix4<f32>) -> SplineCurve {
+        let closed = *self.closed;
+        let transform_point = |p: Vector3<f32>| transform.transform_point(&Point3::from(p)).coords;
+        match *self.kind {
+            SplineKind::CatmullRom => SplineCurve::CatmullRom(CatmullRomSpline::new(
+                self.points
+                    .iter()
+                    .map(|p| transform_point(p.position))
+                    .collect(),
+                closed,
+            )),
+            SplineKind::Bezier => SplineCurve::Bezier(BezierSpline::new(
+                self.points
+                    .iter()
+                    .map(|p| SplinePoint {
+                        position: transform_point(p.position),
+                        in_tangent: transform.transform_vector(&p.in_tangent),
+                        out_tangent: transform.transform_vector(&p.out_tangent),
+                    })
+                    .collect(),
+                closed,
+            )),
+        }
+    }
+}
+
+impl NodeTrait for Spline {
+    crate::impl_query_component!();
+
+    fn local_bounding_box(&self) -> AxisAlignedBoundingBox {
+        const SAMPLES_PER_SEGMENT: usize = 8;
+
+        let curve = self.curve();
+        let sample_count = curve.segment_count() * SAMPLES_PER_SEGMENT;
+        let mut bounding_box = AxisAlignedBoundingBox::default();
+        for point in self.points.iter() {
+            bounding_box.add_point(point.position);
+        }
+        for i in 0..=sample_count {
+            bounding_box.add_point(curve.position(i as f32 / SAMPLES_PER_SEGMENT as f32));
+        }
+        bounding_box
+    }
+
+    fn world_bounding_box(&self) -> AxisAlignedBoundingBox {
+        self.local_bounding_box()
+            .transform(&self.global_transform())
+    }
+
+    fn id(&self) -> Uuid {
+        Self::type_uuid()
+    }
+}
+
+/// Spline builder allows you to construct spline in declarative manner.
+pub struct SplineBuilder {
+    base_builder: BaseBuilder,
+    kind: SplineKind,
+    points: Vec<SplinePoint>,
+    closed: bool,
+}
+
+impl SplineBuilder {
+    /// Creates new builder of an open Catmull-Rom spline without any points.
+    pub fn new(base_builder: BaseBuilder) -> Self {
+        Self {
+            base_builder,
+            kind: SplineKind::CatmullRom,
+            points: Default::default(),
+            closed: false,
+        }
+    }
+
+    /// Sets desired kind of the spline.
+    pub fn with_kind(mut self, kind: SplineKind) -> Self {
+        self.kind = kind;
+        self
+    }
+
+    /// Sets desired control points.
+    pub fn with_points(mut self, points: Vec<SplinePoint>) -> Self {
+        self.points = points;
+        self
+    }
+
+    /// Sets desired control points using their positions only, control handles will be zero.
+    pub fn with_positions(mut self, positions: &[Vector3<f32>]) -> Self {
+        self.points = positions
+            .iter()
+            .map(|position| SplinePoint {
+                position: *position,
+                ..Default::default()
+            })
+            .collect();
+        self
+    }
+
+    /// Sets whether the last point should be connected with the first one or not.
+    pub fn with_closed(mut self, closed: bool) -> Self {
+        self.closed = closed;
+        self
+    }
+
+    fn build_spline(self) -> Spline {
+        Spline {
+            base: self.base_builder.build_base(),
+            kind: self.kind.into(),
+            points: self.points.into(),
+            closed: self.closed.into(),
+        }
+    }
+
+    /// Creates new spline instance.
+    pub fn build_node(self) -> Node {
+        Node::new(self.build_spline())
+    }
+
+    /// Creates new spline instance and adds it to the graph.
+    pub fn build(self, graph: &mut Graph) -> Handle<Node> {
+        graph.add_node(self.build_node())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        core::{algebra::Vector3, math::spline::Spline as _},
+        scene::{
+            base::BaseBuilder,
+            node::NodeTrait,
+            spline::{SplineBuilder, SplineKind},
+        },
+    };
+
+    #[test]
+    fn test_spline_curve() {
+        let spline = SplineBuilder::new(BaseBuilder::new())
+            .with_kind(SplineKind::Bezier)
+            .with_positions(&[Vector3::new(0.0, 0.0, 0.0), Vector3::new(2.0, 0.0, 0.0)])
+            .build_spline();
+
+        let curve = spline.curve();
+        assert_eq!(curve.segment_count(), 1);
+        assert_eq!(curve.position(0.5), Vector3::new(1.0, 0.0, 0.0));
+
+        let bounds = spline.local_bounding_box();
+        assert_eq!(bounds.min, Vector3::new(0.0, 0.0, 0.0));
+        assert_eq!(bounds.max, Vector3::new(2.0, 0.0, 0.0));
+    }
+}
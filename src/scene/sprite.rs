@@ -110,6 +110,7 @@ impl Sprite {
     ///
     /// Negative values could be used to "inverse" the image on the sprite.
     pub fn set_size(&mut self, size: f32) -> f32 {
+        self.invalidate_bounding_box();
         self.size.set_value_and_mark_modified(size)
     }
 
@@ -162,7 +163,8 @@ impl NodeTrait for Sprite {
     }
 
     fn world_bounding_box(&self) -> AxisAlignedBoundingBox {
-        self.base.world_bounding_box()
+        self.local_bounding_box()
+            .transform(&self.global_transform())
     }
 
     fn id(&self) -> Uuid {
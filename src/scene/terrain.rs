@@ -633,6 +633,7 @@ impl Terrain {
         }
 
         self.bounding_box_dirty.set(true);
+        self.invalidate_bounding_box();
     }
 
     /// Returns a reference to chunks of the terrain.
@@ -1011,6 +1012,7 @@ impl Terrain {
 
         self.height_map_size.set_value_and_mark_modified(new_size);
         self.bounding_box_dirty.set(true);
+        self.invalidate_bounding_box();
     }
 }
 
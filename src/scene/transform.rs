@@ -267,6 +267,22 @@ impl Transform {
         self.local_scale.set_value_and_mark_modified(local_scale)
     }
 
+    /// Sets position, rotation and scale of the transform without marking respective variables as
+    /// modified. It is used for temporary changes, that must not be synchronized with other systems
+    /// (physics, property inheritance, etc.).
+    #[inline]
+    pub(crate) fn set_position_rotation_scale_silent(
+        &mut self,
+        local_position: Vector3<f32>,
+        local_rotation: UnitQuaternion<f32>,
+        local_scale: Vector3<f32>,
+    ) {
+        self.dirty.set(true);
+        self.local_position.set_value_silent(local_position);
+        self.local_rotation.set_value_silent(local_rotation);
+        self.local_scale.set_value_silent(local_scale);
+    }
+
     /// Sets pre-rotation of transform. Usually pre-rotation can be used to change
     /// "coordinate" system of transform. It is mostly for FBX compatibility, and
     /// never used in other places of engine.
@@ -2,7 +2,7 @@
 //!
 //! For more info see [`VisibilityCache`]
 
-use crate::scene::graph::NodePool;
+use crate::scene::graph::{spatial::SpatialIndex, NodePool};
 use crate::{
     core::{algebra::Vector3, math::frustum::Frustum, pool::Handle},
     scene::node::Node,
@@ -22,7 +22,9 @@ use fxhash::FxHashMap;
 ///
 /// # Performance
 ///
-/// The cache is based on hash map, so it is very fast and has O(1) complexity for fetching.
+/// The cache is based on hash map, so it is very fast and has O(1) complexity for fetching. Frustum culling is done
+/// using [`SpatialIndex`] of the graph, so only the nodes that cannot be culled by the index (see
+/// [`SpatialIndex::unculled_nodes`]) are tested one-by-one.
 #[derive(Default, Debug, Clone)]
 pub struct VisibilityCache {
     map: FxHashMap<Handle<Node>, bool>,
@@ -47,10 +49,12 @@ impl VisibilityCache {
     }
 
     /// Updates visibility cache - checks visibility for each node in given graph, also performs
-    /// frustum culling if frustum set is specified.
+    /// frustum culling if frustum set is specified. The spatial index must be in sync with the
+    /// nodes, otherwise the results of frustum culling will be incorrect.
     pub fn update(
         &mut self,
         nodes: &NodePool,
+        spatial_index: &SpatialIndex,
         observer_position: Vector3<f32>,
         z_near: f32,
         z_far: f32,
@@ -59,8 +63,8 @@ impl VisibilityCache {
         self.map.clear();
 
         // Check LODs first, it has priority over other visibility settings.
-        for node in nodes.iter() {
-            if let Some(lod_group) = node.lod_group() {
+        for handle in spatial_index.lod_group_nodes() {
+            if let Some(lod_group) = nodes.try_borrow(handle).and_then(|node| node.lod_group()) {
                 for level in lod_group.levels.iter() {
                     for &object in level.objects.iter() {
                         if let Some(object_ref) = nodes.try_borrow(*object) {
@@ -77,27 +81,44 @@ impl VisibilityCache {
             }
         }
 
-        // Fill rest of data from global visibility flag of nodes and check frustums (if any).
-        for (handle, node) in nodes.pair_iter() {
-            // We need to fill only unfilled entries, none of visibility flags of a node can
-            // make it visible again if lod group hid it.
-            self.map.entry(handle).or_insert_with(|| {
-                let mut visibility = node.global_visibility();
-                if visibility && node.frustum_culling() {
-                    // If a node globally visible, check it with each frustum (if any).
-                    if let Some(frustums) = frustums {
-                        let mut visible_by_any_frustum = false;
-                        for frustum in frustums {
-                            if frustum.is_intersects_aabb(&node.world_bounding_box()) {
-                                visible_by_any_frustum = true;
-                                break;
-                            }
+        // We need to fill only unfilled entries, none of visibility flags of a node can make it
+        // visible again if lod group hid it.
+        match frustums.filter(|frustums| !frustums.is_empty()) {
+            Some(frustums) => {
+                // Indexed nodes outside of every frustum are never visited, so they stay hidden.
+                let mut buffer = Vec::new();
+                for frustum in frustums.iter() {
+                    spatial_index.frustum_query(frustum, &mut buffer);
+                    for &handle in buffer.iter() {
+                        if let Some(node) = nodes.try_borrow(handle) {
+                            self.map
+                                .entry(handle)
+                                .or_insert_with(|| node.global_visibility());
                         }
-                        visibility = visible_by_any_frustum;
                     }
                 }
-                visibility
-            });
+
+                for handle in spatial_index.unculled_nodes() {
+                    if let Some(node) = nodes.try_borrow(handle) {
+                        self.map.entry(handle).or_insert_with(|| {
+                            let mut visibility = node.global_visibility();
+                            if visibility && node.frustum_culling() {
+                                visibility = frustums.iter().any(|frustum| {
+                                    frustum.is_intersects_aabb(&node.world_bounding_box())
+                                });
+                            }
+                            visibility
+                        });
+                    }
+                }
+            }
+            None => {
+                for (handle, node) in nodes.pair_iter() {
+                    self.map
+                        .entry(handle)
+                        .or_insert_with(|| node.global_visibility());
+                }
+            }
         }
     }
 
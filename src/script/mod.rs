@@ -5,7 +5,7 @@
 use crate::{
     core::{
         pool::Handle,
-        reflect::{FieldInfo, Reflect, ReflectArray, ReflectList},
+        reflect::{FieldInfo, InvokeError, MethodInfo, Reflect, ReflectArray, ReflectList},
         uuid::Uuid,
         visitor::{Visit, VisitResult, Visitor},
     },
@@ -13,6 +13,7 @@ use crate::{
     event::Event,
     plugin::Plugin,
     scene::{node::Node, Scene},
+    script::task::{AsyncTask, AsyncTaskResult, TaskPoolHandler},
     utils::{component::ComponentProvider, log::Log},
 };
 use std::{
@@ -23,6 +24,7 @@ use std::{
 };
 
 pub mod constructor;
+pub mod task;
 
 /// A script message's payload.
 pub trait ScriptMessagePayload: Any + Send {
@@ -203,6 +205,9 @@ pub struct ScriptContext<'a, 'b, 'c> {
     /// ```
     pub handle: Handle<Node>,
 
+    /// Index of the script instance in the list of scripts of the node. See [`crate::scene::base::Base::script_at`] for more info.
+    pub script_index: usize,
+
     /// A reference to a scene the script instance belongs to. You have full mutable access to scene content
     /// in most of the script methods.
     pub scene: &'b mut Scene,
@@ -217,6 +222,28 @@ pub struct ScriptContext<'a, 'b, 'c> {
     /// A message dispatcher. If you need to receive messages of a particular type, you must subscribe to a type
     /// explicitly. See [`ScriptTrait::on_message`] for more examples.
     pub message_dispatcher: &'c mut ScriptMessageDispatcher,
+
+    /// A task pool handler of the scene, it could be used to run asynchronous tasks, results of which will be
+    /// delivered back to the script. See [`ScriptContext::spawn_task`] for more info.
+    pub task_pool: &'c mut TaskPoolHandler,
+}
+
+impl<'a, 'b, 'c> ScriptContext<'a, 'b, 'c> {
+    /// Spawns an asynchronous task, that belongs to the current script instance. When the task is completed,
+    /// `on_complete` callback will be called with the result of the task, a reference to the script instance
+    /// and a script context. The task is cancelled automatically if the node of the script is deleted. Returns
+    /// a unique id of the task, that could be used to cancel the task. See [`TaskPoolHandler`] docs for
+    /// examples.
+    pub fn spawn_task<S, T, F, C>(&mut self, future: F, on_complete: C) -> Uuid
+    where
+        S: ScriptTrait,
+        T: AsyncTaskResult,
+        F: AsyncTask<T>,
+        C: FnOnce(T, &mut S, &mut ScriptContext) + 'static,
+    {
+        self.task_pool
+            .spawn_script_task(self.handle, self.script_index, future, on_complete)
+    }
 }
 
 /// A set of data, that provides contextual information for script methods.
@@ -244,6 +271,9 @@ pub struct ScriptMessageContext<'a, 'b, 'c> {
     /// ```
     pub handle: Handle<Node>,
 
+    /// Index of the script instance in the list of scripts of the node. See [`crate::scene::base::Base::script_at`] for more info.
+    pub script_index: usize,
+
     /// A reference to a scene the script instance belongs to. You have full mutable access to scene content
     /// in most of the script methods.
     pub scene: &'b mut Scene,
@@ -279,11 +309,42 @@ pub struct ScriptDeinitContext<'a, 'b, 'c> {
     /// any unchecked borrowing using the handle will cause panic!
     pub node_handle: Handle<Node>,
 
+    /// Index of the script instance in the list of scripts of the node. The index is valid only until the
+    /// end of the current frame.
+    pub script_index: usize,
+
     /// An message sender. Every message sent via this sender will be then passed to every [`ScriptTrait::on_message`]
     /// method of every script.
     pub message_sender: &'c ScriptMessageSender,
 }
 
+macro_rules! impl_sibling_access {
+    ($ctx_type:ident) => {
+        impl<'a, 'b, 'c> $ctx_type<'a, 'b, 'c> {
+            /// Tries to find a script of the given type among other scripts of the node, to which the current
+            /// script instance belongs to. The current script instance is never returned, because it is
+            /// temporarily taken out of the node while its methods are called.
+            pub fn sibling_script<T: ScriptTrait>(&self) -> Option<&T> {
+                self.scene
+                    .graph
+                    .try_get(self.handle)
+                    .and_then(|node| node.try_get_script::<T>())
+            }
+
+            /// Same as [`Self::sibling_script`], but returns a mutable reference.
+            pub fn sibling_script_mut<T: ScriptTrait>(&mut self) -> Option<&mut T> {
+                self.scene
+                    .graph
+                    .try_get_mut(self.handle)
+                    .and_then(|node| node.try_get_script_mut::<T>())
+            }
+        }
+    };
+}
+
+impl_sibling_access!(ScriptContext);
+impl_sibling_access!(ScriptMessageContext);
+
 /// Script is a set predefined methods that are called on various stages by the engine. It is used to add
 /// custom behaviour to game entities.
 pub trait ScriptTrait: BaseScript + ComponentProvider {
@@ -322,6 +383,31 @@ pub trait ScriptTrait: BaseScript + ComponentProvider {
     /// 60 times per second (this may change in future releases).
     fn on_update(&mut self, #[allow(unused_variables)] ctx: &mut ScriptContext) {}
 
+    /// Called once per fixed update step right before the physics step, `ctx.dt` contains the fixed time
+    /// step. It should be used to apply forces, set velocities, etc. of rigid bodies, so the changes will
+    /// be taken into account by the physics on the current step.
+    fn on_fixed_update(&mut self, #[allow(unused_variables)] ctx: &mut ScriptContext) {}
+
+    /// Called once per fixed update step after every [`Self::on_update`] of every script of the scene, at
+    /// this moment animation and physics have already written new transforms of the nodes. Global transforms
+    /// of the nodes (and view matrices of the cameras) are re-calculated right after this pass, so any changes
+    /// made here will be visible on the current frame. It should be used for camera following, inverse
+    /// kinematics, etc.
+    fn on_late_update(&mut self, #[allow(unused_variables)] ctx: &mut ScriptContext) {}
+
+    /// Returns execution priority of the script. Update methods ([`Self::on_fixed_update`], [`Self::on_update`],
+    /// [`Self::on_late_update`]) of scripts with lower priority are called first, scripts with the same
+    /// priority are called in order of their nodes in the scene graph. The value must not change during the
+    /// lifetime of the script. Default value is `0`.
+    fn execution_priority(&self) -> i32 {
+        0
+    }
+
+    /// Called once per rendered frame, right before rendering. Unlike [`Self::on_update`], the rate of calls
+    /// is not stable - it depends on the frame rate, `ctx.dt` contains the time (in seconds) that passed since
+    /// the previous frame. It could be used for purely visual effects, that must be as smooth as possible.
+    fn on_frame(&mut self, #[allow(unused_variables)] ctx: &mut ScriptContext) {}
+
     /// Allows you to react to certain script messages. It could be used for communication between scripts; to
     /// bypass borrowing issues. If you need to receive messages of a particular type, you must subscribe to a type
     /// explicitly. Usually it is done in [`ScriptTrait::on_start`] method:
@@ -495,6 +581,19 @@ impl Reflect for Script {
     fn as_list_mut(&mut self, func: &mut dyn FnMut(Option<&mut dyn ReflectList>)) {
         self.instance.deref_mut().as_list_mut(func)
     }
+
+    fn methods_info(&self, func: &mut dyn FnMut(Vec<MethodInfo>)) {
+        self.instance.deref().methods_info(func)
+    }
+
+    fn invoke_method(
+        &mut self,
+        name: &str,
+        args: Vec<Box<dyn Reflect>>,
+        func: &mut dyn FnMut(Result<Box<dyn Reflect>, InvokeError>),
+    ) {
+        self.instance.deref_mut().invoke_method(name, args, func)
+    }
 }
 
 impl Deref for Script {
@@ -602,7 +701,7 @@ mod test {
             variable::InheritableVariable, visitor::prelude::*,
         },
         impl_component_provider,
-        scene::base::Base,
+        scene::base::BaseBuilder,
         script::{Script, ScriptTrait},
     };
 
@@ -621,17 +720,17 @@ mod test {
 
     #[test]
     fn test_script_property_inheritance_on_nodes() {
-        let mut child = Base::default();
-
-        child.script = Some(Script::new(MyScript {
-            field: InheritableVariable::new(1.23),
-        }));
-
-        let mut parent = Base::default();
-
-        parent.script = Some(Script::new(MyScript {
-            field: InheritableVariable::new(3.21),
-        }));
+        let mut child = BaseBuilder::new()
+            .with_script(Script::new(MyScript {
+                field: InheritableVariable::new(1.23),
+            }))
+            .build_base();
+
+        let parent = BaseBuilder::new()
+            .with_script(Script::new(MyScript {
+                field: InheritableVariable::new(3.21),
+            }))
+            .build_base();
 
         child.as_reflect_mut(&mut |child| {
             parent.as_reflect(&mut |parent| {
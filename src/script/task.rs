@@ -0,0 +1,495 @@
+//! Asynchronous tasks owned by scripts. See [`TaskPoolHandler`] docs for more info.
+
+use crate::{
+    core::{
+        futures::{
+            channel::oneshot,
+            future::{abortable, AbortHandle},
+        },
+        pool::Handle,
+        uuid::Uuid,
+    },
+    engine::resource_manager::task::TaskPool,
+    scene::{graph::Graph, node::Node},
+    script::{Script, ScriptContext, ScriptTrait},
+    utils::log::Log,
+};
+use fxhash::FxHashMap;
+use std::{
+    any::Any,
+    future::Future,
+    sync::{
+        mpsc::{channel, Receiver, Sender},
+        Arc,
+    },
+    time::Duration,
+};
+
+/// A result of an asynchronous task. On every platform except WebAssembly it must be `Send`, because the
+/// task is executed on a separate thread.
+#[cfg(not(target_arch = "wasm32"))]
+pub trait AsyncTaskResult: Any + Send + 'static {
+    /// Casts self into `Box<dyn Any>`.
+    fn into_any(self: Box<Self>) -> Box<dyn Any>;
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl<T> AsyncTaskResult for T
+where
+    T: Any + Send + 'static,
+{
+    fn into_any(self: Box<Self>) -> Box<dyn Any> {
+        self
+    }
+}
+
+/// A result of an asynchronous task.
+#[cfg(target_arch = "wasm32")]
+pub trait AsyncTaskResult: Any + 'static {
+    /// Casts self into `Box<dyn Any>`.
+    fn into_any(self: Box<Self>) -> Box<dyn Any>;
+}
+
+#[cfg(target_arch = "wasm32")]
+impl<T> AsyncTaskResult for T
+where
+    T: Any + 'static,
+{
+    fn into_any(self: Box<Self>) -> Box<dyn Any> {
+        self
+    }
+}
+
+/// An asynchronous task. On every platform except WebAssembly it must be `Send`, because the task is
+/// executed on a separate thread.
+#[cfg(not(target_arch = "wasm32"))]
+pub trait AsyncTask<T: AsyncTaskResult>: Future<Output = T> + Send + 'static {}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl<T, F> AsyncTask<T> for F
+where
+    T: AsyncTaskResult,
+    F: Future<Output = T> + Send + 'static,
+{
+}
+
+/// An asynchronous task.
+#[cfg(target_arch = "wasm32")]
+pub trait AsyncTask<T: AsyncTaskResult>: Future<Output = T> + 'static {}
+
+#[cfg(target_arch = "wasm32")]
+impl<T, F> AsyncTask<T> for F
+where
+    T: AsyncTaskResult,
+    F: Future<Output = T> + 'static,
+{
+}
+
+type ScriptTaskCallback = Box<dyn FnOnce(Box<dyn Any>, &mut Script, &mut ScriptContext)>;
+
+struct TaskResult {
+    id: Uuid,
+    payload: Box<dyn AsyncTaskResult>,
+}
+
+struct ScriptTask {
+    node: Handle<Node>,
+    script_index: usize,
+    is_owner: fn(&Script) -> bool,
+    abort_handle: AbortHandle,
+    on_complete: ScriptTaskCallback,
+}
+
+/// A completed task, that is waiting for its completion callback to be called.
+pub(crate) struct CompletedScriptTask {
+    id: Uuid,
+    pub(crate) node: Handle<Node>,
+    script_index: usize,
+    is_owner: fn(&Script) -> bool,
+    payload: Box<dyn Any>,
+    on_complete: ScriptTaskCallback,
+}
+
+impl CompletedScriptTask {
+    // Searches for the script that spawned the task. Indices of scripts could change if some scripts
+    // were removed from the node, in this case the first script of the same type is used.
+    pub(crate) fn script_index(&self, graph: &Graph) -> Option<usize> {
+        let node = graph.try_get(self.node)?;
+
+        let is_owner = |index: usize| {
+            node.script_at(index)
+                .map_or(false, |script| (self.is_owner)(script))
+        };
+
+        if is_owner(self.script_index) {
+            Some(self.script_index)
+        } else {
+            (0..node.script_count()).find(|index| is_owner(*index))
+        }
+    }
+
+    pub(crate) fn complete(self, script: &mut Script, context: &mut ScriptContext) {
+        (self.on_complete)(self.payload, script, context)
+    }
+}
+
+/// Task pool handler runs asynchronous tasks spawned by scripts and delivers their results back to the
+/// scripts. Every scripted scene has its own handler, it is available in [`ScriptContext::task_pool`].
+/// The easiest way to spawn a task is to use [`ScriptContext::spawn_task`]:
+///
+/// ```rust
+/// # use fyrox::{
+/// #     core::{reflect::prelude::*, uuid::Uuid, visitor::prelude::*},
+/// #     impl_component_provider,
+/// #     script::{task, ScriptContext, ScriptTrait},
+/// # };
+/// # use std::time::Duration;
+/// #[derive(Reflect, Visit, Default, Debug, Clone)]
+/// struct MyScript;
+///
+/// # impl_component_provider!(MyScript);
+/// impl ScriptTrait for MyScript {
+///     fn on_start(&mut self, ctx: &mut ScriptContext) {
+///         // Load a model in background and instantiate it when it is loaded.
+///         let model = ctx.resource_manager.request_model("path/to/model.fbx");
+///         ctx.spawn_task(model, |result, _script: &mut MyScript, ctx| {
+///             if let Ok(model) = result {
+///                 model.instantiate(ctx.scene);
+///             }
+///         });
+///
+///         // Do something in 2 seconds.
+///         ctx.spawn_task(
+///             task::wait_for(Duration::from_secs(2)),
+///             |_, _script: &mut MyScript, ctx| {
+///                 ctx.scene.graph[ctx.handle].set_visibility(false);
+///             },
+///         );
+///     }
+///
+///     fn id(&self) -> Uuid {
+///         todo!()
+///     }
+/// }
+/// ```
+///
+/// Any future could be used as a task, for example to wait for an animation signal, you can create a
+/// [`oneshot`] channel, spawn its receiver as a task and complete the channel in
+/// [`ScriptTrait::on_message`] when the respective animation event is received.
+///
+/// # Cancellation
+///
+/// Tasks are cancelled automatically when the node of the script, that spawned a task, is deleted. If the
+/// node is alive, but it has no script of the same type anymore at the moment of completion, the completion
+/// callback will not be called. A task could also be cancelled manually using [`Self::cancel_task`].
+///
+/// If the node is disabled at the moment of completion, the result of the task is kept until the node is
+/// enabled again, the completion callback is called after that.
+pub struct TaskPoolHandler {
+    task_pool: Arc<TaskPool>,
+    sender: Sender<TaskResult>,
+    receiver: Receiver<TaskResult>,
+    tasks: FxHashMap<Uuid, ScriptTask>,
+    // Completed tasks of disabled nodes.
+    postponed_tasks: Vec<CompletedScriptTask>,
+}
+
+impl TaskPoolHandler {
+    pub(crate) fn new(task_pool: Arc<TaskPool>) -> Self {
+        let (sender, receiver) = channel();
+        Self {
+            task_pool,
+            sender,
+            receiver,
+            tasks: Default::default(),
+            postponed_tasks: Default::default(),
+        }
+    }
+
+    /// Spawns a new task that belongs to a script with the given index of the given node. When the task is
+    /// completed, `on_complete` callback will be called with the result of the task, a reference to the
+    /// script and a script context. Returns a unique id of the task, it could be used to cancel the task.
+    ///
+    /// Prefer to use [`ScriptContext::spawn_task`], which fills node handle and script index for you.
+    pub fn spawn_script_task<S, T, F, C>(
+        &mut self,
+        node: Handle<Node>,
+        script_index: usize,
+        future: F,
+        on_complete: C,
+    ) -> Uuid
+    where
+        S: ScriptTrait,
+        T: AsyncTaskResult,
+        F: AsyncTask<T>,
+        C: FnOnce(T, &mut S, &mut ScriptContext) + 'static,
+    {
+        let id = Uuid::new_v4();
+
+        let (future, abort_handle) = abortable(future);
+        let sender = self.sender.clone();
+        self.task_pool.spawn_task(async move {
+            // Aborted tasks does not produce any results.
+            if let Ok(result) = future.await {
+                // The handler could be destroyed already.
+                let _ = sender.send(TaskResult {
+                    id,
+                    payload: Box::new(result),
+                });
+            }
+        });
+
+        let on_complete: ScriptTaskCallback = Box::new(move |payload, script, context| {
+            match (payload.downcast::<T>(), script.cast_mut::<S>()) {
+                (Ok(result), Some(script)) => on_complete(*result, script, context),
+                _ => Log::err(format!(
+                    "Unable to complete task {}, type mismatch! This is a bug!",
+                    id
+                )),
+            }
+        });
+
+        self.tasks.insert(
+            id,
+            ScriptTask {
+                node,
+                script_index,
+                is_owner: |script| script.cast::<S>().is_some(),
+                abort_handle,
+                on_complete,
+            },
+        );
+
+        id
+    }
+
+    /// Cancels a task with the given id. Completion callback of the task will not be called. Returns `true`
+    /// if the task was cancelled, `false` - if there is no such task (for example, it was completed already).
+    pub fn cancel_task(&mut self, id: Uuid) -> bool {
+        if let Some(task) = self.tasks.remove(&id) {
+            task.abort_handle.abort();
+            true
+        } else if let Some(position) = self.postponed_tasks.iter().position(|t| t.id == id) {
+            self.postponed_tasks.remove(position);
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Returns `true` if a task with the given id is still running or if it is waiting until its node is
+    /// enabled.
+    pub fn is_task_running(&self, id: Uuid) -> bool {
+        self.tasks.contains_key(&id) || self.postponed_tasks.iter().any(|t| t.id == id)
+    }
+
+    /// Returns total amount of running tasks (including the tasks, that are waiting until their nodes
+    /// are enabled).
+    pub fn task_count(&self) -> usize {
+        self.tasks.len() + self.postponed_tasks.len()
+    }
+
+    // Cancels every task of the nodes that were deleted.
+    pub(crate) fn cancel_orphaned_tasks(&mut self, graph: &Graph) {
+        self.tasks.retain(|_, task| {
+            if graph.is_valid_handle(task.node) {
+                true
+            } else {
+                task.abort_handle.abort();
+                false
+            }
+        });
+
+        self.postponed_tasks
+            .retain(|task| graph.is_valid_handle(task.node));
+    }
+
+    // Cancels every task of the given script instances and drops results of the tasks, that were completed,
+    // but not delivered yet. Tasks and their results could refer to code of a dynamic plugin, so they must
+    // be destroyed before its library is unloaded.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub(crate) fn cancel_script_tasks(&mut self, scripts: &[(Handle<Node>, usize, Script)]) {
+        self.tasks.retain(|_, task| {
+            let is_owned = scripts
+                .iter()
+                .any(|(node, _, script)| task.node == *node && (task.is_owner)(script));
+            if is_owned {
+                task.abort_handle.abort();
+            }
+            !is_owned
+        });
+
+        self.postponed_tasks.retain(|task| {
+            !scripts
+                .iter()
+                .any(|(node, _, script)| task.node == *node && (task.is_owner)(script))
+        });
+
+        for result in self.receiver.try_iter().collect::<Vec<_>>() {
+            if self.tasks.contains_key(&result.id) {
+                let _ = self.sender.send(result);
+            }
+        }
+    }
+
+    // Returns every completed task, including the postponed ones.
+    pub(crate) fn take_completed_tasks(&mut self) -> Vec<CompletedScriptTask> {
+        let mut completed_tasks = std::mem::take(&mut self.postponed_tasks);
+        while let Ok(result) = self.receiver.try_recv() {
+            // The task could be cancelled after its completion.
+            if let Some(task) = self.tasks.remove(&result.id) {
+                completed_tasks.push(CompletedScriptTask {
+                    id: result.id,
+                    node: task.node,
+                    script_index: task.script_index,
+                    is_owner: task.is_owner,
+                    payload: result.payload.into_any(),
+                    on_complete: task.on_complete,
+                });
+            }
+        }
+        completed_tasks
+    }
+
+    // Keeps a completed task until the next call of `take_completed_tasks`.
+    pub(crate) fn postpone_task(&mut self, task: CompletedScriptTask) {
+        self.postponed_tasks.push(task);
+    }
+}
+
+impl Drop for TaskPoolHandler {
+    fn drop(&mut self) {
+        for task in self.tasks.values() {
+            task.abort_handle.abort();
+        }
+    }
+}
+
+/// A single thread, that completes every timer created by [`wait_for`]. Timers are stored in a binary
+/// heap ordered by their deadlines, the thread sleeps until the nearest deadline or until a new timer is
+/// added.
+#[cfg(not(target_arch = "wasm32"))]
+mod timer {
+    use crate::{
+        core::{
+            futures::channel::oneshot::Sender,
+            parking_lot::{Condvar, Mutex},
+        },
+        lazy_static::lazy_static,
+    };
+    use std::{
+        cmp::Ordering,
+        collections::BinaryHeap,
+        sync::Arc,
+        time::{Duration, Instant},
+    };
+
+    struct Timer {
+        deadline: Instant,
+        sender: Sender<()>,
+    }
+
+    impl PartialEq for Timer {
+        fn eq(&self, other: &Self) -> bool {
+            self.deadline == other.deadline
+        }
+    }
+
+    impl Eq for Timer {}
+
+    impl PartialOrd for Timer {
+        fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+            Some(self.cmp(other))
+        }
+    }
+
+    impl Ord for Timer {
+        // Reversed, so the nearest deadline is on the top of the heap.
+        fn cmp(&self, other: &Self) -> Ordering {
+            other.deadline.cmp(&self.deadline)
+        }
+    }
+
+    #[derive(Default)]
+    struct TimerQueue {
+        timers: Mutex<BinaryHeap<Timer>>,
+        condvar: Condvar,
+    }
+
+    impl TimerQueue {
+        fn run(&self) {
+            let mut timers = self.timers.lock();
+            loop {
+                let now = Instant::now();
+                while timers.peek().map_or(false, |timer| timer.deadline <= now) {
+                    if let Some(timer) = timers.pop() {
+                        let _ = timer.sender.send(());
+                    }
+                }
+
+                match timers.peek().map(|timer| timer.deadline) {
+                    Some(deadline) => {
+                        self.condvar.wait_until(&mut timers, deadline);
+                    }
+                    None => self.condvar.wait(&mut timers),
+                }
+            }
+        }
+    }
+
+    lazy_static! {
+        static ref TIMER_QUEUE: Arc<TimerQueue> = {
+            let queue = Arc::new(TimerQueue::default());
+            let thread_queue = queue.clone();
+            std::thread::Builder::new()
+                .name("Timers".to_string())
+                .spawn(move || thread_queue.run())
+                .expect("Unable to create timer thread!");
+            queue
+        };
+    }
+
+    pub fn add(duration: Duration, sender: Sender<()>) {
+        let queue = &*TIMER_QUEUE;
+        let mut timers = queue.timers.lock();
+        timers.push(Timer {
+            deadline: Instant::now() + duration,
+            sender,
+        });
+        queue.condvar.notify_one();
+    }
+}
+
+/// Creates a future that will be completed after the given amount of time. The time is measured using
+/// the real time, not the engine time.
+///
+/// On every platform except WebAssembly, every timer is served by a single background thread, so it is
+/// fine to create lots of timers at once.
+pub fn wait_for(duration: Duration) -> impl Future<Output = ()> {
+    let (sender, receiver) = oneshot::channel::<()>();
+
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        timer::add(duration, sender);
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    {
+        use crate::core::wasm_bindgen::{closure::Closure, JsCast};
+
+        let callback = Closure::once_into_js(move || {
+            let _ = sender.send(());
+        });
+
+        if let Some(window) = crate::core::web_sys::window() {
+            let _ = window.set_timeout_with_callback_and_timeout_and_arguments_0(
+                callback.unchecked_ref(),
+                duration.as_millis() as i32,
+            );
+        }
+    }
+
+    async move {
+        let _ = receiver.await;
+    }
+}
@@ -2,6 +2,8 @@
 
 use clap::{Parser, Subcommand};
 use convert_case::{Case, Casing};
+use fyrox_core::vfs::archive::{ArchiveBuilder, Compression};
+use fyrox_resource::dependency::DependencyGraph;
 use std::{
     fmt::Display,
     fs::{create_dir_all, remove_dir_all, File},
@@ -35,6 +37,38 @@ enum Commands {
         #[clap(short, long, default_value = "MyScript")]
         name: String,
     },
+    /// Packs a folder (`data` by default) into a single archive, that could be mounted to the virtual
+    /// file system of the engine.
+    Pack {
+        #[clap(short, long, default_value = "data")]
+        input: String,
+
+        #[clap(short, long, default_value = "data.fpk")]
+        output: String,
+
+        /// Do not compress any files.
+        #[clap(long)]
+        no_compression: bool,
+    },
+    /// Prints a report about resources of a folder (`data` by default): unused resources and references
+    /// to missing files. Could also print dependencies and dependents of a resource.
+    Report {
+        #[clap(short, long, default_value = "data")]
+        input: String,
+
+        /// Resources that are loaded by the game directly. Every scene is treated as used if no roots
+        /// are specified.
+        #[clap(short, long)]
+        root: Vec<String>,
+
+        /// Print the resources that are used by the given resource.
+        #[clap(long)]
+        dependencies: Option<String>,
+
+        /// Print the resources that use the given resource.
+        #[clap(long)]
+        dependents: Option<String>,
+    },
 }
 
 fn write_file<P: AsRef<Path>, S: AsRef<str>>(path: P, content: S) {
@@ -595,6 +629,72 @@ impl ScriptTrait for {name} {{
     );
 }
 
+fn pack(input: &str, output: &str, no_compression: bool) {
+    let mut builder = ArchiveBuilder::new();
+    let result = builder
+        .add_directory(input, &mut |path| {
+            if no_compression {
+                Compression::None
+            } else {
+                Compression::for_path(path)
+            }
+        })
+        .and_then(|_| builder.write_to_file(output));
+
+    match result {
+        Ok(_) => {
+            println!("{input} was packed to {output} successfully!");
+            println!(
+                "Mount the archive to the virtual file system at {input} to load the files from it."
+            );
+        }
+        Err(e) => println!("Unable to pack {input}. Reason: {e}"),
+    }
+}
+
+fn report(input: &str, roots: &[String], dependencies: Option<&str>, dependents: Option<&str>) {
+    let graph = match DependencyGraph::scan(input) {
+        Ok(graph) => graph,
+        Err(e) => {
+            println!("Unable to scan {input}. Reason: {e}");
+            return;
+        }
+    };
+
+    if let Some(path) = dependencies {
+        println!("Resources used by {path}:");
+        for dependency in graph.dependencies(path) {
+            println!("\t{dependency}");
+        }
+    }
+
+    if let Some(path) = dependents {
+        println!("Resources that use {path}:");
+        for dependent in graph.dependents(path) {
+            println!("\t{dependent}");
+        }
+    }
+
+    if dependencies.is_some() || dependents.is_some() {
+        return;
+    }
+
+    let unused = if roots.is_empty() {
+        graph.unused_assets(&graph.scenes().collect::<Vec<_>>())
+    } else {
+        graph.unused_assets(roots)
+    };
+    println!("Unused resources ({}):", unused.len());
+    for path in unused {
+        println!("\t{path}");
+    }
+
+    println!("Missing references:");
+    for (path, reference) in graph.missing_references() {
+        println!("\t{path} -> {reference}");
+    }
+}
+
 fn main() {
     let args: Args = Args::parse();
 
@@ -636,5 +736,25 @@ fn main() {
         Commands::Script { name } => {
             init_script(&name);
         }
+        Commands::Pack {
+            input,
+            output,
+            no_compression,
+        } => {
+            pack(&input, &output, no_compression);
+        }
+        Commands::Report {
+            input,
+            root,
+            dependencies,
+            dependents,
+        } => {
+            report(
+                &input,
+                &root,
+                dependencies.as_deref(),
+                dependents.as_deref(),
+            );
+        }
     }
 }